@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // The bundled protoc, so building needs no system install
+    let protoc = protoc_bin_vendored::protoc_bin_path()?;
+    // SAFETY: the build script is single threaded
+    unsafe { std::env::set_var("PROTOC", protoc) };
+
+    // The server is only used by the unit tests, to stand in for an agent
+    tonic_build::configure()
+        .build_server(true)
+        .compile_protos(
+            &["proto/a2a.proto"],
+            &[PathBuf::from("proto"), protoc_bin_vendored::include_path()?],
+        )?;
+    Ok(())
+}
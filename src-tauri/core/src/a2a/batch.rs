@@ -0,0 +1,497 @@
+//! Batch runs: every prompt of a CSV or JSONL file sent to one agent skill
+//! with bounded concurrency, the results written next to the input
+
+use std::{fs, path::Path, sync::Arc, time::Instant};
+
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{
+        build_a2a_message_parts, card_service_url, extract_response_text, post_a2a_message,
+    },
+    db::{a2a_db::SettingA2AServerDbManager, batch_db::BatchRunDbManager},
+    error::AppError,
+    model::{
+        A2AMessage, BatchPrompt, BatchRunItem, BatchRunParams, BatchRunProgress, SettingA2AServer,
+    },
+};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const MAX_CONCURRENCY: usize = 64;
+
+/// A stored batch run, ready to be sent
+pub struct BatchJob {
+    pub run_id: i64,
+    pub a2a_server: SettingA2AServer,
+    pub a2a_url: String,
+    pub skill_id: String,
+    pub prompts: Vec<BatchPrompt>,
+    pub output_path: String,
+    pub concurrency: usize,
+}
+
+/// Read the prompts and store the run, nothing is sent yet
+pub fn prepare_batch_run(params: BatchRunParams) -> Result<BatchJob, AppError> {
+    let a2a_server = match SettingA2AServerDbManager::new().get_by_id(params.a2a_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return Err(AppError::not_found("A2A server not found")),
+        Err(e) => return Err(AppError::from(e.context("Failed to get A2A server"))),
+    };
+
+    let Some(a2a_url) = params
+        .a2a_url
+        .clone()
+        .or_else(|| card_service_url(&a2a_server))
+    else {
+        return Err(AppError::invalid_params("A2A server has no agent card url"));
+    };
+
+    let prompts = read_prompts(&params.input_path)?;
+    if prompts.is_empty() {
+        return Err(AppError::invalid_params("Input file contains no prompts"));
+    }
+
+    let output_path = params
+        .output_path
+        .clone()
+        .unwrap_or_else(|| format!("{}.results.jsonl", params.input_path));
+    let concurrency = params
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+
+    let run_id = BatchRunDbManager::new().insert_run(
+        params.a2a_server_id,
+        &params.skill_id,
+        &params.input_path,
+        &output_path,
+        concurrency,
+        prompts.len(),
+    )?;
+
+    log::info!(
+        "Starting batch run {} with {} prompts against {} (concurrency {})",
+        run_id,
+        prompts.len(),
+        a2a_server.name,
+        concurrency
+    );
+
+    Ok(BatchJob {
+        run_id,
+        a2a_server,
+        a2a_url,
+        skill_id: params.skill_id,
+        prompts,
+        output_path,
+        concurrency,
+    })
+}
+
+/// Send every prompt, storing and reporting each result as it arrives, then
+/// write the results file and finish the run
+pub async fn run_batch(job: BatchJob, progress: impl Fn(BatchRunProgress)) {
+    let BatchJob {
+        run_id,
+        a2a_server,
+        a2a_url,
+        skill_id,
+        prompts,
+        output_path,
+        concurrency,
+    } = job;
+    let db_manager = BatchRunDbManager::new();
+    let a2a_server = Arc::new(a2a_server);
+    let total = prompts.len();
+
+    let mut results = futures_util::stream::iter(prompts.into_iter().enumerate())
+        .map(|(index, prompt)| {
+            let a2a_server = a2a_server.clone();
+            let a2a_url = a2a_url.clone();
+            let skill_id = skill_id.clone();
+            async move { run_prompt(run_id, index, prompt, &a2a_server, &a2a_url, &skill_id).await }
+        })
+        .buffer_unordered(concurrency);
+
+    let mut items = Vec::with_capacity(total);
+    let (mut succeeded, mut failed) = (0, 0);
+
+    while let Some(item) = results.next().await {
+        if item.status == "ok" {
+            succeeded += 1;
+        } else {
+            failed += 1;
+        }
+
+        if let Err(e) = db_manager.insert_item(&item) {
+            log::warn!("Failed to store batch run item: {}", e);
+        }
+
+        progress(BatchRunProgress {
+            run_id,
+            completed: succeeded + failed,
+            total,
+            succeeded,
+            failed,
+            is_complete: false,
+            item: Some(item.clone()),
+        });
+        items.push(item);
+    }
+
+    items.sort_by_key(|item| item.item_index);
+    let status = match write_results(&output_path, &items) {
+        Ok(_) => "completed",
+        Err(e) => {
+            log::error!("Failed to write batch results to {}: {}", output_path, e);
+            "failed"
+        }
+    };
+
+    if let Err(e) = db_manager.finish_run(run_id, status, succeeded, failed) {
+        log::warn!("Failed to finish batch run: {}", e);
+    }
+
+    progress(BatchRunProgress {
+        run_id,
+        completed: total,
+        total,
+        succeeded,
+        failed,
+        is_complete: true,
+        item: None,
+    });
+}
+
+// Send one prompt through the same message/send path as the chat and time it
+async fn run_prompt(
+    run_id: i64,
+    index: usize,
+    prompt: BatchPrompt,
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    skill_id: &str,
+) -> BatchRunItem {
+    let message = A2AMessage {
+        message_id: format!("msg_id:{}", Uuid::new_v4()),
+        kind: "message".to_string(),
+        role: "user".to_string(),
+        parts: build_a2a_message_parts(a2a_server, prompt.prompt.clone()),
+        context_id: None,
+        task_id: None,
+    };
+
+    let started = Instant::now();
+    let result = post_a2a_message(
+        a2a_server,
+        a2a_url,
+        format!("task_id:{}", Uuid::new_v4()),
+        message,
+        skill_id.to_string(),
+    )
+    .await;
+    let latency_ms = started.elapsed().as_millis() as i64;
+
+    let mut item = BatchRunItem {
+        id: None,
+        run_id,
+        item_index: index as i64,
+        prompt_id: prompt.id,
+        prompt: prompt.prompt,
+        status: "error".to_string(),
+        latency_ms,
+        task_state: None,
+        response_text: None,
+        error: None,
+    };
+
+    match result {
+        Ok(response_text) => {
+            let response = serde_json::from_str::<serde_json::Value>(&response_text)
+                .unwrap_or(serde_json::Value::Null);
+            let result = response.get("result");
+
+            item.task_state = result
+                .and_then(|r| r.pointer("/status/state"))
+                .and_then(|s| s.as_str())
+                .map(|s| s.to_string())
+                // A plain message reply has no task lifecycle
+                .or_else(|| result.map(|_| "completed".to_string()));
+            item.error = response
+                .pointer("/error/message")
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string());
+            item.response_text = Some(extract_response_text(&response).unwrap_or(response_text));
+
+            if item.error.is_none() && item.task_state.as_deref() != Some("failed") {
+                item.status = "ok".to_string();
+            }
+        }
+        Err(e) => item.error = Some(e.to_string()),
+    }
+
+    item
+}
+
+fn is_csv(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false)
+}
+
+fn read_prompts(input_path: &str) -> Result<Vec<BatchPrompt>> {
+    if is_csv(input_path) {
+        read_csv_prompts(input_path)
+    } else {
+        read_jsonl_prompts(input_path)
+    }
+}
+
+// A first row with a "prompt" column is the header, the prompt and optional "id"
+// columns are taken from it. Without one every row is a prompt in the first column
+fn read_csv_prompts(input_path: &str) -> Result<Vec<BatchPrompt>> {
+    // Rows may leave out trailing columns they don't use
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(input_path)
+        .with_context(|| format!("failed to open {}", input_path))?;
+    let mut records = reader.records().peekable();
+
+    let find_column = |header: &csv::StringRecord, name: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let (prompt_column, id_column) = match records.peek() {
+        Some(Ok(first)) => match find_column(first, "prompt") {
+            Some(prompt_column) => {
+                let id_column = find_column(first, "id");
+                records.next();
+                (prompt_column, id_column)
+            }
+            None => (0, None),
+        },
+        _ => (0, None),
+    };
+
+    let mut prompts = Vec::new();
+    for record in records {
+        let record = record.context("failed to read CSV record")?;
+        let Some(prompt) = record.get(prompt_column).map(|p| p.trim()) else {
+            continue;
+        };
+        if prompt.is_empty() {
+            continue;
+        }
+        prompts.push(BatchPrompt {
+            id: id_column
+                .and_then(|column| record.get(column))
+                .map(|id| id.to_string()),
+            prompt: prompt.to_string(),
+        });
+    }
+
+    Ok(prompts)
+}
+
+// Each line is either a JSON string or an object with "prompt" and optional "id"
+fn read_jsonl_prompts(input_path: &str) -> Result<Vec<BatchPrompt>> {
+    let content =
+        fs::read_to_string(input_path).with_context(|| format!("failed to read {}", input_path))?;
+
+    let mut prompts = Vec::new();
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let value: serde_json::Value = serde_json::from_str(line)
+            .with_context(|| format!("invalid JSON on line {}", line_number + 1))?;
+
+        let prompt = match &value {
+            serde_json::Value::String(prompt) => BatchPrompt {
+                id: None,
+                prompt: prompt.clone(),
+            },
+            serde_json::Value::Object(object) => BatchPrompt {
+                id: object.get("id").map(|id| match id {
+                    serde_json::Value::String(id) => id.clone(),
+                    other => other.to_string(),
+                }),
+                prompt: object
+                    .get("prompt")
+                    .and_then(|p| p.as_str())
+                    .ok_or_else(|| {
+                        AppError::invalid_params(format!(
+                            "missing \"prompt\" on line {}",
+                            line_number + 1
+                        ))
+                    })?
+                    .to_string(),
+            },
+            _ => {
+                return Err(AppError::invalid_params(format!(
+                    "line {} is neither a string nor an object",
+                    line_number + 1
+                ))
+                .into());
+            }
+        };
+        prompts.push(prompt);
+    }
+
+    Ok(prompts)
+}
+
+fn write_results(output_path: &str, items: &[BatchRunItem]) -> Result<()> {
+    if is_csv(output_path) {
+        let mut writer = csv::Writer::from_path(output_path)
+            .with_context(|| format!("failed to create {}", output_path))?;
+        writer.write_record([
+            "index",
+            "id",
+            "prompt",
+            "status",
+            "latency_ms",
+            "task_state",
+            "response_text",
+            "error",
+        ])?;
+        for item in items {
+            writer.write_record([
+                item.item_index.to_string(),
+                item.prompt_id.clone().unwrap_or_default(),
+                item.prompt.clone(),
+                item.status.clone(),
+                item.latency_ms.to_string(),
+                item.task_state.clone().unwrap_or_default(),
+                item.response_text.clone().unwrap_or_default(),
+                item.error.clone().unwrap_or_default(),
+            ])?;
+        }
+        writer.flush()?;
+    } else {
+        let mut content = String::new();
+        for item in items {
+            content.push_str(&serde_json::to_string(item)?);
+            content.push('\n');
+        }
+        fs::write(output_path, content)
+            .with_context(|| format!("failed to write {}", output_path))?;
+    }
+
+    log::info!("Wrote {} batch results to {}", items.len(), output_path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    // An input file in the temp directory, removed again when dropped
+    struct InputFile(PathBuf);
+
+    impl InputFile {
+        fn new(extension: &str, content: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("batch-{}.{}", Uuid::new_v4(), extension));
+            fs::write(&path, content).unwrap();
+            Self(path)
+        }
+
+        fn path(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for InputFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn prompt(id: Option<&str>, prompt: &str) -> BatchPrompt {
+        BatchPrompt {
+            id: id.map(str::to_string),
+            prompt: prompt.to_string(),
+        }
+    }
+
+    fn read(extension: &str, content: &str) -> Result<Vec<BatchPrompt>> {
+        read_prompts(InputFile::new(extension, content).path())
+    }
+
+    #[test]
+    fn csv_header_picks_the_prompt_and_id_columns() {
+        let prompts = read(
+            "csv",
+            "ID,Category,Prompt\n1,greeting,Hello\n2,farewell,\"Bye, then\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            prompts,
+            vec![prompt(Some("1"), "Hello"), prompt(Some("2"), "Bye, then")]
+        );
+    }
+
+    #[test]
+    fn csv_without_a_header_takes_the_first_column() {
+        let prompts = read("CSV", "Hello,ignored\nHow are you?\n").unwrap();
+
+        assert_eq!(
+            prompts,
+            vec![prompt(None, "Hello"), prompt(None, "How are you?")]
+        );
+    }
+
+    #[test]
+    fn blank_csv_rows_and_prompts_are_skipped() {
+        let prompts = read("csv", "prompt,id\n  Hello  ,a\n\n ,b\n,\nBye,c\n").unwrap();
+
+        assert_eq!(
+            prompts,
+            vec![prompt(Some("a"), "Hello"), prompt(Some("c"), "Bye")]
+        );
+    }
+
+    #[test]
+    fn jsonl_lines_are_strings_or_objects() {
+        let prompts = read(
+            "jsonl",
+            "\"Hello\"\n\n{\"prompt\": \"Bye\", \"id\": \"b\"}\n{\"prompt\": \"Again\", \"id\": 7}\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            prompts,
+            vec![
+                prompt(None, "Hello"),
+                prompt(Some("b"), "Bye"),
+                prompt(Some("7"), "Again"),
+            ]
+        );
+    }
+
+    #[test]
+    fn jsonl_objects_without_a_prompt_are_refused() {
+        let error = read("jsonl", "\"Hello\"\n{\"id\": 1, \"text\": \"Bye\"}\n").unwrap_err();
+        let error = AppError::from(error);
+
+        assert_eq!(error.message, "missing \"prompt\" on line 2");
+        assert_eq!(
+            read("jsonl", "\"Hello\"\n[1]\n").unwrap_err().to_string(),
+            "line 2 is neither a string nor an object"
+        );
+        assert_eq!(
+            read("jsonl", "{\"prompt\":\n").unwrap_err().to_string(),
+            "invalid JSON on line 1"
+        );
+    }
+}
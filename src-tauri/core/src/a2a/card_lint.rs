@@ -0,0 +1,749 @@
+//! Agent card linter: schema validation, the rules the schema cannot express
+//! and probes of whether the agent serves what its card declares
+
+use std::{collections::HashMap, time::Duration};
+
+use reqwest::{Client, RequestBuilder, Url, header::CONTENT_TYPE};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{apply_custom_headers, with_http_scheme},
+    db::a2a_db::SettingA2AServerDbManager,
+    error::AppError,
+    model::{CardLintFinding, CardLintParams, CardLintReport, SettingA2AServer},
+    traffic,
+};
+
+// The subset of JSON Schema used here: type, enum, required, properties, items,
+// minLength and the uri format
+const AGENT_CARD_SCHEMA: &str = include_str!("agent_card.schema.json");
+
+const PROBE_TIMEOUT_SECS: u64 = 10;
+
+// Top-level MIME types, anything else in a mode is most likely a typo
+const MIME_TYPES: [&str; 10] = [
+    "application",
+    "audio",
+    "font",
+    "image",
+    "message",
+    "model",
+    "multipart",
+    "text",
+    "video",
+    // Wildcard of the accept syntax, e.g. "*/*"
+    "*",
+];
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const PUSH_NOTIFICATION_NOT_SUPPORTED: i64 = -32003;
+const UNSUPPORTED_OPERATION: i64 = -32004;
+
+/// Validate an agent card against the schema and lint what the schema cannot see
+pub async fn lint_agent_card(params: CardLintParams) -> Result<CardLintReport, AppError> {
+    let a2a_server = match params.a2a_server_id {
+        Some(id) => match SettingA2AServerDbManager::new().get_by_id(id) {
+            Ok(Some(server)) => Some(server),
+            Ok(None) => return Err(AppError::not_found("A2A server not found")),
+            Err(e) => return Err(AppError::from(e.context("Failed to get A2A server"))),
+        },
+        None => None,
+    };
+    let card_url = params
+        .url
+        .clone()
+        .or_else(|| {
+            a2a_server
+                .as_ref()
+                .map(|server| server.agent_card_url.clone())
+        })
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| with_http_scheme(&url));
+
+    let client = match Client::builder()
+        .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return Err(AppError::from(e)),
+    };
+    let linter = Linter {
+        client,
+        a2a_server,
+        token: params.token.clone(),
+    };
+
+    let card_text = match (&params.card_json, &card_url) {
+        (Some(card_json), _) => card_json.clone(),
+        (None, Some(card_url)) => match linter.fetch(card_url).await {
+            Ok(text) => text,
+            Err(e) => return Err(e),
+        },
+        (None, None) => {
+            return Err(AppError::invalid_params(
+                "Either a card url, a card or an A2A server is required",
+            ));
+        }
+    };
+
+    let mut findings = Vec::new();
+    match serde_json::from_str::<Value>(&card_text) {
+        Ok(card) => {
+            let schema: Value = serde_json::from_str(AGENT_CARD_SCHEMA)
+                .expect("the bundled agent card schema is valid JSON");
+            validate_schema(&schema, &card, "", &mut findings);
+            lint_semantics(&card, card_url.as_deref(), &mut findings);
+            if params.check_network.unwrap_or(true) {
+                linter.lint_network(&card, &mut findings).await;
+            }
+        }
+        Err(e) => findings.push(finding(
+            "",
+            "error",
+            "json",
+            format!(
+                "The card is not valid JSON at line {}, column {}: {}",
+                e.line(),
+                e.column(),
+                e
+            ),
+        )),
+    }
+
+    log::info!(
+        "Linted agent card {}: {} findings",
+        card_url.as_deref().unwrap_or("(inline)"),
+        findings.len()
+    );
+
+    Ok(CardLintReport {
+        card_url,
+        valid: !findings.iter().any(|finding| finding.severity == "error"),
+        findings,
+    })
+}
+
+fn finding(pointer: &str, severity: &str, code: &str, message: String) -> CardLintFinding {
+    CardLintFinding {
+        pointer: pointer.to_string(),
+        severity: severity.to_string(),
+        code: code.to_string(),
+        message,
+    }
+}
+
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.is_i64() || value.is_u64(),
+        other => type_name(value) == other,
+    }
+}
+
+fn validate_schema(
+    schema: &Value,
+    value: &Value,
+    pointer: &str,
+    findings: &mut Vec<CardLintFinding>,
+) {
+    if let Some(expected) = schema["type"].as_str() {
+        if !has_type(value, expected) {
+            findings.push(finding(
+                pointer,
+                "error",
+                "schema",
+                format!("Expected {} but found {}", expected, type_name(value)),
+            ));
+            return;
+        }
+    }
+
+    if let Some(options) = schema["enum"].as_array() {
+        if !options.contains(value) {
+            let options: Vec<String> = options.iter().map(|option| option.to_string()).collect();
+            findings.push(finding(
+                pointer,
+                "error",
+                "schema",
+                format!("Must be one of {}", options.join(", ")),
+            ));
+        }
+    }
+
+    if let Some(text) = value.as_str() {
+        if let Some(min_length) = schema["minLength"].as_u64() {
+            if (text.trim().chars().count() as u64) < min_length {
+                findings.push(finding(
+                    pointer,
+                    "error",
+                    "schema",
+                    "Must not be empty".to_string(),
+                ));
+            }
+        }
+        if schema["format"].as_str() == Some("uri") && Url::parse(text).is_err() {
+            findings.push(finding(
+                pointer,
+                "error",
+                "schema",
+                format!("{} is not an absolute URL", text),
+            ));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for field in schema["required"].as_array().into_iter().flatten() {
+            if let Some(field) = field.as_str() {
+                if !object.contains_key(field) {
+                    findings.push(finding(
+                        &child_pointer(pointer, field),
+                        "error",
+                        "schema",
+                        format!("Missing required field {}", field),
+                    ));
+                }
+            }
+        }
+        if let Some(properties) = schema["properties"].as_object() {
+            for (key, child) in object {
+                match properties.get(key) {
+                    Some(property) => {
+                        validate_schema(property, child, &child_pointer(pointer, key), findings)
+                    }
+                    None => findings.push(finding(
+                        &child_pointer(pointer, key),
+                        "info",
+                        "unknown-field",
+                        format!("{} is not an A2A field and is ignored by clients", key),
+                    )),
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in array.iter().enumerate() {
+            validate_schema(items, item, &format!("{}/{}", pointer, index), findings);
+        }
+    }
+}
+
+// Rules the schema cannot express
+fn lint_semantics(card: &Value, card_url: Option<&str>, findings: &mut Vec<CardLintFinding>) {
+    if let (Some(url), Some(card_url)) = (card["url"].as_str(), card_url) {
+        if let (Ok(url), Ok(card_url)) = (Url::parse(url), Url::parse(card_url)) {
+            if url.origin() != card_url.origin() {
+                findings.push(finding(
+                    "/url",
+                    "warning",
+                    "url-mismatch",
+                    format!(
+                        "The card is served from {} but points clients to {}",
+                        card_url.origin().ascii_serialization(),
+                        url.origin().ascii_serialization()
+                    ),
+                ));
+            }
+        }
+    }
+
+    if card["description"]
+        .as_str()
+        .is_some_and(|text| text.trim().is_empty())
+    {
+        findings.push(finding(
+            "/description",
+            "warning",
+            "empty-description",
+            "The agent description is empty, planners pick agents by it".to_string(),
+        ));
+    }
+
+    for field in ["defaultInputModes", "defaultOutputModes"] {
+        lint_modes(&card[field], &format!("/{}", field), findings);
+    }
+
+    let mut skill_ids: HashMap<&str, usize> = HashMap::new();
+    for (index, skill) in card["skills"].as_array().into_iter().flatten().enumerate() {
+        let pointer = format!("/skills/{}", index);
+
+        if let Some(id) = skill["id"].as_str() {
+            match skill_ids.get(id) {
+                Some(first) => findings.push(finding(
+                    &format!("{}/id", pointer),
+                    "error",
+                    "duplicate-skill-id",
+                    format!("Skill id {} is already used by /skills/{}", id, first),
+                )),
+                None => {
+                    skill_ids.insert(id, index);
+                }
+            }
+        }
+        if skill["description"]
+            .as_str()
+            .is_some_and(|text| text.trim().is_empty())
+        {
+            findings.push(finding(
+                &format!("{}/description", pointer),
+                "warning",
+                "empty-description",
+                "The skill description is empty, planners pick skills by it".to_string(),
+            ));
+        }
+        for field in ["inputModes", "outputModes"] {
+            lint_modes(&skill[field], &format!("{}/{}", pointer, field), findings);
+        }
+    }
+}
+
+fn lint_modes(modes: &Value, pointer: &str, findings: &mut Vec<CardLintFinding>) {
+    for (index, mode) in modes.as_array().into_iter().flatten().enumerate() {
+        let Some(mode) = mode.as_str() else {
+            continue;
+        };
+        let known = mode.split_once('/').is_some_and(|(top, subtype)| {
+            MIME_TYPES.contains(&top.to_ascii_lowercase().as_str()) && !subtype.trim().is_empty()
+        });
+        if !known {
+            let hint = match mode {
+                "text" => ", use text/plain",
+                "json" | "data" => ", use application/json",
+                _ => "",
+            };
+            findings.push(finding(
+                &format!("{}/{}", pointer, index),
+                "warning",
+                "unknown-mime-type",
+                format!("{} is not a known MIME type{}", mode, hint),
+            ));
+        }
+    }
+}
+
+// How the agent answered a probe call
+enum Probe {
+    Unreachable(String),
+    HttpStatus(u16),
+    RpcError(i64),
+    Served,
+}
+
+struct Linter {
+    client: Client,
+    a2a_server: Option<SettingA2AServer>,
+    token: Option<String>,
+}
+
+impl Linter {
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match (&self.a2a_server, &self.token) {
+            (Some(server), _) => apply_custom_headers(request, server),
+            (None, Some(token)) if !token.is_empty() => request.bearer_auth(token),
+            _ => request,
+        }
+    }
+
+    async fn fetch(&self, card_url: &str) -> Result<String, AppError> {
+        let request = self.authorize(
+            self.client
+                .get(card_url)
+                .header("Accept", "application/json"),
+        );
+        let (mut exchange, resp) = traffic::send(request, "agent_card").await;
+        let resp = resp?;
+
+        let status = resp.status();
+        let body = resp.text().await?;
+        exchange.body(&body);
+        if !status.is_success() {
+            return Err(AppError::from_http_status(status, &body));
+        }
+        Ok(body)
+    }
+
+    async fn probe(&self, url: &str, method: &str, params: Value) -> Probe {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": Uuid::new_v4().to_string(),
+            "method": method,
+            "params": params,
+        });
+        let request = self.authorize(
+            self.client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.to_string()),
+        );
+        let (mut exchange, resp) = traffic::send(request, "a2a").await;
+        let resp = match resp {
+            Ok(resp) => resp,
+            Err(e) => return Probe::Unreachable(e.to_string()),
+        };
+
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        exchange.body(&text);
+        match serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|response| response.pointer("/error/code").and_then(|c| c.as_i64()))
+        {
+            Some(code) => Probe::RpcError(code),
+            None if !status.is_success() => Probe::HttpStatus(status.as_u16()),
+            None => Probe::Served,
+        }
+    }
+
+    // Whether the agent url answers and serves what the capabilities declare
+    async fn lint_network(&self, card: &Value, findings: &mut Vec<CardLintFinding>) {
+        let Some(url) = card["url"].as_str().filter(|url| Url::parse(url).is_ok()) else {
+            return;
+        };
+
+        // A read of an unknown task is harmless and any JSON-RPC reply proves reachability
+        let task_id = Uuid::new_v4().to_string();
+        if let Probe::Unreachable(reason) =
+            self.probe(url, "tasks/get", json!({ "id": task_id })).await
+        {
+            findings.push(finding(
+                "/url",
+                "error",
+                "unreachable-url",
+                format!("The agent url does not answer: {}", reason),
+            ));
+            return;
+        }
+
+        // Empty params are rejected before any message reaches the agent
+        if card
+            .pointer("/capabilities/streaming")
+            .and_then(|v| v.as_bool())
+            == Some(true)
+        {
+            let probe = self.probe(url, "message/stream", json!({})).await;
+            if let Some(reason) = not_served(&probe, &[METHOD_NOT_FOUND, UNSUPPORTED_OPERATION]) {
+                findings.push(finding(
+                    "/capabilities/streaming",
+                    "error",
+                    "capability-not-served",
+                    format!("Streaming is declared but message/stream {}", reason),
+                ));
+            }
+        }
+
+        if card
+            .pointer("/capabilities/pushNotifications")
+            .and_then(|v| v.as_bool())
+            == Some(true)
+        {
+            let probe = self
+                .probe(
+                    url,
+                    "tasks/pushNotificationConfig/get",
+                    json!({ "id": task_id }),
+                )
+                .await;
+            if let Some(reason) =
+                not_served(&probe, &[METHOD_NOT_FOUND, PUSH_NOTIFICATION_NOT_SUPPORTED])
+            {
+                findings.push(finding(
+                    "/capabilities/pushNotifications",
+                    "error",
+                    "capability-not-served",
+                    format!(
+                        "Push notifications are declared but tasks/pushNotificationConfig/get {}",
+                        reason
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn not_served(probe: &Probe, codes: &[i64]) -> Option<String> {
+    match probe {
+        Probe::Unreachable(reason) => Some(format!("failed: {}", reason)),
+        Probe::HttpStatus(status @ (404 | 405 | 501)) => {
+            Some(format!("is answered with status {}", status))
+        }
+        Probe::RpcError(code) if codes.contains(code) => {
+            Some(format!("is answered with error {}", code))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, routing::post};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::test_support::serve;
+
+    const CARD_URL: &str = "https://agent.example.com/.well-known/agent-card.json";
+
+    // A card without findings, each test breaks one rule of it
+    fn valid_card() -> Value {
+        json!({
+            "protocolVersion": "0.3.0",
+            "name": "Echo",
+            "description": "Repeats what it is told",
+            "url": "https://agent.example.com/a2a",
+            "preferredTransport": "JSONRPC",
+            "version": "1.0.0",
+            "capabilities": { "streaming": false },
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain", "application/json"],
+            "skills": [{
+                "id": "echo",
+                "name": "Echo",
+                "description": "Repeats the message",
+                "tags": ["test"],
+                "inputModes": ["text/plain"]
+            }]
+        })
+    }
+
+    async fn lint(card: &Value, url: Option<&str>, check_network: bool) -> CardLintReport {
+        lint_agent_card(CardLintParams {
+            url: url.map(str::to_string),
+            card_json: Some(card.to_string()),
+            a2a_server_id: None,
+            token: None,
+            check_network: Some(check_network),
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn findings(card: &Value) -> Vec<(String, String, String)> {
+        lint(card, Some(CARD_URL), false)
+            .await
+            .findings
+            .into_iter()
+            .map(|finding| (finding.pointer, finding.severity, finding.code))
+            .collect()
+    }
+
+    fn found(pointer: &str, severity: &str, code: &str) -> (String, String, String) {
+        (pointer.to_string(), severity.to_string(), code.to_string())
+    }
+
+    #[tokio::test]
+    async fn a_valid_card_has_no_findings() {
+        let report = lint(&valid_card(), Some(CARD_URL), false).await;
+        assert!(report.valid);
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
+    #[tokio::test]
+    async fn schema_types_required_fields_enums_and_uris_are_checked() {
+        let mut card = valid_card();
+        card["version"] = json!(1);
+        card["preferredTransport"] = json!("SOAP");
+        card["url"] = json!("agent.example.com/a2a");
+        card.as_object_mut().unwrap().remove("skills");
+        card["capabilities"]["streaming"] = json!("yes");
+
+        let report = lint(&card, None, false).await;
+        assert!(!report.valid);
+        let mut findings: Vec<_> = report
+            .findings
+            .into_iter()
+            .map(|finding| (finding.pointer, finding.code, finding.message))
+            .collect();
+        findings.sort();
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "/capabilities/streaming".to_string(),
+                    "schema".to_string(),
+                    "Expected boolean but found string".to_string()
+                ),
+                (
+                    "/preferredTransport".to_string(),
+                    "schema".to_string(),
+                    r#"Must be one of "JSONRPC", "GRPC", "HTTP+JSON""#.to_string()
+                ),
+                (
+                    "/skills".to_string(),
+                    "schema".to_string(),
+                    "Missing required field skills".to_string()
+                ),
+                (
+                    "/url".to_string(),
+                    "schema".to_string(),
+                    "agent.example.com/a2a is not an absolute URL".to_string()
+                ),
+                (
+                    "/version".to_string(),
+                    "schema".to_string(),
+                    "Expected string but found number".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_json_is_a_finding() {
+        let report = lint_agent_card(CardLintParams {
+            url: None,
+            card_json: Some("{\"name\": ".to_string()),
+            a2a_server_id: None,
+            token: None,
+            check_network: Some(false),
+        })
+        .await
+        .unwrap();
+
+        assert!(!report.valid);
+        assert_eq!(report.findings[0].code, "json");
+    }
+
+    #[tokio::test]
+    async fn a_card_pointing_to_another_origin_is_flagged() {
+        let mut card = valid_card();
+        card["url"] = json!("https://other.example.com/a2a");
+
+        assert_eq!(
+            findings(&card).await,
+            vec![found("/url", "warning", "url-mismatch")]
+        );
+    }
+
+    #[tokio::test]
+    async fn duplicate_skill_ids_are_flagged() {
+        let mut card = valid_card();
+        let skill = card["skills"][0].clone();
+        card["skills"].as_array_mut().unwrap().push(skill);
+
+        assert_eq!(
+            findings(&card).await,
+            vec![found("/skills/1/id", "error", "duplicate-skill-id")]
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_card_and_skill_descriptions_are_flagged() {
+        let mut card = valid_card();
+        card["description"] = json!(" ");
+        card["skills"][0]["description"] = json!("");
+
+        assert_eq!(
+            findings(&card).await,
+            vec![
+                found("/description", "warning", "empty-description"),
+                found("/skills/0/description", "warning", "empty-description"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_mime_types_are_flagged() {
+        let mut card = valid_card();
+        card["defaultInputModes"] = json!(["text", "*/*"]);
+        card["skills"][0]["inputModes"] = json!(["json", "spreadsheet/"]);
+
+        let report = lint(&card, Some(CARD_URL), false).await;
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.pointer.as_str(), finding.message.as_str()))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                (
+                    "/defaultInputModes/0",
+                    "text is not a known MIME type, use text/plain"
+                ),
+                (
+                    "/skills/0/inputModes/0",
+                    "json is not a known MIME type, use application/json"
+                ),
+                (
+                    "/skills/0/inputModes/1",
+                    "spreadsheet/ is not a known MIME type"
+                ),
+            ]
+        );
+        assert!(
+            report
+                .findings
+                .iter()
+                .all(|finding| finding.code == "unknown-mime-type")
+        );
+        // Warnings alone leave the card valid
+        assert!(report.valid);
+    }
+
+    #[tokio::test]
+    async fn an_agent_url_that_does_not_answer_is_flagged() {
+        // A port that was free a moment ago refuses the connection
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let mut card = valid_card();
+        card["url"] = json!(format!("http://{}/", addr));
+        let report = lint(&card, None, true).await;
+
+        assert_eq!(report.findings.len(), 1);
+        assert_eq!(report.findings[0].pointer, "/url");
+        assert_eq!(report.findings[0].code, "unreachable-url");
+        assert!(!report.valid);
+    }
+
+    #[tokio::test]
+    async fn declared_capabilities_the_agent_does_not_serve_are_flagged() {
+        // Knows tasks/get and nothing else
+        let app = Router::new().route(
+            "/",
+            post(|Json(request): Json<Value>| async move {
+                let error = match request["method"].as_str() {
+                    Some("tasks/get") => json!({ "code": -32001, "message": "Task not found" }),
+                    _ => json!({ "code": METHOD_NOT_FOUND, "message": "Method not found" }),
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }))
+            }),
+        );
+        let mut card = valid_card();
+        card["url"] = json!(format!("{}/", serve(app).await));
+        card["capabilities"] = json!({ "streaming": true, "pushNotifications": true });
+
+        let report = lint(&card, None, true).await;
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .map(|finding| (finding.pointer.as_str(), finding.code.as_str()))
+            .collect();
+        assert_eq!(
+            findings,
+            vec![
+                ("/capabilities/streaming", "capability-not-served"),
+                ("/capabilities/pushNotifications", "capability-not-served"),
+            ]
+        );
+
+        // The same agent passes once its card stops declaring them
+        card["capabilities"] = json!({ "streaming": false });
+        assert!(lint(&card, None, true).await.findings.is_empty());
+    }
+}
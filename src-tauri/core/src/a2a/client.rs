@@ -0,0 +1,408 @@
+//! Building and sending A2A requests, shared by the desktop commands, the MCP
+//! server and the CLI.
+
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Serialize;
+use serde_json::from_str;
+
+use crate::{
+    a2a::transport::{A2AStream, Endpoint, Operation, with_failover},
+    error::AppError,
+    model::{
+        A2ADataPart, A2AMessage, A2AMessagePart, A2ARequest, A2ATextPart, AgentCard,
+        JSONRPCRequest, SettingA2AServer,
+    },
+    traffic,
+};
+
+/// Fetch and parse the agent card at `url`
+pub async fn fetch_agent_card(url: &str, token: Option<&str>) -> anyhow::Result<AgentCard> {
+    let request = Client::new()
+        .get(with_http_scheme(url))
+        .header("Accept", "application/json")
+        .bearer_auth(token.unwrap_or_default());
+    let (mut exchange, response) = traffic::send(request, "agent_card").await;
+    let resp = response.map_err(|e| {
+        log::error!("Failed to send request: {}", e);
+        AppError::from(e)
+    })?;
+
+    let status = resp.status();
+
+    // Read body, but preserve error information
+    let body_text = match resp.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            log::error!("Failed to read body: {}", e);
+            exchange.error(&e);
+            return Err(AppError::from(e).into());
+        }
+    };
+    exchange.body(&body_text);
+
+    if !status.is_success() {
+        log::error!("Request failed with status {}. Body: {}", status, body_text);
+        return Err(AppError::from_http_status(status, &body_text).into());
+    }
+
+    serde_json::from_str::<AgentCard>(&body_text).map_err(|e| {
+        log::error!(
+            "Failed to parse agent card response: {}. Body: {}",
+            e,
+            body_text
+        );
+        AppError::card_parse(e).into()
+    })
+}
+
+// Build message parts, applying the server's protocol data object settings if present
+pub fn build_a2a_message_parts(a2a_server: &SettingA2AServer, text: String) -> Vec<A2AMessagePart> {
+    // Parse protocol_data_object_settings and create appropriate message parts
+    let settings_obj = a2a_server
+        .protocol_data_object_settings
+        .as_deref()
+        .and_then(|settings_json| from_str::<serde_json::Value>(settings_json).ok());
+
+    if let Some(obj) = settings_obj {
+        if obj.get("kind").and_then(|k| k.as_str()) == Some("data") {
+            let data = obj
+                .get("data")
+                .map(|d| {
+                    if let Some(s) = d.as_str() {
+                        s.to_string()
+                    } else {
+                        d.to_string()
+                    }
+                })
+                .unwrap_or_default()
+                .replace("{{USER_PROMPT}}", &text);
+            return vec![A2AMessagePart::Data(A2ADataPart::new(data))];
+        }
+    }
+
+    vec![A2AMessagePart::Text(A2ATextPart::new(text))]
+}
+
+// The service url advertised by the server's stored agent card
+pub fn card_service_url(a2a_server: &SettingA2AServer) -> Option<String> {
+    a2a_server
+        .agent_card_json
+        .as_deref()
+        .and_then(|card_json| from_str::<serde_json::Value>(card_json).ok())
+        .and_then(|card| {
+            card.get("url")
+                .and_then(|url| url.as_str())
+                .map(|url| url.to_string())
+        })
+}
+
+// Send a message to the agent over the transport its card or settings pick,
+// returns the response body in the JSON-RPC shape
+pub async fn post_a2a_message(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    request_id: String,
+    message: A2AMessage,
+    header_skill_id: String,
+) -> anyhow::Result<String> {
+    post_a2a_message_served(a2a_server, a2a_url, request_id, message, &header_skill_id)
+        .await
+        .map(|(body, _)| body)
+}
+
+/// Send a message, failing over across the card's interfaces. Returns the
+/// response body in the JSON-RPC shape with the endpoint that served it
+pub async fn post_a2a_message_served(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    request_id: String,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> anyhow::Result<(String, Endpoint)> {
+    with_failover(a2a_server, a2a_url, Operation::SendMessage, |endpoint| {
+        let request_id = request_id.clone();
+        let message = message.clone();
+        async move {
+            endpoint
+                .send_message(a2a_server, request_id, message, header_skill_id)
+                .await
+        }
+    })
+    .await
+}
+
+/// Open a streaming message request. Events are read off the returned stream
+/// in the JSON-RPC shape, whichever transport serves them, see
+/// [`A2AStream::endpoint`]. Only opening the stream fails over, a stream that
+/// breaks midway is not resent
+pub async fn open_a2a_stream(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    request_id: String,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> anyhow::Result<A2AStream> {
+    with_failover(a2a_server, a2a_url, Operation::StreamMessage, |endpoint| {
+        let request_id = request_id.clone();
+        let message = message.clone();
+        async move {
+            endpoint
+                .stream_message(a2a_server, request_id, message, header_skill_id)
+                .await
+        }
+    })
+    .await
+    .map(|(stream, _)| stream)
+}
+
+// Fetch a task's current state, returns the response body in the JSON-RPC shape
+pub async fn get_a2a_task(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> anyhow::Result<String> {
+    with_failover(
+        a2a_server,
+        a2a_url,
+        Operation::GetTask,
+        move |endpoint| async move {
+            endpoint
+                .get_task(a2a_server, header_skill_id, task_id)
+                .await
+        },
+    )
+    .await
+    .map(|(body, _)| body)
+}
+
+// Ask the agent to cancel a task, returns the response body in the JSON-RPC shape
+pub async fn cancel_a2a_task(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> anyhow::Result<String> {
+    with_failover(
+        a2a_server,
+        a2a_url,
+        Operation::CancelTask,
+        move |endpoint| async move {
+            endpoint
+                .cancel_task(a2a_server, header_skill_id, task_id)
+                .await
+        },
+    )
+    .await
+    .map(|(body, _)| body)
+}
+
+// Send any JSON-RPC request to the agent and return the raw response body
+pub async fn post_a2a_jsonrpc<T: Serialize>(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    header_skill_id: &str,
+    body: &T,
+) -> anyhow::Result<String> {
+    let client = Client::new();
+    let request = build_a2a_http_request(&client, a2a_server, a2a_url, header_skill_id, body);
+    execute_a2a_request(request).await
+}
+
+// Send a request to the agent and read the body, a non-success status is an error
+pub async fn execute_a2a_request(request: RequestBuilder) -> anyhow::Result<String> {
+    let (mut exchange, resp) = traffic::send(request, "a2a").await;
+    let resp = resp.map_err(|e| {
+        log::error!("Failed to send A2A request: {}", e);
+        AppError::from(e)
+    })?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        exchange.body(&error_text);
+        return Err(
+            anyhow::Error::new(AppError::from_http_status(status, &error_text))
+                .context("A2A request failed"),
+        );
+    }
+
+    match resp.text().await {
+        Ok(text) => {
+            exchange.body(&text);
+            Ok(text)
+        }
+        Err(e) => {
+            log::error!("Failed to read A2A response body: {}", e);
+            exchange.error(&e);
+            Err(anyhow::Error::new(AppError::from(e)).context("Failed to read response body"))
+        }
+    }
+}
+
+/// Send a streaming request to the agent. The caller reads the SSE body and
+/// records the events it reads on the exchange
+pub async fn execute_a2a_stream(
+    request: RequestBuilder,
+) -> anyhow::Result<(traffic::Exchange, Response)> {
+    let request = request.header("Accept", "text/event-stream");
+    let (mut exchange, resp) = traffic::send(request, "a2a").await;
+    let resp = resp.map_err(|e| {
+        log::error!("Failed to send A2A stream request: {}", e);
+        AppError::from(e)
+    })?;
+
+    let status = resp.status();
+    if !status.is_success() {
+        let error_text = resp.text().await.unwrap_or_default();
+        exchange.body(&error_text);
+        return Err(
+            anyhow::Error::new(AppError::from_http_status(status, &error_text))
+                .context("A2A request failed"),
+        );
+    }
+
+    Ok((exchange, resp))
+}
+
+// Wrap a message into a JSON-RPC request for message/send or message/stream
+pub fn build_a2a_jsonrpc_request(
+    method: &str,
+    request_id: String,
+    message: A2AMessage,
+) -> JSONRPCRequest<A2ARequest> {
+    let message_id = message.message_id.clone();
+    let request_body = A2ARequest {
+        id: request_id,
+        message,
+        metadata: serde_json::Value::Object(serde_json::Map::new()),
+    };
+
+    JSONRPCRequest {
+        jsonrpc: "2.0".to_string(),
+        id: message_id,
+        method: method.to_string(),
+        params: request_body,
+    }
+}
+
+// Build the HTTP request for a JSON-RPC call, applying the server's custom headers
+pub fn build_a2a_http_request<T: Serialize>(
+    client: &Client,
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    header_skill_id: &str,
+    body: &T,
+) -> reqwest::RequestBuilder {
+    let request_builder = client
+        .post(with_http_scheme(a2a_url))
+        .header("Content-Type", "application/json")
+        .header("X-A2A-Skill-Id", header_skill_id)
+        .json(body);
+
+    apply_custom_headers(request_builder, a2a_server)
+}
+
+// Agent urls are often entered without a scheme, those default to plain http
+pub fn with_http_scheme(url: &str) -> String {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        format!("http://{}", url)
+    } else {
+        url.to_string()
+    }
+}
+
+// Add the server's custom headers, an Authorization value is sent as a bearer token
+pub fn apply_custom_headers(
+    mut request_builder: reqwest::RequestBuilder,
+    a2a_server: &SettingA2AServer,
+) -> reqwest::RequestBuilder {
+    for (key, value) in custom_headers(a2a_server) {
+        if key == "Authorization" {
+            request_builder = request_builder.bearer_auth(value);
+        } else {
+            request_builder = request_builder.header(key, value);
+        }
+    }
+
+    request_builder
+}
+
+// The server's custom headers as name/value pairs, values that aren't strings are skipped
+pub fn custom_headers(a2a_server: &SettingA2AServer) -> Vec<(String, String)> {
+    a2a_server
+        .custom_header_json
+        .as_deref()
+        .and_then(|headers_json| {
+            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(headers_json).ok()
+        })
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| value.as_str().map(|value| (key, value.to_string())))
+        .collect()
+}
+
+// Text carried by one message/stream event: a message, a status update or an artifact chunk
+pub fn stream_event_text(event: &serde_json::Value) -> Option<String> {
+    let result = event.get("result")?;
+    match result.get("kind").and_then(|k| k.as_str()) {
+        Some("artifact-update") => extract_response_text(&serde_json::json!({
+            "result": { "parts": result.pointer("/artifact/parts") }
+        })),
+        Some("task") => None,
+        _ => extract_response_text(event),
+    }
+}
+
+/// Collect the readable text of a message/send response: the reply message
+/// parts, the task status message and any text artifacts
+pub fn extract_response_text(response: &serde_json::Value) -> Option<String> {
+    if let Some(error) = response.get("error") {
+        let code = error
+            .get("code")
+            .and_then(|c| c.as_i64())
+            .unwrap_or_default();
+        let message = error
+            .get("message")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default();
+        return Some(format!("JSON-RPC error {}: {}", code, message));
+    }
+
+    let result = response.get("result")?;
+    let mut texts = Vec::new();
+
+    let mut collect_parts = |parts: Option<&serde_json::Value>| {
+        for part in parts.and_then(|p| p.as_array()).into_iter().flatten() {
+            match part.get("kind").and_then(|k| k.as_str()) {
+                Some("data") => {
+                    if let Some(data) = part.get("data") {
+                        texts.push(data.to_string());
+                    }
+                }
+                _ => {
+                    if let Some(text) = part.get("text").and_then(|t| t.as_str()) {
+                        texts.push(text.to_string());
+                    }
+                }
+            }
+        }
+    };
+
+    collect_parts(result.get("parts"));
+    collect_parts(result.pointer("/status/message/parts"));
+    for artifact in result
+        .get("artifacts")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+    {
+        collect_parts(artifact.get("parts"));
+    }
+
+    if texts.is_empty() {
+        None
+    } else {
+        Some(texts.join("\n\n"))
+    }
+}
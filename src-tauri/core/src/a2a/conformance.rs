@@ -0,0 +1,909 @@
+//! A2A conformance suite: fetches the agent card and runs JSON-RPC, tasks/* and
+//! message/stream checks against an agent, reporting each check as it completes
+
+use std::{future::Future, time::Instant};
+
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{apply_custom_headers, card_service_url, with_http_scheme},
+    model::{ConformanceCheck, SettingA2AServer},
+    sse::SseParser,
+    traffic,
+};
+
+// A single check never waits longer than this, streaming included
+pub const REQUEST_TIMEOUT_SECS: u64 = 60;
+const PROBE_TEXT: &str = "Hello, this is an A2A conformance check. Please reply briefly.";
+
+const TASK_STATES: [&str; 9] = [
+    "submitted",
+    "working",
+    "input-required",
+    "completed",
+    "canceled",
+    "failed",
+    "rejected",
+    "auth-required",
+    "unknown",
+];
+const TERMINAL_STATES: [&str; 4] = ["completed", "canceled", "failed", "rejected"];
+// A stream may also end while the task waits for the user
+const INTERRUPTED_STATES: [&str; 2] = ["input-required", "auth-required"];
+const STREAM_EVENT_KINDS: [&str; 4] = ["message", "task", "status-update", "artifact-update"];
+
+// JSON-RPC and A2A error codes the spec requires
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const TASK_NOT_FOUND: i64 = -32001;
+const TASK_NOT_CANCELABLE: i64 = -32002;
+
+enum Outcome {
+    Passed(String),
+    Skipped(String),
+}
+
+// Err is a failed check with its reason
+type CheckResult = Result<Outcome, String>;
+
+async fn run_check(
+    id: &str,
+    name: &str,
+    check: impl Future<Output = CheckResult>,
+) -> ConformanceCheck {
+    let started = Instant::now();
+    let (status, message) = match check.await {
+        Ok(Outcome::Passed(message)) => ("passed", message),
+        Ok(Outcome::Skipped(message)) => ("skipped", message),
+        Err(message) => ("failed", message),
+    };
+    log::info!("Conformance check {} {}: {}", id, status, message);
+
+    ConformanceCheck {
+        id: id.to_string(),
+        name: name.to_string(),
+        status: status.to_string(),
+        message,
+        duration_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// One run of the suite against a stored server
+pub struct ConformanceSuite {
+    client: Client,
+    server: SettingA2AServer,
+    a2a_url: Option<String>,
+    skill_id: String,
+    card: Option<Value>,
+    // Task created by message/send, used by the tasks/* checks
+    task: Option<Value>,
+}
+
+impl ConformanceSuite {
+    /// The A2A url and skill default to the ones of the fetched card
+    pub fn new(
+        client: Client,
+        server: SettingA2AServer,
+        a2a_url: Option<String>,
+        skill_id: Option<String>,
+    ) -> Self {
+        Self {
+            client,
+            server,
+            a2a_url,
+            skill_id: skill_id.unwrap_or_default(),
+            card: None,
+            task: None,
+        }
+    }
+
+    /// Run every check in order, handing each to `on_check` as it completes.
+    /// Returns the A2A url the checks were sent to
+    pub async fn run(mut self, mut on_check: impl FnMut(ConformanceCheck)) -> Option<String> {
+        on_check(run_check("card.fetch", "Agent card fetch", self.fetch_card()).await);
+        on_check(
+            run_check("card.schema", "Agent card required fields", async {
+                self.check_card_schema()
+            })
+            .await,
+        );
+
+        self.a2a_url = self
+            .a2a_url
+            .take()
+            .or_else(|| self.card_str("/url").map(|url| url.to_string()))
+            .or_else(|| card_service_url(&self.server))
+            .map(|url| with_http_scheme(&url));
+        if self.skill_id.is_empty() {
+            self.skill_id = self
+                .card_str("/skills/0/id")
+                .unwrap_or_default()
+                .to_string();
+        }
+
+        on_check(
+            run_check(
+                "message.send",
+                "message/send happy path",
+                self.message_send(),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "jsonrpc.unknown_method",
+                "Unknown method returns -32601",
+                self.expect_rpc_error("conformance/unknown-method", json!({}), METHOD_NOT_FOUND),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "jsonrpc.invalid_params",
+                "Bad params return -32602",
+                self.expect_rpc_error(
+                    "message/send",
+                    json!({ "message": "not a message" }),
+                    INVALID_PARAMS,
+                ),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "jsonrpc.parse_error",
+                "Malformed JSON returns -32700",
+                self.parse_error(),
+            )
+            .await,
+        );
+        on_check(run_check("tasks.get", "tasks/get returns the task", self.tasks_get()).await);
+        on_check(
+            run_check(
+                "tasks.get_unknown",
+                "tasks/get of an unknown task returns -32001",
+                self.expect_rpc_error(
+                    "tasks/get",
+                    json!({ "id": Uuid::new_v4().to_string() }),
+                    TASK_NOT_FOUND,
+                ),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "tasks.cancel",
+                "tasks/cancel follows the task state",
+                self.tasks_cancel(),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "tasks.cancel_unknown",
+                "tasks/cancel of an unknown task returns -32001",
+                self.expect_rpc_error(
+                    "tasks/cancel",
+                    json!({ "id": Uuid::new_v4().to_string() }),
+                    TASK_NOT_FOUND,
+                ),
+            )
+            .await,
+        );
+        on_check(
+            run_check(
+                "stream.events",
+                "message/stream event order and terminal state",
+                self.message_stream(),
+            )
+            .await,
+        );
+
+        self.a2a_url
+    }
+
+    fn card_str(&self, pointer: &str) -> Option<&str> {
+        self.card.as_ref()?.pointer(pointer)?.as_str()
+    }
+
+    async fn fetch_card(&mut self) -> CheckResult {
+        let request = apply_custom_headers(
+            self.client
+                .get(with_http_scheme(&self.server.agent_card_url))
+                .header("Accept", "application/json"),
+            &self.server,
+        );
+        let (mut exchange, resp) = traffic::send(request, "agent_card").await;
+        let resp = resp.map_err(|e| format!("Failed to fetch the agent card: {}", e))?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read the agent card: {}", e))?;
+        exchange.body(&body);
+        if !status.is_success() {
+            return Err(format!("Agent card request failed with status {}", status));
+        }
+
+        let card = serde_json::from_str::<Value>(&body)
+            .map_err(|e| format!("Agent card is not valid JSON: {}", e))?;
+        if !card.is_object() {
+            return Err("Agent card is not a JSON object".to_string());
+        }
+        self.card = Some(card);
+
+        Ok(Outcome::Passed(format!(
+            "Fetched the card of {}",
+            self.card_str("/name").unwrap_or("an unnamed agent")
+        )))
+    }
+
+    fn check_card_schema(&self) -> CheckResult {
+        let Some(card) = &self.card else {
+            return Ok(Outcome::Skipped("No agent card was fetched".to_string()));
+        };
+
+        let mut problems = Vec::new();
+        for field in ["name", "description", "url", "version"] {
+            if !card[field].is_string() {
+                problems.push(format!("{} must be a string", field));
+            }
+        }
+        if let Some(url) = card["url"].as_str() {
+            if reqwest::Url::parse(url).is_err() {
+                problems.push(format!("url {} is not an absolute URL", url));
+            }
+        }
+        if !card["capabilities"].is_object() {
+            problems.push("capabilities must be an object".to_string());
+        }
+        for field in ["defaultInputModes", "defaultOutputModes"] {
+            if !is_string_array(&card[field]) {
+                problems.push(format!("{} must be an array of strings", field));
+            }
+        }
+        match card["skills"].as_array() {
+            Some(skills) => {
+                for (index, skill) in skills.iter().enumerate() {
+                    for field in ["id", "name", "description"] {
+                        if !skill[field].is_string() {
+                            problems.push(format!("skills[{}].{} must be a string", index, field));
+                        }
+                    }
+                    if !is_string_array(&skill["tags"]) {
+                        problems.push(format!(
+                            "skills[{}].tags must be an array of strings",
+                            index
+                        ));
+                    }
+                }
+            }
+            None => problems.push("skills must be an array".to_string()),
+        }
+
+        if problems.is_empty() {
+            Ok(Outcome::Passed(
+                "The card has every required field".to_string(),
+            ))
+        } else {
+            Err(problems.join("; "))
+        }
+    }
+
+    fn a2a_url(&self) -> Result<&str, String> {
+        self.a2a_url
+            .as_deref()
+            .ok_or_else(|| "Neither the card nor the server settings give an A2A url".to_string())
+    }
+
+    // POST a raw body to the A2A url, the caller records what it reads on the exchange
+    async fn post(
+        &self,
+        body: String,
+        accept: &str,
+    ) -> Result<(traffic::Exchange, reqwest::Response), String> {
+        let request = apply_custom_headers(
+            self.client
+                .post(self.a2a_url()?)
+                .header(CONTENT_TYPE, "application/json")
+                .header("Accept", accept)
+                .header("X-A2A-Skill-Id", &self.skill_id)
+                .body(body),
+            &self.server,
+        );
+        let (exchange, resp) = traffic::send(request, "a2a").await;
+        let resp = resp.map_err(|e| format!("Request failed: {}", e))?;
+        Ok((exchange, resp))
+    }
+
+    // The parsed JSON response, whatever the HTTP status
+    async fn post_json(&self, body: String) -> Result<Value, String> {
+        let (mut exchange, resp) = self.post(body, "application/json").await?;
+        let status = resp.status();
+        let text = resp
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read the response: {}", e))?;
+        exchange.body(&text);
+        serde_json::from_str(&text).map_err(|_| {
+            format!(
+                "Status {} with a body that is not JSON: {}",
+                status,
+                text.chars().take(200).collect::<String>()
+            )
+        })
+    }
+
+    /// A JSON-RPC call, failing on a response that is not a valid JSON-RPC envelope
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = Uuid::new_v4().to_string();
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let response = self.post_json(request.to_string()).await?;
+        check_envelope(&response, &json!(id))?;
+        Ok(response)
+    }
+
+    async fn expect_rpc_error(&self, method: &str, params: Value, code: i64) -> CheckResult {
+        let response = self.rpc(method, params).await?;
+        expect_error_code(&response, code)
+    }
+
+    async fn parse_error(&self) -> CheckResult {
+        let response = self
+            .post_json(r#"{"jsonrpc": "2.0", "method": "message/send", "params": "#.to_string())
+            .await?;
+        check_envelope(&response, &Value::Null)?;
+        expect_error_code(&response, PARSE_ERROR)
+    }
+
+    async fn message_send(&mut self) -> CheckResult {
+        let message = json!({
+            "kind": "message",
+            "role": "user",
+            "messageId": Uuid::new_v4().to_string(),
+            "parts": [{ "kind": "text", "text": PROBE_TEXT }],
+        });
+        let response = self
+            .rpc("message/send", json!({ "message": message }))
+            .await?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("message/send returned an error: {}", error));
+        }
+
+        let result = &response["result"];
+        match result["kind"].as_str() {
+            Some("message") => {
+                check_message(result)?;
+                Ok(Outcome::Passed(
+                    "The agent replied with a message".to_string(),
+                ))
+            }
+            Some("task") => {
+                let state = check_task(result)?.to_string();
+                self.task = Some(result.clone());
+                Ok(Outcome::Passed(format!(
+                    "The agent created task {} in state {}",
+                    result["id"].as_str().unwrap_or_default(),
+                    state
+                )))
+            }
+            other => Err(format!(
+                "result.kind must be \"message\" or \"task\", got {}",
+                other.unwrap_or("nothing")
+            )),
+        }
+    }
+
+    async fn tasks_get(&mut self) -> CheckResult {
+        let Some(task_id) = self.task_id() else {
+            return Ok(Outcome::Skipped(
+                "message/send replied without a task".to_string(),
+            ));
+        };
+
+        let response = self.rpc("tasks/get", json!({ "id": task_id })).await?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("tasks/get returned an error: {}", error));
+        }
+        let result = &response["result"];
+        if result["id"].as_str() != Some(task_id.as_str()) {
+            return Err(format!(
+                "tasks/get returned task {} instead of {}",
+                result["id"], task_id
+            ));
+        }
+        let state = check_task(result)?.to_string();
+        // Later checks use the latest state
+        self.task = Some(result.clone());
+
+        Ok(Outcome::Passed(format!("Task {} is {}", task_id, state)))
+    }
+
+    async fn tasks_cancel(&self) -> CheckResult {
+        let (Some(task_id), Some(task)) = (self.task_id(), &self.task) else {
+            return Ok(Outcome::Skipped(
+                "message/send replied without a task".to_string(),
+            ));
+        };
+        let state = task
+            .pointer("/status/state")
+            .and_then(|s| s.as_str())
+            .unwrap_or_default();
+
+        let response = self.rpc("tasks/cancel", json!({ "id": task_id })).await?;
+        if TERMINAL_STATES.contains(&state) {
+            return expect_error_code(&response, TASK_NOT_CANCELABLE)
+                .map(|_| Outcome::Passed(format!("Canceling the {} task returned -32002", state)));
+        }
+
+        match response.get("error") {
+            // The task may have finished between tasks/get and tasks/cancel
+            Some(error) if error["code"].as_i64() == Some(TASK_NOT_CANCELABLE) => Ok(
+                Outcome::Passed("The task finished before it could be canceled".to_string()),
+            ),
+            Some(error) => Err(format!("tasks/cancel returned an error: {}", error)),
+            None => {
+                let state = check_task(&response["result"])?;
+                if state == "canceled" {
+                    Ok(Outcome::Passed("The task was canceled".to_string()))
+                } else {
+                    Err(format!(
+                        "tasks/cancel succeeded but the task is {} instead of canceled",
+                        state
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn message_stream(&self) -> CheckResult {
+        let streaming = self
+            .card
+            .as_ref()
+            .and_then(|card| card.pointer("/capabilities/streaming"))
+            .and_then(|streaming| streaming.as_bool())
+            .unwrap_or(false);
+        if !streaming {
+            return Ok(Outcome::Skipped(
+                "The agent card does not advertise streaming".to_string(),
+            ));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": "message/stream",
+            "params": {
+                "message": {
+                    "kind": "message",
+                    "role": "user",
+                    "messageId": Uuid::new_v4().to_string(),
+                    "parts": [{ "kind": "text", "text": PROBE_TEXT }],
+                }
+            },
+        });
+        let (mut exchange, resp) = self.post(request.to_string(), "text/event-stream").await?;
+
+        let status = resp.status();
+        let content_type = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let body = resp
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to read the stream: {}", e))?;
+        if !content_type.contains("text/event-stream") {
+            return Err(format!(
+                "Status {} with content type {} instead of text/event-stream",
+                status, content_type
+            ));
+        }
+
+        let mut parser = SseParser::new();
+        let mut events = parser.push(&body);
+        events.extend(parser.finish());
+        for event in &events {
+            exchange.event(event);
+        }
+        let results = events
+            .iter()
+            .enumerate()
+            .map(|(index, event)| {
+                let response = serde_json::from_str::<Value>(&event.data)
+                    .map_err(|e| format!("Event {} is not JSON: {}", index, e))?;
+                check_envelope(&response, &json!(id))
+                    .map_err(|e| format!("Event {}: {}", index, e))?;
+                if let Some(error) = response.get("error") {
+                    return Err(format!("Event {} is an error: {}", index, error));
+                }
+                Ok(response["result"].clone())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        check_stream_order(&results).map(|state| {
+            Outcome::Passed(format!(
+                "{} events in order, ended in state {}",
+                results.len(),
+                state
+            ))
+        })
+    }
+
+    fn task_id(&self) -> Option<String> {
+        self.task.as_ref()?["id"].as_str().map(|id| id.to_string())
+    }
+}
+
+fn is_string_array(value: &Value) -> bool {
+    value
+        .as_array()
+        .is_some_and(|items| items.iter().all(|item| item.is_string()))
+}
+
+fn check_envelope(response: &Value, id: &Value) -> Result<(), String> {
+    if response["jsonrpc"].as_str() != Some("2.0") {
+        return Err("Response is missing \"jsonrpc\": \"2.0\"".to_string());
+    }
+    if response.get("id") != Some(id) {
+        return Err(format!(
+            "Response id {} does not match the request id {}",
+            response.get("id").unwrap_or(&Value::Null),
+            id
+        ));
+    }
+    match (response.get("result"), response.get("error")) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        _ => Err("Response must have exactly one of result and error".to_string()),
+    }
+}
+
+fn expect_error_code(response: &Value, code: i64) -> CheckResult {
+    match response.pointer("/error/code").and_then(|c| c.as_i64()) {
+        Some(actual) if actual == code => Ok(Outcome::Passed(format!("Returned error {}", code))),
+        Some(actual) => Err(format!("Expected error {} but got {}", code, actual)),
+        None => Err(format!("Expected error {} but the call succeeded", code)),
+    }
+}
+
+fn check_message(message: &Value) -> Result<(), String> {
+    if message["role"].as_str() != Some("agent") {
+        return Err("A reply message must have role \"agent\"".to_string());
+    }
+    if !message["messageId"].is_string() {
+        return Err("A reply message must have a messageId".to_string());
+    }
+    match message["parts"].as_array() {
+        Some(parts) if !parts.is_empty() => {
+            for (index, part) in parts.iter().enumerate() {
+                if !matches!(part["kind"].as_str(), Some("text" | "file" | "data")) {
+                    return Err(format!("parts[{}].kind must be text, file or data", index));
+                }
+            }
+            Ok(())
+        }
+        _ => Err("A reply message must have at least one part".to_string()),
+    }
+}
+
+// Returns the state of a valid task
+fn check_task(task: &Value) -> Result<&str, String> {
+    if !task["id"].is_string() {
+        return Err("A task must have a string id".to_string());
+    }
+    if !task["contextId"].is_string() {
+        return Err("A task must have a string contextId".to_string());
+    }
+    match task
+        .pointer("/status/state")
+        .and_then(|state| state.as_str())
+    {
+        Some(state) if TASK_STATES.contains(&state) => Ok(state),
+        Some(state) => Err(format!("{} is not a valid task state", state)),
+        None => Err("A task must have status.state".to_string()),
+    }
+}
+
+/// A stream is a single message, or a task followed by status and artifact
+/// updates where only the last status update is final. Returns the end state
+fn check_stream_order(results: &[Value]) -> Result<String, String> {
+    let Some(first) = results.first() else {
+        return Err("The stream ended without events".to_string());
+    };
+
+    for (index, result) in results.iter().enumerate() {
+        let kind = result["kind"].as_str().unwrap_or_default();
+        if !STREAM_EVENT_KINDS.contains(&kind) {
+            return Err(format!("Event {} has unknown kind {}", index, kind));
+        }
+    }
+
+    match first["kind"].as_str() {
+        Some("message") => {
+            if results.len() > 1 {
+                return Err("A stream that starts with a message must end with it".to_string());
+            }
+            check_message(first)?;
+            return Ok("message".to_string());
+        }
+        Some("task") => {
+            check_task(first)?;
+        }
+        _ => return Err("The first event must be a task or a message".to_string()),
+    }
+
+    let mut state = first
+        .pointer("/status/state")
+        .and_then(|state| state.as_str())
+        .unwrap_or_default()
+        .to_string();
+    for (index, result) in results.iter().enumerate().skip(1) {
+        match result["kind"].as_str() {
+            Some("status-update") => {
+                let Some(next) = result
+                    .pointer("/status/state")
+                    .and_then(|state| state.as_str())
+                    .filter(|state| TASK_STATES.contains(state))
+                else {
+                    return Err(format!("Event {} has no valid status.state", index));
+                };
+                state = next.to_string();
+                let is_final = result["final"].as_bool().unwrap_or(false);
+                if is_final && index != results.len() - 1 {
+                    return Err(format!("Event {} is final but more events follow", index));
+                }
+            }
+            Some("artifact-update") => {
+                if !result
+                    .pointer("/artifact/parts")
+                    .is_some_and(|parts| parts.is_array())
+                {
+                    return Err(format!("Event {} has an artifact without parts", index));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "Event {} must be a status or artifact update",
+                    index
+                ));
+            }
+        }
+    }
+
+    if TERMINAL_STATES.contains(&state.as_str()) || INTERRUPTED_STATES.contains(&state.as_str()) {
+        Ok(state)
+    } else {
+        Err(format!(
+            "The stream ended in state {} instead of a terminal or interrupted one",
+            state
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        Json, Router,
+        body::Bytes,
+        extract::State,
+        http::{StatusCode, header},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+    };
+
+    use super::*;
+    use crate::test_support::{a2a_server, serve};
+
+    const TASK_ID: &str = "task-1";
+
+    // What the stub agent gets wrong, each fault breaks exactly one check
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Fault {
+        None,
+        CardStatus,
+        CardSchema,
+        SendKind,
+        UnknownMethod,
+        InvalidParams,
+        ParseError,
+        GetWrongTask,
+        GetUnknown,
+        CancelState,
+        CancelUnknown,
+        StreamOrder,
+    }
+
+    const FAULTS: [(Fault, &str); 11] = [
+        (Fault::CardStatus, "card.fetch"),
+        (Fault::CardSchema, "card.schema"),
+        (Fault::SendKind, "message.send"),
+        (Fault::UnknownMethod, "jsonrpc.unknown_method"),
+        (Fault::InvalidParams, "jsonrpc.invalid_params"),
+        (Fault::ParseError, "jsonrpc.parse_error"),
+        (Fault::GetWrongTask, "tasks.get"),
+        (Fault::GetUnknown, "tasks.get_unknown"),
+        (Fault::CancelState, "tasks.cancel"),
+        (Fault::CancelUnknown, "tasks.cancel_unknown"),
+        (Fault::StreamOrder, "stream.events"),
+    ];
+
+    async fn agent_card(State(fault): State<Fault>) -> Response {
+        let mut card = json!({
+            "name": "Stub",
+            "description": "Conformance stub agent",
+            "url": "http://127.0.0.1/",
+            "version": "1.0.0",
+            "capabilities": { "streaming": true },
+            "defaultInputModes": ["text/plain"],
+            "defaultOutputModes": ["text/plain"],
+            "skills": [{ "id": "echo", "name": "Echo", "description": "Echoes", "tags": [] }],
+        });
+        match fault {
+            Fault::CardStatus => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+            Fault::CardSchema => {
+                card["skills"] = json!("echo");
+                Json(card).into_response()
+            }
+            _ => Json(card).into_response(),
+        }
+    }
+
+    fn task(id: &str, state: &str) -> Value {
+        json!({ "kind": "task", "id": id, "contextId": "ctx-1", "status": { "state": state } })
+    }
+
+    fn error(id: &Value, code: i64) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": "stub error" } })
+    }
+
+    fn result(id: &Value, result: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "result": result })
+    }
+
+    async fn jsonrpc(State(fault): State<Fault>, body: Bytes) -> Response {
+        let Ok(request) = serde_json::from_slice::<Value>(&body) else {
+            let code = if fault == Fault::ParseError {
+                -32600
+            } else {
+                PARSE_ERROR
+            };
+            return Json(error(&Value::Null, code)).into_response();
+        };
+        let id = &request["id"];
+        let task_id = request.pointer("/params/id").and_then(|id| id.as_str());
+
+        let response = match request["method"].as_str().unwrap_or_default() {
+            "message/send"
+                if !request
+                    .pointer("/params/message")
+                    .is_some_and(Value::is_object) =>
+            {
+                match fault {
+                    Fault::InvalidParams => result(id, task(TASK_ID, "working")),
+                    _ => error(id, INVALID_PARAMS),
+                }
+            }
+            "message/send" => match fault {
+                Fault::SendKind => result(id, json!({ "kind": "reply" })),
+                _ => result(id, task(TASK_ID, "working")),
+            },
+            "message/stream" => return stream(id, fault),
+            "tasks/get" => match (task_id, fault) {
+                (Some(TASK_ID), Fault::GetWrongTask) => result(id, task("task-2", "working")),
+                (Some(TASK_ID), _) => result(id, task(TASK_ID, "working")),
+                (_, Fault::GetUnknown) => error(id, -32603),
+                _ => error(id, TASK_NOT_FOUND),
+            },
+            "tasks/cancel" => match (task_id, fault) {
+                (Some(TASK_ID), Fault::CancelState) => result(id, task(TASK_ID, "working")),
+                (Some(TASK_ID), _) => result(id, task(TASK_ID, "canceled")),
+                (_, Fault::CancelUnknown) => error(id, -32603),
+                _ => error(id, TASK_NOT_FOUND),
+            },
+            _ if fault == Fault::UnknownMethod => result(id, json!({})),
+            _ => error(id, METHOD_NOT_FOUND),
+        };
+        Json(response).into_response()
+    }
+
+    fn stream(id: &Value, fault: Fault) -> Response {
+        let status_update = |state: &str, is_final: bool| {
+            json!({
+                "kind": "status-update",
+                "taskId": "task-2",
+                "contextId": "ctx-1",
+                "status": { "state": state },
+                "final": is_final,
+            })
+        };
+        let events = [
+            task("task-2", "submitted"),
+            status_update("working", fault == Fault::StreamOrder),
+            json!({
+                "kind": "artifact-update",
+                "taskId": "task-2",
+                "contextId": "ctx-1",
+                "artifact": { "artifactId": "a1", "parts": [{ "kind": "text", "text": "hi" }] },
+            }),
+            status_update("completed", true),
+        ];
+        let body: String = events
+            .into_iter()
+            .map(|event| format!("data: {}\n\n", result(id, event)))
+            .collect();
+        ([(header::CONTENT_TYPE, "text/event-stream")], body).into_response()
+    }
+
+    async fn run_against(fault: Fault) -> Vec<ConformanceCheck> {
+        let app = Router::new()
+            .route("/.well-known/agent-card.json", get(agent_card))
+            .route("/", post(jsonrpc))
+            .with_state(fault);
+        let base_url = serve(app).await;
+
+        let mut server = a2a_server(None);
+        server.agent_card_url = format!("{}/.well-known/agent-card.json", base_url);
+        let suite = ConformanceSuite::new(Client::new(), server, Some(base_url), None);
+
+        let mut checks = Vec::new();
+        suite.run(|check| checks.push(check)).await;
+        checks
+    }
+
+    fn with_status<'a>(checks: &'a [ConformanceCheck], status: &str) -> Vec<&'a str> {
+        checks
+            .iter()
+            .filter(|check| check.status == status)
+            .map(|check| check.id.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn every_check_passes_against_a_conforming_agent() {
+        let checks = run_against(Fault::None).await;
+        assert_eq!(checks.len(), FAULTS.len());
+        assert_eq!(
+            with_status(&checks, "passed").len(),
+            FAULTS.len(),
+            "{:?}",
+            checks
+        );
+    }
+
+    #[tokio::test]
+    async fn every_check_fails_on_its_fault() {
+        for (fault, check_id) in FAULTS {
+            let checks = run_against(fault).await;
+            assert_eq!(
+                with_status(&checks, "failed"),
+                [check_id],
+                "{:?}: {:?}",
+                fault,
+                checks
+            );
+        }
+    }
+
+    #[test]
+    fn stream_order_requires_a_single_final_update() {
+        let completed =
+            json!({ "kind": "status-update", "status": { "state": "completed" }, "final": true });
+        let working = json!({ "kind": "status-update", "status": { "state": "working" } });
+        let task = task("t", "submitted");
+
+        assert_eq!(
+            check_stream_order(&[task.clone(), working.clone(), completed.clone()]).unwrap(),
+            "completed"
+        );
+        assert!(check_stream_order(&[task.clone(), working]).is_err());
+        assert!(check_stream_order(&[task, completed.clone(), completed]).is_err());
+        assert!(check_stream_order(&[]).is_err());
+    }
+}
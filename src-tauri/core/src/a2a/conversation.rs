@@ -0,0 +1,313 @@
+//! Conversation-aware messaging: the agent's contextId and pending task are
+//! remembered per conversation and every exchange lands in the history
+
+use anyhow::Result;
+
+use crate::{
+    a2a::{
+        client::{build_a2a_message_parts, extract_response_text, post_a2a_message_served},
+        transport::Endpoint,
+    },
+    artifact::store_response_artifacts,
+    db::{
+        a2a_db::SettingA2AServerDbManager, conversation_db::A2AConversationContextDbManager,
+        message_db::ConversationMessageDbManager,
+    },
+    error::AppError,
+    model::{
+        A2AMessage, A2AMessageParams, ContinueA2ATaskParams, SaveConversationMessageParams,
+        SettingA2AServer,
+    },
+};
+
+// Task states in which the agent waits for a follow-up message on the same task
+const PENDING_TASK_STATES: [&str; 2] = ["input-required", "auth-required"];
+
+/// Send a message/send request, continuing the conversation's context with the agent.
+/// Returns the raw response body, a JSON-RPC error in it is returned as an error
+pub async fn send_message(params: &A2AMessageParams) -> Result<String> {
+    let a2a_server = find_server(params.a2a_server_id)?;
+
+    // Reuse the contextId the agent handed out earlier in this conversation
+    let context_id = params.context_id.clone().or_else(|| {
+        params
+            .conversation_id
+            .as_deref()
+            .and_then(|conversation_id| {
+                remembered_context_id(conversation_id, params.a2a_server_id)
+            })
+    });
+
+    let message = A2AMessage {
+        message_id: params.message_id.clone(),
+        kind: "message".to_string(),
+        role: "user".to_string(),
+        parts: build_a2a_message_parts(&a2a_server, params.text.clone()),
+        context_id,
+        task_id: None,
+    };
+
+    let result = post_a2a_message_served(
+        &a2a_server,
+        &params.a2a_url,
+        params.task_id.clone(),
+        message,
+        &params.header_skill_id,
+    )
+    .await;
+
+    if let Some(conversation_id) = &params.conversation_id {
+        record_agent_exchange(
+            conversation_id,
+            &a2a_server,
+            &params.message_id,
+            &params.header_skill_id,
+            &params.text,
+            result
+                .as_ref()
+                .map(|(body, endpoint)| (body.as_str(), endpoint))
+                .map_err(|e| e.to_string()),
+        );
+    }
+
+    let (response_text, _) = result?;
+    // The UI tells "task not found" from other agent failures by the code
+    if let Some(err) = AppError::from_json_rpc_body(&response_text) {
+        return Err(err.into());
+    }
+    if let Some(conversation_id) = &params.conversation_id {
+        remember_task_context(conversation_id, params.a2a_server_id, &response_text);
+    }
+    tokio::spawn(store_response_artifacts(
+        params.conversation_id.clone(),
+        params.a2a_server_id,
+        response_text.clone(),
+    ));
+    Ok(response_text)
+}
+
+/// Answer the task the agent left waiting for input in this conversation
+pub async fn continue_task(params: &ContinueA2ATaskParams) -> Result<String> {
+    let context = A2AConversationContextDbManager::new()
+        .get(&params.conversation_id, params.a2a_server_id)?
+        .ok_or_else(|| AppError::not_found("No A2A task found for this conversation"))?;
+
+    let task_state = context.task_state.as_deref().unwrap_or_default();
+    if !PENDING_TASK_STATES.contains(&task_state) {
+        return Err(AppError::invalid_params(format!(
+            "A2A task is not waiting for input (state: {})",
+            if task_state.is_empty() {
+                "unknown"
+            } else {
+                task_state
+            }
+        ))
+        .into());
+    }
+
+    let Some(task_id) = context.task_id else {
+        return Err(AppError::invalid_params("Pending A2A task has no taskId").into());
+    };
+
+    let a2a_server = find_server(params.a2a_server_id)?;
+
+    log::info!(
+        "Continuing A2A task {} ({}) for conversation {}",
+        task_id,
+        task_state,
+        params.conversation_id
+    );
+
+    let message = A2AMessage {
+        message_id: params.message_id.clone(),
+        kind: "message".to_string(),
+        role: "user".to_string(),
+        parts: build_a2a_message_parts(&a2a_server, params.text.clone()),
+        context_id: context.context_id,
+        task_id: Some(task_id.clone()),
+    };
+
+    let result = post_a2a_message_served(
+        &a2a_server,
+        &params.a2a_url,
+        task_id,
+        message,
+        &params.header_skill_id,
+    )
+    .await;
+
+    record_agent_exchange(
+        &params.conversation_id,
+        &a2a_server,
+        &params.message_id,
+        &params.header_skill_id,
+        &params.text,
+        result
+            .as_ref()
+            .map(|(body, endpoint)| (body.as_str(), endpoint))
+            .map_err(|e| e.to_string()),
+    );
+
+    let (response_text, _) = result?;
+    if let Some(err) = AppError::from_json_rpc_body(&response_text) {
+        return Err(err.into());
+    }
+    remember_task_context(
+        &params.conversation_id,
+        params.a2a_server_id,
+        &response_text,
+    );
+    tokio::spawn(store_response_artifacts(
+        Some(params.conversation_id.clone()),
+        params.a2a_server_id,
+        response_text.clone(),
+    ));
+    Ok(response_text)
+}
+
+fn find_server(a2a_server_id: i32) -> Result<SettingA2AServer> {
+    match SettingA2AServerDbManager::new().get_by_id(a2a_server_id) {
+        Ok(Some(server)) => Ok(server),
+        Ok(None) => Err(AppError::not_found("A2A server not found").into()),
+        Err(e) => Err(e.context("Failed to get A2A server")),
+    }
+}
+
+/// Persist an agent call and its outcome as two conversation messages. A
+/// response keeps the endpoint that served it
+pub fn record_agent_exchange(
+    conversation_id: &str,
+    a2a_server: &SettingA2AServer,
+    message_id: &str,
+    skill_id: &str,
+    request_text: &str,
+    response: Result<(&str, &Endpoint), String>,
+) {
+    let db_manager = ConversationMessageDbManager::new();
+    let skill_name = skill_name_from_card(a2a_server, skill_id);
+
+    let base =
+        |suffix: &str, kind: &str, role: &str, content: String| SaveConversationMessageParams {
+            message_id: format!("{}:{}", message_id, suffix),
+            conversation_id: conversation_id.to_string(),
+            role: role.to_string(),
+            kind: kind.to_string(),
+            content,
+            a2a_server_id: a2a_server.id,
+            agent_name: Some(a2a_server.name.clone()),
+            skill_id: Some(skill_id.to_string()),
+            skill_name: skill_name.clone(),
+            task_id: None,
+            task_state: None,
+            model_key: None,
+            endpoint_url: None,
+            transport: None,
+        };
+
+    let request = base("request", "agent_request", "user", request_text.to_string());
+
+    let outcome = match response {
+        Ok((response_text, endpoint)) => {
+            let value = serde_json::from_str::<serde_json::Value>(response_text)
+                .unwrap_or(serde_json::Value::Null);
+            let result = value.get("result");
+            let mut message = base(
+                "response",
+                "agent_response",
+                "agent",
+                extract_response_text(&value).unwrap_or_else(|| response_text.to_string()),
+            );
+            message.task_id = result
+                .and_then(|r| r.get("id").or_else(|| r.get("taskId")))
+                .and_then(|id| id.as_str())
+                .map(|id| id.to_string());
+            message.task_state = result
+                .and_then(|r| r.pointer("/status/state"))
+                .and_then(|state| state.as_str())
+                .map(|state| state.to_string())
+                .or_else(|| value.get("error").map(|_| "failed".to_string()));
+            message.endpoint_url = Some(endpoint.url.clone());
+            message.transport = Some(endpoint.transport.name().to_string());
+            message
+        }
+        Err(error) => {
+            let mut message = base("response", "error", "agent", error);
+            message.task_state = Some("failed".to_string());
+            message
+        }
+    };
+
+    for message in [request, outcome] {
+        if let Err(e) = db_manager.upsert(&message) {
+            log::warn!("Failed to record agent exchange: {}", e);
+        }
+    }
+}
+
+// Resolve the human readable skill name from the stored agent card
+fn skill_name_from_card(a2a_server: &SettingA2AServer, skill_id: &str) -> Option<String> {
+    let card: serde_json::Value =
+        serde_json::from_str(a2a_server.agent_card_json.as_deref()?).ok()?;
+    card.get("skills")?
+        .as_array()?
+        .iter()
+        .find(|skill| skill.get("id").and_then(|id| id.as_str()) == Some(skill_id))
+        .and_then(|skill| skill.get("name"))
+        .and_then(|name| name.as_str())
+        .map(|name| name.to_string())
+}
+
+// Look up the contextId an agent assigned earlier in the conversation
+pub fn remembered_context_id(conversation_id: &str, a2a_server_id: i32) -> Option<String> {
+    match A2AConversationContextDbManager::new().get(conversation_id, a2a_server_id) {
+        Ok(context) => context.and_then(|c| c.context_id),
+        Err(e) => {
+            log::warn!("Failed to load A2A conversation context: {}", e);
+            None
+        }
+    }
+}
+
+// Store the contextId/taskId/state carried by a message/send response
+pub fn remember_task_context(conversation_id: &str, a2a_server_id: i32, response_text: &str) {
+    let Some((context_id, task_id, task_state)) = extract_task_context(response_text) else {
+        log::debug!("A2A response carries no task context, nothing to remember");
+        return;
+    };
+
+    if let Err(e) = A2AConversationContextDbManager::new().upsert(
+        conversation_id,
+        a2a_server_id,
+        context_id.as_deref(),
+        task_id.as_deref(),
+        task_state.as_deref(),
+    ) {
+        log::warn!("Failed to remember A2A conversation context: {}", e);
+    }
+}
+
+// Read (contextId, taskId, state) from a JSON-RPC response whose result is a Task or a Message
+fn extract_task_context(
+    response_text: &str,
+) -> Option<(Option<String>, Option<String>, Option<String>)> {
+    let response: serde_json::Value = serde_json::from_str(response_text).ok()?;
+    let result = response.get("result")?;
+    let as_string =
+        |value: Option<&serde_json::Value>| value.and_then(|v| v.as_str()).map(|v| v.to_string());
+
+    let context_id = as_string(result.get("contextId"));
+    let (task_id, task_state) = match result.get("kind").and_then(|k| k.as_str()) {
+        Some("task") => (
+            as_string(result.get("id")),
+            as_string(result.pointer("/status/state")),
+        ),
+        // A direct message reply completes the exchange unless it references a task
+        _ => (as_string(result.get("taskId")), None),
+    };
+
+    if context_id.is_none() && task_id.is_none() {
+        return None;
+    }
+
+    Some((context_id, task_id, task_state))
+}
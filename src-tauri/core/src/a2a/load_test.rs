@@ -0,0 +1,637 @@
+//! Load-testing engine: fires message/send or message/stream requests at an
+//! agent at a fixed rate or concurrency and aggregates the outcomes as they
+//! arrive, so a long run holds neither every sample nor every request handle
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use futures_util::StreamExt;
+use reqwest::Client;
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{build_a2a_http_request, build_a2a_jsonrpc_request, build_a2a_message_parts},
+    model::{
+        A2AMessage, LatencyStats, LoadTestParams, LoadTestProgress, LoadTestSummary,
+        SettingA2AServer,
+    },
+    sse::SseParser,
+};
+
+pub const DEFAULT_CONCURRENCY: usize = 10;
+pub const MAX_CONCURRENCY: usize = 512;
+pub const MAX_RATE_PER_SEC: f64 = 1000.0;
+pub const MAX_DURATION_SECS: u64 = 3600;
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+// Upper bounds of the reported histogram buckets, the last bucket catches the rest
+const HISTOGRAM_BUCKETS_MS: [f64; 11] = [
+    5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0,
+];
+
+// Percentiles come from log-scaled buckets growing by 1% from 10µs, so they are
+// within 1% of the exact value. 2400 buckets reach past two days
+const PRECISION_MIN_MS: f64 = 0.01;
+const PRECISION_GROWTH: f64 = 1.01;
+const PRECISION_BUCKETS: usize = 2400;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Load {
+    // Open model: new requests start on a fixed schedule regardless of responses
+    Rate(f64),
+    // Closed model: a fixed number of requests are kept in flight
+    Concurrency(usize),
+}
+
+enum Outcome {
+    Ok,
+    JsonRpcError(i64),
+    HttpStatus(u16),
+    Transport(&'static str),
+}
+
+struct Sample {
+    latency_ms: f64,
+    ttfe_ms: Option<f64>,
+    outcome: Outcome,
+}
+
+/// Latencies aggregated as they are recorded, in constant memory
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    count: usize,
+    sum_ms: f64,
+    min_ms: f64,
+    max_ms: f64,
+    precise: Vec<usize>,
+    report: [usize; HISTOGRAM_BUCKETS_MS.len() + 1],
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum_ms: 0.0,
+            min_ms: f64::INFINITY,
+            max_ms: 0.0,
+            precise: vec![0; PRECISION_BUCKETS],
+            report: [0; HISTOGRAM_BUCKETS_MS.len() + 1],
+        }
+    }
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, value_ms: f64) {
+        let value_ms = value_ms.max(0.0);
+        self.count += 1;
+        self.sum_ms += value_ms;
+        self.min_ms = self.min_ms.min(value_ms);
+        self.max_ms = self.max_ms.max(value_ms);
+        self.precise[precise_bucket(value_ms)] += 1;
+
+        let bucket = HISTOGRAM_BUCKETS_MS
+            .iter()
+            .position(|bound| value_ms <= *bound)
+            .unwrap_or(HISTOGRAM_BUCKETS_MS.len());
+        self.report[bucket] += 1;
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Nearest-rank percentile, 0 while nothing was recorded
+    pub fn percentile(&self, p: f64) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        let rank = ((p / 100.0) * self.count as f64).ceil() as usize;
+        let rank = rank.clamp(1, self.count);
+        // The extremes are known exactly
+        if rank == 1 {
+            return self.min_ms;
+        }
+        if rank == self.count {
+            return self.max_ms;
+        }
+
+        let mut seen = 0;
+        for (bucket, count) in self.precise.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return precise_upper_bound(bucket).clamp(self.min_ms, self.max_ms);
+            }
+        }
+        self.max_ms
+    }
+
+    pub fn stats(&self) -> LatencyStats {
+        let histogram = HISTOGRAM_BUCKETS_MS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .zip(self.report)
+            .collect();
+
+        LatencyStats {
+            count: self.count,
+            min_ms: if self.count == 0 { 0.0 } else { self.min_ms },
+            mean_ms: if self.count == 0 {
+                0.0
+            } else {
+                self.sum_ms / self.count as f64
+            },
+            p50_ms: self.percentile(50.0),
+            p95_ms: self.percentile(95.0),
+            p99_ms: self.percentile(99.0),
+            max_ms: self.max_ms,
+            histogram,
+        }
+    }
+}
+
+fn precise_bucket(value_ms: f64) -> usize {
+    if value_ms <= PRECISION_MIN_MS {
+        return 0;
+    }
+    let bucket = ((value_ms / PRECISION_MIN_MS).ln() / PRECISION_GROWTH.ln()).ceil() as usize;
+    bucket.min(PRECISION_BUCKETS - 1)
+}
+
+fn precise_upper_bound(bucket: usize) -> f64 {
+    PRECISION_MIN_MS * PRECISION_GROWTH.powi(bucket as i32)
+}
+
+// Outcomes of the finished requests
+#[derive(Default)]
+struct Aggregate {
+    total: usize,
+    latency: LatencyHistogram,
+    ttfe: LatencyHistogram,
+    errors_by_jsonrpc_code: BTreeMap<String, usize>,
+    errors_by_http_status: BTreeMap<String, usize>,
+    transport_errors: BTreeMap<String, usize>,
+}
+
+impl Aggregate {
+    // Latencies are only kept for successful requests
+    fn record(&mut self, sample: Sample) {
+        self.total += 1;
+        match sample.outcome {
+            Outcome::Ok => {
+                self.latency.record(sample.latency_ms);
+                if let Some(ttfe_ms) = sample.ttfe_ms {
+                    self.ttfe.record(ttfe_ms);
+                }
+            }
+            Outcome::JsonRpcError(code) => {
+                *self
+                    .errors_by_jsonrpc_code
+                    .entry(code.to_string())
+                    .or_insert(0) += 1;
+            }
+            Outcome::HttpStatus(status) => {
+                *self
+                    .errors_by_http_status
+                    .entry(status.to_string())
+                    .or_insert(0) += 1;
+            }
+            Outcome::Transport(kind) => {
+                *self.transport_errors.entry(kind.to_string()).or_insert(0) += 1;
+            }
+        }
+    }
+
+    fn summary(&self, elapsed_secs: f64) -> LoadTestSummary {
+        let succeeded = self.latency.count();
+        LoadTestSummary {
+            total: self.total,
+            succeeded,
+            failed: self.total - succeeded,
+            elapsed_secs,
+            throughput_rps: if elapsed_secs > 0.0 {
+                self.total as f64 / elapsed_secs
+            } else {
+                0.0
+            },
+            latency: self.latency.stats(),
+            time_to_first_event: (self.ttfe.count() > 0).then(|| self.ttfe.stats()),
+            errors_by_jsonrpc_code: self.errors_by_jsonrpc_code.clone(),
+            errors_by_http_status: self.errors_by_http_status.clone(),
+            transport_errors: self.transport_errors.clone(),
+        }
+    }
+}
+
+pub struct LoadTestRunner {
+    client: Client,
+    a2a_server: SettingA2AServer,
+    a2a_url: String,
+    skill_id: String,
+    prompt: String,
+    stream: bool,
+    deadline: Instant,
+    stop: Arc<AtomicBool>,
+    sent: AtomicUsize,
+    completed: AtomicUsize,
+    failed: AtomicUsize,
+    aggregate: Mutex<Aggregate>,
+}
+
+impl LoadTestRunner {
+    /// A run of `params.duration_secs` from now, ended early once `stop` is set
+    pub fn new(
+        client: Client,
+        a2a_server: SettingA2AServer,
+        a2a_url: String,
+        params: &LoadTestParams,
+        stop: Arc<AtomicBool>,
+    ) -> Self {
+        Self {
+            client,
+            a2a_server,
+            a2a_url,
+            skill_id: params.skill_id.clone(),
+            prompt: params.prompt.clone(),
+            stream: params.stream,
+            deadline: Instant::now() + Duration::from_secs(params.duration_secs),
+            stop,
+            sent: AtomicUsize::new(0),
+            completed: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+            aggregate: Mutex::new(Aggregate::default()),
+        }
+    }
+
+    /// Whether the run was stopped before its deadline
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+
+    fn should_stop(&self) -> bool {
+        self.is_stopped() || Instant::now() >= self.deadline
+    }
+
+    /// Fire requests until the deadline or a stop, then wait for the ones in
+    /// flight and summarize the run
+    pub async fn run(self: Arc<Self>, load: Load) -> LoadTestSummary {
+        let started = Instant::now();
+        let mut in_flight = JoinSet::new();
+
+        match load {
+            Load::Concurrency(concurrency) => {
+                for _ in 0..concurrency {
+                    let runner = self.clone();
+                    in_flight.spawn(async move {
+                        while !runner.should_stop() {
+                            runner.fire().await;
+                        }
+                    });
+                }
+            }
+            Load::Rate(rate) => {
+                let mut ticker = tokio::time::interval(Duration::from_secs_f64(1.0 / rate));
+                loop {
+                    ticker.tick().await;
+                    if self.should_stop() {
+                        break;
+                    }
+                    // Reap the finished requests, only the running ones are held
+                    while in_flight.try_join_next().is_some() {}
+
+                    let runner = self.clone();
+                    in_flight.spawn(async move { runner.fire().await });
+                }
+            }
+        }
+
+        while in_flight.join_next().await.is_some() {}
+
+        let elapsed_secs = started.elapsed().as_secs_f64();
+        match self.aggregate.lock() {
+            Ok(aggregate) => aggregate.summary(elapsed_secs),
+            Err(e) => {
+                log::error!("Failed to read load test outcomes: {}", e);
+                Aggregate::default().summary(elapsed_secs)
+            }
+        }
+    }
+
+    pub fn progress(&self, run_id: i64, started: Instant) -> LoadTestProgress {
+        LoadTestProgress {
+            run_id,
+            elapsed_secs: started.elapsed().as_secs_f64(),
+            sent: self.sent.load(Ordering::SeqCst),
+            completed: self.completed.load(Ordering::SeqCst),
+            failed: self.failed.load(Ordering::SeqCst),
+            is_complete: false,
+            summary: None,
+        }
+    }
+
+    // Fire one request and record its outcome
+    async fn fire(&self) {
+        self.sent.fetch_add(1, Ordering::SeqCst);
+        let sample = self.send_once().await;
+
+        if !matches!(sample.outcome, Outcome::Ok) {
+            self.failed.fetch_add(1, Ordering::SeqCst);
+        }
+        self.completed.fetch_add(1, Ordering::SeqCst);
+
+        if let Ok(mut aggregate) = self.aggregate.lock() {
+            aggregate.record(sample);
+        }
+    }
+
+    async fn send_once(&self) -> Sample {
+        let message = A2AMessage {
+            message_id: format!("msg_id:{}", Uuid::new_v4()),
+            kind: "message".to_string(),
+            role: "user".to_string(),
+            parts: build_a2a_message_parts(&self.a2a_server, self.prompt.clone()),
+            context_id: None,
+            task_id: None,
+        };
+        let method = if self.stream {
+            "message/stream"
+        } else {
+            "message/send"
+        };
+        let jsonrpc_request =
+            build_a2a_jsonrpc_request(method, format!("task_id:{}", Uuid::new_v4()), message);
+
+        let mut request_builder = build_a2a_http_request(
+            &self.client,
+            &self.a2a_server,
+            &self.a2a_url,
+            &self.skill_id,
+            &jsonrpc_request,
+        );
+        if self.stream {
+            request_builder = request_builder.header("Accept", "text/event-stream");
+        }
+
+        let started = Instant::now();
+        let elapsed_ms = |started: Instant| started.elapsed().as_secs_f64() * 1000.0;
+
+        let resp = match request_builder.send().await {
+            Ok(resp) => resp,
+            Err(e) => {
+                return Sample {
+                    latency_ms: elapsed_ms(started),
+                    ttfe_ms: None,
+                    outcome: Outcome::Transport(classify_transport_error(&e)),
+                };
+            }
+        };
+
+        let status = resp.status();
+        if !status.is_success() {
+            // Drain the body so the connection can be reused
+            let _ = resp.bytes().await;
+            return Sample {
+                latency_ms: elapsed_ms(started),
+                ttfe_ms: None,
+                outcome: Outcome::HttpStatus(status.as_u16()),
+            };
+        }
+
+        if !self.stream {
+            let outcome = match resp.bytes().await {
+                Ok(body) => jsonrpc_outcome(&body),
+                Err(e) => Outcome::Transport(classify_transport_error(&e)),
+            };
+            return Sample {
+                latency_ms: elapsed_ms(started),
+                ttfe_ms: None,
+                outcome,
+            };
+        }
+
+        let mut parser = SseParser::new();
+        let mut body = resp.bytes_stream();
+        let mut ttfe_ms = None;
+        let mut outcome = Outcome::Ok;
+
+        while let Some(chunk) = body.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(e) => {
+                    outcome = Outcome::Transport(classify_transport_error(&e));
+                    break;
+                }
+            };
+
+            for event in parser.push(&chunk) {
+                if ttfe_ms.is_none() {
+                    ttfe_ms = Some(elapsed_ms(started));
+                }
+                if let Outcome::JsonRpcError(code) = jsonrpc_outcome(event.data.as_bytes()) {
+                    outcome = Outcome::JsonRpcError(code);
+                }
+            }
+        }
+
+        if let Some(event) = parser.finish() {
+            if ttfe_ms.is_none() {
+                ttfe_ms = Some(elapsed_ms(started));
+            }
+            if let Outcome::JsonRpcError(code) = jsonrpc_outcome(event.data.as_bytes()) {
+                outcome = Outcome::JsonRpcError(code);
+            }
+        }
+
+        if ttfe_ms.is_none() && matches!(outcome, Outcome::Ok) {
+            outcome = Outcome::Transport("empty_stream");
+        }
+
+        Sample {
+            latency_ms: elapsed_ms(started),
+            ttfe_ms,
+            outcome,
+        }
+    }
+}
+
+// A JSON-RPC body is an error when it carries an "error" member
+fn jsonrpc_outcome(body: &[u8]) -> Outcome {
+    match serde_json::from_slice::<serde_json::Value>(body) {
+        Ok(value) => match value.get("error") {
+            Some(error) => Outcome::JsonRpcError(
+                error
+                    .get("code")
+                    .and_then(|c| c.as_i64())
+                    .unwrap_or_default(),
+            ),
+            None => Outcome::Ok,
+        },
+        Err(_) => Outcome::Transport("invalid_json"),
+    }
+}
+
+fn classify_transport_error(e: &reqwest::Error) -> &'static str {
+    if e.is_timeout() {
+        "timeout"
+    } else if e.is_connect() {
+        "connect"
+    } else if e.is_body() || e.is_decode() {
+        "body"
+    } else {
+        "request"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, routing::post};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::test_support;
+
+    fn params(duration_secs: u64) -> LoadTestParams {
+        LoadTestParams {
+            name: None,
+            a2a_server_id: 1,
+            skill_id: "echo".to_string(),
+            prompt: "ping".to_string(),
+            a2a_url: None,
+            mode: "concurrency".to_string(),
+            rate_per_sec: None,
+            concurrency: None,
+            duration_secs,
+            stream: false,
+            timeout_secs: None,
+        }
+    }
+
+    async fn run_against(app: Router, load: Load) -> LoadTestSummary {
+        let url = test_support::serve(app).await;
+        let runner = Arc::new(LoadTestRunner::new(
+            Client::new(),
+            test_support::a2a_server(None),
+            url,
+            &params(1),
+            Arc::new(AtomicBool::new(false)),
+        ));
+        runner.run(load).await
+    }
+
+    #[test]
+    fn percentiles_are_within_one_percent() {
+        let mut histogram = LatencyHistogram::default();
+        for value in 1..=1000 {
+            histogram.record(value as f64);
+        }
+
+        for (p, exact) in [(50.0, 500.0), (95.0, 950.0), (99.0, 990.0)] {
+            let estimate = histogram.percentile(p);
+            assert!(
+                (estimate - exact).abs() <= exact * 0.01,
+                "p{} estimated {} for {}",
+                p,
+                estimate,
+                exact
+            );
+        }
+        assert_eq!(histogram.percentile(100.0), 1000.0);
+        assert_eq!(histogram.percentile(0.0), 1.0);
+    }
+
+    #[test]
+    fn percentile_of_a_single_value_is_that_value() {
+        let mut histogram = LatencyHistogram::default();
+        histogram.record(42.0);
+        assert_eq!(histogram.percentile(50.0), 42.0);
+        assert_eq!(histogram.percentile(99.0), 42.0);
+    }
+
+    #[test]
+    fn empty_histogram_reports_zeros() {
+        let stats = LatencyHistogram::default().stats();
+        assert_eq!(stats.count, 0);
+        assert_eq!(stats.min_ms, 0.0);
+        assert_eq!(stats.mean_ms, 0.0);
+        assert_eq!(stats.p99_ms, 0.0);
+        assert_eq!(stats.histogram.len(), HISTOGRAM_BUCKETS_MS.len() + 1);
+        assert!(stats.histogram.iter().all(|(_, count)| *count == 0));
+    }
+
+    #[test]
+    fn report_buckets_count_by_upper_bound() {
+        let mut histogram = LatencyHistogram::default();
+        for value in [3.0, 5.0, 7.0, 20000.0] {
+            histogram.record(value);
+        }
+
+        let stats = histogram.stats();
+        assert_eq!(stats.histogram[0], (Some(5.0), 2));
+        assert_eq!(stats.histogram[1], (Some(10.0), 1));
+        assert_eq!(stats.histogram[HISTOGRAM_BUCKETS_MS.len()], (None, 1));
+        assert_eq!(stats.min_ms, 3.0);
+        assert_eq!(stats.max_ms, 20000.0);
+        assert_eq!(stats.mean_ms, 20015.0 / 4.0);
+    }
+
+    #[test]
+    fn precise_buckets_stay_in_range() {
+        assert_eq!(precise_bucket(0.0), 0);
+        assert_eq!(precise_bucket(f64::MAX), PRECISION_BUCKETS - 1);
+        let bucket = precise_bucket(123.4);
+        assert!(precise_upper_bound(bucket) >= 123.4 * 0.9999);
+        assert!(precise_upper_bound(bucket) <= 123.4 * PRECISION_GROWTH);
+    }
+
+    #[tokio::test]
+    async fn run_against_a_stub_agent_counts_successes() {
+        let app = Router::new().route(
+            "/",
+            post(|Json(request): Json<Value>| async move {
+                Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "result": {"kind": "message", "role": "agent", "parts": []},
+                }))
+            }),
+        );
+
+        let summary = run_against(app, Load::Concurrency(4)).await;
+        assert!(summary.total > 0);
+        assert_eq!(summary.failed, 0);
+        assert_eq!(summary.succeeded, summary.total);
+        assert_eq!(summary.latency.count, summary.total);
+        assert!(summary.latency.p50_ms <= summary.latency.max_ms);
+    }
+
+    #[tokio::test]
+    async fn run_against_a_failing_stub_agent_groups_errors() {
+        let app = Router::new().route(
+            "/",
+            post(|Json(request): Json<Value>| async move {
+                Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {"code": -32001, "message": "Task not found"},
+                }))
+            }),
+        );
+
+        let summary = run_against(app, Load::Rate(50.0)).await;
+        assert!(summary.total > 0);
+        assert_eq!(summary.succeeded, 0);
+        assert_eq!(
+            summary.errors_by_jsonrpc_code.get("-32001"),
+            Some(&summary.total)
+        );
+        assert_eq!(summary.latency.count, 0);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod batch;
+pub mod card_lint;
+pub mod client;
+pub mod conformance;
+pub mod conversation;
+pub mod load_test;
+pub mod replay;
+pub mod transport;
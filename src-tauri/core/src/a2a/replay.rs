@@ -0,0 +1,460 @@
+//! Replay of recorded A2A exchanges against another server, with a structural
+//! diff of the recorded and the new response
+
+use std::{collections::BTreeSet, time::Instant};
+
+use reqwest::{
+    Client,
+    header::{CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+};
+use serde_json::Value;
+
+use crate::{
+    a2a::client::{apply_custom_headers, card_service_url, with_http_scheme},
+    db::{a2a_db::SettingA2AServerDbManager, traffic_db::TrafficDbManager},
+    error::AppError,
+    model::{JsonDiffEntry, ReplayParams, ReplayResult, SettingA2AServer, TrafficEntry},
+    redact::REDACTED,
+    sse::SseParser,
+    traffic,
+};
+
+// Ids and times the agent generates on every run, left out of the diff wherever they appear
+const VOLATILE_KEYS: [&str; 7] = [
+    "messageId",
+    "taskId",
+    "contextId",
+    "artifactId",
+    "timestamp",
+    "createdAt",
+    "updatedAt",
+];
+
+// The JSON-RPC envelope id and the id of the task the agent created. Other "id"
+// fields are data and are compared
+const VOLATILE_PATHS: [&str; 2] = ["/id", "/result/id"];
+
+// Computed by the HTTP client for the new request
+const SKIPPED_HEADERS: [&str; 3] = ["host", "content-length", "connection"];
+
+/// Send a recorded A2A request to another server and diff both responses
+pub async fn replay_traffic_entry(params: ReplayParams) -> Result<ReplayResult, AppError> {
+    let entry = match TrafficDbManager::new().get_by_id(params.traffic_entry_id) {
+        Ok(Some(entry)) => entry,
+        Ok(None) => return Err(AppError::not_found("Traffic entry not found")),
+        Err(e) => return Err(AppError::from(e.context("Failed to get traffic entry"))),
+    };
+    if entry.source != "a2a" && entry.source != "replay" {
+        return Err(AppError::invalid_params(
+            "Only A2A exchanges can be replayed",
+        ));
+    }
+    // gRPC calls are recorded with their request as JSON, which can't be sent as is
+    if entry
+        .request_headers
+        .iter()
+        .any(|header| header.value.starts_with("application/grpc"))
+    {
+        return Err(AppError::invalid_params("gRPC exchanges can't be replayed"));
+    }
+
+    let Some(body) = params.body.clone().or_else(|| entry.request_body.clone()) else {
+        return Err(AppError::invalid_params(
+            "The recorded exchange has no request body",
+        ));
+    };
+    let request_json = match serde_json::from_str::<Value>(&body) {
+        Ok(request_json) => request_json,
+        Err(e) => {
+            return Err(AppError::invalid_params(format!(
+                "Request body is not valid JSON: {}",
+                e
+            )));
+        }
+    };
+    // The request goes to the target's JSON-RPC url, HTTP+JSON calls like
+    // `POST /v1/message:send` or `GET /v1/tasks/{id}` have no JSON-RPC equivalent there
+    if !entry.method.eq_ignore_ascii_case("POST") || !is_json_rpc_request(&request_json) {
+        return Err(AppError::invalid_params(format!(
+            "Only JSON-RPC exchanges can be replayed, {} {} is not one",
+            entry.method, entry.url
+        )));
+    }
+
+    let a2a_server = match SettingA2AServerDbManager::new().get_by_id(params.a2a_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return Err(AppError::not_found("A2A server not found")),
+        Err(e) => return Err(AppError::from(e.context("Failed to get A2A server"))),
+    };
+    let Some(a2a_url) = params
+        .a2a_url
+        .clone()
+        .or_else(|| card_service_url(&a2a_server))
+    else {
+        return Err(AppError::invalid_params("A2A server has no agent card url"));
+    };
+
+    let mut overrides = HeaderMap::new();
+    for (name, value) in params.headers.iter().flatten() {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                overrides.insert(name, value);
+            }
+            _ => {
+                return Err(AppError::invalid_params(format!(
+                    "Invalid header: {}",
+                    name
+                )));
+            }
+        }
+    }
+    let removed = params.remove_headers.unwrap_or_default();
+    let recorded = recorded_headers(&entry, &a2a_server, &removed);
+
+    let url = with_http_scheme(&a2a_url);
+    // Precedence: explicit overrides, then the target server's headers, then the recorded ones
+    let request = apply_custom_headers(Client::new().post(&url).headers(recorded), &a2a_server)
+        .headers(overrides)
+        .body(body);
+
+    let started = Instant::now();
+    let (mut exchange, resp) = traffic::send(request, "replay").await;
+    let resp = match resp {
+        Ok(resp) => resp,
+        Err(e) => {
+            log::error!("Failed to replay traffic entry {}: {}", entry.url, e);
+            return Err(AppError::from(e));
+        }
+    };
+
+    let status = resp.status().as_u16();
+    let is_stream = resp
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("text/event-stream"));
+
+    let response = if is_stream {
+        let bytes = match resp.bytes().await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                exchange.error(&e);
+                return Err(AppError::from(e));
+            }
+        };
+        let mut parser = SseParser::new();
+        let mut events = parser.push(&bytes);
+        events.extend(parser.finish());
+        for event in &events {
+            exchange.event(event);
+        }
+        Value::Array(events.iter().map(|event| parse_json(&event.data)).collect())
+    } else {
+        let text = match resp.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                exchange.error(&e);
+                return Err(AppError::from(e));
+            }
+        };
+        exchange.body(&text);
+        parse_json(&text)
+    };
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let differences = json_diff(
+        &recorded_response(&entry),
+        &response,
+        &params.ignore_paths.unwrap_or_default(),
+    );
+    log::info!(
+        "Replayed traffic entry {} against {}: {} differences",
+        params.traffic_entry_id,
+        url,
+        differences.len()
+    );
+
+    Ok(ReplayResult {
+        url,
+        status,
+        duration_ms,
+        response,
+        identical: differences.is_empty(),
+        differences,
+    })
+}
+
+/// Structural diff of two JSON values, skipping the volatile fields and the
+/// given JSON pointers. Paths are JSON pointers, the root is "/"
+pub fn json_diff(original: &Value, replayed: &Value, ignored: &[String]) -> Vec<JsonDiffEntry> {
+    let mut differences = Vec::new();
+    diff_at("", original, replayed, ignored, &mut differences);
+    differences
+}
+
+fn diff_at(
+    path: &str,
+    original: &Value,
+    replayed: &Value,
+    ignored: &[String],
+    differences: &mut Vec<JsonDiffEntry>,
+) {
+    // The root is reported as "/", so it is ignored as "/" too
+    let reported = if path.is_empty() { "/" } else { path };
+    if ignored.iter().any(|ignored| ignored == reported) {
+        return;
+    }
+
+    match (original, replayed) {
+        (Value::Object(original), Value::Object(replayed)) => {
+            let keys: BTreeSet<&String> = original.keys().chain(replayed.keys()).collect();
+            for key in keys {
+                let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                if is_volatile(key, &child) {
+                    continue;
+                }
+                diff_child(
+                    child,
+                    original.get(key),
+                    replayed.get(key),
+                    ignored,
+                    differences,
+                );
+            }
+        }
+        (Value::Array(original), Value::Array(replayed)) => {
+            for index in 0..original.len().max(replayed.len()) {
+                let child = format!("{}/{}", path, index);
+                diff_child(
+                    child,
+                    original.get(index),
+                    replayed.get(index),
+                    ignored,
+                    differences,
+                );
+            }
+        }
+        _ if original == replayed => {}
+        _ => differences.push(diff_entry(path, "changed", Some(original), Some(replayed))),
+    }
+}
+
+fn is_volatile(key: &str, path: &str) -> bool {
+    if VOLATILE_KEYS.contains(&key) {
+        return true;
+    }
+    // A streamed response is an array of events, each with its own envelope
+    let envelope_path = path
+        .strip_prefix('/')
+        .and_then(|rest| rest.split_once('/'))
+        .filter(|(index, _)| index.parse::<usize>().is_ok())
+        .map_or(path, |(index, _)| &path[index.len() + 1..]);
+    VOLATILE_PATHS.contains(&path) || VOLATILE_PATHS.contains(&envelope_path)
+}
+
+fn diff_child(
+    path: String,
+    original: Option<&Value>,
+    replayed: Option<&Value>,
+    ignored: &[String],
+    differences: &mut Vec<JsonDiffEntry>,
+) {
+    if ignored.contains(&path) {
+        return;
+    }
+
+    match (original, replayed) {
+        (Some(original), Some(replayed)) => {
+            diff_at(&path, original, replayed, ignored, differences)
+        }
+        (Some(original), None) => {
+            differences.push(diff_entry(&path, "removed", Some(original), None))
+        }
+        (None, Some(replayed)) => {
+            differences.push(diff_entry(&path, "added", None, Some(replayed)))
+        }
+        (None, None) => {}
+    }
+}
+
+fn diff_entry(
+    path: &str,
+    kind: &str,
+    original: Option<&Value>,
+    replayed: Option<&Value>,
+) -> JsonDiffEntry {
+    JsonDiffEntry {
+        path: if path.is_empty() { "/" } else { path }.to_string(),
+        kind: kind.to_string(),
+        original: original.cloned(),
+        replayed: replayed.cloned(),
+    }
+}
+
+fn is_json_rpc_request(request: &Value) -> bool {
+    request.get("jsonrpc").and_then(|v| v.as_str()) == Some("2.0")
+        && request
+            .get("method")
+            .is_some_and(|method| method.is_string())
+}
+
+// Recorded request headers worth sending again. Credentials were redacted when
+// recorded and headers the target server sets itself are left to it
+fn recorded_headers(
+    entry: &TrafficEntry,
+    a2a_server: &SettingA2AServer,
+    removed: &[String],
+) -> HeaderMap {
+    let server_headers: Vec<String> = a2a_server
+        .custom_header_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<serde_json::Map<String, Value>>(json).ok())
+        .map(|headers| headers.keys().map(|key| key.to_ascii_lowercase()).collect())
+        .unwrap_or_default();
+
+    let mut headers = HeaderMap::new();
+    for header in &entry.request_headers {
+        let name = header.name.to_ascii_lowercase();
+        if header.value == REDACTED
+            || SKIPPED_HEADERS.contains(&name.as_str())
+            || server_headers.contains(&name)
+            || removed
+                .iter()
+                .any(|removed| removed.eq_ignore_ascii_case(&name))
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&header.value),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    headers
+}
+
+// The recorded response, SSE responses as the array of their event payloads
+fn recorded_response(entry: &TrafficEntry) -> Value {
+    if entry.sse_events.is_empty() {
+        parse_json(entry.response_body.as_deref().unwrap_or_default())
+    } else {
+        Value::Array(
+            entry
+                .sse_events
+                .iter()
+                .map(|event| parse_json(&event.data))
+                .collect(),
+        )
+    }
+}
+
+fn parse_json(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn paths(differences: &[JsonDiffEntry]) -> Vec<(&str, &str)> {
+        differences
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry.kind.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn added_removed_and_changed_entries_are_reported() {
+        let original =
+            json!({ "result": { "kept": 1, "gone": true, "text": "a", "list": [1, 2] } });
+        let replayed = json!({ "result": { "kept": 1, "text": "b", "list": [1], "new": null } });
+
+        let differences = json_diff(&original, &replayed, &[]);
+        assert_eq!(
+            paths(&differences),
+            vec![
+                ("/result/gone", "removed"),
+                ("/result/list/1", "removed"),
+                ("/result/new", "added"),
+                ("/result/text", "changed"),
+            ]
+        );
+        let changed = &differences[3];
+        assert_eq!(changed.original, Some(json!("a")));
+        assert_eq!(changed.replayed, Some(json!("b")));
+
+        assert_eq!(
+            paths(&json_diff(&json!(1), &json!("1"), &[])),
+            vec![("/", "changed")]
+        );
+        assert!(json_diff(&original, &original, &[]).is_empty());
+    }
+
+    #[test]
+    fn volatile_keys_are_skipped_wherever_they_appear() {
+        let message = |id: &str, time: &str| {
+            json!({ "result": { "status": { "message": {
+                "messageId": id, "taskId": id, "contextId": id, "timestamp": time,
+                "parts": [{ "kind": "text", "text": "Hi" }]
+            } } } })
+        };
+
+        assert!(json_diff(&message("a", "t1"), &message("b", "t2"), &[]).is_empty());
+    }
+
+    #[test]
+    fn only_envelope_and_task_ids_are_volatile() {
+        let response = |id: &str| {
+            json!({
+                "jsonrpc": "2.0", "id": id,
+                "result": { "id": id, "kind": "task", "metadata": { "id": id } }
+            })
+        };
+
+        assert_eq!(
+            paths(&json_diff(&response("1"), &response("2"), &[])),
+            vec![("/result/metadata/id", "changed")]
+        );
+        assert!(is_volatile("id", "/id"));
+        assert!(is_volatile("id", "/result/id"));
+        assert!(!is_volatile("id", "/result/data/id"));
+    }
+
+    #[test]
+    fn streamed_events_each_have_a_volatile_envelope() {
+        let events = |id: &str, text: &str| {
+            json!([
+                { "jsonrpc": "2.0", "id": id, "result": { "id": id, "kind": "task" } },
+                { "jsonrpc": "2.0", "id": id, "result": { "kind": "message", "parts": [{ "text": text }] } }
+            ])
+        };
+
+        assert!(json_diff(&events("1", "Hi"), &events("2", "Hi"), &[]).is_empty());
+        assert_eq!(
+            paths(&json_diff(&events("1", "Hi"), &events("2", "Hello"), &[])),
+            vec![("/1/result/parts/0/text", "changed")]
+        );
+        assert!(is_volatile("id", "/0/id"));
+        assert!(is_volatile("id", "/3/result/id"));
+        assert!(!is_volatile("id", "/0/result/artifact/id"));
+    }
+
+    #[test]
+    fn ignored_paths_and_everything_below_them_are_skipped() {
+        let original = json!({ "result": { "usage": { "tokens": 1 }, "text": "a", "extra": [1] } });
+        let replayed = json!({ "result": { "usage": { "tokens": 2 }, "text": "b" } });
+        let ignored = vec!["/result/usage".to_string(), "/result/extra".to_string()];
+
+        assert_eq!(
+            paths(&json_diff(&original, &replayed, &ignored)),
+            vec![("/result/text", "changed")]
+        );
+        assert!(json_diff(&original, &replayed, &["/".to_string()]).is_empty());
+    }
+}
@@ -0,0 +1,824 @@
+//! gRPC binding over the `a2a.v1.A2AService` of proto/a2a.proto. Requests are
+//! built from the same message types the other bindings send and replies are
+//! converted to the JSON shapes the JSON-RPC binding returns
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Map, Value, json};
+use tonic::{
+    Code, Status, Streaming,
+    metadata::{Ascii, MetadataKey, MetadataValue},
+    transport::{Channel, ClientTlsConfig},
+};
+
+use crate::{
+    a2a::{
+        client::{custom_headers, with_http_scheme},
+        transport::{A2AStream, Endpoint, Transport},
+    },
+    error::{AppError, ErrorKind},
+    model::{A2AMessage, A2AMessagePart, SettingA2AServer},
+    traffic,
+};
+
+#[allow(dead_code, clippy::all)]
+mod proto {
+    tonic::include_proto!("a2a.v1");
+}
+
+use proto::{
+    a2a_service_client::A2aServiceClient, file_part, part, send_message_response, stream_response,
+};
+
+pub async fn send_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<String> {
+    let request_id = message.message_id.clone();
+    let request = grpc_request(a2a_server, send_message_request(&message), header_skill_id);
+    let mut exchange = traffic::Exchange::grpc(
+        url,
+        "SendMessage",
+        &json!({ "message": message }),
+        request.metadata(),
+    );
+    let mut client = connect(url, &mut exchange).await?;
+
+    match client.send_message(request).await {
+        Ok(response) => {
+            exchange.ok();
+            let result = match response.into_inner().payload {
+                Some(send_message_response::Payload::Task(task)) => task_json(task),
+                Some(send_message_response::Payload::Msg(message)) => message_json(message),
+                None => {
+                    return Err(AppError::new(
+                        ErrorKind::InvalidResponse,
+                        "Agent sent an empty SendMessageResponse",
+                    )
+                    .into());
+                }
+            };
+            let body = jsonrpc_result(&request_id, result).to_string();
+            exchange.body(&body);
+            Ok(body)
+        }
+        Err(status) => {
+            exchange.error(&status);
+            status_response(&request_id, status).map(|body| body.to_string())
+        }
+    }
+}
+
+pub async fn stream_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<A2AStream> {
+    let request_id = message.message_id.clone();
+    let request = grpc_request(a2a_server, send_message_request(&message), header_skill_id);
+    let mut exchange = traffic::Exchange::grpc(
+        url,
+        "SendStreamingMessage",
+        &json!({ "message": message }),
+        request.metadata(),
+    );
+    let mut client = connect(url, &mut exchange).await?;
+
+    match client.send_streaming_message(request).await {
+        Ok(response) => {
+            exchange.ok();
+            let endpoint = Endpoint {
+                transport: Transport::Grpc,
+                url: url.to_string(),
+            };
+            Ok(A2AStream::grpc(
+                endpoint,
+                request_id,
+                exchange,
+                GrpcStream {
+                    events: response.into_inner(),
+                    done: false,
+                },
+            ))
+        }
+        Err(status) => {
+            exchange.error(&status);
+            Err(status_error(status))
+        }
+    }
+}
+
+pub async fn get_task(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> Result<String> {
+    let request = proto::GetTaskRequest {
+        name: format!("tasks/{}", task_id),
+        history_length: 0,
+    };
+    let request = grpc_request(a2a_server, request, header_skill_id);
+    let mut exchange = traffic::Exchange::grpc(
+        url,
+        "GetTask",
+        &json!({ "id": task_id }),
+        request.metadata(),
+    );
+    let mut client = connect(url, &mut exchange).await?;
+
+    let result = client.get_task(request).await;
+    task_response(task_id, result, exchange)
+}
+
+pub async fn cancel_task(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> Result<String> {
+    let request = proto::CancelTaskRequest {
+        name: format!("tasks/{}", task_id),
+    };
+    let request = grpc_request(a2a_server, request, header_skill_id);
+    let mut exchange = traffic::Exchange::grpc(
+        url,
+        "CancelTask",
+        &json!({ "id": task_id }),
+        request.metadata(),
+    );
+    let mut client = connect(url, &mut exchange).await?;
+
+    let result = client.cancel_task(request).await;
+    task_response(task_id, result, exchange)
+}
+
+/// The update stream of SendStreamingMessage
+pub struct GrpcStream {
+    events: Streaming<proto::StreamResponse>,
+    done: bool,
+}
+
+impl GrpcStream {
+    pub async fn next(
+        &mut self,
+        exchange: &mut traffic::Exchange,
+        request_id: &str,
+    ) -> Result<Option<Value>> {
+        while !self.done {
+            match self.events.message().await {
+                Ok(Some(response)) => {
+                    let Some(payload) = response.payload else {
+                        continue;
+                    };
+                    let event = jsonrpc_result(request_id, stream_payload_json(payload));
+                    exchange.json_event(&event);
+                    return Ok(Some(event));
+                }
+                Ok(None) => self.done = true,
+                Err(status) => {
+                    self.done = true;
+                    exchange.error(&status);
+                    return status_response(request_id, status).map(Some);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+async fn connect(url: &str, exchange: &mut traffic::Exchange) -> Result<A2aServiceClient<Channel>> {
+    let url = with_http_scheme(url);
+    let mut endpoint = Channel::from_shared(url.clone())
+        .map_err(|_| AppError::invalid_params(format!("Invalid agent url: {}", url)))?;
+    if url.starts_with("https://") {
+        endpoint = endpoint
+            .tls_config(ClientTlsConfig::new().with_webpki_roots())
+            .map_err(|e| AppError::internal(format!("Failed to set up TLS: {}", e)))?;
+    }
+
+    match endpoint.connect().await {
+        Ok(channel) => Ok(A2aServiceClient::new(channel)),
+        Err(e) => {
+            log::error!("Failed to connect to gRPC agent {}: {}", url, e);
+            exchange.error(&e);
+            Err(AppError::new(
+                ErrorKind::AgentUnreachable,
+                format!("Failed to connect to {}: {}", url, e),
+            )
+            .with_details(json!({ "url": url, "connect": true }))
+            .into())
+        }
+    }
+}
+
+// Wrap a gRPC request with the skill header and the server's custom headers as metadata
+fn grpc_request<T>(
+    a2a_server: &SettingA2AServer,
+    message: T,
+    header_skill_id: &str,
+) -> tonic::Request<T> {
+    let mut request = tonic::Request::new(message);
+    let metadata = request.metadata_mut();
+
+    let mut headers = vec![("x-a2a-skill-id".to_string(), header_skill_id.to_string())];
+    for (key, value) in custom_headers(a2a_server) {
+        if key == "Authorization" {
+            headers.push(("authorization".to_string(), format!("Bearer {}", value)));
+        } else {
+            headers.push((key.to_ascii_lowercase(), value));
+        }
+    }
+
+    for (key, value) in headers {
+        match (
+            MetadataKey::<Ascii>::from_bytes(key.as_bytes()),
+            MetadataValue::<Ascii>::try_from(value.as_str()),
+        ) {
+            (Ok(key), Ok(value)) => {
+                metadata.insert(key, value);
+            }
+            _ => log::warn!("Skipping header {} that isn't valid gRPC metadata", key),
+        }
+    }
+
+    request
+}
+
+fn task_response(
+    task_id: &str,
+    result: Result<tonic::Response<proto::Task>, Status>,
+    mut exchange: traffic::Exchange,
+) -> Result<String> {
+    match result {
+        Ok(response) => {
+            exchange.ok();
+            let body = jsonrpc_result(task_id, task_json(response.into_inner())).to_string();
+            exchange.body(&body);
+            Ok(body)
+        }
+        Err(status) => {
+            exchange.error(&status);
+            status_response(task_id, status).map(|body| body.to_string())
+        }
+    }
+}
+
+fn jsonrpc_result(id: &str, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+// Statuses an agent answers a request with become the JSON-RPC error they
+// stand for, so callers tell "task not found" apart the same way. Connection
+// level failures stay errors
+fn status_response(id: &str, status: Status) -> Result<Value> {
+    let code = match status.code() {
+        Code::NotFound => -32001,
+        Code::FailedPrecondition => -32002,
+        Code::Unimplemented => -32004,
+        Code::InvalidArgument => -32602,
+        Code::Unavailable
+        | Code::DeadlineExceeded
+        | Code::Unauthenticated
+        | Code::PermissionDenied
+        | Code::ResourceExhausted
+        | Code::Cancelled => return Err(status_error(status)),
+        _ => -32603,
+    };
+
+    Ok(json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": {
+            "code": code,
+            "message": status.message(),
+            "data": { "grpcCode": status.code() as i32 },
+        },
+    }))
+}
+
+fn status_error(status: Status) -> anyhow::Error {
+    let kind = match status.code() {
+        Code::Unavailable | Code::Cancelled => ErrorKind::AgentUnreachable,
+        Code::DeadlineExceeded => ErrorKind::Timeout,
+        Code::Unauthenticated | Code::PermissionDenied => ErrorKind::Unauthorized,
+        Code::ResourceExhausted => ErrorKind::RateLimited,
+        _ => ErrorKind::UpstreamUnavailable,
+    };
+
+    AppError::new(
+        kind,
+        format!(
+            "gRPC call failed ({:?}): {}",
+            status.code(),
+            status.message()
+        ),
+    )
+    .with_details(json!({ "grpcCode": status.code() as i32 }))
+    .into()
+}
+
+fn send_message_request(message: &A2AMessage) -> proto::SendMessageRequest {
+    proto::SendMessageRequest {
+        request: Some(proto::Message {
+            message_id: message.message_id.clone(),
+            context_id: message.context_id.clone().unwrap_or_default(),
+            task_id: message.task_id.clone().unwrap_or_default(),
+            role: if message.role == "agent" {
+                proto::Role::Agent as i32
+            } else {
+                proto::Role::User as i32
+            },
+            content: message.parts.iter().map(proto_part).collect(),
+            metadata: None,
+            extensions: Vec::new(),
+        }),
+        configuration: None,
+        metadata: None,
+    }
+}
+
+// A data part carries its JSON as text, an object goes over as the Struct itself,
+// anything else wrapped as `{"data": ...}`
+fn proto_part(part: &A2AMessagePart) -> proto::Part {
+    let part = match part {
+        A2AMessagePart::Text(text) => part::Part::Text(text.text.clone()),
+        A2AMessagePart::Data(data) => {
+            let value = serde_json::from_str::<Value>(&data.data)
+                .unwrap_or_else(|_| Value::String(data.data.clone()));
+            let fields = match value {
+                Value::Object(fields) => fields,
+                value => Map::from_iter([("data".to_string(), value)]),
+            };
+            part::Part::Data(proto::DataPart {
+                data: Some(proto_struct(fields)),
+            })
+        }
+    };
+
+    proto::Part { part: Some(part) }
+}
+
+fn proto_struct(fields: Map<String, Value>) -> prost_types::Struct {
+    prost_types::Struct {
+        fields: fields
+            .into_iter()
+            .map(|(key, value)| (key, proto_value(value)))
+            .collect(),
+    }
+}
+
+fn proto_value(value: Value) -> prost_types::Value {
+    use prost_types::value::Kind;
+
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(value) => Kind::BoolValue(value),
+        Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        Value::String(value) => Kind::StringValue(value),
+        Value::Array(values) => Kind::ListValue(prost_types::ListValue {
+            values: values.into_iter().map(proto_value).collect(),
+        }),
+        Value::Object(fields) => Kind::StructValue(proto_struct(fields)),
+    };
+
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn struct_json(fields: BTreeMap<String, prost_types::Value>) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key, value_json(value)))
+            .collect(),
+    )
+}
+
+fn value_json(value: prost_types::Value) -> Value {
+    use prost_types::value::Kind;
+
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(value)) => Value::Bool(value),
+        Some(Kind::NumberValue(value)) => json!(value),
+        Some(Kind::StringValue(value)) => Value::String(value),
+        Some(Kind::ListValue(list)) => {
+            Value::Array(list.values.into_iter().map(value_json).collect())
+        }
+        Some(Kind::StructValue(fields)) => struct_json(fields.fields),
+    }
+}
+
+fn stream_payload_json(payload: stream_response::Payload) -> Value {
+    match payload {
+        stream_response::Payload::Task(task) => task_json(task),
+        stream_response::Payload::Msg(message) => message_json(message),
+        stream_response::Payload::StatusUpdate(update) => {
+            let mut event = json!({
+                "kind": "status-update",
+                "taskId": update.task_id,
+                "contextId": update.context_id,
+                "status": update.status.map(status_json),
+                "final": update.r#final,
+            });
+            set_metadata(&mut event, update.metadata);
+            event
+        }
+        stream_response::Payload::ArtifactUpdate(update) => {
+            let mut event = json!({
+                "kind": "artifact-update",
+                "taskId": update.task_id,
+                "contextId": update.context_id,
+                "artifact": update.artifact.map(artifact_json),
+                "append": update.append,
+                "lastChunk": update.last_chunk,
+            });
+            set_metadata(&mut event, update.metadata);
+            event
+        }
+    }
+}
+
+fn task_json(task: proto::Task) -> Value {
+    let mut value = json!({
+        "kind": "task",
+        "id": task.id,
+        "contextId": task.context_id,
+        "status": task.status.map(status_json),
+        "artifacts": task.artifacts.into_iter().map(artifact_json).collect::<Vec<_>>(),
+        "history": task.history.into_iter().map(message_json).collect::<Vec<_>>(),
+    });
+    set_metadata(&mut value, task.metadata);
+    value
+}
+
+fn status_json(status: proto::TaskStatus) -> Value {
+    // TASK_STATE_INPUT_REQUIRED is "input-required" in the JSON bindings, which
+    // spell TASK_STATE_CANCELLED "canceled"
+    let state = proto::TaskState::try_from(status.state)
+        .ok()
+        .and_then(|state| state.as_str_name().strip_prefix("TASK_STATE_"))
+        .filter(|state| *state != "UNSPECIFIED")
+        .map(|state| match state {
+            "CANCELLED" => "canceled".to_string(),
+            state => state.to_ascii_lowercase().replace('_', "-"),
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut value = json!({ "state": state });
+    if let Some(message) = status.update {
+        value["message"] = message_json(message);
+    }
+    if let Some(time) = status.timestamp.and_then(|timestamp| {
+        chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos.max(0) as u32)
+    }) {
+        value["timestamp"] = Value::String(time.to_rfc3339());
+    }
+    value
+}
+
+fn message_json(message: proto::Message) -> Value {
+    let role = match proto::Role::try_from(message.role) {
+        Ok(proto::Role::User) => "user",
+        _ => "agent",
+    };
+
+    let mut value = json!({
+        "kind": "message",
+        "messageId": message.message_id,
+        "role": role,
+        "parts": message.content.into_iter().map(part_json).collect::<Vec<_>>(),
+    });
+    if !message.context_id.is_empty() {
+        value["contextId"] = Value::String(message.context_id);
+    }
+    if !message.task_id.is_empty() {
+        value["taskId"] = Value::String(message.task_id);
+    }
+    set_metadata(&mut value, message.metadata);
+    value
+}
+
+fn artifact_json(artifact: proto::Artifact) -> Value {
+    let mut value = json!({
+        "artifactId": artifact.artifact_id,
+        "parts": artifact.parts.into_iter().map(part_json).collect::<Vec<_>>(),
+    });
+    if !artifact.name.is_empty() {
+        value["name"] = Value::String(artifact.name);
+    }
+    if !artifact.description.is_empty() {
+        value["description"] = Value::String(artifact.description);
+    }
+    set_metadata(&mut value, artifact.metadata);
+    value
+}
+
+fn part_json(part: proto::Part) -> Value {
+    match part.part {
+        Some(part::Part::Text(text)) => json!({ "kind": "text", "text": text }),
+        Some(part::Part::Data(data)) => json!({
+            "kind": "data",
+            "data": data.data.map(|data| struct_json(data.fields)).unwrap_or(Value::Null),
+        }),
+        Some(part::Part::File(file)) => {
+            let mut content = Map::new();
+            match file.file {
+                Some(file_part::File::FileWithUri(uri)) => {
+                    content.insert("uri".to_string(), Value::String(uri));
+                }
+                Some(file_part::File::FileWithBytes(bytes)) => {
+                    content.insert("bytes".to_string(), Value::String(STANDARD.encode(bytes)));
+                }
+                None => {}
+            }
+            if !file.mime_type.is_empty() {
+                content.insert("mimeType".to_string(), Value::String(file.mime_type));
+            }
+            if !file.name.is_empty() {
+                content.insert("name".to_string(), Value::String(file.name));
+            }
+            json!({ "kind": "file", "file": content })
+        }
+        None => json!({ "kind": "text", "text": "" }),
+    }
+}
+
+fn set_metadata(value: &mut Value, metadata: Option<prost_types::Struct>) {
+    if let Some(metadata) = metadata {
+        value["metadata"] = struct_json(metadata.fields);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use tokio::net::TcpListener;
+    use tokio_stream::{Stream, wrappers::TcpListenerStream};
+    use tonic::{Request, Response, transport::Server};
+
+    use super::*;
+    use crate::{model::A2ATextPart, test_support::a2a_server};
+    use proto::a2a_service_server::{A2aService, A2aServiceServer};
+
+    type EventStream = Pin<Box<dyn Stream<Item = Result<proto::StreamResponse, Status>> + Send>>;
+
+    // Knows task t1, task done can't be canceled any more
+    struct StubAgent;
+
+    fn task(id: &str, state: proto::TaskState) -> proto::Task {
+        proto::Task {
+            id: id.to_string(),
+            context_id: "c1".to_string(),
+            status: Some(proto::TaskStatus {
+                state: state as i32,
+                update: None,
+                timestamp: None,
+            }),
+            artifacts: Vec::new(),
+            history: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    fn known_task(name: &str) -> Option<&str> {
+        name.strip_prefix("tasks/")
+            .filter(|id| matches!(*id, "t1" | "done"))
+    }
+
+    fn task_not_found(name: &str) -> Status {
+        Status::not_found(format!("{} not found", name))
+    }
+
+    #[tonic::async_trait]
+    impl A2aService for StubAgent {
+        type SendStreamingMessageStream = EventStream;
+        type TaskSubscriptionStream = EventStream;
+
+        // Answers with the task, the sent message as its history and the skill
+        // header as its metadata
+        async fn send_message(
+            &self,
+            request: Request<proto::SendMessageRequest>,
+        ) -> Result<Response<proto::SendMessageResponse>, Status> {
+            let skill_id = request
+                .metadata()
+                .get("x-a2a-skill-id")
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string();
+            let mut task = task("t1", proto::TaskState::Working);
+            task.history = request.into_inner().request.into_iter().collect();
+            task.metadata = Some(proto_struct(Map::from_iter([(
+                "skill".to_string(),
+                Value::String(skill_id),
+            )])));
+            Ok(Response::new(proto::SendMessageResponse {
+                payload: Some(send_message_response::Payload::Task(task)),
+            }))
+        }
+
+        async fn send_streaming_message(
+            &self,
+            _: Request<proto::SendMessageRequest>,
+        ) -> Result<Response<EventStream>, Status> {
+            let events = vec![
+                Ok(proto::StreamResponse {
+                    payload: Some(stream_response::Payload::Task(task(
+                        "t1",
+                        proto::TaskState::Working,
+                    ))),
+                }),
+                Ok(proto::StreamResponse {
+                    payload: Some(stream_response::Payload::StatusUpdate(
+                        proto::TaskStatusUpdateEvent {
+                            task_id: "t1".to_string(),
+                            context_id: "c1".to_string(),
+                            status: task("t1", proto::TaskState::Completed).status,
+                            r#final: true,
+                            metadata: None,
+                        },
+                    )),
+                }),
+            ];
+            Ok(Response::new(Box::pin(tokio_stream::iter(events))))
+        }
+
+        async fn get_task(
+            &self,
+            request: Request<proto::GetTaskRequest>,
+        ) -> Result<Response<proto::Task>, Status> {
+            let name = &request.get_ref().name;
+            let id = known_task(name).ok_or_else(|| task_not_found(name))?;
+            Ok(Response::new(task(id, proto::TaskState::Completed)))
+        }
+
+        async fn cancel_task(
+            &self,
+            request: Request<proto::CancelTaskRequest>,
+        ) -> Result<Response<proto::Task>, Status> {
+            let name = &request.get_ref().name;
+            match known_task(name).ok_or_else(|| task_not_found(name))? {
+                "done" => Err(Status::failed_precondition("task done is completed")),
+                id => Ok(Response::new(task(id, proto::TaskState::Cancelled))),
+            }
+        }
+
+        async fn task_subscription(
+            &self,
+            _: Request<proto::TaskSubscriptionRequest>,
+        ) -> Result<Response<EventStream>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn create_task_push_notification_config(
+            &self,
+            _: Request<proto::CreateTaskPushNotificationConfigRequest>,
+        ) -> Result<Response<proto::TaskPushNotificationConfig>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_task_push_notification_config(
+            &self,
+            _: Request<proto::GetTaskPushNotificationConfigRequest>,
+        ) -> Result<Response<proto::TaskPushNotificationConfig>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn list_task_push_notification_config(
+            &self,
+            _: Request<proto::ListTaskPushNotificationConfigRequest>,
+        ) -> Result<Response<proto::ListTaskPushNotificationConfigResponse>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn get_agent_card(
+            &self,
+            _: Request<proto::GetAgentCardRequest>,
+        ) -> Result<Response<proto::AgentCard>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+
+        async fn delete_task_push_notification_config(
+            &self,
+            _: Request<proto::DeleteTaskPushNotificationConfigRequest>,
+        ) -> Result<Response<()>, Status> {
+            Err(Status::unimplemented("not used"))
+        }
+    }
+
+    async fn serve() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(A2aServiceServer::new(StubAgent))
+                .serve_with_incoming(TcpListenerStream::new(listener)),
+        );
+        format!("http://{}", addr)
+    }
+
+    fn message(text: &str) -> A2AMessage {
+        A2AMessage {
+            message_id: "m1".to_string(),
+            kind: "message".to_string(),
+            role: "user".to_string(),
+            parts: vec![A2AMessagePart::Text(A2ATextPart::new(text.to_string()))],
+            context_id: Some("c1".to_string()),
+            task_id: None,
+        }
+    }
+
+    fn body(body: String) -> Value {
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn send_message_round_trips() {
+        let url = serve().await;
+        let response = body(
+            send_message(&a2a_server(None), &url, message("hello"), "skill-1")
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(response["id"], "m1");
+        let task = &response["result"];
+        assert_eq!(task["kind"], "task");
+        assert_eq!(task["status"]["state"], "working");
+        assert_eq!(task["metadata"]["skill"], "skill-1");
+        assert_eq!(
+            task["history"][0],
+            json!({
+                "kind": "message",
+                "messageId": "m1",
+                "role": "user",
+                "contextId": "c1",
+                "parts": [{ "kind": "text", "text": "hello" }],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_message_converts_each_event() {
+        let url = serve().await;
+        let mut stream = stream_message(&a2a_server(None), &url, message("hello"), "skill-1")
+            .await
+            .unwrap();
+
+        let first = stream.next_event().await.unwrap().unwrap();
+        assert_eq!(first["result"]["kind"], "task");
+        let second = stream.next_event().await.unwrap().unwrap();
+        assert_eq!(second["result"]["kind"], "status-update");
+        assert_eq!(second["result"]["status"]["state"], "completed");
+        assert_eq!(second["result"]["final"], true);
+        assert!(stream.next_event().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn get_task_round_trips_and_maps_not_found() {
+        let url = serve().await;
+        let server = a2a_server(None);
+
+        let response = body(get_task(&server, &url, "skill-1", "t1").await.unwrap());
+        assert_eq!(response["id"], "t1");
+        assert_eq!(response["result"]["id"], "t1");
+        assert_eq!(response["result"]["status"]["state"], "completed");
+
+        let response = body(get_task(&server, &url, "skill-1", "t2").await.unwrap());
+        assert_eq!(response["error"]["code"], -32001);
+        assert_eq!(response["error"]["message"], "tasks/t2 not found");
+    }
+
+    #[tokio::test]
+    async fn cancel_task_round_trips_and_maps_not_cancelable() {
+        let url = serve().await;
+        let server = a2a_server(None);
+
+        let response = body(cancel_task(&server, &url, "skill-1", "t1").await.unwrap());
+        assert_eq!(response["result"]["status"]["state"], "canceled");
+
+        let response = body(cancel_task(&server, &url, "skill-1", "done").await.unwrap());
+        assert_eq!(response["error"]["code"], -32002);
+    }
+
+    #[tokio::test]
+    async fn unreachable_agents_are_a_connect_failure() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = get_task(&a2a_server(None), &url, "skill-1", "t1")
+            .await
+            .unwrap_err();
+        // Never reached the agent, so any request may go to the next endpoint
+        assert!(AppError::from(error).is_connect_failure());
+    }
+}
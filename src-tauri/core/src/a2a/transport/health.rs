@@ -0,0 +1,77 @@
+//! Health of the endpoints agents are reached at, kept in memory for the life
+//! of the process. An endpoint that just failed is tried after the healthy ones
+//! until its cooldown runs out, so a dead interface doesn't stall every request
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+
+use super::{Endpoint, Transport};
+
+// Cooldown after the first failure, doubled with each consecutive one up to the cap
+const BASE_COOLDOWN: Duration = Duration::from_secs(10);
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+
+struct Health {
+    consecutive_failures: u32,
+    cooling_until: Instant,
+}
+
+lazy_static! {
+    // By transport and url, one url may serve several transports
+    static ref ENDPOINT_HEALTH: Mutex<HashMap<(Transport, String), Health>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Whether the endpoint is outside the cooldown of a recent failure
+pub fn is_healthy(endpoint: &Endpoint) -> bool {
+    let Ok(health) = ENDPOINT_HEALTH.lock() else {
+        return true;
+    };
+    health
+        .get(&key(endpoint))
+        .is_none_or(|health| health.cooling_until <= Instant::now())
+}
+
+/// The endpoint served a request, its failures are forgotten
+pub fn record_success(endpoint: &Endpoint) {
+    if let Ok(mut health) = ENDPOINT_HEALTH.lock() {
+        health.remove(&key(endpoint));
+    }
+}
+
+/// The endpoint could not serve a request, it cools down before being preferred again
+pub fn record_failure(endpoint: &Endpoint) {
+    let Ok(mut health) = ENDPOINT_HEALTH.lock() else {
+        return;
+    };
+    let consecutive_failures = health
+        .get(&key(endpoint))
+        .map_or(1, |health| health.consecutive_failures.saturating_add(1));
+    let cooldown = BASE_COOLDOWN
+        .saturating_mul(2u32.saturating_pow(consecutive_failures - 1))
+        .min(MAX_COOLDOWN);
+
+    log::debug!(
+        "{} endpoint {} failed {} time(s) in a row, cooling down for {}s",
+        endpoint.transport.name(),
+        endpoint.url,
+        consecutive_failures,
+        cooldown.as_secs()
+    );
+    health.insert(
+        key(endpoint),
+        Health {
+            consecutive_failures,
+            cooling_until: Instant::now() + cooldown,
+        },
+    );
+}
+
+fn key(endpoint: &Endpoint) -> (Transport, String) {
+    (endpoint.transport, endpoint.url.clone())
+}
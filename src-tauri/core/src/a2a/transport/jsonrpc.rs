@@ -0,0 +1,72 @@
+//! JSON-RPC 2.0 over HTTP, every call is a POST to the agent url
+
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    a2a::{
+        client::{
+            build_a2a_http_request, build_a2a_jsonrpc_request, execute_a2a_stream, post_a2a_jsonrpc,
+        },
+        transport::{A2AStream, Endpoint, Transport},
+    },
+    model::{A2AMessage, JSONRPCRequest, SettingA2AServer},
+};
+
+pub async fn send_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    request_id: String,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<String> {
+    let jsonrpc_request = build_a2a_jsonrpc_request("message/send", request_id, message);
+    post_a2a_jsonrpc(a2a_server, url, header_skill_id, &jsonrpc_request).await
+}
+
+pub async fn stream_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    request_id: String,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<A2AStream> {
+    let jsonrpc_request = build_a2a_jsonrpc_request("message/stream", request_id, message);
+    let request = build_a2a_http_request(
+        &Client::new(),
+        a2a_server,
+        url,
+        header_skill_id,
+        &jsonrpc_request,
+    );
+    let (exchange, response) = execute_a2a_stream(request).await?;
+    let endpoint = Endpoint {
+        transport: Transport::JsonRpc,
+        url: url.to_string(),
+    };
+    Ok(A2AStream::new(
+        endpoint,
+        jsonrpc_request.id,
+        exchange,
+        response,
+    ))
+}
+
+// tasks/get and tasks/cancel, both take just the task id
+pub async fn task_call(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    header_skill_id: &str,
+    method: &str,
+    task_id: &str,
+) -> Result<String> {
+    let request = JSONRPCRequest {
+        jsonrpc: "2.0".to_string(),
+        id: format!("req_id:{}", Uuid::new_v4()),
+        method: method.to_string(),
+        params: json!({ "id": task_id }),
+    };
+    post_a2a_jsonrpc(a2a_server, url, header_skill_id, &request).await
+}
@@ -0,0 +1,678 @@
+//! The protocol bindings an agent is reached over. Every transport hands its
+//! results back in the JSON-RPC shape (`{"result": ...}` or `{"error": ...}`),
+//! so callers read responses and stream events the same way whichever one
+//! served them.
+
+mod grpc;
+mod health;
+mod jsonrpc;
+mod rest;
+
+use std::{collections::VecDeque, future::Future};
+
+use anyhow::Result;
+use reqwest::Response;
+use serde_json::{Value, from_str};
+
+use crate::{
+    error::{AppError, ErrorKind},
+    model::{A2AMessage, SettingA2AServer},
+    sse::{SseEvent, SseParser},
+    traffic,
+};
+
+/// An A2A protocol binding, named as in agent cards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    JsonRpc,
+    HttpJson,
+    Grpc,
+}
+
+impl Transport {
+    /// Parse a transport name from an agent card or the server settings
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_uppercase().as_str() {
+            "JSONRPC" => Some(Self::JsonRpc),
+            "HTTP+JSON" | "REST" => Some(Self::HttpJson),
+            "GRPC" => Some(Self::Grpc),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::JsonRpc => "JSONRPC",
+            Self::HttpJson => "HTTP+JSON",
+            Self::Grpc => "GRPC",
+        }
+    }
+}
+
+/// A url the agent serves and the transport spoken there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub transport: Transport,
+    pub url: String,
+}
+
+/// The interfaces the server's stored card advertises, in the card's order:
+/// `url` with its preferred transport first, then `additionalInterfaces`.
+/// `a2a_url` stands in for the card's main url. Interfaces whose transport
+/// this client doesn't speak are left out
+pub fn card_endpoints(a2a_server: &SettingA2AServer, a2a_url: &str) -> Vec<Endpoint> {
+    let card = a2a_server
+        .agent_card_json
+        .as_deref()
+        .and_then(|card_json| from_str::<Value>(card_json).ok())
+        .unwrap_or(Value::Null);
+    let card_url = card.get("url").and_then(|url| url.as_str());
+    let preferred = card
+        .get("preferredTransport")
+        .and_then(|transport| transport.as_str())
+        .unwrap_or("JSONRPC");
+
+    let mut endpoints: Vec<Endpoint> = Vec::new();
+    let mut add = |transport: &str, url: &str| {
+        let Some(transport) = Transport::from_name(transport) else {
+            return;
+        };
+        let endpoint = Endpoint {
+            transport,
+            url: url.to_string(),
+        };
+        if !endpoints.contains(&endpoint) {
+            endpoints.push(endpoint);
+        }
+    };
+
+    add(preferred, a2a_url);
+    for interface in card
+        .get("additionalInterfaces")
+        .and_then(|interfaces| interfaces.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let (Some(url), Some(transport)) = (
+            interface.get("url").and_then(|url| url.as_str()),
+            interface.get("transport").and_then(|t| t.as_str()),
+        ) else {
+            continue;
+        };
+        // The card's main interface is usually listed again, a2a_url already covers it
+        if Some(url) == card_url && transport.eq_ignore_ascii_case(preferred) {
+            continue;
+        }
+        add(transport, url);
+    }
+
+    endpoints
+}
+
+/// The transport the server settings force, if any
+pub fn forced_transport(a2a_server: &SettingA2AServer) -> Option<Transport> {
+    a2a_server
+        .transport
+        .as_deref()
+        .and_then(Transport::from_name)
+}
+
+/// Every endpoint a request to the server may go to, in the order they are
+/// tried: the forced transport's interfaces when the settings name one,
+/// otherwise the card's preferred interface followed by its alternates.
+/// Endpoints still cooling down after a failure move behind the healthy ones
+pub fn endpoint_candidates(a2a_server: &SettingA2AServer, a2a_url: &str) -> Vec<Endpoint> {
+    let endpoints = card_endpoints(a2a_server, a2a_url);
+    let mut endpoints = match forced_transport(a2a_server) {
+        Some(transport) => {
+            let forced: Vec<Endpoint> = endpoints
+                .into_iter()
+                .filter(|endpoint| endpoint.transport == transport)
+                .collect();
+            if forced.is_empty() {
+                vec![Endpoint {
+                    transport,
+                    url: a2a_url.to_string(),
+                }]
+            } else {
+                forced
+            }
+        }
+        None if endpoints.is_empty() => vec![Endpoint {
+            transport: Transport::JsonRpc,
+            url: a2a_url.to_string(),
+        }],
+        None => endpoints,
+    };
+
+    // The sort is stable, the card's order holds among healthy and among cooling endpoints
+    endpoints.sort_by_key(|endpoint| !health::is_healthy(endpoint));
+    endpoints
+}
+
+/// Where a request to the server goes first, see [`endpoint_candidates`]
+pub fn resolve_endpoint(a2a_server: &SettingA2AServer, a2a_url: &str) -> Endpoint {
+    let endpoint = endpoint_candidates(a2a_server, a2a_url).remove(0);
+    log::debug!(
+        "A2A server {} resolved to {} at {}",
+        a2a_server.name,
+        endpoint.transport.name(),
+        endpoint.url
+    );
+    endpoint
+}
+
+/// The requests sent through [`with_failover`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    SendMessage,
+    StreamMessage,
+    GetTask,
+    CancelTask,
+}
+
+impl Operation {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::SendMessage => "message/send",
+            Self::StreamMessage => "message/stream",
+            Self::GetTask => "tasks/get",
+            Self::CancelTask => "tasks/cancel",
+        }
+    }
+
+    // Reading a task twice is harmless, sending or canceling twice is not
+    fn is_idempotent(self) -> bool {
+        matches!(self, Self::GetTask)
+    }
+}
+
+/// Run a request against the server's endpoints in order until one serves it.
+/// A request the endpoint never received moves on to the next endpoint. After a
+/// timeout or 5xx the agent may already have acted on it, so only a read moves on
+/// then. Any other outcome is final, a JSON-RPC error in the body included.
+/// Returns the result with the endpoint that served it, which is logged
+pub async fn with_failover<T, F, Fut>(
+    a2a_server: &SettingA2AServer,
+    a2a_url: &str,
+    operation: Operation,
+    mut request: F,
+) -> Result<(T, Endpoint)>
+where
+    F: FnMut(Endpoint) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let endpoints = endpoint_candidates(a2a_server, a2a_url);
+    let count = endpoints.len();
+    let mut last_error = None;
+
+    for (index, endpoint) in endpoints.into_iter().enumerate() {
+        match request(endpoint.clone()).await {
+            Ok(value) => {
+                health::record_success(&endpoint);
+                log::info!(
+                    "A2A server {} {} served by {}{} endpoint {}",
+                    a2a_server.name,
+                    operation.name(),
+                    if index > 0 { "fallback " } else { "" },
+                    endpoint.transport.name(),
+                    endpoint.url
+                );
+                return Ok((value, endpoint));
+            }
+            Err(e) if is_endpoint_failure(&e) => {
+                health::record_failure(&endpoint);
+                if !may_resend(&e, operation) {
+                    return Err(e);
+                }
+                log::warn!(
+                    "A2A server {} failed over {} endpoint {} ({}/{}): {:#}",
+                    a2a_server.name,
+                    endpoint.transport.name(),
+                    endpoint.url,
+                    index + 1,
+                    count,
+                    e
+                );
+                last_error = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    match last_error {
+        Some(e) if count > 1 => {
+            Err(e.context(format!("All {} endpoints of the agent failed", count)))
+        }
+        Some(e) => Err(e),
+        None => Err(AppError::internal("Agent has no endpoint to send to").into()),
+    }
+}
+
+fn app_error(error: &anyhow::Error) -> Option<&AppError> {
+    error
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<AppError>())
+}
+
+// The endpoint itself is down or overloaded, it cools down whether or not the
+// request moves on
+fn is_endpoint_failure(error: &anyhow::Error) -> bool {
+    app_error(error).is_some_and(|e| {
+        matches!(
+            e.kind,
+            ErrorKind::AgentUnreachable | ErrorKind::Timeout | ErrorKind::UpstreamUnavailable
+        )
+    })
+}
+
+// Whether the request can go to another endpoint without the agent handling it twice
+fn may_resend(error: &anyhow::Error, operation: Operation) -> bool {
+    operation.is_idempotent() || app_error(error).is_some_and(AppError::is_connect_failure)
+}
+
+impl Endpoint {
+    /// Send a message, returns the response body in the JSON-RPC shape
+    pub async fn send_message(
+        &self,
+        a2a_server: &SettingA2AServer,
+        request_id: String,
+        message: A2AMessage,
+        header_skill_id: &str,
+    ) -> Result<String> {
+        match self.transport {
+            Transport::JsonRpc => {
+                jsonrpc::send_message(a2a_server, &self.url, request_id, message, header_skill_id)
+                    .await
+            }
+            Transport::HttpJson => {
+                rest::send_message(a2a_server, &self.url, message, header_skill_id).await
+            }
+            Transport::Grpc => {
+                grpc::send_message(a2a_server, &self.url, message, header_skill_id).await
+            }
+        }
+    }
+
+    /// Send a message and stream the agent's events back
+    pub async fn stream_message(
+        &self,
+        a2a_server: &SettingA2AServer,
+        request_id: String,
+        message: A2AMessage,
+        header_skill_id: &str,
+    ) -> Result<A2AStream> {
+        match self.transport {
+            Transport::JsonRpc => {
+                jsonrpc::stream_message(a2a_server, &self.url, request_id, message, header_skill_id)
+                    .await
+            }
+            Transport::HttpJson => {
+                rest::stream_message(a2a_server, &self.url, message, header_skill_id).await
+            }
+            Transport::Grpc => {
+                grpc::stream_message(a2a_server, &self.url, message, header_skill_id).await
+            }
+        }
+    }
+
+    /// Fetch a task, returns the response body in the JSON-RPC shape
+    pub async fn get_task(
+        &self,
+        a2a_server: &SettingA2AServer,
+        header_skill_id: &str,
+        task_id: &str,
+    ) -> Result<String> {
+        match self.transport {
+            Transport::JsonRpc => {
+                jsonrpc::task_call(a2a_server, &self.url, header_skill_id, "tasks/get", task_id)
+                    .await
+            }
+            Transport::HttpJson => {
+                rest::get_task(a2a_server, &self.url, header_skill_id, task_id).await
+            }
+            Transport::Grpc => {
+                grpc::get_task(a2a_server, &self.url, header_skill_id, task_id).await
+            }
+        }
+    }
+
+    /// Cancel a task, returns the response body in the JSON-RPC shape
+    pub async fn cancel_task(
+        &self,
+        a2a_server: &SettingA2AServer,
+        header_skill_id: &str,
+        task_id: &str,
+    ) -> Result<String> {
+        match self.transport {
+            Transport::JsonRpc => {
+                jsonrpc::task_call(
+                    a2a_server,
+                    &self.url,
+                    header_skill_id,
+                    "tasks/cancel",
+                    task_id,
+                )
+                .await
+            }
+            Transport::HttpJson => {
+                rest::cancel_task(a2a_server, &self.url, header_skill_id, task_id).await
+            }
+            Transport::Grpc => {
+                grpc::cancel_task(a2a_server, &self.url, header_skill_id, task_id).await
+            }
+        }
+    }
+}
+
+/// A streaming response being read. Events come out in the JSON-RPC shape and
+/// are recorded on the traffic exchange as they are read
+pub struct A2AStream {
+    endpoint: Endpoint,
+    // Id the JSON-RPC envelope of converted events carries
+    request_id: String,
+    exchange: traffic::Exchange,
+    body: StreamBody,
+}
+
+enum StreamBody {
+    // An SSE body of the JSON-RPC or HTTP+JSON binding
+    Sse {
+        response: Response,
+        parser: SseParser,
+        pending: VecDeque<SseEvent>,
+        done: bool,
+    },
+    Grpc(grpc::GrpcStream),
+}
+
+impl A2AStream {
+    fn new(
+        endpoint: Endpoint,
+        request_id: String,
+        exchange: traffic::Exchange,
+        response: Response,
+    ) -> Self {
+        Self {
+            endpoint,
+            request_id,
+            exchange,
+            body: StreamBody::Sse {
+                response,
+                parser: SseParser::new(),
+                pending: VecDeque::new(),
+                done: false,
+            },
+        }
+    }
+
+    fn grpc(
+        endpoint: Endpoint,
+        request_id: String,
+        exchange: traffic::Exchange,
+        stream: grpc::GrpcStream,
+    ) -> Self {
+        Self {
+            endpoint,
+            request_id,
+            exchange,
+            body: StreamBody::Grpc(stream),
+        }
+    }
+
+    /// The endpoint serving the stream
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
+    }
+
+    /// The next event, `None` once the agent closes the stream. Events that
+    /// aren't JSON are skipped
+    pub async fn next_event(&mut self) -> Result<Option<Value>> {
+        let transport = self.endpoint.transport;
+        let (response, parser, pending, done) = match &mut self.body {
+            StreamBody::Sse {
+                response,
+                parser,
+                pending,
+                done,
+            } => (response, parser, pending, done),
+            StreamBody::Grpc(stream) => {
+                return stream.next(&mut self.exchange, &self.request_id).await;
+            }
+        };
+
+        loop {
+            if let Some(event) = pending.pop_front() {
+                self.exchange.event(&event);
+                let Ok(value) = from_str::<Value>(&event.data) else {
+                    continue;
+                };
+                return Ok(Some(match transport {
+                    Transport::HttpJson => rest::to_jsonrpc_response(&self.request_id, value),
+                    _ => value,
+                }));
+            }
+            if *done {
+                return Ok(None);
+            }
+
+            match response.chunk().await {
+                Ok(Some(chunk)) => pending.extend(parser.push(&chunk)),
+                Ok(None) => {
+                    *done = true;
+                    pending.extend(parser.finish());
+                }
+                Err(e) => {
+                    self.exchange.error(&e);
+                    return Err(anyhow::Error::new(AppError::from(e)).context("Stream error"));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_support::a2a_server;
+
+    fn endpoint(transport: Transport, url: &str) -> Endpoint {
+        Endpoint {
+            transport,
+            url: url.to_string(),
+        }
+    }
+
+    fn connect_failure() -> anyhow::Error {
+        AppError::new(ErrorKind::AgentUnreachable, "refused")
+            .with_details(json!({ "connect": true }))
+            .into()
+    }
+
+    // Endpoint health is process wide, each test uses its own host
+    fn card(host: &str) -> String {
+        json!({
+            "url": format!("http://{host}/rpc"),
+            "preferredTransport": "JSONRPC",
+            "additionalInterfaces": [
+                { "url": format!("http://{host}/rpc"), "transport": "JSONRPC" },
+                { "url": format!("http://{host}/rest"), "transport": "HTTP+JSON" },
+                { "url": format!("{host}:50051"), "transport": "GRPC" },
+                { "url": format!("ws://{host}/ws"), "transport": "WEBSOCKET" },
+            ],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn card_endpoints_follow_the_card_order() {
+        let server = a2a_server(Some(&card("order.test")));
+        assert_eq!(
+            card_endpoints(&server, "http://proxy.test/rpc"),
+            [
+                endpoint(Transport::JsonRpc, "http://proxy.test/rpc"),
+                endpoint(Transport::HttpJson, "http://order.test/rest"),
+                endpoint(Transport::Grpc, "order.test:50051"),
+            ]
+        );
+
+        let server = a2a_server(None);
+        assert_eq!(
+            card_endpoints(&server, "http://bare.test/rpc"),
+            [endpoint(Transport::JsonRpc, "http://bare.test/rpc")]
+        );
+    }
+
+    #[test]
+    fn forced_transport_limits_the_candidates() {
+        let mut server = a2a_server(Some(&card("forced.test")));
+        server.transport = Some("GRPC".to_string());
+        assert_eq!(
+            endpoint_candidates(&server, "http://forced.test/rpc"),
+            [endpoint(Transport::Grpc, "forced.test:50051")]
+        );
+
+        // A transport the card doesn't list is tried at the main url
+        let mut server = a2a_server(None);
+        server.transport = Some("REST".to_string());
+        assert_eq!(
+            endpoint_candidates(&server, "http://forced.test/rpc"),
+            [endpoint(Transport::HttpJson, "http://forced.test/rpc")]
+        );
+    }
+
+    #[test]
+    fn cooling_endpoints_are_tried_last() {
+        let server = a2a_server(Some(&card("cooling.test")));
+        health::record_failure(&endpoint(Transport::JsonRpc, "http://cooling.test/rpc"));
+        assert_eq!(
+            endpoint_candidates(&server, "http://cooling.test/rpc"),
+            [
+                endpoint(Transport::HttpJson, "http://cooling.test/rest"),
+                endpoint(Transport::Grpc, "cooling.test:50051"),
+                endpoint(Transport::JsonRpc, "http://cooling.test/rpc"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_fails_over_when_an_endpoint_is_unreachable() {
+        let server = a2a_server(Some(&card("failover.test")));
+        let tried = RefCell::new(Vec::new());
+        let (value, served_by) = with_failover(
+            &server,
+            "http://failover.test/rpc",
+            Operation::SendMessage,
+            |endpoint| {
+                tried.borrow_mut().push(endpoint.transport);
+                async move {
+                    match endpoint.transport {
+                        Transport::JsonRpc => Err(connect_failure()),
+                        _ => Ok(endpoint.url),
+                    }
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*tried.borrow(), [Transport::JsonRpc, Transport::HttpJson]);
+        assert_eq!(value, "http://failover.test/rest");
+        assert_eq!(served_by.transport, Transport::HttpJson);
+        assert!(!health::is_healthy(&endpoint(
+            Transport::JsonRpc,
+            "http://failover.test/rpc"
+        )));
+    }
+
+    #[tokio::test]
+    async fn send_is_not_resent_after_a_timeout_or_5xx() {
+        for (host, error) in [
+            (
+                "timeout.test",
+                AppError::new(ErrorKind::Timeout, "timed out"),
+            ),
+            (
+                "unavailable.test",
+                AppError::new(ErrorKind::UpstreamUnavailable, "503"),
+            ),
+            // Connected, the agent may have read the message before the reset
+            (
+                "reset.test",
+                AppError::new(ErrorKind::AgentUnreachable, "reset"),
+            ),
+        ] {
+            let server = a2a_server(Some(&card(host)));
+            let url = format!("http://{host}/rpc");
+            let tried = RefCell::new(0);
+            let result = with_failover(&server, &url, Operation::SendMessage, |_| {
+                *tried.borrow_mut() += 1;
+                let error = error.clone();
+                async move { Err::<(), _>(error.into()) }
+            })
+            .await;
+
+            assert_eq!(AppError::from(result.unwrap_err()).kind, error.kind);
+            assert_eq!(*tried.borrow(), 1);
+            // The endpoint still cools down
+            assert!(!health::is_healthy(&endpoint(Transport::JsonRpc, &url)));
+        }
+    }
+
+    #[tokio::test]
+    async fn get_task_fails_over_after_a_timeout() {
+        let server = a2a_server(Some(&card("read.test")));
+        let (_, served_by) = with_failover(
+            &server,
+            "http://read.test/rpc",
+            Operation::GetTask,
+            |endpoint| async move {
+                match endpoint.transport {
+                    Transport::JsonRpc => {
+                        Err(AppError::new(ErrorKind::Timeout, "timed out").into())
+                    }
+                    _ => Ok(()),
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(served_by.transport, Transport::HttpJson);
+    }
+
+    #[tokio::test]
+    async fn failover_stops_at_an_answer_from_the_agent() {
+        let server = a2a_server(Some(&card("final.test")));
+        let tried = RefCell::new(0);
+        let error = with_failover(&server, "http://final.test/rpc", Operation::GetTask, |_| {
+            *tried.borrow_mut() += 1;
+            async { Err::<(), _>(AppError::from_json_rpc(-32001, "Task not found", None).into()) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(*tried.borrow(), 1);
+        assert_eq!(AppError::from(error).kind, ErrorKind::TaskNotFound);
+    }
+
+    #[tokio::test]
+    async fn failover_reports_when_every_endpoint_failed() {
+        let server = a2a_server(Some(&card("down.test")));
+        let error = with_failover(
+            &server,
+            "http://down.test/rpc",
+            Operation::SendMessage,
+            |_| async { Err::<(), _>(connect_failure()) },
+        )
+        .await
+        .unwrap_err();
+
+        assert!(format!("{:#}", error).starts_with("All 3 endpoints of the agent failed"));
+        assert_eq!(AppError::from(error).kind, ErrorKind::AgentUnreachable);
+    }
+}
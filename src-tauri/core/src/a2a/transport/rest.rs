@@ -0,0 +1,203 @@
+//! HTTP+JSON binding: `POST {url}/v1/message:send`, `POST {url}/v1/message:stream`,
+//! `GET {url}/v1/tasks/{id}` and `POST {url}/v1/tasks/{id}:cancel`
+
+use anyhow::{Context, Result};
+use reqwest::{Client, Method, RequestBuilder, Url};
+use serde_json::{Value, json};
+
+use crate::{
+    a2a::{
+        client::{apply_custom_headers, execute_a2a_request, execute_a2a_stream, with_http_scheme},
+        transport::{A2AStream, Endpoint, Transport},
+    },
+    error::AppError,
+    model::{A2AMessage, SettingA2AServer},
+};
+
+// Wrapper keys a result may come in (proto JSON style) and the kind each stands for
+const RESULT_WRAPPERS: [(&str, &str); 5] = [
+    ("task", "task"),
+    ("message", "message"),
+    ("msg", "message"),
+    ("statusUpdate", "status-update"),
+    ("artifactUpdate", "artifact-update"),
+];
+
+pub async fn send_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<String> {
+    let request_id = message.message_id.clone();
+    let request = rest_request(
+        Method::POST,
+        a2a_server,
+        url,
+        &["message:send"],
+        header_skill_id,
+    )?
+    .json(&message_send_params(message));
+
+    let body = execute_a2a_request(request).await?;
+    to_jsonrpc_body(&request_id, &body)
+}
+
+pub async fn stream_message(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    message: A2AMessage,
+    header_skill_id: &str,
+) -> Result<A2AStream> {
+    let request_id = message.message_id.clone();
+    let request = rest_request(
+        Method::POST,
+        a2a_server,
+        url,
+        &["message:stream"],
+        header_skill_id,
+    )?
+    .json(&message_send_params(message));
+
+    let (exchange, response) = execute_a2a_stream(request).await?;
+    let endpoint = Endpoint {
+        transport: Transport::HttpJson,
+        url: url.to_string(),
+    };
+    Ok(A2AStream::new(endpoint, request_id, exchange, response))
+}
+
+pub async fn get_task(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> Result<String> {
+    let request = rest_request(
+        Method::GET,
+        a2a_server,
+        url,
+        &["tasks", task_id],
+        header_skill_id,
+    )?;
+
+    let body = execute_a2a_request(request).await?;
+    to_jsonrpc_body(task_id, &body)
+}
+
+pub async fn cancel_task(
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    header_skill_id: &str,
+    task_id: &str,
+) -> Result<String> {
+    let request = rest_request(
+        Method::POST,
+        a2a_server,
+        url,
+        &["tasks", &format!("{}:cancel", task_id)],
+        header_skill_id,
+    )?
+    .json(&json!({}));
+
+    let body = execute_a2a_request(request).await?;
+    to_jsonrpc_body(task_id, &body)
+}
+
+/// Wrap a REST result in the JSON-RPC response shape the rest of the client reads
+pub fn to_jsonrpc_response(id: &str, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": unwrap_result(result) })
+}
+
+fn to_jsonrpc_body(id: &str, body: &str) -> Result<String> {
+    let result = serde_json::from_str::<Value>(body)
+        .map_err(AppError::from)
+        .context("Agent sent an invalid HTTP+JSON response")?;
+    Ok(to_jsonrpc_response(id, result).to_string())
+}
+
+// A v0.3 agent answers with the Task or Message itself, newer ones wrap it as
+// `{"task": ...}`, which is unwrapped with its kind filled in
+fn unwrap_result(result: Value) -> Value {
+    if result.get("kind").is_some() {
+        return result;
+    }
+
+    for (key, kind) in RESULT_WRAPPERS {
+        if let Some(Value::Object(inner)) = result.get(key) {
+            let mut inner = inner.clone();
+            inner
+                .entry("kind")
+                .or_insert_with(|| Value::String(kind.to_string()));
+            return Value::Object(inner);
+        }
+    }
+
+    result
+}
+
+fn message_send_params(message: A2AMessage) -> Value {
+    json!({ "message": message, "metadata": {} })
+}
+
+// `{url}/v1/<segments>` with the server's custom headers. Segments are
+// percent-encoded, so task ids can't escape their path segment
+fn rest_request(
+    method: Method,
+    a2a_server: &SettingA2AServer,
+    url: &str,
+    segments: &[&str],
+    header_skill_id: &str,
+) -> Result<RequestBuilder> {
+    let invalid_url = || AppError::invalid_params(format!("Invalid agent url: {}", url));
+    let mut request_url = Url::parse(&with_http_scheme(url)).map_err(|_| invalid_url())?;
+    request_url
+        .path_segments_mut()
+        .map_err(|_| invalid_url())?
+        .pop_if_empty()
+        .push("v1")
+        .extend(segments);
+
+    let request_builder = Client::new()
+        .request(method, request_url)
+        .header("X-A2A-Skill-Id", header_skill_id);
+
+    Ok(apply_custom_headers(request_builder, a2a_server))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_results_are_unwrapped_with_their_kind() {
+        assert_eq!(
+            unwrap_result(json!({ "task": { "id": "t1", "status": { "state": "working" } } })),
+            json!({ "id": "t1", "kind": "task", "status": { "state": "working" } })
+        );
+        assert_eq!(
+            unwrap_result(json!({ "msg": { "messageId": "m1" } })),
+            json!({ "messageId": "m1", "kind": "message" })
+        );
+        // A kind the agent sent is kept
+        assert_eq!(
+            unwrap_result(json!({ "message": { "messageId": "m1", "kind": "custom" } })),
+            json!({ "messageId": "m1", "kind": "custom" })
+        );
+    }
+
+    #[test]
+    fn bare_results_are_kept() {
+        let task = json!({ "id": "t1", "kind": "task", "message": { "messageId": "m1" } });
+        assert_eq!(unwrap_result(task.clone()), task);
+        assert_eq!(unwrap_result(json!({ "other": 1 })), json!({ "other": 1 }));
+    }
+
+    #[test]
+    fn jsonrpc_response_carries_the_id() {
+        assert_eq!(
+            to_jsonrpc_response("t1", json!({ "task": { "id": "t1" } })),
+            json!({ "jsonrpc": "2.0", "id": "t1", "result": { "id": "t1", "kind": "task" } })
+        );
+    }
+}
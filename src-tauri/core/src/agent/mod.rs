@@ -0,0 +1,3 @@
+pub mod prompt;
+pub mod react;
+pub mod server;
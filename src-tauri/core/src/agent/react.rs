@@ -0,0 +1,279 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{
+        build_a2a_message_parts, card_service_url, extract_response_text, post_a2a_message,
+    },
+    agent::prompt::{HOST_AGENT_SYSTEM_PROMPT, build_system_prompt},
+    llm::{
+        chat_model_name, client_for, complete,
+        context_window::{ContextBudget, Summarizer, fit_messages},
+        preset::preset_options,
+    },
+    model::{A2AMessage, ChatMessage, GenerationOptions, SettingA2AServer, SettingModel},
+};
+
+pub const DEFAULT_MAX_ITERATIONS: usize = 8;
+
+/// One step of the ReAct loop, reported while the loop runs
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HostAgentStep {
+    Thought {
+        text: String,
+    },
+    Action {
+        agent_name: String,
+        skill_name: String,
+        message: String,
+    },
+    Observation {
+        agent_name: String,
+        skill_name: String,
+        state: String,
+        text: String,
+    },
+    FinalAnswer {
+        text: String,
+    },
+}
+
+/// A `send_to_agent(agent_name="…", skill_name="…", message="…")` action
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendToAgentAction {
+    pub agent_name: String,
+    pub skill_name: String,
+    pub message: String,
+}
+
+/// Rust port of the frontend `A2AHostAgent` ReAct loop
+pub struct HostAgent {
+    servers: Vec<SettingA2AServer>,
+    model: SettingModel,
+    system_prompt_template: String,
+    max_iterations: usize,
+}
+
+impl HostAgent {
+    pub fn new(servers: Vec<SettingA2AServer>, model: SettingModel) -> Self {
+        Self {
+            servers,
+            model,
+            system_prompt_template: HOST_AGENT_SYSTEM_PROMPT.to_string(),
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    pub fn with_system_prompt_template(mut self, template: String) -> Self {
+        self.system_prompt_template = template;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations.max(1);
+        self
+    }
+
+    /// Run the loop until the model gives a final answer, reporting every step
+    pub async fn run(
+        &self,
+        question: &str,
+        mut on_step: impl FnMut(HostAgentStep),
+    ) -> Result<String> {
+        let client = client_for(&self.model);
+
+        let model_name = chat_model_name(&self.model)?;
+
+        // The model's default preset applies on top of the planner's low temperature
+        let options = GenerationOptions {
+            temperature: Some(0.3),
+            ..Default::default()
+        }
+        .merge(&preset_options(self.model.id, None)?);
+        let budget = ContextBudget::for_model(Some(&self.model), &model_name, options.max_tokens);
+
+        let mut messages = vec![
+            ChatMessage {
+                role: "system".to_string(),
+                content: build_system_prompt(&self.system_prompt_template, &self.servers),
+            },
+            ChatMessage {
+                role: "user".to_string(),
+                content: format!("<question>{}</question>", question),
+            },
+        ];
+
+        for iteration in 1..=self.max_iterations {
+            // Observations pile up every turn, keep what is sent inside the window
+            let summarizer = Summarizer {
+                client: &client,
+                model_name: &model_name,
+            };
+            let (fitted, _) = fit_messages(messages, &budget, Some(summarizer)).await?;
+            messages = fitted;
+
+            let content = complete(&client, &model_name, &messages, &options).await?;
+
+            if let Some(thought) = extract_tag(&content, "thought") {
+                on_step(HostAgentStep::Thought {
+                    text: thought.to_string(),
+                });
+            }
+
+            if let Some(final_answer) = extract_tag(&content, "final_answer") {
+                on_step(HostAgentStep::FinalAnswer {
+                    text: final_answer.to_string(),
+                });
+                return Ok(final_answer.to_string());
+            }
+
+            let action = extract_tag(&content, "action")
+                .and_then(parse_send_to_agent)
+                .context("ReAct failed, no action found")?;
+            on_step(HostAgentStep::Action {
+                agent_name: action.agent_name.clone(),
+                skill_name: action.skill_name.clone(),
+                message: action.message.clone(),
+            });
+
+            let (state, text) = self.send_to_agent(&action).await?;
+            on_step(HostAgentStep::Observation {
+                agent_name: action.agent_name.clone(),
+                skill_name: action.skill_name.clone(),
+                state,
+                text: text.clone(),
+            });
+
+            // Keep the model's own step so the next turn sees what it already did,
+            // anything it wrote after </action> is dropped
+            let step = content
+                .find("</action>")
+                .map(|end| &content[..end + "</action>".len()])
+                .unwrap_or(&content);
+            messages.push(ChatMessage {
+                role: "assistant".to_string(),
+                content: step.to_string(),
+            });
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: format!("<observation>{}. {}</observation>", iteration, text),
+            });
+        }
+
+        Err(anyhow::anyhow!(
+            "ReAct stopped after {} iterations without a final answer",
+            self.max_iterations
+        ))
+    }
+
+    // Resolve the agent and skill by name like the frontend and send the message
+    async fn send_to_agent(&self, action: &SendToAgentAction) -> Result<(String, String)> {
+        let server = self
+            .servers
+            .iter()
+            .find(|server| server.name == action.agent_name)
+            .context("ReAct failed, no a2a server found")?;
+
+        let card: serde_json::Value = server
+            .agent_card_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default();
+        let skill_id = card
+            .get("skills")
+            .and_then(|s| s.as_array())
+            .into_iter()
+            .flatten()
+            .find(|skill| skill.get("name").and_then(|n| n.as_str()) == Some(&action.skill_name))
+            .and_then(|skill| skill.get("id"))
+            .and_then(|id| id.as_str())
+            .context("ReAct failed, no skill found")?;
+        let a2a_url = card_service_url(server).context("A2A server has no agent card url")?;
+
+        let message = A2AMessage {
+            message_id: format!("msg_id:{}", Uuid::new_v4()),
+            kind: "message".to_string(),
+            role: "user".to_string(),
+            parts: build_a2a_message_parts(server, action.message.clone()),
+            context_id: None,
+            task_id: None,
+        };
+
+        let response_text = post_a2a_message(
+            server,
+            &a2a_url,
+            format!("task_id:{}", Uuid::new_v4()),
+            message,
+            skill_id.to_string(),
+        )
+        .await?;
+
+        let response: serde_json::Value =
+            serde_json::from_str(&response_text).unwrap_or(serde_json::Value::Null);
+        let state = response
+            .pointer("/result/status/state")
+            .and_then(|s| s.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| {
+                if response.get("error").is_some() {
+                    "failed".to_string()
+                } else {
+                    "completed".to_string()
+                }
+            });
+        let text = extract_response_text(&response)
+            .unwrap_or_else(|| "ReAct: please check the result in the A2A Server.".to_string());
+
+        Ok((state, text))
+    }
+}
+
+/// Content of the first `<tag>…</tag>`, trimmed
+pub fn extract_tag<'a>(content: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = content.find(&open)? + open.len();
+    let end = content[start..].find(&close)? + start;
+    Some(content[start..end].trim())
+}
+
+/// Parse `send_to_agent(agent_name="…", skill_name="…", message="…")`
+pub fn parse_send_to_agent(action: &str) -> Option<SendToAgentAction> {
+    let start = action.find("send_to_agent")? + "send_to_agent".len();
+    let params = action[start..].trim_start().strip_prefix('(')?;
+    let params = &params[..params.rfind(')')?];
+
+    Some(SendToAgentAction {
+        agent_name: quoted_param(params, "agent_name")?,
+        skill_name: quoted_param(params, "skill_name")?,
+        message: quoted_param(params, "message")?.replace("\\n", "\n"),
+    })
+}
+
+// Value of `name="…"`, the value itself cannot contain a double quote
+fn quoted_param(params: &str, name: &str) -> Option<String> {
+    let mut search_from = 0;
+    while let Some(position) = params[search_from..].find(name) {
+        let after_name = search_from + position + name.len();
+        // Skip matches that are the tail of a longer identifier
+        let preceded_by_ident = params[..search_from + position]
+            .chars()
+            .next_back()
+            .map(|c| c.is_alphanumeric() || c == '_')
+            .unwrap_or(false);
+
+        let rest = params[after_name..].trim_start();
+        if !preceded_by_ident {
+            if let Some(rest) = rest.strip_prefix('=') {
+                if let Some(value) = rest.trim_start().strip_prefix('"') {
+                    let end = value.find('"')?;
+                    return Some(value[..end].to_string());
+                }
+            }
+        }
+        search_from = after_name;
+    }
+    None
+}
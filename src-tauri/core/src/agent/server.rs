@@ -0,0 +1,874 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode, header, uri::Authority},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+    routing::{get, post},
+};
+use lazy_static::lazy_static;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tokio::sync::{mpsc, oneshot};
+use uuid::Uuid;
+
+use crate::{
+    agent::react::{HostAgent, HostAgentStep},
+    db::{
+        a2a_db::SettingA2AServerDbManager, host_agent_profile_db::HostAgentProfileDbManager,
+        model_db::SettingModelDbManager,
+    },
+    error::AppError,
+    model::{HostAgentServerParams, HostAgentServerStatus},
+};
+
+// JSON-RPC and A2A error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+const TASK_NOT_FOUND: i64 = -32001;
+const TASK_NOT_CANCELABLE: i64 = -32002;
+
+// Tasks kept for tasks/get, running ones included
+const MAX_STORED_TASKS: usize = 200;
+
+struct ServerState {
+    base_url: String,
+    // Bound to every interface, the card takes its url from the Host the client used
+    wildcard: bool,
+    token: Option<String>,
+    tasks: std::sync::Mutex<VecDeque<Value>>,
+    // Senders that stop the running tasks, by task id
+    cancels: std::sync::Mutex<HashMap<String, oneshot::Sender<()>>>,
+}
+
+impl ServerState {
+    fn new(base_url: String, wildcard: bool, token: Option<String>) -> Self {
+        Self {
+            base_url,
+            wildcard,
+            token,
+            tasks: std::sync::Mutex::new(VecDeque::new()),
+            cancels: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+struct RunningServer {
+    status: HostAgentServerStatus,
+    shutdown: oneshot::Sender<()>,
+}
+
+lazy_static! {
+    static ref RUNNING_SERVER: std::sync::Mutex<Option<RunningServer>> =
+        std::sync::Mutex::new(None);
+}
+
+/// Start the embedded A2A server, fails if one is already running.
+/// A host other machines can reach needs a token, the agent spends the model's API key
+pub async fn start(params: HostAgentServerParams) -> Result<HostAgentServerStatus> {
+    let host = params
+        .host
+        .filter(|host| !host.trim().is_empty())
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+    let token = params.token.filter(|token| !token.is_empty());
+    if token.is_none() && !is_loopback(&host) {
+        return Err(AppError::invalid_params(format!(
+            "A token is required to serve on {}, which other machines can reach",
+            host
+        ))
+        .into());
+    }
+
+    // Held until the server is stored, so two starts can't both bind
+    let mut running = RUNNING_SERVER
+        .lock()
+        .map_err(|e| anyhow::anyhow!("failed to acquire server lock: {e}"))?;
+    if running.is_some() {
+        return Err(anyhow::anyhow!("Host agent server is already running"));
+    }
+
+    let listener = std::net::TcpListener::bind((host.as_str(), params.port))
+        .with_context(|| format!("failed to bind {}:{}", host, params.port))?;
+    listener.set_nonblocking(true)?;
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+    let local_addr: SocketAddr = listener.local_addr()?;
+    let base_url = format!("http://{}/", advertised_addr(local_addr));
+
+    let state = Arc::new(ServerState::new(
+        base_url.clone(),
+        local_addr.ip().is_unspecified(),
+        token,
+    ));
+
+    let app = router(state.clone());
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+    tokio::spawn(async move {
+        let result = axum::serve(listener, app)
+            .with_graceful_shutdown(async {
+                let _ = shutdown_rx.await;
+            })
+            .await;
+        if let Err(e) = result {
+            log::error!("Host agent server stopped with error: {}", e);
+        }
+    });
+
+    let status = HostAgentServerStatus {
+        running: true,
+        url: Some(base_url),
+        auth_required: state.token.is_some(),
+    };
+
+    *running = Some(RunningServer {
+        status: status.clone(),
+        shutdown: shutdown_tx,
+    });
+
+    log::info!("Host agent A2A server listening on {}", local_addr);
+    Ok(status)
+}
+
+/// Stop the embedded A2A server, returns whether one was running
+pub fn stop() -> Result<bool> {
+    let running = RUNNING_SERVER
+        .lock()
+        .map_err(|e| anyhow::anyhow!("failed to acquire server lock: {e}"))?
+        .take();
+
+    match running {
+        Some(server) => {
+            let _ = server.shutdown.send(());
+            log::info!("Host agent A2A server stopped");
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+pub fn status() -> Result<HostAgentServerStatus> {
+    let running = RUNNING_SERVER
+        .lock()
+        .map_err(|e| anyhow::anyhow!("failed to acquire server lock: {e}"))?;
+
+    Ok(running
+        .as_ref()
+        .map(|server| server.status.clone())
+        .unwrap_or(HostAgentServerStatus {
+            running: false,
+            url: None,
+            auth_required: false,
+        }))
+}
+
+fn is_loopback(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+// A wildcard address like 0.0.0.0 is no use to clients, advertise the interface
+// with the default route instead. Connecting a UDP socket sends nothing
+fn advertised_addr(local_addr: SocketAddr) -> SocketAddr {
+    if !local_addr.ip().is_unspecified() {
+        return local_addr;
+    }
+    let interface_ip = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .ok()
+        .filter(|ip| !ip.is_unspecified())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST));
+    SocketAddr::new(interface_ip, local_addr.port())
+}
+
+fn router(state: Arc<ServerState>) -> Router {
+    Router::new()
+        .route("/.well-known/agent-card.json", get(agent_card))
+        .route("/.well-known/agent.json", get(agent_card))
+        .route("/", post(jsonrpc))
+        .with_state(state)
+}
+
+async fn agent_card(State(state): State<Arc<ServerState>>, headers: HeaderMap) -> Json<Value> {
+    let agent_names: Vec<String> = SettingA2AServerDbManager::new()
+        .get_enabled()
+        .map(|servers| servers.into_iter().map(|server| server.name).collect())
+        .unwrap_or_default();
+
+    let mut description =
+        "Host agent that plans with a ReAct loop and delegates to other A2A agents".to_string();
+    if !agent_names.is_empty() {
+        description.push_str(&format!(": {}", agent_names.join(", ")));
+    }
+
+    let mut card = json!({
+        "protocolVersion": "0.3.0",
+        "name": "A2A Client Host Agent",
+        "description": description,
+        "url": card_url(&state, &headers),
+        "preferredTransport": "JSONRPC",
+        "version": env!("CARGO_PKG_VERSION"),
+        "capabilities": {
+            "streaming": true,
+            "pushNotifications": false,
+            "stateTransitionHistory": false,
+        },
+        "defaultInputModes": ["text/plain"],
+        "defaultOutputModes": ["text/plain"],
+        "skills": [{
+            "id": "host_agent",
+            "name": "Host Agent",
+            "description": "Answers a question by orchestrating the enabled A2A agents and their skills",
+            "tags": ["orchestration", "react"],
+            "examples": agent_names
+                .iter()
+                .take(3)
+                .map(|name| format!("Ask {} for help with a task", name))
+                .collect::<Vec<_>>(),
+        }],
+    });
+
+    if state.token.is_some() {
+        card["securitySchemes"] = json!({ "bearer": { "type": "http", "scheme": "bearer" } });
+        card["security"] = json!([{ "bearer": [] }]);
+    }
+
+    Json(card)
+}
+
+// The Host a client reached a wildcard bound server by works for that client
+fn card_url(state: &ServerState, headers: &HeaderMap) -> String {
+    let request_host = headers
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .filter(|host| host.parse::<Authority>().is_ok());
+    match request_host {
+        Some(host) if state.wildcard => format!("http://{}/", host),
+        _ => state.base_url.clone(),
+    }
+}
+
+async fn jsonrpc(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    if let Some(rejection) = reject_request(&state, &headers) {
+        return rejection;
+    }
+
+    let request: Value = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return Json(error_response(Value::Null, PARSE_ERROR, &e.to_string())).into_response();
+        }
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(|m| m.as_str()) else {
+        return Json(error_response(id, INVALID_REQUEST, "Missing method")).into_response();
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    match method {
+        "message/send" => Json(send_message(&state, id, params).await).into_response(),
+        "message/stream" => stream_message(state, id, params).into_response(),
+        "tasks/get" => {
+            let task_id = params
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            match find_task(&state, task_id) {
+                Some(task) => {
+                    Json(json!({ "jsonrpc": "2.0", "id": id, "result": task })).into_response()
+                }
+                None => Json(error_response(id, TASK_NOT_FOUND, "Task not found")).into_response(),
+            }
+        }
+        "tasks/cancel" => {
+            let task_id = params
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            match cancel_task(&state, task_id) {
+                Ok(task) => {
+                    Json(json!({ "jsonrpc": "2.0", "id": id, "result": task })).into_response()
+                }
+                Err((code, message)) => Json(error_response(id, code, message)).into_response(),
+            }
+        }
+        other => Json(error_response(
+            id,
+            METHOD_NOT_FOUND,
+            &format!("Method not found: {}", other),
+        ))
+        .into_response(),
+    }
+}
+
+// Only JSON-RPC calls of A2A clients get through. Browsers send an Origin, so a
+// web page can't drive the agent, and a cross-site form post can't be JSON
+fn reject_request(state: &ServerState, headers: &HeaderMap) -> Option<Response> {
+    if headers.contains_key(header::ORIGIN) {
+        return Some((StatusCode::FORBIDDEN, "Browser requests are not accepted").into_response());
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+    if !is_json {
+        return Some(
+            (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "Content-Type must be application/json",
+            )
+                .into_response(),
+        );
+    }
+
+    if let Some(token) = &state.token {
+        let authorized = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|presented| tokens_match(presented, token));
+        if !authorized {
+            return Some(
+                (StatusCode::UNAUTHORIZED, "Missing or invalid bearer token").into_response(),
+            );
+        }
+    }
+
+    None
+}
+
+// Compares digests byte by byte without stopping early, so the time taken
+// says nothing about how much of the token was right, nor its length
+fn tokens_match(presented: &str, token: &str) -> bool {
+    let presented = Sha256::digest(presented.as_bytes());
+    let token = Sha256::digest(token.as_bytes());
+    presented
+        .iter()
+        .zip(token.iter())
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+async fn send_message(state: &ServerState, id: Value, params: Value) -> Value {
+    let incoming = match IncomingMessage::from_params(&params) {
+        Ok(incoming) => incoming,
+        Err(message) => return error_response(id, INVALID_PARAMS, &message),
+    };
+    let agent = match load_host_agent() {
+        Ok(agent) => agent,
+        Err(e) => return error_response(id, INTERNAL_ERROR, &e.to_string()),
+    };
+
+    let task = run_task(state, &incoming, agent.run(&incoming.text, |_| {})).await;
+
+    json!({ "jsonrpc": "2.0", "id": id, "result": task })
+}
+
+fn stream_message(
+    state: Arc<ServerState>,
+    id: Value,
+    params: Value,
+) -> Sse<impl futures_util::Stream<Item = Result<Event, Infallible>>> {
+    let (tx, rx) = mpsc::unbounded_channel::<Value>();
+    let rpc_event = {
+        let id = id.clone();
+        move |result: Value| json!({ "jsonrpc": "2.0", "id": id, "result": result })
+    };
+
+    tokio::spawn(async move {
+        let incoming = match IncomingMessage::from_params(&params) {
+            Ok(incoming) => incoming,
+            Err(message) => {
+                let _ = tx.send(error_response(id, INVALID_PARAMS, &message));
+                return;
+            }
+        };
+        let agent = match load_host_agent() {
+            Ok(agent) => agent,
+            Err(e) => {
+                let _ = tx.send(error_response(id, INTERNAL_ERROR, &e.to_string()));
+                return;
+            }
+        };
+
+        let mut submitted = incoming.task("submitted", None);
+        submitted["history"] = json!([incoming.message]);
+        let _ = tx.send(rpc_event(submitted));
+
+        let run = agent.run(&incoming.text, |step| {
+            let _ = tx.send(rpc_event(incoming.step_update(&step)));
+        });
+        let task = run_task(&state, &incoming, run).await;
+
+        if let Some(artifact) = task.get("artifacts").and_then(|a| a.get(0)) {
+            let _ = tx.send(rpc_event(json!({
+                "kind": "artifact-update",
+                "taskId": incoming.task_id,
+                "contextId": incoming.context_id,
+                "artifact": artifact,
+                "lastChunk": true,
+            })));
+        }
+        let _ = tx.send(rpc_event(json!({
+            "kind": "status-update",
+            "taskId": incoming.task_id,
+            "contextId": incoming.context_id,
+            "status": task["status"],
+            "final": true,
+        })));
+    });
+
+    let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv()
+            .await
+            .map(|message| (Ok(Event::default().data(message.to_string())), rx))
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// The user message of a message/send or message/stream request
+struct IncomingMessage {
+    message: Value,
+    text: String,
+    task_id: String,
+    context_id: String,
+}
+
+impl IncomingMessage {
+    fn from_params(params: &Value) -> Result<Self, String> {
+        let message = params
+            .get("message")
+            .cloned()
+            .ok_or_else(|| "Missing message".to_string())?;
+
+        let text = message
+            .get("parts")
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|part| part.get("kind").and_then(|k| k.as_str()) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.trim().is_empty() {
+            return Err("The message has no text part".to_string());
+        }
+
+        let context_id = message
+            .get("contextId")
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        Ok(Self {
+            message,
+            text,
+            task_id: Uuid::new_v4().to_string(),
+            context_id,
+        })
+    }
+
+    fn task(&self, state: &str, status_message: Option<Value>) -> Value {
+        let mut status = json!({
+            "state": state,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        });
+        if let Some(status_message) = status_message {
+            status["message"] = status_message;
+        }
+
+        json!({
+            "kind": "task",
+            "id": self.task_id,
+            "contextId": self.context_id,
+            "status": status,
+        })
+    }
+
+    fn agent_message(&self, parts: Value) -> Value {
+        json!({
+            "kind": "message",
+            "messageId": Uuid::new_v4().to_string(),
+            "role": "agent",
+            "parts": parts,
+            "taskId": self.task_id,
+            "contextId": self.context_id,
+        })
+    }
+
+    // Every ReAct step is reported as a working status update carrying the step as data
+    fn step_update(&self, step: &HostAgentStep) -> Value {
+        let data = serde_json::to_value(step).unwrap_or(Value::Null);
+        json!({
+            "kind": "status-update",
+            "taskId": self.task_id,
+            "contextId": self.context_id,
+            "status": {
+                "state": "working",
+                "message": self.agent_message(json!([{ "kind": "data", "data": data }])),
+                "timestamp": chrono::Utc::now().to_rfc3339(),
+            },
+            "final": false,
+        })
+    }
+
+    fn working_task(&self) -> Value {
+        let mut task = self.task("working", None);
+        task["history"] = json!([self.message]);
+        task
+    }
+
+    fn finished_task(&self, result: Result<String>) -> Value {
+        let mut task = match &result {
+            Ok(answer) => {
+                let mut task = self.task("completed", None);
+                task["artifacts"] = json!([{
+                    "artifactId": Uuid::new_v4().to_string(),
+                    "name": "final_answer",
+                    "parts": [{ "kind": "text", "text": answer }],
+                }]);
+                task
+            }
+            Err(e) => {
+                log::warn!("Host agent task {} failed: {:#}", self.task_id, e);
+                self.task(
+                    "failed",
+                    Some(
+                        self.agent_message(json!([{ "kind": "text", "text": format!("{:#}", e) }])),
+                    ),
+                )
+            }
+        };
+        task["history"] = json!([self.message]);
+        task
+    }
+}
+
+// Plans with the default host agent profile when one is set
+fn load_host_agent() -> Result<HostAgent> {
+    let profile = HostAgentProfileDbManager::new().get_default()?;
+
+    let servers: Vec<_> = SettingA2AServerDbManager::new()
+        .get_enabled()?
+        .into_iter()
+        .filter(
+            |server| match profile.as_ref().and_then(|p| p.a2a_server_ids.as_ref()) {
+                Some(ids) => server.id.map(|id| ids.contains(&id)).unwrap_or(false),
+                None => true,
+            },
+        )
+        .collect();
+    if servers.is_empty() {
+        return Err(anyhow::anyhow!("No A2A server is enabled"));
+    }
+
+    let model_db = SettingModelDbManager::new();
+    let model = match profile.as_ref().and_then(|p| p.model_id) {
+        Some(model_id) => model_db
+            .get_by_id(model_id)?
+            .context("The profile's model no longer exists")?,
+        None => model_db
+            .get_enabled()?
+            .into_iter()
+            .find(|model| !model.api_key.is_empty())
+            .context("No enabled model with an API key")?,
+    };
+
+    let mut agent = HostAgent::new(servers, model);
+    if let Some(profile) = profile {
+        if let Some(template) = profile.system_prompt_template {
+            agent = agent.with_system_prompt_template(template);
+        }
+        agent = agent.with_max_iterations(profile.max_iterations.max(1) as usize);
+    }
+
+    Ok(agent)
+}
+
+// Stores the task as working while the agent runs, tasks/cancel stops it
+async fn run_task(
+    state: &ServerState,
+    incoming: &IncomingMessage,
+    run: impl Future<Output = Result<String>>,
+) -> Value {
+    let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+    if let Ok(mut cancels) = state.cancels.lock() {
+        cancels.insert(incoming.task_id.clone(), cancel_tx);
+    }
+    store_task(state, incoming.working_task());
+
+    // Cancel first, tasks/cancel already answered with the canceled task
+    let task = tokio::select! {
+        biased;
+        Ok(()) = cancel_rx => canceled(incoming.working_task()),
+        result = run => incoming.finished_task(result),
+    };
+
+    if let Ok(mut cancels) = state.cancels.lock() {
+        cancels.remove(&incoming.task_id);
+    }
+    store_task(state, task.clone());
+    task
+}
+
+fn cancel_task(state: &ServerState, task_id: &str) -> Result<Value, (i64, &'static str)> {
+    let task = find_task(state, task_id).ok_or((TASK_NOT_FOUND, "Task not found"))?;
+    let cancel = state
+        .cancels
+        .lock()
+        .ok()
+        .and_then(|mut cancels| cancels.remove(task_id));
+    // The send fails when the task finished since it was looked up
+    match cancel.map(|cancel| cancel.send(())) {
+        Some(Ok(())) => {
+            let task = canceled(task);
+            store_task(state, task.clone());
+            Ok(task)
+        }
+        _ => Err((TASK_NOT_CANCELABLE, "Task is already in a terminal state")),
+    }
+}
+
+fn canceled(mut task: Value) -> Value {
+    task["status"] = json!({
+        "state": "canceled",
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+    });
+    task
+}
+
+// Replaces the stored task with the same id
+fn store_task(state: &ServerState, task: Value) {
+    if let Ok(mut tasks) = state.tasks.lock() {
+        tasks.retain(|stored| stored.get("id") != task.get("id"));
+        tasks.push_back(task);
+        while tasks.len() > MAX_STORED_TASKS {
+            tasks.pop_front();
+        }
+    }
+}
+
+fn find_task(state: &ServerState, task_id: &str) -> Option<Value> {
+    let tasks = state.tasks.lock().ok()?;
+    tasks
+        .iter()
+        .find(|task| task.get("id").and_then(|id| id.as_str()) == Some(task_id))
+        .cloned()
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve;
+
+    fn incoming() -> IncomingMessage {
+        IncomingMessage::from_params(&json!({
+            "message": {
+                "kind": "message",
+                "messageId": "m1",
+                "role": "user",
+                "parts": [{ "kind": "text", "text": "hello" }],
+            },
+        }))
+        .unwrap()
+    }
+
+    fn state(wildcard: bool) -> Arc<ServerState> {
+        Arc::new(ServerState::new(
+            "http://127.0.0.1:41241/".to_string(),
+            wildcard,
+            None,
+        ))
+    }
+
+    fn stored_state(state: &ServerState, task_id: &str) -> Value {
+        find_task(state, task_id).unwrap()["status"]["state"].clone()
+    }
+
+    #[test]
+    fn only_loopback_hosts_count_as_local() {
+        assert!(is_loopback("127.0.0.1"));
+        assert!(is_loopback("localhost"));
+        assert!(is_loopback("[::1]"));
+        assert!(!is_loopback("0.0.0.0"));
+        assert!(!is_loopback("192.168.1.10"));
+    }
+
+    #[tokio::test]
+    async fn reachable_hosts_need_a_token() {
+        let params = HostAgentServerParams {
+            port: 0,
+            host: Some("0.0.0.0".to_string()),
+            token: None,
+        };
+        let error = start(params).await.unwrap_err();
+        assert!(error.to_string().contains("A token is required"));
+    }
+
+    #[test]
+    fn wildcard_servers_advertise_the_request_host() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "10.0.0.5:41241".parse().unwrap());
+        assert_eq!(card_url(&state(true), &headers), "http://10.0.0.5:41241/");
+        assert_eq!(card_url(&state(false), &headers), "http://127.0.0.1:41241/");
+        assert_eq!(
+            card_url(&state(true), &HeaderMap::new()),
+            "http://127.0.0.1:41241/"
+        );
+
+        let advertised = advertised_addr("0.0.0.0:41241".parse().unwrap());
+        assert!(!advertised.ip().is_unspecified());
+        assert_eq!(advertised.port(), 41241);
+    }
+
+    #[tokio::test]
+    async fn tasks_are_working_until_they_finish() {
+        let state = state(false);
+        let incoming = incoming();
+        let task_id = incoming.task_id.clone();
+        let (finish_tx, finish_rx) = oneshot::channel::<()>();
+        let handle = {
+            let state = state.clone();
+            tokio::spawn(async move {
+                let run = async {
+                    let _ = finish_rx.await;
+                    Ok("done".to_string())
+                };
+                run_task(&state, &incoming, run).await
+            })
+        };
+        while find_task(&state, &task_id).is_none() {
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(stored_state(&state, &task_id), "working");
+
+        finish_tx.send(()).unwrap();
+        let task = handle.await.unwrap();
+        assert_eq!(task["status"]["state"], "completed");
+        assert_eq!(stored_state(&state, &task_id), "completed");
+        assert_eq!(
+            cancel_task(&state, &task_id).unwrap_err().0,
+            TASK_NOT_CANCELABLE
+        );
+    }
+
+    #[tokio::test]
+    async fn cancel_stops_a_running_task() {
+        let state = state(false);
+        let incoming = incoming();
+        let task_id = incoming.task_id.clone();
+        let handle = {
+            let state = state.clone();
+            tokio::spawn(async move { run_task(&state, &incoming, std::future::pending()).await })
+        };
+        while find_task(&state, &task_id).is_none() {
+            tokio::task::yield_now().await;
+        }
+
+        let canceled = cancel_task(&state, &task_id).unwrap();
+        assert_eq!(canceled["status"]["state"], "canceled");
+        assert_eq!(canceled["history"][0]["messageId"], "m1");
+
+        let task = handle.await.unwrap();
+        assert_eq!(task["status"]["state"], "canceled");
+        assert_eq!(stored_state(&state, &task_id), "canceled");
+        assert_eq!(
+            cancel_task(&state, "missing").unwrap_err().0,
+            TASK_NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn only_json_rpc_calls_without_an_origin_get_through() {
+        let state = Arc::new(ServerState::new(
+            "http://127.0.0.1:41241/".to_string(),
+            false,
+            Some("s3cret".to_string()),
+        ));
+        let url = serve(router(state)).await;
+        let call = |content_type: &str, token: &str| {
+            reqwest::Client::new()
+                .post(&url)
+                .header(header::CONTENT_TYPE, content_type)
+                .bearer_auth(token)
+                .body(r#"{"jsonrpc":"2.0","id":1,"method":"tasks/list"}"#)
+        };
+
+        let response = call("text/plain", "s3cret").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let response = call("application/x-www-form-urlencoded", "s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = call("application/json", "s3cret")
+            .header(header::ORIGIN, "https://example.com")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        for token in ["s3cre", "s3cret!", "S3CRET"] {
+            let response = call("application/json", token).send().await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{token}");
+        }
+
+        let response = call("application/json; charset=utf-8", "s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn only_one_server_runs_at_a_time() {
+        let params = || HostAgentServerParams {
+            port: 0,
+            host: None,
+            token: None,
+        };
+        let started = start(params()).await.unwrap();
+        assert!(started.url.unwrap().starts_with("http://127.0.0.1:"));
+        assert!(start(params()).await.is_err());
+        assert!(status().unwrap().running);
+        assert!(stop().unwrap());
+        assert!(!status().unwrap().running);
+    }
+}
@@ -0,0 +1,735 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use reqwest::{Client, Url, redirect::Policy};
+
+use crate::{
+    a2a::client::extract_response_text,
+    db::{artifact_db::ArtifactDbManager, artifact_store},
+    error::AppError,
+    model::{A2AArtifact, A2AArtifactUpdateEvent, StoredArtifact},
+};
+
+// Limits for downloading a file part an agent only linked to
+const FILE_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_FILE_PART_BYTES: usize = 50 * 1024 * 1024;
+
+// Longest file name an artifact is opened under
+const MAX_FILE_NAME_CHARS: usize = 120;
+// Types the OS would run or follow instead of showing in a viewer
+const EXECUTABLE_EXTENSIONS: [&str; 34] = [
+    "app",
+    "appimage",
+    "application",
+    "appref-ms",
+    "bat",
+    "chm",
+    "cmd",
+    "com",
+    "command",
+    "cpl",
+    "desktop",
+    "dll",
+    "exe",
+    "gadget",
+    "hta",
+    "inf",
+    "jar",
+    "js",
+    "jse",
+    "lnk",
+    "msc",
+    "msi",
+    "msp",
+    "pif",
+    "ps1",
+    "psm1",
+    "reg",
+    "scf",
+    "scr",
+    "sh",
+    "url",
+    "vbe",
+    "vbs",
+    "wsf",
+];
+
+/// Reassembles artifacts streamed as `artifact-update` events.
+/// Chunks with `append: true` extend the artifact, anything else replaces it,
+/// and the artifact is handed out once `lastChunk` is seen.
+#[derive(Default)]
+pub struct ArtifactAssembler {
+    pending: HashMap<(String, String), A2AArtifact>,
+}
+
+impl ArtifactAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one update event, returns the complete artifact on its last chunk
+    pub fn push(&mut self, event: A2AArtifactUpdateEvent) -> Option<A2AArtifact> {
+        let key = (event.task_id.clone(), event.artifact.artifact_id.clone());
+
+        let artifact = match self.pending.remove(&key) {
+            Some(mut existing) if event.append.unwrap_or(false) => {
+                append_parts(&mut existing.parts, event.artifact.parts);
+                if event.artifact.name.is_some() {
+                    existing.name = event.artifact.name;
+                }
+                if event.artifact.description.is_some() {
+                    existing.description = event.artifact.description;
+                }
+                if event.artifact.metadata.is_some() {
+                    existing.metadata = event.artifact.metadata;
+                }
+                existing
+            }
+            _ => event.artifact,
+        };
+
+        if event.last_chunk.unwrap_or(false) {
+            Some(artifact)
+        } else {
+            self.pending.insert(key, artifact);
+            None
+        }
+    }
+
+    /// Hand out artifacts whose stream ended without a `lastChunk` marker
+    pub fn finish(&mut self) -> Vec<(String, A2AArtifact)> {
+        self.pending
+            .drain()
+            .map(|((task_id, _), artifact)| (task_id, artifact))
+            .collect()
+    }
+}
+
+/// The artifact update a message/stream event carries, if it is one
+pub fn artifact_update(event: &serde_json::Value) -> Option<A2AArtifactUpdateEvent> {
+    let result = event.get("result")?;
+    if result.get("kind").and_then(|k| k.as_str()) != Some("artifact-update") {
+        return None;
+    }
+    serde_json::from_value(result.clone()).ok()
+}
+
+/// Readable text of an artifact's text and data parts
+pub fn artifact_text(artifact: &A2AArtifact) -> Option<String> {
+    extract_response_text(&serde_json::json!({ "result": { "parts": artifact.parts } }))
+}
+
+// Text chunks continue the previous text part, everything else becomes a new part
+fn append_parts(parts: &mut Vec<serde_json::Value>, chunk: Vec<serde_json::Value>) {
+    for part in chunk {
+        let is_text = part.get("kind").and_then(|k| k.as_str()) == Some("text");
+        if let (true, Some(last)) = (is_text, parts.last_mut()) {
+            if last.get("kind").and_then(|k| k.as_str()) == Some("text") {
+                let addition = part
+                    .get("text")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default();
+                let merged = format!(
+                    "{}{}",
+                    last.get("text")
+                        .and_then(|t| t.as_str())
+                        .unwrap_or_default(),
+                    addition
+                );
+                last["text"] = serde_json::Value::String(merged);
+                continue;
+            }
+        }
+        parts.push(part);
+    }
+}
+
+/// Store every artifact carried by a message/send response (a Task result)
+pub async fn store_response_artifacts(
+    conversation_id: Option<String>,
+    a2a_server_id: i32,
+    response_text: String,
+) {
+    let Ok(response) = serde_json::from_str::<serde_json::Value>(&response_text) else {
+        return;
+    };
+    let Some(result) = response.get("result") else {
+        return;
+    };
+    let Some(artifacts) = result.get("artifacts").and_then(|a| a.as_array()) else {
+        return;
+    };
+
+    let task_id = result
+        .get("id")
+        .and_then(|id| id.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let context_id = result
+        .get("contextId")
+        .and_then(|id| id.as_str())
+        .map(|id| id.to_string());
+
+    for value in artifacts {
+        match serde_json::from_value::<A2AArtifact>(value.clone()) {
+            Ok(artifact) => {
+                if let Err(e) = store_artifact(
+                    &artifact,
+                    &task_id,
+                    context_id.clone(),
+                    conversation_id.clone(),
+                    Some(a2a_server_id),
+                )
+                .await
+                {
+                    log::warn!("Failed to store artifact {}: {}", artifact.artifact_id, e);
+                }
+            }
+            Err(e) => log::warn!("Skipping malformed artifact: {}", e),
+        }
+    }
+}
+
+/// Write each part of a complete artifact into the blob store and index it.
+/// File parts that only carry a uri are indexed without content, they are
+/// fetched the first time the artifact is opened or saved
+pub async fn store_artifact(
+    artifact: &A2AArtifact,
+    task_id: &str,
+    context_id: Option<String>,
+    conversation_id: Option<String>,
+    a2a_server_id: Option<i32>,
+) -> Result<Vec<StoredArtifact>> {
+    let db_manager = ArtifactDbManager::new();
+    let mut stored = Vec::new();
+
+    for (part_index, part) in artifact.parts.iter().enumerate() {
+        let (part_kind, content, mime_type, file_name) = read_part_content(part)?;
+        let (content_hash, size_bytes, uri) = match content {
+            PartContent::Bytes(content) => (
+                artifact_store::put_blob(&content)?,
+                content.len() as i64,
+                None,
+            ),
+            PartContent::Uri(uri) => (String::new(), 0, Some(uri)),
+        };
+
+        let record = StoredArtifact {
+            id: None,
+            artifact_id: artifact.artifact_id.clone(),
+            task_id: task_id.to_string(),
+            context_id: context_id.clone(),
+            conversation_id: conversation_id.clone(),
+            a2a_server_id,
+            name: artifact.name.clone(),
+            description: artifact.description.clone(),
+            part_index: part_index as i32,
+            part_kind,
+            mime_type,
+            file_name,
+            content_hash,
+            size_bytes,
+            uri,
+            created_at: None,
+        };
+
+        if let Some(previous_hash) = db_manager.upsert(&record)? {
+            if db_manager.count_by_hash(&previous_hash)? == 0 {
+                artifact_store::remove_blob(&previous_hash)?;
+            }
+        }
+        stored.push(record);
+    }
+
+    Ok(stored)
+}
+
+/// A stored artifact part, `not found` when there is none with the ID
+pub fn find_artifact(id: i32) -> Result<StoredArtifact> {
+    ArtifactDbManager::new()
+        .get_by_id(id)?
+        .ok_or_else(|| AppError::not_found("Artifact not found").into())
+}
+
+/// The artifact part and the blob holding its content, a part that only
+/// carries a uri is downloaded into the store first
+pub async fn artifact_blob(id: i32) -> Result<(StoredArtifact, PathBuf)> {
+    let mut artifact = find_artifact(id)?;
+
+    if artifact.content_hash.is_empty() {
+        let uri = artifact
+            .uri
+            .clone()
+            .context("artifact has neither content nor uri")?;
+        let content = fetch_file_uri(&uri).await?;
+        let content_hash = artifact_store::put_blob(&content)?;
+        ArtifactDbManager::new().set_content(id, &content_hash, content.len() as i64)?;
+        artifact.content_hash = content_hash;
+        artifact.size_bytes = content.len() as i64;
+    }
+
+    let blob_path = artifact_store::blob_path(&artifact.content_hash)?;
+    Ok((artifact, blob_path))
+}
+
+/// Delete an artifact part, and its blob once no other part references it
+pub fn delete_artifact(id: i32) -> Result<usize> {
+    let db_manager = ArtifactDbManager::new();
+    let artifact = find_artifact(id)?;
+
+    let rows_affected = db_manager.delete_by_id(id)?;
+    // Blobs are shared between identical artifacts, only drop the last reference
+    if !artifact.content_hash.is_empty() && db_manager.count_by_hash(&artifact.content_hash)? == 0 {
+        artifact_store::remove_blob(&artifact.content_hash)?;
+    }
+    Ok(rows_affected)
+}
+
+/// Name an artifact is written under before the OS opens it. Only ASCII
+/// letters, digits, `-`, `_`, `.` and spaces are kept, and executable types are refused
+pub fn safe_file_name(artifact: &StoredArtifact) -> Result<String> {
+    let name = artifact.file_name.clone().unwrap_or_else(|| {
+        format!(
+            "{}.{}",
+            artifact.name.as_deref().unwrap_or(&artifact.artifact_id),
+            extension_for_mime(artifact.mime_type.as_deref())
+        )
+    });
+
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // Leading dots hide the file, Windows drops trailing dots and spaces
+    let name: String = name
+        .trim_matches(['.', ' '])
+        .chars()
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    let name = match name.trim_end_matches(['.', ' ']) {
+        "" => "artifact".to_string(),
+        name => name.to_string(),
+    };
+
+    let extension = name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase());
+    if let Some(extension) = extension.filter(|e| EXECUTABLE_EXTENSIONS.contains(&e.as_str())) {
+        return Err(AppError::invalid_params(format!(
+            "Refusing to open artifact {}: .{} files are executable",
+            name, extension
+        ))
+        .into());
+    }
+
+    Ok(name)
+}
+
+fn extension_for_mime(mime_type: Option<&str>) -> &'static str {
+    match mime_type.unwrap_or_default() {
+        "application/json" => "json",
+        "text/markdown" => "md",
+        "text/html" => "html",
+        "text/csv" => "csv",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/svg+xml" => "svg",
+        "application/pdf" => "pdf",
+        "text/plain" => "txt",
+        _ => "bin",
+    }
+}
+
+// Content of a part, file parts that point at a uri are fetched on demand
+enum PartContent {
+    Bytes(Vec<u8>),
+    Uri(String),
+}
+
+// Resolve a part to (kind, content, mime type, file name)
+fn read_part_content(
+    part: &serde_json::Value,
+) -> Result<(String, PartContent, Option<String>, Option<String>)> {
+    let kind = part
+        .get("kind")
+        .and_then(|k| k.as_str())
+        .unwrap_or("text")
+        .to_string();
+
+    match kind.as_str() {
+        "file" => {
+            let file = part.get("file").context("file part has no file")?;
+            let mime_type = file
+                .get("mimeType")
+                .and_then(|m| m.as_str())
+                .map(|m| m.to_string());
+            let file_name = file
+                .get("name")
+                .and_then(|n| n.as_str())
+                .map(|n| n.to_string());
+
+            let content = if let Some(bytes) = file.get("bytes").and_then(|b| b.as_str()) {
+                PartContent::Bytes(
+                    STANDARD
+                        .decode(bytes)
+                        .context("failed to decode file part bytes")?,
+                )
+            } else if let Some(uri) = file.get("uri").and_then(|u| u.as_str()) {
+                PartContent::Uri(uri.to_string())
+            } else {
+                return Err(anyhow::anyhow!("file part has neither bytes nor uri"));
+            };
+
+            Ok((kind, content, mime_type, file_name))
+        }
+        "data" => {
+            let data = part.get("data").cloned().unwrap_or(serde_json::Value::Null);
+            let content = serde_json::to_vec_pretty(&data)?;
+            Ok((
+                kind,
+                PartContent::Bytes(content),
+                Some("application/json".to_string()),
+                None,
+            ))
+        }
+        _ => {
+            let text = part
+                .get("text")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default();
+            Ok((
+                kind,
+                PartContent::Bytes(text.as_bytes().to_vec()),
+                Some("text/plain".to_string()),
+                None,
+            ))
+        }
+    }
+}
+
+/// Download a file part from the uri an agent gave. Only http(s) urls of
+/// public addresses are fetched, redirects aren't followed and the body is capped
+pub async fn fetch_file_uri(uri: &str) -> Result<Vec<u8>> {
+    fetch(uri, MAX_FILE_PART_BYTES, false).await
+}
+
+async fn fetch(uri: &str, max_bytes: usize, allow_local: bool) -> Result<Vec<u8>> {
+    let refused = |reason: &str| -> anyhow::Error {
+        AppError::invalid_params(format!("Refusing to fetch file part {}: {}", uri, reason)).into()
+    };
+
+    let url = Url::parse(uri).map_err(|e| refused(&e.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(refused("only http and https are allowed"));
+    }
+    let host = url.host_str().ok_or_else(|| refused("no host"))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let ip_host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .ok();
+    let addrs: Vec<SocketAddr> = match ip_host {
+        Some(ip) => vec![SocketAddr::new(ip, port)],
+        None => tokio::net::lookup_host((host, port))
+            .await
+            .with_context(|| format!("failed to resolve {}", host))?
+            .collect(),
+    };
+    if addrs.is_empty() {
+        return Err(refused("the host has no address"));
+    }
+    if !allow_local && addrs.iter().any(|addr| !is_public(addr.ip())) {
+        return Err(refused("the host is a local or private address"));
+    }
+
+    // Pin the checked addresses so the name can't be rebound between check and connect
+    let mut builder = Client::builder()
+        .timeout(FILE_FETCH_TIMEOUT)
+        .redirect(Policy::none());
+    if ip_host.is_none() {
+        builder = builder.resolve_to_addrs(host, &addrs);
+    }
+    let client = builder.build()?;
+
+    let mut response = client
+        .get(url.clone())
+        .send()
+        .await
+        .with_context(|| format!("failed to download file part from {}", uri))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow::anyhow!(
+            "file part download from {} failed with status {}",
+            uri,
+            status
+        ));
+    }
+
+    let too_large = || refused(&format!("it is larger than {} bytes", max_bytes));
+    if response
+        .content_length()
+        .is_some_and(|length| length > max_bytes as u64)
+    {
+        return Err(too_large());
+    }
+    let mut content = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if content.len() + chunk.len() > max_bytes {
+            return Err(too_large());
+        }
+        content.extend_from_slice(&chunk);
+    }
+
+    Ok(content)
+}
+
+// Addresses on the internet, not the machine itself or a private network
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // Shared address space of carrier-grade NAT, 100.64.0.0/10
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local fc00::/7 and link-local fe80::/10
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Router, routing::get};
+
+    use super::*;
+    use crate::test_support::serve;
+
+    fn update(artifact_id: &str, text: &str, append: bool, last_chunk: bool) -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": {
+                "kind": "artifact-update",
+                "taskId": "task-1",
+                "contextId": "ctx-1",
+                "artifact": {
+                    "artifactId": artifact_id,
+                    "name": "answer",
+                    "parts": [{ "kind": "text", "text": text }]
+                },
+                "append": append,
+                "lastChunk": last_chunk
+            }
+        })
+    }
+
+    fn push(assembler: &mut ArtifactAssembler, event: serde_json::Value) -> Option<A2AArtifact> {
+        assembler.push(artifact_update(&event).expect("an artifact-update event"))
+    }
+
+    #[test]
+    fn appended_chunks_extend_the_artifact() {
+        let mut assembler = ArtifactAssembler::new();
+
+        assert!(push(&mut assembler, update("a", "Hello", false, false)).is_none());
+        assert!(push(&mut assembler, update("a", ", ", true, false)).is_none());
+        let artifact = push(&mut assembler, update("a", "world", true, true)).unwrap();
+
+        assert_eq!(artifact.artifact_id, "a");
+        assert_eq!(artifact.parts.len(), 1);
+        assert_eq!(artifact_text(&artifact).as_deref(), Some("Hello, world"));
+        assert!(assembler.finish().is_empty());
+    }
+
+    #[test]
+    fn chunks_without_append_replace_the_artifact() {
+        let mut assembler = ArtifactAssembler::new();
+
+        push(&mut assembler, update("a", "draft", false, false));
+        push(&mut assembler, update("a", " answer", true, false));
+        let artifact = push(&mut assembler, update("a", "final answer", false, true)).unwrap();
+
+        assert_eq!(artifact_text(&artifact).as_deref(), Some("final answer"));
+    }
+
+    #[test]
+    fn finish_hands_out_artifacts_missing_their_last_chunk() {
+        let mut assembler = ArtifactAssembler::new();
+
+        push(&mut assembler, update("a", "first", false, false));
+        push(&mut assembler, update("b", "second", false, false));
+        push(&mut assembler, update("b", " part", true, false));
+
+        let mut unfinished = assembler.finish();
+        unfinished.sort_by(|(_, x), (_, y)| x.artifact_id.cmp(&y.artifact_id));
+        let texts: Vec<_> = unfinished
+            .iter()
+            .map(|(task_id, artifact)| (task_id.as_str(), artifact_text(artifact).unwrap()))
+            .collect();
+        assert_eq!(
+            texts,
+            [
+                ("task-1", "first".to_string()),
+                ("task-1", "second part".to_string())
+            ]
+        );
+        assert!(assembler.finish().is_empty());
+    }
+
+    #[test]
+    fn only_artifact_update_events_are_artifact_updates() {
+        let status = serde_json::json!({
+            "result": { "kind": "status-update", "taskId": "task-1", "status": { "state": "working" } }
+        });
+        assert!(artifact_update(&status).is_none());
+        assert!(artifact_update(&serde_json::json!({ "error": { "code": -32603 } })).is_none());
+        assert!(artifact_update(&update("a", "text", false, true)).is_some());
+    }
+
+    fn stored(file_name: Option<&str>, name: Option<&str>, mime_type: &str) -> StoredArtifact {
+        StoredArtifact {
+            id: Some(1),
+            artifact_id: "artifact-1".to_string(),
+            task_id: "task-1".to_string(),
+            context_id: None,
+            conversation_id: None,
+            a2a_server_id: None,
+            name: name.map(str::to_string),
+            description: None,
+            part_index: 0,
+            part_kind: "file".to_string(),
+            mime_type: Some(mime_type.to_string()),
+            file_name: file_name.map(str::to_string),
+            content_hash: String::new(),
+            size_bytes: 0,
+            uri: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn file_names_keep_only_safe_characters() {
+        let name = |file_name, name, mime_type| safe_file_name(&stored(file_name, name, mime_type));
+
+        assert_eq!(
+            name(Some("report 2024.pdf"), None, "application/pdf").unwrap(),
+            "report 2024.pdf"
+        );
+        assert_eq!(
+            name(Some("a\"&calc&\".txt"), None, "text/plain").unwrap(),
+            "a__calc__.txt"
+        );
+        assert_eq!(
+            name(Some("../../.bashrc"), None, "text/plain").unwrap(),
+            "_.._.bashrc"
+        );
+        assert_eq!(
+            name(Some("notes.txt:stream"), None, "text/plain").unwrap(),
+            "notes.txt_stream"
+        );
+        assert_eq!(
+            name(None, Some("Summary"), "text/markdown").unwrap(),
+            "Summary.md"
+        );
+        assert_eq!(name(None, None, "image/png").unwrap(), "artifact-1.png");
+        assert_eq!(name(Some("..."), None, "text/plain").unwrap(), "artifact");
+        assert_eq!(
+            name(Some(&"x".repeat(200)), None, "text/plain")
+                .unwrap()
+                .len(),
+            MAX_FILE_NAME_CHARS
+        );
+    }
+
+    #[test]
+    fn executable_file_names_are_refused() {
+        for file_name in [
+            "setup.exe",
+            "run.BAT",
+            "shortcut.lnk",
+            "script.ps1",
+            "invoice.pdf.exe",
+            "trailing.exe. ",
+            "page.hta",
+        ] {
+            let error = safe_file_name(&stored(Some(file_name), None, "text/plain")).unwrap_err();
+            assert!(
+                error.to_string().contains("executable"),
+                "{file_name}: {error}"
+            );
+        }
+        assert!(safe_file_name(&stored(None, Some("tool"), "application/x-msdownload")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn file_uris_must_be_http_urls_of_public_addresses() {
+        let refused = [
+            "file:///etc/passwd",
+            "ftp://example.com/report.pdf",
+            "http://127.0.0.1:8080/report.pdf",
+            "http://localhost/report.pdf",
+            "http://10.0.0.8/report.pdf",
+            "http://192.168.1.1/report.pdf",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.64.0.1/report.pdf",
+            "http://[::1]/report.pdf",
+            "http://[fd00::1]/report.pdf",
+            "http://[::ffff:172.16.0.1]/report.pdf",
+        ];
+        for uri in refused {
+            let error = fetch_file_uri(uri).await.unwrap_err();
+            assert!(
+                error.to_string().starts_with("Refusing to fetch"),
+                "{uri}: {error}"
+            );
+        }
+
+        assert!(is_public("93.184.215.14".parse().unwrap()));
+        assert!(is_public("2606:4700::1111".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn file_downloads_stop_at_the_byte_cap() {
+        let app = Router::new().route("/report.bin", get(|| async { vec![7u8; 64] }));
+        let uri = format!("{}/report.bin", serve(app).await);
+
+        assert_eq!(fetch(&uri, 64, true).await.unwrap().len(), 64);
+        let error = fetch(&uri, 16, true).await.unwrap_err();
+        assert!(
+            error.to_string().contains("larger than 16 bytes"),
+            "{error}"
+        );
+    }
+}
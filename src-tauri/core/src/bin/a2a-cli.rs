@@ -0,0 +1,503 @@
+//! `a2a-cli`, a command line client over the desktop app's index.db
+
+use std::{collections::HashMap, io::Write, path::PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use a2a_core::{
+    StoreConfig,
+    a2a::{
+        client::{
+            build_a2a_message_parts, cancel_a2a_task, card_service_url, extract_response_text,
+            fetch_agent_card, get_a2a_task, open_a2a_stream, post_a2a_message, stream_event_text,
+        },
+        transport::Transport,
+    },
+    artifact::{ArtifactAssembler, artifact_text, artifact_update},
+    db::{a2a_db::SettingA2AServerDbManager, model_db::SettingModelDbManager},
+    error::AppError,
+    model::{A2AMessage, SettingA2AServer, SettingA2AServerParams},
+};
+
+const USAGE: &str = "\
+Usage: a2a-cli [--db <path>] <command>
+
+Commands:
+  card fetch <url> [--token <token>]
+  send --server <name|id> [--skill <id>] [--url <a2a-url>] [--context <id>] [--json] <text>
+  stream --server <name|id> [--skill <id>] [--url <a2a-url>] [--context <id>] [--json] <text>
+  task get --server <name|id> [--url <a2a-url>] <task-id>
+  task cancel --server <name|id> [--url <a2a-url>] <task-id>
+  servers list [--json]
+  servers add --name <name> --card-url <url> [--transport <JSONRPC|HTTP+JSON|GRPC>]
+              [--header <name=value>]... [--no-fetch]
+  models list [--json]
+
+Servers are the A2A servers configured in the desktop app, given by name or id.
+Their transport comes from the agent card unless one is set with --transport.
+--db defaults to the desktop app's index.db.";
+
+// Options that take a value
+const VALUE_OPTIONS: [&str; 10] = [
+    "db",
+    "token",
+    "server",
+    "skill",
+    "url",
+    "context",
+    "name",
+    "card-url",
+    "transport",
+    "header",
+];
+// Options that stand alone, any other -- word is an error
+const FLAGS: [&str; 3] = ["help", "json", "no-fetch"];
+
+/// Parsed command line: positional words, options with their values and flags
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, Vec<String>>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    fn parse(raw: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = Args {
+            positional: Vec::new(),
+            options: HashMap::new(),
+            flags: Vec::new(),
+        };
+
+        let mut raw = raw.into_iter();
+        while let Some(arg) = raw.next() {
+            if arg == "--" {
+                args.positional.extend(raw.by_ref());
+                break;
+            }
+            let Some(name) = arg.strip_prefix("--") else {
+                if arg == "-h" {
+                    args.flags.push("help".to_string());
+                } else {
+                    args.positional.push(arg);
+                }
+                continue;
+            };
+
+            // Both --name value and --name=value
+            let (name, inline) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (name, None),
+            };
+            if VALUE_OPTIONS.contains(&name) {
+                let value = match inline.or_else(|| raw.next()) {
+                    Some(value) => value,
+                    None => bail!("--{} needs a value", name),
+                };
+                args.options
+                    .entry(name.to_string())
+                    .or_default()
+                    .push(value);
+            } else if FLAGS.contains(&name) {
+                if inline.is_some() {
+                    bail!("--{} takes no value", name);
+                }
+                args.flags.push(name.to_string());
+            } else {
+                bail!("Unknown option --{}\n\n{}", name, USAGE);
+            }
+        }
+
+        Ok(args)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options
+            .get(name)
+            .and_then(|values| values.last())
+            .map(|value| value.as_str())
+    }
+
+    fn required(&self, name: &str) -> Result<&str> {
+        self.option(name)
+            .with_context(|| format!("--{} is required\n\n{}", name, USAGE))
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+
+    fn command(&self) -> Vec<&str> {
+        self.positional.iter().map(|arg| arg.as_str()).collect()
+    }
+}
+
+fn main() {
+    if let Err(e) = run(std::env::args().skip(1)) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Run a command line, without the program name
+fn run(raw: impl IntoIterator<Item = String>) -> Result<()> {
+    let args = Args::parse(raw)?;
+    if args.flag("help") || args.positional.is_empty() {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let mut store = StoreConfig::default_location()?;
+    if let Some(path) = args.option("db") {
+        store.db_path = PathBuf::from(path);
+    }
+    a2a_core::init(&store)?;
+
+    tokio::runtime::Runtime::new()?.block_on(dispatch(&args))
+}
+
+async fn dispatch(args: &Args) -> Result<()> {
+    match args.command().as_slice() {
+        ["card", "fetch", url] => card_fetch(args, url).await,
+        ["send", text @ ..] if !text.is_empty() => send(args, &text.join(" ")).await,
+        ["stream", text @ ..] if !text.is_empty() => stream(args, &text.join(" ")).await,
+        ["task", "get", task_id] => task_get(args, task_id).await,
+        ["task", "cancel", task_id] => task_cancel(args, task_id).await,
+        ["servers", "list"] => servers_list(args),
+        ["servers", "add"] => servers_add(args).await,
+        ["models", "list"] => models_list(args),
+        _ => bail!(
+            "Unknown command: {}\n\n{}",
+            args.positional.join(" "),
+            USAGE
+        ),
+    }
+}
+
+async fn card_fetch(args: &Args, url: &str) -> Result<()> {
+    let card = fetch_agent_card(url, args.option("token")).await?;
+    println!("{}", serde_json::to_string_pretty(&card)?);
+    Ok(())
+}
+
+async fn send(args: &Args, text: &str) -> Result<()> {
+    let target = Target::resolve(args)?;
+    let response_text = post_a2a_message(
+        &target.server,
+        &target.a2a_url,
+        format!("task_id:{}", Uuid::new_v4()),
+        target.message(args, text),
+        target.skill_id.clone(),
+    )
+    .await?;
+
+    if args.flag("json") {
+        println!("{}", pretty(&response_text));
+        return Ok(());
+    }
+    if let Some(err) = AppError::from_json_rpc_body(&response_text) {
+        return Err(err.into());
+    }
+
+    let response = serde_json::from_str::<Value>(&response_text).unwrap_or(Value::Null);
+    if let Some(task_id) = response.pointer("/result/id").and_then(|id| id.as_str()) {
+        let state = response
+            .pointer("/result/status/state")
+            .and_then(|state| state.as_str())
+            .unwrap_or("unknown");
+        eprintln!("task {} ({})", task_id, state);
+    }
+    println!(
+        "{}",
+        extract_response_text(&response).unwrap_or(response_text)
+    );
+    Ok(())
+}
+
+async fn stream(args: &Args, text: &str) -> Result<()> {
+    let target = Target::resolve(args)?;
+    let mut stream = open_a2a_stream(
+        &target.server,
+        &target.a2a_url,
+        format!("task_id:{}", Uuid::new_v4()),
+        target.message(args, text),
+        &target.skill_id,
+    )
+    .await?;
+
+    let json = args.flag("json");
+    let mut stdout = std::io::stdout();
+    // Artifact chunks are printed once the artifact is complete, so a chunk
+    // that replaces earlier ones never shows up next to them
+    let mut assembler = ArtifactAssembler::new();
+    while let Some(event) = stream.next_event().await? {
+        if json {
+            println!("{}", event);
+            continue;
+        }
+        if let Some(error) = event.get("error") {
+            return Err(AppError::from_json_rpc_error(error).into());
+        }
+        if let Some(update) = artifact_update(&event) {
+            if let Some(text) = assembler.push(update).as_ref().and_then(artifact_text) {
+                print!("{}", text);
+                stdout.flush()?;
+            }
+            continue;
+        }
+        if let Some(text) = stream_event_text(&event) {
+            print!("{}", text);
+            stdout.flush()?;
+        }
+    }
+
+    if !json {
+        for (_, artifact) in assembler.finish() {
+            if let Some(text) = artifact_text(&artifact) {
+                print!("{}", text);
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+async fn task_get(args: &Args, task_id: &str) -> Result<()> {
+    let target = Target::resolve(args)?;
+    let response_text =
+        get_a2a_task(&target.server, &target.a2a_url, &target.skill_id, task_id).await?;
+    print_task_response(&response_text)
+}
+
+async fn task_cancel(args: &Args, task_id: &str) -> Result<()> {
+    let target = Target::resolve(args)?;
+    let response_text =
+        cancel_a2a_task(&target.server, &target.a2a_url, &target.skill_id, task_id).await?;
+    print_task_response(&response_text)
+}
+
+fn print_task_response(response_text: &str) -> Result<()> {
+    if let Some(err) = AppError::from_json_rpc_body(response_text) {
+        return Err(err.into());
+    }
+    println!("{}", pretty(response_text));
+    Ok(())
+}
+
+fn servers_list(args: &Args) -> Result<()> {
+    let servers = SettingA2AServerDbManager::new().get_all()?;
+
+    if args.flag("json") {
+        // Custom headers usually carry credentials, they stay out of the output
+        let servers: Vec<Value> = servers
+            .iter()
+            .map(|server| {
+                json!({
+                    "id": server.id,
+                    "name": server.name,
+                    "agentCardUrl": server.agent_card_url,
+                    "url": card_service_url(server),
+                    "transport": server.transport,
+                    "enabled": server.enabled,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&servers)?);
+        return Ok(());
+    }
+
+    println!("ID\tENABLED\tNAME\tAGENT CARD URL");
+    for server in servers {
+        println!(
+            "{}\t{}\t{}\t{}",
+            server.id.unwrap_or_default(),
+            server.enabled,
+            server.name,
+            server.agent_card_url
+        );
+    }
+    Ok(())
+}
+
+async fn servers_add(args: &Args) -> Result<()> {
+    let name = args.required("name")?;
+    let agent_card_url = args.required("card-url")?;
+    let transport = match args.option("transport") {
+        Some(transport) => match Transport::from_name(transport) {
+            Some(transport) => Some(transport.name().to_string()),
+            None => bail!("Unknown transport: {}", transport),
+        },
+        None => None,
+    };
+
+    let mut headers = serde_json::Map::new();
+    for header in args.options.get("header").into_iter().flatten() {
+        let Some((header_name, value)) = header.split_once('=') else {
+            bail!("--header expects name=value, got: {}", header);
+        };
+        headers.insert(header_name.trim().to_string(), json!(value.trim()));
+    }
+
+    // The desktop app stores the fetched card with the server, so does the CLI
+    let agent_card_json = if args.flag("no-fetch") {
+        None
+    } else {
+        let token = headers
+            .get("Authorization")
+            .and_then(|token| token.as_str());
+        let card = fetch_agent_card(agent_card_url, token)
+            .await
+            .context("Failed to fetch the agent card, pass --no-fetch to add it anyway")?;
+        Some(serde_json::to_string(&card)?)
+    };
+
+    let id = SettingA2AServerDbManager::new().insert(&SettingA2AServerParams {
+        name: name.to_string(),
+        agent_card_url: agent_card_url.to_string(),
+        agent_card_json,
+        custom_header_json: (!headers.is_empty()).then(|| Value::Object(headers).to_string()),
+        protocol_data_object_settings: None,
+        transport,
+        enabled: true,
+    })?;
+    println!("Added A2A server {} with id {}", name, id);
+    Ok(())
+}
+
+fn models_list(args: &Args) -> Result<()> {
+    let models = SettingModelDbManager::new().get_all()?;
+
+    if args.flag("json") {
+        // API keys stay out of the output
+        let models: Vec<Value> = models
+            .iter()
+            .map(|model| {
+                json!({
+                    "id": model.id,
+                    "modelKey": model.model_key,
+                    "apiUrl": model.api_url,
+                    "enabled": model.enabled,
+                    "contextWindow": model.context_window,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&models)?);
+        return Ok(());
+    }
+
+    println!("ID\tENABLED\tMODEL\tAPI URL");
+    for model in models {
+        println!(
+            "{}\t{}\t{}\t{}",
+            model.id.unwrap_or_default(),
+            model.enabled,
+            model.model_key,
+            model.api_url
+        );
+    }
+    Ok(())
+}
+
+/// The configured server a message or task call goes to
+struct Target {
+    server: SettingA2AServer,
+    a2a_url: String,
+    skill_id: String,
+}
+
+impl Target {
+    fn resolve(args: &Args) -> Result<Self> {
+        let server_arg = args.required("server")?;
+        let db_manager = SettingA2AServerDbManager::new();
+        let server = match server_arg.parse::<i32>() {
+            Ok(id) => db_manager.get_by_id(id)?,
+            Err(_) => db_manager.get_by_name(server_arg)?,
+        }
+        .ok_or_else(|| AppError::not_found(format!("A2A server '{}' not found", server_arg)))?;
+
+        let a2a_url = args
+            .option("url")
+            .map(|url| url.to_string())
+            .or_else(|| card_service_url(&server))
+            .context("The server's agent card has no url, pass --url")?;
+
+        // The first skill of the card unless one is given
+        let skill_id = args
+            .option("skill")
+            .map(|skill| skill.to_string())
+            .or_else(|| {
+                server
+                    .agent_card_json
+                    .as_deref()
+                    .and_then(|json| serde_json::from_str::<Value>(json).ok())
+                    .and_then(|card| {
+                        card.pointer("/skills/0/id")
+                            .and_then(|id| id.as_str())
+                            .map(|id| id.to_string())
+                    })
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            server,
+            a2a_url,
+            skill_id,
+        })
+    }
+
+    fn message(&self, args: &Args, text: &str) -> A2AMessage {
+        A2AMessage {
+            message_id: format!("msg_id:{}", Uuid::new_v4()),
+            kind: "message".to_string(),
+            role: "user".to_string(),
+            parts: build_a2a_message_parts(&self.server, text.to_string()),
+            context_id: args.option("context").map(|id| id.to_string()),
+            task_id: None,
+        }
+    }
+}
+
+// Pretty-print a JSON body, anything else as it came
+fn pretty(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| body.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &[&str]) -> Result<Args> {
+        Args::parse(raw.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn options_flags_and_positional_words_are_parsed() {
+        let args = parse(&[
+            "send",
+            "--server=echo",
+            "--json",
+            "hello",
+            "--skill",
+            "s1",
+            "--",
+            "--world",
+        ])
+        .unwrap();
+        assert_eq!(args.command(), ["send", "hello", "--world"]);
+        assert_eq!(args.option("server"), Some("echo"));
+        assert_eq!(args.option("skill"), Some("s1"));
+        assert!(args.flag("json"));
+        assert!(!args.flag("no-fetch"));
+    }
+
+    #[test]
+    fn unknown_or_malformed_options_are_rejected() {
+        let error = parse(&["send", "--jsn", "hello"]).err().unwrap();
+        assert!(error.to_string().starts_with("Unknown option --jsn"));
+        assert!(parse(&["send", "--json=yes"]).is_err());
+        assert!(parse(&["send", "--server"]).is_err());
+    }
+}
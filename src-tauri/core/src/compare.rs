@@ -0,0 +1,514 @@
+//! Side-by-side comparison: one prompt sent to several agents and models at
+//! once, each answer timed and stored as a result of the comparison
+
+use std::time::Instant;
+
+use ai::chat_completions::{
+    ChatCompletionMessage, ChatCompletionRequestBuilder, ChatCompletionRequestStreamOptions,
+};
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{
+        build_a2a_message_parts, card_service_url, extract_response_text, open_a2a_stream,
+        post_a2a_message, stream_event_text,
+    },
+    artifact::{ArtifactAssembler, artifact_text, artifact_update},
+    db::{
+        a2a_db::SettingA2AServerDbManager, compare_db::ComparisonDbManager,
+        model_db::SettingModelDbManager,
+    },
+    error::{AppError, ErrorKind},
+    llm::{chat_model_name, client::ChatClient, preset::preset_options},
+    model::{
+        A2AMessage, CompareParams, CompareTarget, ComparisonResult, SettingA2AServer, SettingModel,
+    },
+};
+
+pub const MAX_TARGETS: usize = 16;
+
+/// A target resolved against the settings tables before anything is sent
+pub enum ResolvedTarget {
+    Agent {
+        server: SettingA2AServer,
+        a2a_url: String,
+        skill_id: String,
+    },
+    Model(SettingModel),
+}
+
+/// Check the prompt and resolve every target, nothing is sent when one fails
+pub fn resolve_targets(params: &CompareParams) -> Result<Vec<ResolvedTarget>, AppError> {
+    if params.prompt.trim().is_empty() {
+        return Err(AppError::invalid_params("Prompt cannot be empty"));
+    }
+
+    if params.targets.len() < 2 || params.targets.len() > MAX_TARGETS {
+        return Err(AppError::invalid_params(format!(
+            "A comparison needs between 2 and {} targets",
+            MAX_TARGETS
+        )));
+    }
+
+    params.targets.iter().map(resolve_target).collect()
+}
+
+/// Send the prompt to one target and store its result. Answer text is handed
+/// to `emit` as it arrives
+pub async fn run_target(
+    comparison_id: i64,
+    index: usize,
+    target: CompareTarget,
+    resolved: ResolvedTarget,
+    prompt: &str,
+    system_prompt: Option<&str>,
+    emit: impl Fn(&str),
+) -> ComparisonResult {
+    let label = match &resolved {
+        ResolvedTarget::Agent { server, .. } => server.name.clone(),
+        ResolvedTarget::Model(model) => model.model_key.clone(),
+    };
+
+    let mut result = ComparisonResult {
+        id: None,
+        comparison_id,
+        target_index: index as i64,
+        target_kind: target.kind,
+        a2a_server_id: target.a2a_server_id,
+        skill_id: target.skill_id,
+        model_id: target.model_id,
+        label,
+        status: "error".to_string(),
+        answer: None,
+        error: None,
+        latency_ms: 0,
+        time_to_first_token_ms: None,
+        prompt_tokens: None,
+        completion_tokens: None,
+        total_tokens: None,
+    };
+
+    let started = Instant::now();
+    let outcome = match resolved {
+        ResolvedTarget::Agent {
+            server,
+            a2a_url,
+            skill_id,
+        } => {
+            run_agent(
+                &server,
+                &a2a_url,
+                &skill_id,
+                prompt,
+                started,
+                &mut result,
+                &emit,
+            )
+            .await
+        }
+        ResolvedTarget::Model(model) => {
+            run_model(&model, system_prompt, prompt, started, &mut result, &emit).await
+        }
+    };
+    result.latency_ms = started.elapsed().as_millis() as i64;
+
+    match outcome {
+        Ok(answer) => {
+            result.status = "ok".to_string();
+            result.answer = Some(answer);
+        }
+        Err(e) => {
+            log::warn!(
+                "Comparison {} target {} ({}) failed: {}",
+                comparison_id,
+                index,
+                result.label,
+                e
+            );
+            result.error = Some(e.to_string());
+        }
+    }
+
+    match ComparisonDbManager::new().insert_result(&result) {
+        Ok(id) => result.id = Some(id),
+        Err(e) => log::warn!("Failed to store comparison result: {}", e),
+    }
+
+    result
+}
+
+fn resolve_target(target: &CompareTarget) -> Result<ResolvedTarget, AppError> {
+    match target.kind.as_str() {
+        "a2a" => {
+            let server_id = target
+                .a2a_server_id
+                .ok_or_else(|| AppError::invalid_params("A2A target needs a2aServerId"))?;
+            let skill_id = target
+                .skill_id
+                .clone()
+                .ok_or_else(|| AppError::invalid_params("A2A target needs skillId"))?;
+            let server = SettingA2AServerDbManager::new()
+                .get_by_id(server_id)
+                .map_err(|e| AppError::from(e.context("Failed to get A2A server")))?
+                .ok_or_else(|| {
+                    AppError::not_found(format!("A2A server {} not found", server_id))
+                })?;
+            let a2a_url = card_service_url(&server).ok_or_else(|| {
+                AppError::invalid_params(format!(
+                    "A2A server {} has no agent card url",
+                    server.name
+                ))
+            })?;
+            Ok(ResolvedTarget::Agent {
+                server,
+                a2a_url,
+                skill_id,
+            })
+        }
+        "model" => {
+            let model_id = target
+                .model_id
+                .ok_or_else(|| AppError::invalid_params("Model target needs modelId"))?;
+            let model = SettingModelDbManager::new()
+                .get_by_id(model_id)
+                .map_err(|e| AppError::from(e.context("Failed to get model")))?
+                .ok_or_else(|| AppError::not_found(format!("Model {} not found", model_id)))?;
+            if model.api_key.trim().is_empty() {
+                return Err(AppError::new(
+                    ErrorKind::Unauthorized,
+                    format!("Model {} has no API key", model.model_key),
+                ));
+            }
+            Ok(ResolvedTarget::Model(model))
+        }
+        other => Err(AppError::invalid_params(format!(
+            "Unknown comparison target kind: {}",
+            other
+        ))),
+    }
+}
+
+// Streams over message/stream when the agent card advertises it, otherwise
+// falls back to a single message/send
+async fn run_agent(
+    server: &SettingA2AServer,
+    a2a_url: &str,
+    skill_id: &str,
+    prompt: &str,
+    started: Instant,
+    result: &mut ComparisonResult,
+    emit: &impl Fn(&str),
+) -> anyhow::Result<String> {
+    let message = A2AMessage {
+        message_id: format!("msg_id:{}", Uuid::new_v4()),
+        kind: "message".to_string(),
+        role: "user".to_string(),
+        parts: build_a2a_message_parts(server, prompt.to_string()),
+        context_id: None,
+        task_id: None,
+    };
+    let request_id = format!("task_id:{}", Uuid::new_v4());
+
+    if !supports_streaming(server) {
+        let response_text =
+            post_a2a_message(server, a2a_url, request_id, message, skill_id.to_string()).await?;
+        result.time_to_first_token_ms = Some(started.elapsed().as_millis() as i64);
+
+        if let Some(error) = AppError::from_json_rpc_body(&response_text) {
+            return Err(error.into());
+        }
+        let response = serde_json::from_str::<serde_json::Value>(&response_text)
+            .unwrap_or(serde_json::Value::Null);
+        let answer = extract_response_text(&response).unwrap_or(response_text);
+        emit(&answer);
+        return Ok(answer);
+    }
+
+    let mut stream = open_a2a_stream(server, a2a_url, request_id, message, skill_id).await?;
+    // The answer is the text of the reassembled artifacts, so a chunk sent
+    // without append replaces its artifact and status updates stay out of it
+    let mut assembler = ArtifactAssembler::new();
+    let mut artifacts = Vec::new();
+    // Agents that reply without artifacts answer with a message or their final status
+    let mut reply = None;
+
+    while let Some(event) = stream.next_event().await? {
+        if let Some(error) = event.get("error") {
+            return Err(AppError::from_json_rpc_error(error).into());
+        }
+        let kind = event.pointer("/result/kind").and_then(|k| k.as_str());
+
+        if let Some(update) = artifact_update(&event) {
+            if result.time_to_first_token_ms.is_none() {
+                result.time_to_first_token_ms = Some(started.elapsed().as_millis() as i64);
+            }
+            if let Some(text) = assembler.push(update).as_ref().and_then(artifact_text) {
+                emit(&text);
+                artifacts.push(text);
+            }
+        } else if kind == Some("message") || is_final_status(&event) {
+            if let Some(text) = stream_event_text(&event) {
+                if result.time_to_first_token_ms.is_none() {
+                    result.time_to_first_token_ms = Some(started.elapsed().as_millis() as i64);
+                }
+                reply = Some(text);
+            }
+        }
+    }
+    for (_, artifact) in assembler.finish() {
+        if let Some(text) = artifact_text(&artifact) {
+            emit(&text);
+            artifacts.push(text);
+        }
+    }
+
+    if !artifacts.is_empty() {
+        return Ok(artifacts.concat());
+    }
+    let answer = reply.unwrap_or_default();
+    emit(&answer);
+    Ok(answer)
+}
+
+async fn run_model(
+    model: &SettingModel,
+    system_prompt: Option<&str>,
+    prompt: &str,
+    started: Instant,
+    result: &mut ComparisonResult,
+    emit: &impl Fn(&str),
+) -> anyhow::Result<String> {
+    let client = ChatClient::for_model(model);
+
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = system_prompt.filter(|p| !p.trim().is_empty()) {
+        messages.push(ChatCompletionMessage::System(
+            system_prompt.to_string().into(),
+        ));
+    }
+    messages.push(ChatCompletionMessage::User(prompt.to_string().into()));
+
+    let mut builder = ChatCompletionRequestBuilder::default();
+    builder
+        .model(chat_model_name(model)?)
+        .messages(messages)
+        .stream(true)
+        // Usage only comes in a final chunk when it is asked for
+        .stream_options(ChatCompletionRequestStreamOptions {
+            include_usage: Some(true),
+        });
+    let request = builder
+        .build()
+        .map_err(|e| AppError::internal(format!("Failed to build request: {}", e)))?;
+
+    let mut stream = client
+        .stream_chat_completions(&request, &preset_options(model.id, None)?)
+        .await?;
+
+    let mut answer = String::new();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+
+        if let Some(content) = chunk.choices.first().and_then(|c| c.delta.content.as_ref()) {
+            if result.time_to_first_token_ms.is_none() {
+                result.time_to_first_token_ms = Some(started.elapsed().as_millis() as i64);
+            }
+            emit(content);
+            answer.push_str(content);
+        }
+
+        if let Some(usage) = &chunk.usage {
+            result.prompt_tokens = Some(usage.prompt_tokens as i64);
+            result.completion_tokens = Some(usage.completion_tokens as i64);
+            result.total_tokens = Some(usage.total_tokens as i64);
+        }
+    }
+
+    Ok(answer)
+}
+
+fn is_final_status(event: &serde_json::Value) -> bool {
+    event.pointer("/result/kind").and_then(|k| k.as_str()) == Some("status-update")
+        && event
+            .pointer("/result/final")
+            .and_then(|f| f.as_bool())
+            .unwrap_or(false)
+}
+
+fn supports_streaming(server: &SettingA2AServer) -> bool {
+    server
+        .agent_card_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+        .and_then(|card| {
+            card.pointer("/capabilities/streaming")
+                .and_then(|s| s.as_bool())
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Json, Router, extract::State, http::header, response::IntoResponse, routing::post};
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::test_support::{a2a_server, serve};
+
+    fn empty_result() -> ComparisonResult {
+        ComparisonResult {
+            id: None,
+            comparison_id: 1,
+            target_index: 0,
+            target_kind: "a2a".to_string(),
+            a2a_server_id: None,
+            skill_id: None,
+            model_id: None,
+            label: "stub".to_string(),
+            status: "error".to_string(),
+            answer: None,
+            error: None,
+            latency_ms: 0,
+            time_to_first_token_ms: None,
+            prompt_tokens: None,
+            completion_tokens: None,
+            total_tokens: None,
+        }
+    }
+
+    // Answers message/stream with the given results as server-sent events
+    async fn stub_stream_agent(results: Vec<Value>) -> String {
+        let events: String = results
+            .into_iter()
+            .map(|result| {
+                let event = json!({ "jsonrpc": "2.0", "id": "1", "result": result });
+                format!("data: {}\n\n", event)
+            })
+            .collect();
+        let app = Router::new().route(
+            "/",
+            post(move || async move { ([(header::CONTENT_TYPE, "text/event-stream")], events) }),
+        );
+        serve(app).await
+    }
+
+    fn chunk(artifact_id: &str, text: &str, append: bool, last_chunk: bool) -> Value {
+        json!({
+            "kind": "artifact-update", "taskId": "t1", "contextId": "c1",
+            "append": append, "lastChunk": last_chunk,
+            "artifact": { "artifactId": artifact_id, "parts": [{ "kind": "text", "text": text }] }
+        })
+    }
+
+    fn status(text: &str, last: bool) -> Value {
+        json!({
+            "kind": "status-update", "taskId": "t1", "contextId": "c1", "final": last,
+            "status": {
+                "state": if last { "completed" } else { "working" },
+                "message": {
+                    "kind": "message", "messageId": "m1", "role": "agent",
+                    "parts": [{ "kind": "text", "text": text }]
+                }
+            }
+        })
+    }
+
+    async fn run_streaming_agent(results: Vec<Value>) -> (String, Vec<String>) {
+        let url = stub_stream_agent(results).await;
+        let server = a2a_server(Some(r#"{"capabilities":{"streaming":true}}"#));
+        let emitted = Mutex::new(Vec::new());
+        let answer = run_agent(
+            &server,
+            &url,
+            "echo",
+            "Hi",
+            Instant::now(),
+            &mut empty_result(),
+            &|text: &str| emitted.lock().unwrap().push(text.to_string()),
+        )
+        .await
+        .unwrap();
+        (answer, emitted.into_inner().unwrap())
+    }
+
+    #[tokio::test]
+    async fn agent_answers_are_the_reassembled_artifacts() {
+        let (answer, emitted) = run_streaming_agent(vec![
+            status("Thinking", false),
+            chunk("a1", "Draft", false, false),
+            // Without append the chunk replaces the draft
+            chunk("a1", "Hel", false, false),
+            chunk("a1", "lo", true, true),
+            status("Done", true),
+        ])
+        .await;
+
+        assert_eq!(answer, "Hello");
+        assert_eq!(emitted, vec!["Hello"]);
+    }
+
+    #[tokio::test]
+    async fn agents_without_artifacts_answer_with_their_final_status() {
+        let (answer, _) =
+            run_streaming_agent(vec![status("Thinking", false), status("Hello", true)]).await;
+
+        assert_eq!(answer, "Hello");
+    }
+
+    #[tokio::test]
+    async fn model_streams_ask_for_usage() {
+        type Bodies = Arc<Mutex<Vec<Value>>>;
+
+        async fn completions(
+            State(bodies): State<Bodies>,
+            Json(body): Json<Value>,
+        ) -> axum::response::Response {
+            bodies.lock().unwrap().push(body);
+            let content = json!({
+                "id": "1", "object": "chat.completion.chunk", "created": 0, "model": "stub",
+                "choices": [{ "index": 0, "delta": { "content": "Hello" } }]
+            });
+            let usage = json!({
+                "id": "1", "object": "chat.completion.chunk", "created": 0, "model": "stub",
+                "choices": [],
+                "usage": { "prompt_tokens": 3, "completion_tokens": 1, "total_tokens": 4 }
+            });
+            let events = format!("data: {}\n\ndata: {}\n\ndata: [DONE]\n\n", content, usage);
+            ([(header::CONTENT_TYPE, "text/event-stream")], events).into_response()
+        }
+
+        let bodies = Bodies::default();
+        let app = Router::new()
+            .route("/chat/completions", post(completions))
+            .with_state(bodies.clone());
+        let model = SettingModel {
+            id: None,
+            model_key: "stub".to_string(),
+            model_name: None,
+            enabled: true,
+            api_url: serve(app).await,
+            api_key: "key".to_string(),
+            context_window: None,
+            context_policy: None,
+        };
+
+        let mut result = empty_result();
+        let answer = run_model(
+            &model,
+            None,
+            "Hi",
+            Instant::now(),
+            &mut result,
+            &|_: &str| {},
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(answer, "Hello");
+        assert_eq!(result.total_tokens, Some(4));
+        let body = bodies.lock().unwrap().pop().unwrap();
+        assert_eq!(body["stream_options"], json!({ "include_usage": true }));
+    }
+}
@@ -1,10 +1,10 @@
 use anyhow::{Context, Result};
 use log::{debug, error, info, warn};
 use rusqlite::Row;
-use tauri::AppHandle;
 
 use crate::model::{SettingA2AServer, SettingA2AServerParams, UpdateSettingA2AServerParams};
 
+#[derive(Default)]
 pub struct SettingA2AServerDbManager;
 
 impl SettingA2AServerDbManager {
@@ -13,7 +13,7 @@ impl SettingA2AServerDbManager {
     }
 
     /// Initialize the A2A server table
-    pub fn init(&self, _handler: &AppHandle) -> Result<()> {
+    pub fn init(&self) -> Result<()> {
         let db = crate::db::rusqlite::DB
             .lock()
             .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
@@ -27,6 +27,7 @@ impl SettingA2AServerDbManager {
                 agent_card_json TEXT,
                 custom_header_json TEXT,
                 protocol_data_object_settings TEXT,
+                transport TEXT,
                 enabled INTEGER NOT NULL DEFAULT 0,
                 created_at TEXT DEFAULT (datetime('now')),
                 updated_at TEXT DEFAULT (datetime('now'))
@@ -103,6 +104,33 @@ impl SettingA2AServerDbManager {
             info!("Table migration not needed: protocol_data_object_settings column already exists");
         }
 
+        // Check if transport column exists
+        let transport_column_exists = connection
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('tb_setting_a2a_server') WHERE name = 'transport'",
+                [],
+                |row| row.get::<_, i32>(0)
+            )
+            .unwrap_or(0);
+
+        if transport_column_exists == 0 {
+            info!("Migrating table: adding transport column");
+            match connection.execute(
+                "ALTER TABLE tb_setting_a2a_server ADD COLUMN transport TEXT",
+                [],
+            ) {
+                Ok(_) => info!("Successfully added transport column"),
+                Err(e) => {
+                    warn!(
+                        "Failed to add transport column: {}. This might be expected for new tables.",
+                        e
+                    );
+                }
+            }
+        } else {
+            info!("Table migration not needed: transport column already exists");
+        }
+
         Ok(())
     }
 
@@ -114,7 +142,7 @@ impl SettingA2AServerDbManager {
         exclude_id: Option<i32>,
     ) -> Result<bool> {
         let sql = match exclude_id {
-            Some(id) => {
+            Some(_) => {
                 "SELECT COUNT(*) FROM tb_setting_a2a_server WHERE agent_card_url = ? AND id != ?"
             }
             None => "SELECT COUNT(*) FROM tb_setting_a2a_server WHERE agent_card_url = ?",
@@ -154,13 +182,14 @@ impl SettingA2AServerDbManager {
         }
 
         let result = db.connection.execute(
-            "INSERT INTO tb_setting_a2a_server (name, agent_card_url, agent_card_json, custom_header_json, protocol_data_object_settings, enabled) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO tb_setting_a2a_server (name, agent_card_url, agent_card_json, custom_header_json, protocol_data_object_settings, transport, enabled) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             (
                 &params.name,
                 &params.agent_card_url,
                 &params.agent_card_json,
                 &params.custom_header_json,
                 &params.protocol_data_object_settings,
+                params.transport.as_deref().filter(|t| !t.is_empty()),
                 params.enabled as i32,
             ),
         );
@@ -230,6 +259,12 @@ impl SettingA2AServerDbManager {
             values.push(Box::new(protocol_data_object_settings.clone()));
         }
 
+        // An empty transport clears the override
+        if let Some(transport) = &params.transport {
+            update_fields.push("transport = ?");
+            values.push(Box::new(Some(transport.clone()).filter(|t| !t.is_empty())));
+        }
+
         if let Some(enabled) = params.enabled {
             update_fields.push("enabled = ?");
             values.push(Box::new(enabled as i32));
@@ -499,6 +534,7 @@ impl SettingA2AServerDbManager {
         let agent_card_json: Option<String> = row.get("agent_card_json").ok();
         let custom_header_json: Option<String> = row.get("custom_header_json").ok();
         let protocol_data_object_settings: Option<String> = row.get("protocol_data_object_settings").ok();
+        let transport: Option<String> = row.get("transport").ok();
         let enabled: bool = row.get::<_, i32>("enabled").unwrap_or(0) != 0;
         let created_at: Option<String> = row.get("created_at").ok();
         let updated_at: Option<String> = row.get("updated_at").ok();
@@ -510,6 +546,7 @@ impl SettingA2AServerDbManager {
             agent_card_json,
             custom_header_json,
             protocol_data_object_settings,
+            transport,
             enabled,
             created_at,
             updated_at,
@@ -0,0 +1,295 @@
+use anyhow::{Context, Result};
+use log::{error, info, warn};
+use rusqlite::Row;
+
+use crate::model::{ArtifactQueryParams, StoredArtifact};
+
+#[derive(Default)]
+pub struct ArtifactDbManager;
+
+impl ArtifactDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the artifact index table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_artifact (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                artifact_id TEXT NOT NULL,
+                task_id TEXT NOT NULL,
+                context_id TEXT,
+                conversation_id TEXT,
+                a2a_server_id INTEGER,
+                name TEXT,
+                description TEXT,
+                part_index INTEGER NOT NULL,
+                part_kind TEXT NOT NULL,
+                mime_type TEXT,
+                file_name TEXT,
+                content_hash TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT DEFAULT (datetime('now')),
+                UNIQUE (task_id, artifact_id, part_index)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_artifact_conversation ON tb_artifact (conversation_id);
+            CREATE INDEX IF NOT EXISTS idx_artifact_hash ON tb_artifact (content_hash);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create artifact table")?;
+
+        self.migrate_table_if_needed(&db.connection)?;
+
+        info!("Artifact table initialized successfully");
+        Ok(())
+    }
+
+    /// Migrate existing table to add the uri column if needed
+    fn migrate_table_if_needed(&self, connection: &rusqlite::Connection) -> Result<()> {
+        let column_exists = connection
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('tb_artifact') WHERE name = 'uri'",
+                [],
+                |row| row.get::<_, i32>(0),
+            )
+            .unwrap_or(0);
+
+        if column_exists == 0 {
+            info!("Migrating table: adding uri column");
+            if let Err(e) = connection.execute("ALTER TABLE tb_artifact ADD COLUMN uri TEXT", []) {
+                warn!("Failed to add uri column: {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index a stored artifact part, replacing an earlier version of the same part.
+    /// Returns the content hash the part previously pointed to, if it changed.
+    pub fn upsert(&self, artifact: &StoredArtifact) -> Result<Option<String>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let previous_hash: Option<String> = db
+            .connection
+            .query_row(
+                "SELECT content_hash FROM tb_artifact WHERE task_id = ? AND artifact_id = ? AND part_index = ?",
+                (&artifact.task_id, &artifact.artifact_id, artifact.part_index),
+                |row| row.get(0),
+            )
+            .ok();
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_artifact (artifact_id, task_id, context_id, conversation_id, a2a_server_id, name, description, part_index, part_kind, mime_type, file_name, content_hash, size_bytes, uri)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT (task_id, artifact_id, part_index) DO UPDATE SET
+                context_id = excluded.context_id,
+                conversation_id = COALESCE(excluded.conversation_id, conversation_id),
+                a2a_server_id = COALESCE(excluded.a2a_server_id, a2a_server_id),
+                name = excluded.name,
+                description = excluded.description,
+                part_kind = excluded.part_kind,
+                mime_type = excluded.mime_type,
+                file_name = excluded.file_name,
+                content_hash = excluded.content_hash,
+                size_bytes = excluded.size_bytes,
+                uri = excluded.uri",
+            rusqlite::params![
+                &artifact.artifact_id,
+                &artifact.task_id,
+                &artifact.context_id,
+                &artifact.conversation_id,
+                &artifact.a2a_server_id,
+                &artifact.name,
+                &artifact.description,
+                artifact.part_index,
+                &artifact.part_kind,
+                &artifact.mime_type,
+                &artifact.file_name,
+                &artifact.content_hash,
+                artifact.size_bytes,
+                &artifact.uri,
+            ],
+        );
+
+        match result {
+            Ok(_) => {
+                info!(
+                    "Indexed artifact {} part {} of task {} ({})",
+                    artifact.artifact_id, artifact.part_index, artifact.task_id, artifact.content_hash
+                );
+                // Parts not downloaded yet have no blob to release
+                Ok(previous_hash
+                    .filter(|hash| !hash.is_empty() && hash != &artifact.content_hash))
+            }
+            Err(e) => {
+                error!("Failed to index artifact: {}", e);
+                Err(e).context("failed to index artifact")
+            }
+        }
+    }
+
+    /// List artifacts, optionally filtered by conversation, task or server
+    pub fn list(&self, params: &ArtifactQueryParams) -> Result<Vec<StoredArtifact>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut conditions = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if let Some(conversation_id) = &params.conversation_id {
+            conditions.push("conversation_id = ?");
+            values.push(Box::new(conversation_id.clone()));
+        }
+
+        if let Some(task_id) = &params.task_id {
+            conditions.push("task_id = ?");
+            values.push(Box::new(task_id.clone()));
+        }
+
+        if let Some(a2a_server_id) = params.a2a_server_id {
+            conditions.push("a2a_server_id = ?");
+            values.push(Box::new(a2a_server_id));
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let sql = format!(
+            "SELECT * FROM tb_artifact {} ORDER BY created_at DESC, id DESC",
+            where_clause
+        );
+
+        let mut stmt = db
+            .connection
+            .prepare(&sql)
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params_from_iter(values.iter()),
+                Self::extract_artifact_row,
+            )
+            .context("failed to map query")?;
+
+        let artifacts = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect artifacts")?;
+
+        Ok(artifacts)
+    }
+
+    /// Get an artifact part by ID
+    pub fn get_by_id(&self, id: i32) -> Result<Option<StoredArtifact>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_artifact WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_artifact_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(artifact)) => Ok(Some(artifact)),
+            Some(Err(e)) => Err(e).context("failed to get artifact"),
+            None => Ok(None),
+        }
+    }
+
+    /// Point a part fetched from its uri at the blob now holding its content
+    pub fn set_content(&self, id: i32, content_hash: &str, size_bytes: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute(
+                "UPDATE tb_artifact SET content_hash = ?1, size_bytes = ?2 WHERE id = ?3",
+                (content_hash, size_bytes, id),
+            )
+            .context("failed to update artifact content")
+    }
+
+    /// Count index entries that still reference a blob
+    pub fn count_by_hash(&self, content_hash: &str) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let count = db
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM tb_artifact WHERE content_hash = ?",
+                [content_hash],
+                |row| row.get(0),
+            )
+            .context("failed to count artifact references")?;
+
+        Ok(count)
+    }
+
+    /// Delete an artifact part by ID
+    pub fn delete_by_id(&self, id: i32) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db
+            .connection
+            .execute("DELETE FROM tb_artifact WHERE id = ?", [id]);
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Deleted artifact with id: {}, rows affected: {}",
+                    id, rows_affected
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete artifact: {}", e);
+                Err(e).context("failed to delete artifact")
+            }
+        }
+    }
+
+    /// Extract artifact from database row
+    fn extract_artifact_row(row: &Row) -> rusqlite::Result<StoredArtifact> {
+        Ok(StoredArtifact {
+            id: row.get("id").ok(),
+            artifact_id: row.get("artifact_id")?,
+            task_id: row.get("task_id")?,
+            context_id: row.get("context_id").ok(),
+            conversation_id: row.get("conversation_id").ok(),
+            a2a_server_id: row.get("a2a_server_id").ok(),
+            name: row.get("name").ok(),
+            description: row.get("description").ok(),
+            part_index: row.get("part_index")?,
+            part_kind: row.get("part_kind")?,
+            mime_type: row.get("mime_type").ok(),
+            file_name: row.get("file_name").ok(),
+            content_hash: row.get("content_hash")?,
+            size_bytes: row.get("size_bytes").unwrap_or(0),
+            uri: row.get("uri").ok(),
+            created_at: row.get("created_at").ok(),
+        })
+    }
+}
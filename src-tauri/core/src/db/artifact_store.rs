@@ -0,0 +1,70 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use log::info;
+use sha2::{Digest, Sha256};
+
+lazy_static! {
+    pub static ref ARTIFACT_DIR: Arc<Mutex<Option<PathBuf>>> = Arc::new(Mutex::new(None));
+}
+
+/// Create the content-addressed artifact directory
+pub fn init_artifact_store(artifact_dir: &Path) -> Result<()> {
+    let artifact_dir = artifact_dir.to_path_buf();
+    fs::create_dir_all(&artifact_dir).context("create artifact directory failed")?;
+    info!("Artifact directory: {:?}", artifact_dir);
+
+    *ARTIFACT_DIR
+        .lock()
+        .map_err(|e| anyhow::anyhow!("failed to acquire artifact dir lock: {e}"))? = Some(artifact_dir);
+
+    Ok(())
+}
+
+/// Path of the blob with the given SHA-256 hex digest
+pub fn blob_path(content_hash: &str) -> Result<PathBuf> {
+    let root = ARTIFACT_DIR
+        .lock()
+        .map_err(|e| anyhow::anyhow!("failed to acquire artifact dir lock: {e}"))?
+        .clone()
+        .context("artifact store is not initialized")?;
+
+    // Shard by the first two hex chars to keep directories small
+    let prefix = content_hash.get(..2).unwrap_or("00");
+    Ok(root.join(prefix).join(content_hash))
+}
+
+/// Write content into the store and return its SHA-256 hex digest.
+/// Identical content is stored only once.
+pub fn put_blob(content: &[u8]) -> Result<String> {
+    let content_hash = format!("{:x}", Sha256::digest(content));
+    let path = blob_path(&content_hash)?;
+
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("create artifact shard directory failed")?;
+        }
+        // Write to a temp file first so a crash never leaves a truncated blob behind
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, content).context("write artifact blob failed")?;
+        fs::rename(&tmp_path, &path).context("move artifact blob failed")?;
+        info!("Stored artifact blob {} ({} bytes)", content_hash, content.len());
+    }
+
+    Ok(content_hash)
+}
+
+/// Remove a blob from the store, ignoring blobs that are already gone
+pub fn remove_blob(content_hash: &str) -> Result<()> {
+    let path = blob_path(content_hash)?;
+    if path.exists() {
+        fs::remove_file(&path).context("remove artifact blob failed")?;
+        info!("Removed artifact blob {}", content_hash);
+    }
+    Ok(())
+}
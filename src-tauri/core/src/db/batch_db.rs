@@ -0,0 +1,251 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{BatchRun, BatchRunItem};
+
+#[derive(Default)]
+pub struct BatchRunDbManager;
+
+impl BatchRunDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the batch run tables
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_batch_run (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                a2a_server_id INTEGER NOT NULL,
+                skill_id TEXT NOT NULL,
+                input_path TEXT NOT NULL,
+                output_path TEXT NOT NULL,
+                concurrency INTEGER NOT NULL,
+                status TEXT NOT NULL DEFAULT 'running',
+                total INTEGER NOT NULL DEFAULT 0,
+                succeeded INTEGER NOT NULL DEFAULT 0,
+                failed INTEGER NOT NULL DEFAULT 0,
+                started_at TEXT DEFAULT (datetime('now')),
+                finished_at TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS tb_batch_run_item (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                run_id INTEGER NOT NULL,
+                item_index INTEGER NOT NULL,
+                prompt_id TEXT,
+                prompt TEXT NOT NULL,
+                status TEXT NOT NULL,
+                latency_ms INTEGER NOT NULL,
+                task_state TEXT,
+                response_text TEXT,
+                error TEXT,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_batch_run_item_run ON tb_batch_run_item (run_id);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create batch run tables")?;
+
+        info!("Batch run tables initialized successfully");
+        Ok(())
+    }
+
+    /// Create a run in the running state
+    pub fn insert_run(
+        &self,
+        a2a_server_id: i32,
+        skill_id: &str,
+        input_path: &str,
+        output_path: &str,
+        concurrency: usize,
+        total: usize,
+    ) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_batch_run (a2a_server_id, skill_id, input_path, output_path, concurrency, total)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                a2a_server_id,
+                skill_id,
+                input_path,
+                output_path,
+                concurrency as i64,
+                total as i64,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted batch run with id: {}", id);
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert batch run: {}", e);
+                Err(e).context("failed to insert batch run")
+            }
+        }
+    }
+
+    /// Store the outcome of one prompt
+    pub fn insert_item(&self, item: &BatchRunItem) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute(
+                "INSERT INTO tb_batch_run_item (run_id, item_index, prompt_id, prompt, status, latency_ms, task_state, response_text, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    item.run_id,
+                    item.item_index,
+                    &item.prompt_id,
+                    &item.prompt,
+                    &item.status,
+                    item.latency_ms,
+                    &item.task_state,
+                    &item.response_text,
+                    &item.error,
+                ],
+            )
+            .context("failed to insert batch run item")?;
+
+        Ok(db.connection.last_insert_rowid())
+    }
+
+    /// Mark a run as finished with its final counters
+    pub fn finish_run(&self, id: i64, status: &str, succeeded: usize, failed: usize) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_batch_run SET status = ?1, succeeded = ?2, failed = ?3, finished_at = datetime('now') WHERE id = ?4",
+            (status, succeeded as i64, failed as i64, id),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Finished batch run {} with status {}: {} succeeded, {} failed",
+                    id, status, succeeded, failed
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to finish batch run: {}", e);
+                Err(e).context("failed to finish batch run")
+            }
+        }
+    }
+
+    /// Get all runs, newest first
+    pub fn get_all_runs(&self) -> Result<Vec<BatchRun>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_batch_run ORDER BY id DESC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_run_row)
+            .context("failed to map query")?;
+
+        let runs = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect batch runs")?;
+
+        Ok(runs)
+    }
+
+    /// Get the results of a run in input order
+    pub fn get_items(&self, run_id: i64) -> Result<Vec<BatchRunItem>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_batch_run_item WHERE run_id = ? ORDER BY item_index ASC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([run_id], Self::extract_item_row)
+            .context("failed to map query")?;
+
+        let items = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect batch run items")?;
+
+        Ok(items)
+    }
+
+    /// Delete a run with its results
+    pub fn delete_run(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute("DELETE FROM tb_batch_run_item WHERE run_id = ?", [id])
+            .context("failed to delete batch run items")?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_batch_run WHERE id = ?", [id])
+            .context("failed to delete batch run")?;
+
+        info!("Deleted batch run with id: {}", id);
+        Ok(rows_affected)
+    }
+
+    /// Extract batch run from database row
+    fn extract_run_row(row: &Row) -> rusqlite::Result<BatchRun> {
+        Ok(BatchRun {
+            id: row.get("id").ok(),
+            a2a_server_id: row.get("a2a_server_id")?,
+            skill_id: row.get("skill_id")?,
+            input_path: row.get("input_path")?,
+            output_path: row.get("output_path")?,
+            concurrency: row.get("concurrency")?,
+            status: row.get("status")?,
+            total: row.get("total")?,
+            succeeded: row.get("succeeded")?,
+            failed: row.get("failed")?,
+            started_at: row.get("started_at").ok(),
+            finished_at: row.get("finished_at").ok(),
+        })
+    }
+
+    /// Extract batch run item from database row
+    fn extract_item_row(row: &Row) -> rusqlite::Result<BatchRunItem> {
+        Ok(BatchRunItem {
+            id: row.get("id").ok(),
+            run_id: row.get("run_id")?,
+            item_index: row.get("item_index")?,
+            prompt_id: row.get("prompt_id").ok(),
+            prompt: row.get("prompt")?,
+            status: row.get("status")?,
+            latency_ms: row.get("latency_ms")?,
+            task_state: row.get("task_state").ok(),
+            response_text: row.get("response_text").ok(),
+            error: row.get("error").ok(),
+        })
+    }
+}
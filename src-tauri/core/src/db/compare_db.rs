@@ -0,0 +1,220 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{CompareParams, Comparison, ComparisonResult};
+
+#[derive(Default)]
+pub struct ComparisonDbManager;
+
+impl ComparisonDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the comparison tables
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_comparison (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT,
+                prompt TEXT NOT NULL,
+                system_prompt TEXT,
+                target_count INTEGER NOT NULL,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS tb_comparison_result (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                comparison_id INTEGER NOT NULL,
+                target_index INTEGER NOT NULL,
+                target_kind TEXT NOT NULL,
+                a2a_server_id INTEGER,
+                skill_id TEXT,
+                model_id INTEGER,
+                label TEXT NOT NULL,
+                status TEXT NOT NULL,
+                answer TEXT,
+                error TEXT,
+                latency_ms INTEGER NOT NULL,
+                time_to_first_token_ms INTEGER,
+                prompt_tokens INTEGER,
+                completion_tokens INTEGER,
+                total_tokens INTEGER,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_comparison_result_comparison ON tb_comparison_result (comparison_id);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create comparison tables")?;
+
+        info!("Comparison tables initialized successfully");
+        Ok(())
+    }
+
+    /// Create the comparison group
+    pub fn insert(&self, params: &CompareParams) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_comparison (name, prompt, system_prompt, target_count) VALUES (?1, ?2, ?3, ?4)",
+            (
+                &params.name,
+                &params.prompt,
+                &params.system_prompt,
+                params.targets.len() as i64,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted comparison with id: {}", id);
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert comparison: {}", e);
+                Err(e).context("failed to insert comparison")
+            }
+        }
+    }
+
+    /// Store the answer of one target
+    pub fn insert_result(&self, result: &ComparisonResult) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute(
+                "INSERT INTO tb_comparison_result (comparison_id, target_index, target_kind, a2a_server_id, skill_id, model_id, label, status, answer, error, latency_ms, time_to_first_token_ms, prompt_tokens, completion_tokens, total_tokens)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                rusqlite::params![
+                    result.comparison_id,
+                    result.target_index,
+                    &result.target_kind,
+                    result.a2a_server_id,
+                    &result.skill_id,
+                    result.model_id,
+                    &result.label,
+                    &result.status,
+                    &result.answer,
+                    &result.error,
+                    result.latency_ms,
+                    result.time_to_first_token_ms,
+                    result.prompt_tokens,
+                    result.completion_tokens,
+                    result.total_tokens,
+                ],
+            )
+            .context("failed to insert comparison result")?;
+
+        Ok(db.connection.last_insert_rowid())
+    }
+
+    /// Get all comparisons, newest first
+    pub fn get_all(&self) -> Result<Vec<Comparison>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_comparison ORDER BY id DESC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_comparison_row)
+            .context("failed to map query")?;
+
+        let comparisons = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect comparisons")?;
+
+        Ok(comparisons)
+    }
+
+    /// Get the results of a comparison in target order
+    pub fn get_results(&self, comparison_id: i64) -> Result<Vec<ComparisonResult>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_comparison_result WHERE comparison_id = ? ORDER BY target_index ASC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([comparison_id], Self::extract_result_row)
+            .context("failed to map query")?;
+
+        let results = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect comparison results")?;
+
+        Ok(results)
+    }
+
+    /// Delete a comparison with its results
+    pub fn delete(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute("DELETE FROM tb_comparison_result WHERE comparison_id = ?", [id])
+            .context("failed to delete comparison results")?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_comparison WHERE id = ?", [id])
+            .context("failed to delete comparison")?;
+
+        info!("Deleted comparison with id: {}", id);
+        Ok(rows_affected)
+    }
+
+    /// Extract comparison from database row
+    fn extract_comparison_row(row: &Row) -> rusqlite::Result<Comparison> {
+        Ok(Comparison {
+            id: row.get("id").ok(),
+            name: row.get("name").ok(),
+            prompt: row.get("prompt")?,
+            system_prompt: row.get("system_prompt").ok(),
+            target_count: row.get("target_count")?,
+            created_at: row.get("created_at").ok(),
+        })
+    }
+
+    /// Extract comparison result from database row
+    fn extract_result_row(row: &Row) -> rusqlite::Result<ComparisonResult> {
+        Ok(ComparisonResult {
+            id: row.get("id").ok(),
+            comparison_id: row.get("comparison_id")?,
+            target_index: row.get("target_index")?,
+            target_kind: row.get("target_kind")?,
+            a2a_server_id: row.get("a2a_server_id").ok(),
+            skill_id: row.get("skill_id").ok(),
+            model_id: row.get("model_id").ok(),
+            label: row.get("label")?,
+            status: row.get("status")?,
+            answer: row.get("answer").ok(),
+            error: row.get("error").ok(),
+            latency_ms: row.get("latency_ms")?,
+            time_to_first_token_ms: row.get("time_to_first_token_ms").ok(),
+            prompt_tokens: row.get("prompt_tokens").ok(),
+            completion_tokens: row.get("completion_tokens").ok(),
+            total_tokens: row.get("total_tokens").ok(),
+        })
+    }
+}
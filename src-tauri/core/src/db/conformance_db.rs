@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{ConformanceCheck, ConformanceRun};
+
+#[derive(Default)]
+pub struct ConformanceDbManager;
+
+impl ConformanceDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the conformance run table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_conformance_run (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                a2a_server_id INTEGER NOT NULL,
+                a2a_url TEXT,
+                status TEXT NOT NULL DEFAULT 'running',
+                passed INTEGER NOT NULL DEFAULT 0,
+                failed INTEGER NOT NULL DEFAULT 0,
+                skipped INTEGER NOT NULL DEFAULT 0,
+                checks_json TEXT NOT NULL DEFAULT '[]',
+                started_at TEXT DEFAULT (datetime('now')),
+                finished_at TEXT
+            );
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create conformance run table")?;
+
+        info!("Conformance run table initialized successfully");
+        Ok(())
+    }
+
+    /// Create a run in the running state
+    pub fn insert_run(&self, a2a_server_id: i32, a2a_url: Option<&str>) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_conformance_run (a2a_server_id, a2a_url) VALUES (?1, ?2)",
+            (a2a_server_id, a2a_url),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted conformance run with id: {}", id);
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert conformance run: {}", e);
+                Err(e).context("failed to insert conformance run")
+            }
+        }
+    }
+
+    /// Store the checks and the final status of a run
+    pub fn finish_run(
+        &self,
+        id: i64,
+        a2a_url: Option<&str>,
+        checks: &[ConformanceCheck],
+    ) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let checks_json = serde_json::to_string(checks).context("failed to serialize checks")?;
+        let count = |status: &str| checks.iter().filter(|check| check.status == status).count();
+        let (passed, failed, skipped) = (count("passed"), count("failed"), count("skipped"));
+        let status = if failed == 0 { "passed" } else { "failed" };
+
+        let result = db.connection.execute(
+            "UPDATE tb_conformance_run SET a2a_url = ?1, status = ?2, passed = ?3, failed = ?4, skipped = ?5, checks_json = ?6, finished_at = datetime('now') WHERE id = ?7",
+            (a2a_url, status, passed, failed, skipped, &checks_json, id),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Finished conformance run {} with status {}", id, status);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to finish conformance run: {}", e);
+                Err(e).context("failed to finish conformance run")
+            }
+        }
+    }
+
+    /// Get all runs, newest first
+    pub fn get_all_runs(&self) -> Result<Vec<ConformanceRun>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_conformance_run ORDER BY id DESC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_run_row)
+            .context("failed to map query")?;
+
+        let runs = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect conformance runs")?;
+
+        Ok(runs)
+    }
+
+    /// Get a run by id
+    pub fn get_by_id(&self, id: i64) -> Result<Option<ConformanceRun>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_conformance_run WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_run_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(run) => Ok(Some(run.context("failed to read conformance run")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete a run
+    pub fn delete_run(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_conformance_run WHERE id = ?", [id])
+            .context("failed to delete conformance run")?;
+
+        info!("Deleted conformance run with id: {}", id);
+        Ok(rows_affected)
+    }
+
+    /// Extract conformance run from database row
+    fn extract_run_row(row: &Row) -> rusqlite::Result<ConformanceRun> {
+        let checks_json: String = row.get("checks_json")?;
+        let checks = serde_json::from_str(&checks_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+
+        Ok(ConformanceRun {
+            id: row.get("id").ok(),
+            a2a_server_id: row.get("a2a_server_id")?,
+            a2a_url: row.get("a2a_url")?,
+            status: row.get("status")?,
+            passed: row.get::<_, i64>("passed")? as usize,
+            failed: row.get::<_, i64>("failed")? as usize,
+            skipped: row.get::<_, i64>("skipped")? as usize,
+            checks,
+            started_at: row.get("started_at").ok(),
+            finished_at: row.get("finished_at").ok(),
+        })
+    }
+}
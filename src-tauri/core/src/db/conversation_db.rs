@@ -0,0 +1,179 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::A2AConversationContext;
+
+#[derive(Default)]
+pub struct A2AConversationContextDbManager;
+
+impl A2AConversationContextDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the A2A conversation context table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_a2a_conversation_context (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                conversation_id TEXT NOT NULL,
+                a2a_server_id INTEGER NOT NULL,
+                context_id TEXT,
+                task_id TEXT,
+                task_state TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now')),
+                UNIQUE (conversation_id, a2a_server_id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_a2a_conversation_context_conversation ON tb_a2a_conversation_context (conversation_id);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create A2A conversation context table")?;
+
+        info!("A2A conversation context table initialized successfully");
+        Ok(())
+    }
+
+    /// Remember the latest contextId/taskId an agent returned for a conversation.
+    /// A missing contextId or taskId keeps the previously stored value.
+    pub fn upsert(
+        &self,
+        conversation_id: &str,
+        a2a_server_id: i32,
+        context_id: Option<&str>,
+        task_id: Option<&str>,
+        task_state: Option<&str>,
+    ) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_a2a_conversation_context (conversation_id, a2a_server_id, context_id, task_id, task_state)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (conversation_id, a2a_server_id) DO UPDATE SET
+                context_id = COALESCE(excluded.context_id, context_id),
+                task_id = COALESCE(excluded.task_id, task_id),
+                task_state = excluded.task_state,
+                updated_at = datetime('now')",
+            (conversation_id, a2a_server_id, context_id, task_id, task_state),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Stored A2A context for conversation: {}, server: {}, context: {:?}, task: {:?}, state: {:?}",
+                    conversation_id, a2a_server_id, context_id, task_id, task_state
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to store A2A conversation context: {}", e);
+                Err(e).context("failed to store A2A conversation context")
+            }
+        }
+    }
+
+    /// Get the context stored for a conversation and agent
+    pub fn get(
+        &self,
+        conversation_id: &str,
+        a2a_server_id: i32,
+    ) -> Result<Option<A2AConversationContext>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare(
+                "SELECT * FROM tb_a2a_conversation_context WHERE conversation_id = ? AND a2a_server_id = ?",
+            )
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map(
+                (conversation_id, a2a_server_id),
+                Self::extract_conversation_context_row,
+            )
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(context)) => Ok(Some(context)),
+            Some(Err(e)) => Err(e).context("failed to get A2A conversation context"),
+            None => Ok(None),
+        }
+    }
+
+    /// Get all agent contexts stored for a conversation
+    pub fn get_by_conversation(&self, conversation_id: &str) -> Result<Vec<A2AConversationContext>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare(
+                "SELECT * FROM tb_a2a_conversation_context WHERE conversation_id = ? ORDER BY updated_at DESC",
+            )
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([conversation_id], Self::extract_conversation_context_row)
+            .context("failed to map query")?;
+
+        let contexts = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect A2A conversation contexts")?;
+
+        Ok(contexts)
+    }
+
+    /// Forget every agent context of a conversation
+    pub fn delete_by_conversation(&self, conversation_id: &str) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "DELETE FROM tb_a2a_conversation_context WHERE conversation_id = ?",
+            [conversation_id],
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Deleted A2A contexts for conversation: {}, rows affected: {}",
+                    conversation_id, rows_affected
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete A2A conversation contexts: {}", e);
+                Err(e).context("failed to delete A2A conversation contexts")
+            }
+        }
+    }
+
+    /// Extract A2A conversation context from database row
+    fn extract_conversation_context_row(row: &Row) -> rusqlite::Result<A2AConversationContext> {
+        Ok(A2AConversationContext {
+            id: row.get("id").ok(),
+            conversation_id: row.get("conversation_id")?,
+            a2a_server_id: row.get("a2a_server_id")?,
+            context_id: row.get("context_id").ok(),
+            task_id: row.get("task_id").ok(),
+            task_state: row.get("task_state").ok(),
+            created_at: row.get("created_at").ok(),
+            updated_at: row.get("updated_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,325 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{HostAgentProfile, HostAgentProfileParams};
+
+#[derive(Default)]
+pub struct HostAgentProfileDbManager;
+
+impl HostAgentProfileDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the host agent profile tables
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_host_agent_profile (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                system_prompt_template TEXT,
+                max_iterations INTEGER NOT NULL DEFAULT 8,
+                model_id INTEGER,
+                a2a_server_ids_json TEXT,
+                is_default INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS tb_conversation_host_agent_profile (
+                conversation_id TEXT PRIMARY KEY,
+                profile_id INTEGER NOT NULL,
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create host agent profile tables")?;
+
+        info!("Host agent profile tables initialized successfully");
+        Ok(())
+    }
+
+    /// Insert a new profile
+    pub fn insert(&self, params: &HostAgentProfileParams, max_iterations: i64) -> Result<i64> {
+        let a2a_server_ids_json = params
+            .a2a_server_ids
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_host_agent_profile (name, description, system_prompt_template, max_iterations, model_id, a2a_server_ids_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            (
+                &params.name,
+                &params.description,
+                &params.system_prompt_template,
+                max_iterations,
+                params.model_id,
+                &a2a_server_ids_json,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted host agent profile with id: {id}");
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert host agent profile: {}", e);
+                Err(e).context("failed to insert host agent profile")
+            }
+        }
+    }
+
+    /// Overwrite a profile with its merged and validated state
+    pub fn update(&self, profile: &HostAgentProfile) -> Result<usize> {
+        let id = profile.id.context("host agent profile has no id")?;
+        let a2a_server_ids_json = profile
+            .a2a_server_ids
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_host_agent_profile SET name = ?1, description = ?2, system_prompt_template = ?3,
+             max_iterations = ?4, model_id = ?5, a2a_server_ids_json = ?6, updated_at = datetime('now') WHERE id = ?7",
+            (
+                &profile.name,
+                &profile.description,
+                &profile.system_prompt_template,
+                profile.max_iterations,
+                profile.model_id,
+                &a2a_server_ids_json,
+                id,
+            ),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Updated host agent profile with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to update host agent profile: {}", e);
+                Err(e).context("failed to update host agent profile")
+            }
+        }
+    }
+
+    /// Get all profiles
+    pub fn get_all(&self) -> Result<Vec<HostAgentProfile>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_host_agent_profile ORDER BY name ASC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_profile_row)
+            .context("failed to map query")?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect host agent profiles")
+    }
+
+    /// Get a profile by ID
+    pub fn get_by_id(&self, id: i64) -> Result<Option<HostAgentProfile>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_host_agent_profile WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_profile_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(profile)) => Ok(Some(profile)),
+            Some(Err(e)) => Err(e).context("failed to get host agent profile"),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the default profile, if one is set
+    pub fn get_default(&self) -> Result<Option<HostAgentProfile>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_host_agent_profile WHERE is_default = 1 LIMIT 1")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([], Self::extract_profile_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(profile)) => Ok(Some(profile)),
+            Some(Err(e)) => Err(e).context("failed to get default host agent profile"),
+            None => Ok(None),
+        }
+    }
+
+    /// Make a profile the default, or clear the default when `id` is None
+    pub fn set_default(&self, id: Option<i64>) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        // One statement so there is never more than one default
+        let result = db.connection.execute(
+            "UPDATE tb_host_agent_profile SET is_default = CASE WHEN id = ?1 THEN 1 ELSE 0 END
+             WHERE is_default = 1 OR id = ?1",
+            [id],
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Default host agent profile set to: {:?}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to set default host agent profile: {}", e);
+                Err(e).context("failed to set default host agent profile")
+            }
+        }
+    }
+
+    /// Delete a profile and the conversation selections pointing at it
+    pub fn delete_by_id(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db
+            .connection
+            .execute("DELETE FROM tb_host_agent_profile WHERE id = ?", [id]);
+
+        match result {
+            Ok(rows_affected) => {
+                db.connection
+                    .execute(
+                        "DELETE FROM tb_conversation_host_agent_profile WHERE profile_id = ?",
+                        [id],
+                    )
+                    .context("failed to delete conversation profile selections")?;
+                info!("Deleted host agent profile with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete host agent profile: {}", e);
+                Err(e).context("failed to delete host agent profile")
+            }
+        }
+    }
+
+    /// Select the profile of a conversation, None goes back to the default
+    pub fn select_for_conversation(
+        &self,
+        conversation_id: &str,
+        profile_id: Option<i64>,
+    ) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = match profile_id {
+            Some(profile_id) => db.connection.execute(
+                "INSERT INTO tb_conversation_host_agent_profile (conversation_id, profile_id) VALUES (?1, ?2)
+                 ON CONFLICT(conversation_id) DO UPDATE SET profile_id = excluded.profile_id, updated_at = datetime('now')",
+                (conversation_id, profile_id),
+            ),
+            None => db.connection.execute(
+                "DELETE FROM tb_conversation_host_agent_profile WHERE conversation_id = ?",
+                [conversation_id],
+            ),
+        };
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Selected host agent profile {:?} for conversation: {}",
+                    profile_id, conversation_id
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to select host agent profile: {}", e);
+                Err(e).context("failed to select host agent profile")
+            }
+        }
+    }
+
+    /// The profile selected for a conversation, falling back to the default profile
+    pub fn get_for_conversation(&self, conversation_id: &str) -> Result<Option<HostAgentProfile>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare(
+                "SELECT p.* FROM tb_host_agent_profile p
+                 LEFT JOIN tb_conversation_host_agent_profile c ON c.profile_id = p.id AND c.conversation_id = ?1
+                 WHERE c.conversation_id IS NOT NULL OR p.is_default = 1
+                 ORDER BY c.conversation_id IS NULL ASC
+                 LIMIT 1",
+            )
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([conversation_id], Self::extract_profile_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(profile)) => Ok(Some(profile)),
+            Some(Err(e)) => Err(e).context("failed to get conversation host agent profile"),
+            None => Ok(None),
+        }
+    }
+
+    /// Extract host agent profile from database row
+    fn extract_profile_row(row: &Row) -> rusqlite::Result<HostAgentProfile> {
+        let a2a_server_ids_json: Option<String> = row.get("a2a_server_ids_json").ok();
+
+        Ok(HostAgentProfile {
+            id: row.get("id").ok(),
+            name: row.get("name")?,
+            description: row.get("description").ok(),
+            system_prompt_template: row.get("system_prompt_template").ok(),
+            max_iterations: row.get("max_iterations")?,
+            model_id: row.get("model_id").ok(),
+            a2a_server_ids: a2a_server_ids_json.and_then(|json| serde_json::from_str(&json).ok()),
+            is_default: row.get::<_, i32>("is_default").unwrap_or(0) != 0,
+            created_at: row.get("created_at").ok(),
+            updated_at: row.get("updated_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{LoadTestParams, LoadTestRun, LoadTestSummary};
+
+#[derive(Default)]
+pub struct LoadTestDbManager;
+
+impl LoadTestDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the load test run table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_load_test_run (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT,
+                a2a_server_id INTEGER NOT NULL,
+                skill_id TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'running',
+                params_json TEXT NOT NULL,
+                summary_json TEXT,
+                started_at TEXT DEFAULT (datetime('now')),
+                finished_at TEXT
+            );
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create load test run table")?;
+
+        info!("Load test run table initialized successfully");
+        Ok(())
+    }
+
+    /// Create a run in the running state
+    pub fn insert_run(&self, params: &LoadTestParams) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let params_json = serde_json::to_string(params).context("failed to serialize params")?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_load_test_run (name, a2a_server_id, skill_id, params_json) VALUES (?1, ?2, ?3, ?4)",
+            (&params.name, params.a2a_server_id, &params.skill_id, &params_json),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted load test run with id: {}", id);
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert load test run: {}", e);
+                Err(e).context("failed to insert load test run")
+            }
+        }
+    }
+
+    /// Store the final status and summary of a run
+    pub fn finish_run(&self, id: i64, status: &str, summary: &LoadTestSummary) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let summary_json = serde_json::to_string(summary).context("failed to serialize summary")?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_load_test_run SET status = ?1, summary_json = ?2, finished_at = datetime('now') WHERE id = ?3",
+            (status, &summary_json, id),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Finished load test run {} with status {}", id, status);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to finish load test run: {}", e);
+                Err(e).context("failed to finish load test run")
+            }
+        }
+    }
+
+    /// Get all runs, newest first
+    pub fn get_all_runs(&self) -> Result<Vec<LoadTestRun>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_load_test_run ORDER BY id DESC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_run_row)
+            .context("failed to map query")?;
+
+        let runs = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect load test runs")?;
+
+        Ok(runs)
+    }
+
+    /// Get runs by id, used to compare several runs side by side
+    pub fn get_by_ids(&self, ids: &[i64]) -> Result<Vec<LoadTestRun>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let placeholders = vec!["?"; ids.len()].join(", ");
+        let mut stmt = db
+            .connection
+            .prepare(&format!(
+                "SELECT * FROM tb_load_test_run WHERE id IN ({}) ORDER BY id ASC",
+                placeholders
+            ))
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(ids.iter()), Self::extract_run_row)
+            .context("failed to map query")?;
+
+        let runs = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect load test runs")?;
+
+        Ok(runs)
+    }
+
+    /// Delete a run
+    pub fn delete_run(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_load_test_run WHERE id = ?", [id])
+            .context("failed to delete load test run")?;
+
+        info!("Deleted load test run with id: {}", id);
+        Ok(rows_affected)
+    }
+
+    /// Extract load test run from database row
+    fn extract_run_row(row: &Row) -> rusqlite::Result<LoadTestRun> {
+        let params_json: String = row.get("params_json")?;
+        let params = serde_json::from_str(&params_json).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e))
+        })?;
+        let summary = row
+            .get::<_, Option<String>>("summary_json")?
+            .and_then(|json| serde_json::from_str(&json).ok());
+
+        Ok(LoadTestRun {
+            id: row.get("id").ok(),
+            name: row.get("name").ok(),
+            a2a_server_id: row.get("a2a_server_id")?,
+            skill_id: row.get("skill_id")?,
+            status: row.get("status")?,
+            params,
+            summary,
+            started_at: row.get("started_at").ok(),
+            finished_at: row.get("finished_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,273 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{SettingMcpServer, SettingMcpServerParams, UpdateSettingMcpServerParams};
+
+#[derive(Default)]
+pub struct SettingMcpServerDbManager;
+
+impl SettingMcpServerDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the MCP server table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_setting_mcp_server (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                transport TEXT NOT NULL,
+                command TEXT,
+                args_json TEXT,
+                env_json TEXT,
+                url TEXT,
+                custom_header_json TEXT,
+                tools_json TEXT,
+                enabled INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_setting_mcp_server_enabled ON tb_setting_mcp_server (enabled);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create setting MCP server table")?;
+
+        info!("Setting MCP server table initialized successfully");
+        Ok(())
+    }
+
+    /// Insert a new MCP server
+    pub fn insert(&self, params: &SettingMcpServerParams) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_setting_mcp_server (name, transport, command, args_json, env_json, url, custom_header_json, enabled) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            (
+                &params.name,
+                &params.transport,
+                &params.command,
+                &params.args_json,
+                &params.env_json,
+                &params.url,
+                &params.custom_header_json,
+                params.enabled as i32,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted MCP server with id: {id}");
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert MCP server: {}", e);
+                Err(e).context("failed to insert MCP server")
+            }
+        }
+    }
+
+    /// Update an existing MCP server
+    pub fn update(&self, params: &UpdateSettingMcpServerParams) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut update_fields = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        let optional_fields = [
+            ("name = ?", &params.name),
+            ("transport = ?", &params.transport),
+            ("command = ?", &params.command),
+            ("args_json = ?", &params.args_json),
+            ("env_json = ?", &params.env_json),
+            ("url = ?", &params.url),
+            ("custom_header_json = ?", &params.custom_header_json),
+        ];
+        for (field, value) in optional_fields {
+            if let Some(value) = value {
+                update_fields.push(field);
+                values.push(Box::new(value.clone()));
+            }
+        }
+        // Anything but a rename or toggle changes the connection, so the cached tool list is stale
+        let connection_changed = update_fields.iter().any(|field| *field != "name = ?");
+
+        if let Some(enabled) = params.enabled {
+            update_fields.push("enabled = ?");
+            values.push(Box::new(enabled as i32));
+        }
+
+        if update_fields.is_empty() {
+            return Ok(0);
+        }
+
+        if connection_changed {
+            update_fields.push("tools_json = NULL");
+        }
+        update_fields.push("updated_at = datetime('now')");
+        values.push(Box::new(params.id));
+
+        let sql = format!(
+            "UPDATE tb_setting_mcp_server SET {} WHERE id = ?",
+            update_fields.join(", ")
+        );
+
+        let result = db
+            .connection
+            .execute(&sql, rusqlite::params_from_iter(values.iter()));
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Updated MCP server with id: {}, rows affected: {}",
+                    params.id, rows_affected
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to update MCP server: {}", e);
+                Err(e).context("failed to update MCP server")
+            }
+        }
+    }
+
+    /// Cache the tool list of a server
+    pub fn update_tools_json(&self, id: i32, tools_json: &str) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute(
+                "UPDATE tb_setting_mcp_server SET tools_json = ?1 WHERE id = ?2",
+                (tools_json, id),
+            )
+            .context("failed to update MCP server tools")
+    }
+
+    /// Get all MCP servers
+    pub fn get_all(&self) -> Result<Vec<SettingMcpServer>> {
+        self.query("SELECT * FROM tb_setting_mcp_server ORDER BY id ASC")
+    }
+
+    /// Get enabled MCP servers
+    pub fn get_enabled(&self) -> Result<Vec<SettingMcpServer>> {
+        self.query("SELECT * FROM tb_setting_mcp_server WHERE enabled = 1 ORDER BY id ASC")
+    }
+
+    /// Get an MCP server by ID
+    pub fn get_by_id(&self, id: i32) -> Result<Option<SettingMcpServer>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_setting_mcp_server WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_mcp_server_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(server)) => Ok(Some(server)),
+            Some(Err(e)) => Err(e).context("failed to get MCP server"),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete an MCP server by ID
+    pub fn delete_by_id(&self, id: i32) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db
+            .connection
+            .execute("DELETE FROM tb_setting_mcp_server WHERE id = ?", [id]);
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Deleted MCP server with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete MCP server: {}", e);
+                Err(e).context("failed to delete MCP server")
+            }
+        }
+    }
+
+    /// Toggle the enabled status of an MCP server
+    pub fn toggle_enabled(&self, id: i32) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_setting_mcp_server SET enabled = CASE WHEN enabled = 1 THEN 0 ELSE 1 END, updated_at = datetime('now') WHERE id = ?",
+            [id],
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Toggled enabled status for MCP server with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to toggle enabled status: {}", e);
+                Err(e).context("failed to toggle enabled status")
+            }
+        }
+    }
+
+    fn query(&self, sql: &str) -> Result<Vec<SettingMcpServer>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db.connection.prepare(sql).context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([], Self::extract_mcp_server_row)
+            .context("failed to map query")?;
+
+        let servers = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect MCP servers")?;
+
+        Ok(servers)
+    }
+
+    /// Extract MCP server from database row
+    fn extract_mcp_server_row(row: &Row) -> rusqlite::Result<SettingMcpServer> {
+        Ok(SettingMcpServer {
+            id: row.get("id").ok(),
+            name: row.get("name")?,
+            transport: row.get("transport")?,
+            command: row.get("command").ok(),
+            args_json: row.get("args_json").ok(),
+            env_json: row.get("env_json").ok(),
+            url: row.get("url").ok(),
+            custom_header_json: row.get("custom_header_json").ok(),
+            tools_json: row.get("tools_json").ok(),
+            enabled: row.get::<_, i32>("enabled").unwrap_or(0) != 0,
+            created_at: row.get("created_at").ok(),
+            updated_at: row.get("updated_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,198 @@
+use anyhow::{Context, Result};
+use log::{error, info, warn};
+use rusqlite::Row;
+
+use crate::model::{ConversationMessage, SaveConversationMessageParams};
+
+#[derive(Default)]
+pub struct ConversationMessageDbManager;
+
+impl ConversationMessageDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the conversation message table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_conversation_message (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                message_id TEXT NOT NULL UNIQUE,
+                conversation_id TEXT NOT NULL,
+                role TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                content TEXT NOT NULL,
+                a2a_server_id INTEGER,
+                agent_name TEXT,
+                skill_id TEXT,
+                skill_name TEXT,
+                task_id TEXT,
+                task_state TEXT,
+                model_key TEXT,
+                endpoint_url TEXT,
+                transport TEXT,
+                created_at TEXT DEFAULT (datetime('now'))
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_conversation_message_conversation ON tb_conversation_message (conversation_id);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create conversation message table")?;
+
+        self.migrate_table_if_needed(&db.connection)?;
+
+        info!("Conversation message table initialized successfully");
+        Ok(())
+    }
+
+    /// Migrate existing table to add the serving endpoint columns if needed
+    fn migrate_table_if_needed(&self, connection: &rusqlite::Connection) -> Result<()> {
+        for column in ["endpoint_url", "transport"] {
+            let column_exists = connection
+                .query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('tb_conversation_message') WHERE name = ?",
+                    [column],
+                    |row| row.get::<_, i32>(0),
+                )
+                .unwrap_or(0);
+
+            if column_exists == 0 {
+                info!("Migrating table: adding {} column", column);
+                if let Err(e) = connection.execute(
+                    &format!("ALTER TABLE tb_conversation_message ADD COLUMN {} TEXT", column),
+                    [],
+                ) {
+                    warn!("Failed to add {} column: {}", column, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Insert a message, or replace its content when the message is saved again
+    pub fn upsert(&self, params: &SaveConversationMessageParams) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_conversation_message (message_id, conversation_id, role, kind, content, a2a_server_id, agent_name, skill_id, skill_name, task_id, task_state, model_key, endpoint_url, transport)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT (message_id) DO UPDATE SET
+                content = excluded.content,
+                task_id = COALESCE(excluded.task_id, task_id),
+                task_state = COALESCE(excluded.task_state, task_state),
+                endpoint_url = COALESCE(excluded.endpoint_url, endpoint_url),
+                transport = COALESCE(excluded.transport, transport)",
+            rusqlite::params![
+                &params.message_id,
+                &params.conversation_id,
+                &params.role,
+                &params.kind,
+                &params.content,
+                &params.a2a_server_id,
+                &params.agent_name,
+                &params.skill_id,
+                &params.skill_name,
+                &params.task_id,
+                &params.task_state,
+                &params.model_key,
+                &params.endpoint_url,
+                &params.transport,
+            ],
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.query_row(
+                    "SELECT id FROM tb_conversation_message WHERE message_id = ?",
+                    [&params.message_id],
+                    |row| row.get(0),
+                )?;
+                info!("Saved conversation message with id: {}", id);
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to save conversation message: {}", e);
+                Err(e).context("failed to save conversation message")
+            }
+        }
+    }
+
+    /// Get all messages of a conversation in chronological order
+    pub fn get_by_conversation(&self, conversation_id: &str) -> Result<Vec<ConversationMessage>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_conversation_message WHERE conversation_id = ? ORDER BY id ASC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([conversation_id], Self::extract_message_row)
+            .context("failed to map query")?;
+
+        let messages = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect conversation messages")?;
+
+        Ok(messages)
+    }
+
+    /// Delete all messages of a conversation
+    pub fn delete_by_conversation(&self, conversation_id: &str) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "DELETE FROM tb_conversation_message WHERE conversation_id = ?",
+            [conversation_id],
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!(
+                    "Deleted messages of conversation: {}, rows affected: {}",
+                    conversation_id, rows_affected
+                );
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete conversation messages: {}", e);
+                Err(e).context("failed to delete conversation messages")
+            }
+        }
+    }
+
+    /// Extract conversation message from database row
+    pub(crate) fn extract_message_row(row: &Row) -> rusqlite::Result<ConversationMessage> {
+        Ok(ConversationMessage {
+            id: row.get("id").ok(),
+            message_id: row.get("message_id")?,
+            conversation_id: row.get("conversation_id")?,
+            role: row.get("role")?,
+            kind: row.get("kind")?,
+            content: row.get("content")?,
+            a2a_server_id: row.get("a2a_server_id").ok(),
+            agent_name: row.get("agent_name").ok(),
+            skill_id: row.get("skill_id").ok(),
+            skill_name: row.get("skill_name").ok(),
+            task_id: row.get("task_id").ok(),
+            task_state: row.get("task_state").ok(),
+            model_key: row.get("model_key").ok(),
+            endpoint_url: row.get("endpoint_url").ok(),
+            transport: row.get("transport").ok(),
+            created_at: row.get("created_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,84 @@
+pub mod a2a_db;
+pub mod artifact_db;
+pub mod artifact_store;
+pub mod batch_db;
+pub mod compare_db;
+pub mod conformance_db;
+pub mod conversation_db;
+pub mod host_agent_profile_db;
+pub mod load_test_db;
+pub mod mcp_db;
+pub mod message_db;
+pub mod model_db;
+pub mod model_preset_db;
+pub mod prompt_template_db;
+pub mod rusqlite;
+pub mod search_db;
+pub mod traffic_db;
+
+use anyhow::Result;
+
+/// Initialize all database tables
+pub fn init_all_tables() -> Result<()> {
+    // Initialize A2A server table
+    let a2a_manager = a2a_db::SettingA2AServerDbManager::new();
+    a2a_manager.init()?;
+    
+    // Initialize model table
+    let model_manager = model_db::SettingModelDbManager::new();
+    model_manager.init()?;
+
+    // Initialize A2A conversation context table
+    let conversation_manager = conversation_db::A2AConversationContextDbManager::new();
+    conversation_manager.init()?;
+
+    // Initialize artifact index table
+    let artifact_manager = artifact_db::ArtifactDbManager::new();
+    artifact_manager.init()?;
+
+    // Initialize conversation message table
+    let message_manager = message_db::ConversationMessageDbManager::new();
+    message_manager.init()?;
+
+    // Initialize conversation full-text search index
+    let search_manager = search_db::ConversationSearchDbManager::new();
+    search_manager.init()?;
+
+    // Initialize batch run tables
+    let batch_manager = batch_db::BatchRunDbManager::new();
+    batch_manager.init()?;
+
+    // Initialize load test run table
+    let load_test_manager = load_test_db::LoadTestDbManager::new();
+    load_test_manager.init()?;
+
+    // Initialize comparison tables
+    let compare_manager = compare_db::ComparisonDbManager::new();
+    compare_manager.init()?;
+
+    // Initialize MCP server table
+    let mcp_manager = mcp_db::SettingMcpServerDbManager::new();
+    mcp_manager.init()?;
+
+    // Initialize prompt template table
+    let prompt_template_manager = prompt_template_db::PromptTemplateDbManager::new();
+    prompt_template_manager.init()?;
+
+    // Initialize host agent profile tables
+    let host_agent_profile_manager = host_agent_profile_db::HostAgentProfileDbManager::new();
+    host_agent_profile_manager.init()?;
+
+    // Initialize model preset table
+    let model_preset_manager = model_preset_db::ModelPresetDbManager::new();
+    model_preset_manager.init()?;
+
+    // Initialize conformance run table
+    let conformance_manager = conformance_db::ConformanceDbManager::new();
+    conformance_manager.init()?;
+
+    // Initialize HTTP traffic recorder table
+    let traffic_manager = traffic_db::TrafficDbManager::new();
+    traffic_manager.init()?;
+
+    Ok(())
+}
\ No newline at end of file
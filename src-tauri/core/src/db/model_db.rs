@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
-use log::{error, info};
+use log::{error, info, warn};
 use rusqlite::Row;
-use tauri::AppHandle;
 
 use crate::model::{SettingModel, SettingModelParams, UpdateSettingModelParams};
 
+// API model names the built-in providers were always called with
+const DEFAULT_MODEL_NAMES: [(&str, &str); 2] =
+    [("DeepSeek", "deepseek-chat"), ("OpenAI", "gpt-4o-mini")];
+
+#[derive(Default)]
 pub struct SettingModelDbManager;
 
 impl SettingModelDbManager {
@@ -13,7 +17,7 @@ impl SettingModelDbManager {
     }
 
     /// Initialize the setting model table
-    pub fn init(&self, _handler: &AppHandle) -> Result<()> {
+    pub fn init(&self) -> Result<()> {
         let db = crate::db::rusqlite::DB
             .lock()
             .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
@@ -37,10 +41,56 @@ impl SettingModelDbManager {
             .execute(sql, [])
             .context("failed to create setting model table")?;
 
+        // Migrate existing table if needed
+        self.migrate_table_if_needed(&db.connection)?;
+
         info!("Setting model table initialized successfully");
         Ok(())
     }
 
+    /// Migrate existing table to add the context window and model name columns if needed
+    fn migrate_table_if_needed(&self, connection: &rusqlite::Connection) -> Result<()> {
+        for (column, column_type) in [
+            ("context_window", "INTEGER"),
+            ("context_policy", "TEXT"),
+            ("model_name", "TEXT"),
+        ] {
+            let column_exists = connection
+                .query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('tb_setting_model') WHERE name = ?",
+                    [column],
+                    |row| row.get::<_, i32>(0),
+                )
+                .unwrap_or(0);
+
+            if column_exists == 0 {
+                info!("Migrating table: adding {} column", column);
+                if let Err(e) = connection.execute(
+                    &format!(
+                        "ALTER TABLE tb_setting_model ADD COLUMN {} {}",
+                        column, column_type
+                    ),
+                    [],
+                ) {
+                    warn!("Failed to add {} column: {}", column, e);
+                }
+            }
+        }
+
+        // Rows saved before the name was stored get the name their provider was always called with
+        for (model_key, model_name) in DEFAULT_MODEL_NAMES {
+            if let Err(e) = connection.execute(
+                "UPDATE tb_setting_model SET model_name = ?1
+                 WHERE model_key = ?2 AND (model_name IS NULL OR trim(model_name) = '')",
+                [model_name, model_key],
+            ) {
+                warn!("Failed to backfill model_name of {}: {}", model_key, e);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Insert a new setting model
     pub fn insert(&self, params: &SettingModelParams) -> Result<i64> {
         let db = crate::db::rusqlite::DB
@@ -48,13 +98,16 @@ impl SettingModelDbManager {
             .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
 
         let result = db.connection.execute(
-            "INSERT INTO tb_setting_model (model_key, enabled, api_url, api_key)
-             VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO tb_setting_model (model_key, model_name, enabled, api_url, api_key, context_window, context_policy)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             (
                 &params.model_key,
+                &params.model_name,
                 params.enabled as i32,
                 &params.api_url,
                 &params.api_key,
+                params.context_window,
+                &params.context_policy,
             ),
         );
 
@@ -85,6 +138,11 @@ impl SettingModelDbManager {
             values.push(Box::new(enabled as i32));
         }
 
+        if let Some(model_name) = &params.model_name {
+            update_fields.push("model_name = ?");
+            values.push(Box::new(model_name.clone()));
+        }
+
         if let Some(api_url) = &params.api_url {
             update_fields.push("api_url = ?");
             values.push(Box::new(api_url.clone()));
@@ -95,6 +153,16 @@ impl SettingModelDbManager {
             values.push(Box::new(api_key.clone()));
         }
 
+        if let Some(context_window) = params.context_window {
+            update_fields.push("context_window = ?");
+            values.push(Box::new(context_window));
+        }
+
+        if let Some(context_policy) = &params.context_policy {
+            update_fields.push("context_policy = ?");
+            values.push(Box::new(context_policy.clone()));
+        }
+
         if update_fields.is_empty() {
             return Ok(0);
         }
@@ -328,6 +396,69 @@ impl SettingModelDbManager {
             enabled: row.get::<_, i32>(2)? != 0,
             api_url: row.get(3)?,
             api_key: row.get(4)?,
+            model_name: row.get("model_name").ok(),
+            context_window: row.get("context_window").ok(),
+            context_policy: row.get("context_policy").ok(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::*;
+
+    #[test]
+    fn migration_backfills_model_names_of_known_providers() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE tb_setting_model (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    model_key TEXT NOT NULL UNIQUE,
+                    enabled INTEGER NOT NULL DEFAULT 0,
+                    api_url TEXT NOT NULL,
+                    api_key TEXT NOT NULL
+                );
+                INSERT INTO tb_setting_model (model_key, api_url, api_key) VALUES
+                    ('DeepSeek', 'https://api.deepseek.com/v1', 'k1'),
+                    ('OpenAI', 'https://api.openai.com/v1', 'k2'),
+                    ('qwen-max', 'https://dashscope.aliyuncs.com/v1', 'k3');",
+            )
+            .unwrap();
+
+        let manager = SettingModelDbManager::new();
+        manager.migrate_table_if_needed(&connection).unwrap();
+        // Running it again keeps names a user set since
+        connection
+            .execute(
+                "UPDATE tb_setting_model SET model_name = 'deepseek-reasoner' WHERE model_key = 'DeepSeek'",
+                [],
+            )
+            .unwrap();
+        manager.migrate_table_if_needed(&connection).unwrap();
+
+        let mut stmt = connection
+            .prepare("SELECT * FROM tb_setting_model ORDER BY id")
+            .unwrap();
+        let models: Vec<SettingModel> = stmt
+            .query_map([], SettingModelDbManager::extract_setting_model_row)
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let names: Vec<_> = models
+            .iter()
+            .map(|model| (model.model_key.as_str(), model.model_name.as_deref()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("DeepSeek", Some("deepseek-reasoner")),
+                ("OpenAI", Some("gpt-4o-mini")),
+                ("qwen-max", None),
+            ]
+        );
+        assert!(models.iter().all(|model| model.context_window.is_none()));
+    }
+}
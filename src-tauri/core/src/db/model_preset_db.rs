@@ -0,0 +1,334 @@
+use anyhow::{Context, Result};
+use log::{error, info, warn};
+use rusqlite::Row;
+
+use crate::model::{GenerationOptions, ModelPreset, ModelPresetParams};
+
+#[derive(Default)]
+pub struct ModelPresetDbManager;
+
+impl ModelPresetDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the model preset table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_model_preset (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                model_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                is_default INTEGER NOT NULL DEFAULT 0,
+                temperature REAL,
+                top_p REAL,
+                max_tokens INTEGER,
+                stop_json TEXT,
+                presence_penalty REAL,
+                frequency_penalty REAL,
+                seed INTEGER,
+                response_format_json TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now')),
+                UNIQUE (model_id, name)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_model_preset_model ON tb_model_preset (model_id);
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create model preset table")?;
+
+        self.migrate_table_if_needed(&db.connection)?;
+
+        info!("Model preset table initialized successfully");
+        Ok(())
+    }
+
+    /// Migrate tables created with only temperature and max_tokens to hold every option
+    fn migrate_table_if_needed(&self, connection: &rusqlite::Connection) -> Result<()> {
+        let columns = [
+            ("top_p", "REAL"),
+            ("stop_json", "TEXT"),
+            ("presence_penalty", "REAL"),
+            ("frequency_penalty", "REAL"),
+            ("seed", "INTEGER"),
+            ("response_format_json", "TEXT"),
+        ];
+        for (column, column_type) in columns {
+            let column_exists = connection
+                .query_row(
+                    "SELECT COUNT(*) FROM pragma_table_info('tb_model_preset') WHERE name = ?",
+                    [column],
+                    |row| row.get::<_, i32>(0),
+                )
+                .unwrap_or(0);
+
+            if column_exists == 0 {
+                info!("Migrating table: adding {} column", column);
+                if let Err(e) = connection.execute(
+                    &format!(
+                        "ALTER TABLE tb_model_preset ADD COLUMN {} {}",
+                        column, column_type
+                    ),
+                    [],
+                ) {
+                    warn!("Failed to add {} column: {}", column, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Insert a new preset
+    pub fn insert(&self, params: &ModelPresetParams) -> Result<i64> {
+        let options = &params.options;
+        let stop_json = options
+            .stop
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let response_format_json = options
+            .response_format
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_model_preset (model_id, name, temperature, top_p, max_tokens, stop_json,
+             presence_penalty, frequency_penalty, seed, response_format_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            (
+                params.model_id,
+                &params.name,
+                options.temperature,
+                options.top_p,
+                options.max_tokens,
+                &stop_json,
+                options.presence_penalty,
+                options.frequency_penalty,
+                options.seed,
+                &response_format_json,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted model preset with id: {id}");
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert model preset: {}", e);
+                Err(e).context("failed to insert model preset")
+            }
+        }
+    }
+
+    /// Update the name and options of a preset
+    pub fn update(&self, id: i64, name: &str, options: &GenerationOptions) -> Result<usize> {
+        let stop_json = options
+            .stop
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let response_format_json = options
+            .response_format
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_model_preset SET name = ?1, temperature = ?2, top_p = ?3, max_tokens = ?4, stop_json = ?5,
+             presence_penalty = ?6, frequency_penalty = ?7, seed = ?8, response_format_json = ?9,
+             updated_at = datetime('now') WHERE id = ?10",
+            (
+                name,
+                options.temperature,
+                options.top_p,
+                options.max_tokens,
+                &stop_json,
+                options.presence_penalty,
+                options.frequency_penalty,
+                options.seed,
+                &response_format_json,
+                id,
+            ),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Updated model preset with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to update model preset: {}", e);
+                Err(e).context("failed to update model preset")
+            }
+        }
+    }
+
+    /// Get the presets of a model
+    pub fn get_by_model_id(&self, model_id: i32) -> Result<Vec<ModelPreset>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_model_preset WHERE model_id = ? ORDER BY name ASC")
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map([model_id], Self::extract_model_preset_row)
+            .context("failed to map query")?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect model presets")
+    }
+
+    /// Get a preset by ID
+    pub fn get_by_id(&self, id: i64) -> Result<Option<ModelPreset>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_model_preset WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_model_preset_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(preset)) => Ok(Some(preset)),
+            Some(Err(e)) => Err(e).context("failed to get model preset"),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the default preset of a model
+    pub fn get_default(&self, model_id: i32) -> Result<Option<ModelPreset>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_model_preset WHERE model_id = ? AND is_default = 1 LIMIT 1")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([model_id], Self::extract_model_preset_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(preset)) => Ok(Some(preset)),
+            Some(Err(e)) => Err(e).context("failed to get default model preset"),
+            None => Ok(None),
+        }
+    }
+
+    /// Make a preset the default of its model
+    pub fn set_default(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        // One statement so a model never has two defaults
+        let result = db.connection.execute(
+            "UPDATE tb_model_preset SET is_default = CASE WHEN id = ?1 THEN 1 ELSE 0 END
+             WHERE model_id = (SELECT model_id FROM tb_model_preset WHERE id = ?1)",
+            [id],
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Set model preset {} as default", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to set default model preset: {}", e);
+                Err(e).context("failed to set default model preset")
+            }
+        }
+    }
+
+    /// Delete a preset by ID
+    pub fn delete_by_id(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db
+            .connection
+            .execute("DELETE FROM tb_model_preset WHERE id = ?", [id]);
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Deleted model preset with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete model preset: {}", e);
+                Err(e).context("failed to delete model preset")
+            }
+        }
+    }
+
+    /// Delete the presets of a deleted model
+    pub fn delete_by_model_id(&self, model_id: i32) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute("DELETE FROM tb_model_preset WHERE model_id = ?", [model_id])
+            .context("failed to delete model presets")
+    }
+
+    /// Extract model preset from database row
+    fn extract_model_preset_row(row: &Row) -> rusqlite::Result<ModelPreset> {
+        let stop_json: Option<String> = row.get("stop_json").ok();
+        let response_format_json: Option<String> = row.get("response_format_json").ok();
+
+        Ok(ModelPreset {
+            id: row.get("id").ok(),
+            model_id: row.get("model_id")?,
+            name: row.get("name")?,
+            is_default: row.get::<_, i32>("is_default").unwrap_or(0) != 0,
+            options: GenerationOptions {
+                temperature: row.get::<_, f64>("temperature").ok().map(|v| v as f32),
+                top_p: row.get::<_, f64>("top_p").ok().map(|v| v as f32),
+                max_tokens: row.get("max_tokens").ok(),
+                stop: stop_json.and_then(|json| serde_json::from_str(&json).ok()),
+                presence_penalty: row.get::<_, f64>("presence_penalty").ok().map(|v| v as f32),
+                frequency_penalty: row
+                    .get::<_, f64>("frequency_penalty")
+                    .ok()
+                    .map(|v| v as f32),
+                seed: row.get("seed").ok(),
+                response_format: response_format_json
+                    .and_then(|json| serde_json::from_str(&json).ok()),
+            },
+            created_at: row.get("created_at").ok(),
+            updated_at: row.get("updated_at").ok(),
+        })
+    }
+}
@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{PromptTemplate, PromptTemplateParams};
+
+#[derive(Default)]
+pub struct PromptTemplateDbManager;
+
+impl PromptTemplateDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the prompt template table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_prompt_template (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                content TEXT NOT NULL,
+                variables_json TEXT NOT NULL DEFAULT '[]',
+                tags_json TEXT NOT NULL DEFAULT '[]',
+                a2a_server_id INTEGER,
+                skill_id TEXT,
+                created_at TEXT DEFAULT (datetime('now')),
+                updated_at TEXT DEFAULT (datetime('now'))
+            );
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create prompt template table")?;
+
+        info!("Prompt template table initialized successfully");
+        Ok(())
+    }
+
+    /// Insert a new prompt template
+    pub fn insert(&self, params: &PromptTemplateParams) -> Result<i64> {
+        let variables_json = serde_json::to_string(&params.variables)?;
+        let tags_json = serde_json::to_string(&params.tags)?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_prompt_template (name, description, content, variables_json, tags_json, a2a_server_id, skill_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            (
+                &params.name,
+                &params.description,
+                &params.content,
+                &variables_json,
+                &tags_json,
+                params.a2a_server_id,
+                &params.skill_id,
+            ),
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                info!("Inserted prompt template with id: {id}");
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert prompt template: {}", e);
+                Err(e).context("failed to insert prompt template")
+            }
+        }
+    }
+
+    /// Overwrite a prompt template with its merged and validated state
+    pub fn update(&self, template: &PromptTemplate) -> Result<usize> {
+        let id = template.id.context("prompt template has no id")?;
+        let variables_json = serde_json::to_string(&template.variables)?;
+        let tags_json = serde_json::to_string(&template.tags)?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db.connection.execute(
+            "UPDATE tb_prompt_template SET name = ?1, description = ?2, content = ?3, variables_json = ?4,
+             tags_json = ?5, a2a_server_id = ?6, skill_id = ?7, updated_at = datetime('now') WHERE id = ?8",
+            (
+                &template.name,
+                &template.description,
+                &template.content,
+                &variables_json,
+                &tags_json,
+                template.a2a_server_id,
+                &template.skill_id,
+                id,
+            ),
+        );
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Updated prompt template with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to update prompt template: {}", e);
+                Err(e).context("failed to update prompt template")
+            }
+        }
+    }
+
+    /// Replace the tags of a prompt template
+    pub fn set_tags(&self, id: i64, tags: &[String]) -> Result<usize> {
+        let tags_json = serde_json::to_string(tags)?;
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        db.connection
+            .execute(
+                "UPDATE tb_prompt_template SET tags_json = ?1, updated_at = datetime('now') WHERE id = ?2",
+                (&tags_json, id),
+            )
+            .context("failed to tag prompt template")
+    }
+
+    /// Get all prompt templates, optionally only those carrying a tag
+    pub fn get_all(&self, tag: Option<&str>) -> Result<Vec<PromptTemplate>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let templates = match tag {
+            Some(tag) => {
+                let mut stmt = db
+                    .connection
+                    .prepare(
+                        "SELECT * FROM tb_prompt_template
+                         WHERE EXISTS (SELECT 1 FROM json_each(tb_prompt_template.tags_json) WHERE value = ?1)
+                         ORDER BY name ASC",
+                    )
+                    .context("failed to prepare query")?;
+                stmt.query_map([tag], Self::extract_prompt_template_row)
+                    .context("failed to map query")?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            }
+            None => {
+                let mut stmt = db
+                    .connection
+                    .prepare("SELECT * FROM tb_prompt_template ORDER BY name ASC")
+                    .context("failed to prepare query")?;
+                stmt.query_map([], Self::extract_prompt_template_row)
+                    .context("failed to map query")?
+                    .collect::<rusqlite::Result<Vec<_>>>()
+            }
+        };
+
+        templates.context("failed to collect prompt templates")
+    }
+
+    /// Get a prompt template by ID
+    pub fn get_by_id(&self, id: i64) -> Result<Option<PromptTemplate>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_prompt_template WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_prompt_template_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(Ok(template)) => Ok(Some(template)),
+            Some(Err(e)) => Err(e).context("failed to get prompt template"),
+            None => Ok(None),
+        }
+    }
+
+    /// Delete a prompt template by ID
+    pub fn delete_by_id(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let result = db
+            .connection
+            .execute("DELETE FROM tb_prompt_template WHERE id = ?", [id]);
+
+        match result {
+            Ok(rows_affected) => {
+                info!("Deleted prompt template with id: {}", id);
+                Ok(rows_affected)
+            }
+            Err(e) => {
+                error!("Failed to delete prompt template: {}", e);
+                Err(e).context("failed to delete prompt template")
+            }
+        }
+    }
+
+    /// Extract prompt template from database row
+    fn extract_prompt_template_row(row: &Row) -> rusqlite::Result<PromptTemplate> {
+        let variables_json: String = row.get("variables_json")?;
+        let tags_json: String = row.get("tags_json")?;
+
+        Ok(PromptTemplate {
+            id: row.get("id").ok(),
+            name: row.get("name")?,
+            description: row.get("description").ok(),
+            content: row.get("content")?,
+            variables: serde_json::from_str(&variables_json).unwrap_or_default(),
+            tags: serde_json::from_str(&tags_json).unwrap_or_default(),
+            a2a_server_id: row.get("a2a_server_id").ok(),
+            skill_id: row.get("skill_id").ok(),
+            created_at: row.get("created_at").ok(),
+            updated_at: row.get("updated_at").ok(),
+        })
+    }
+}
@@ -1,10 +1,9 @@
-use std::{ fs, sync::{ Arc, Mutex, atomic::AtomicBool } };
+use std::{ fs, path::Path, sync::{ Arc, Mutex, atomic::AtomicBool }, time::Duration };
 
 use anyhow::{ Context, Result };
 use lazy_static::lazy_static;
-use log::info;
+use log::{ info, warn };
 use rusqlite::Connection;
-use tauri::{ AppHandle, Manager };
 
 #[derive(Debug)]
 pub struct DbManager {
@@ -17,6 +16,10 @@ pub struct SqlState(pub AtomicBool);
 
 unsafe impl Sync for DbManager {}
 
+// The app, `app mcp` and a2a-cli may hold index.db open at the same time, a
+// writer waits this long for another one's lock instead of failing with SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
 lazy_static! {
     pub static ref DB: Arc<Mutex<DbManager>> = Arc::new(Mutex::new(DbManager::default()));
 }
@@ -32,26 +35,17 @@ impl Default for DbManager {
     }
 }
 
-pub fn init_db_conn(handle: &AppHandle) -> Result<()> {
+/// Open index.db at the given path as the global connection
+pub fn open_db_conn(db_path: &Path) -> Result<()> {
     let mut db_manager = DB.lock().map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
 
-    let mut db_dir = handle.path().app_data_dir().context("get app_data_dir failed")?;
-
-    db_dir.push("a2a-client-db");
-    if !db_dir.exists() {
-        if let Err(e) = fs::create_dir_all(&db_dir) {
-            eprintln!("Failed to create db directory: {:?}", e);
-            eprintln!("db_dir path: {:?}", db_dir);
+    if let Some(db_dir) = db_path.parent() {
+        if !db_dir.exists() {
+            fs::create_dir_all(db_dir).context("create db directory failed")?;
         }
     }
 
-    info!("Database directory: {:?}", db_dir);
-    info!(
-        "Database directory absolute path: {:?}",
-        db_dir.canonicalize().unwrap_or(db_dir.clone())
-    );
-
-    let db_path = db_dir.join("index.db");
+    let db_path = db_path.to_path_buf();
     if !db_path.exists() {
         fs::File::create_new(&db_path).context("create index.db failed")?;
     }
@@ -73,6 +67,15 @@ pub fn init_db_conn(handle: &AppHandle) -> Result<()> {
     }
 
     db_manager.connection = Connection::open(&db_path).context("open db connection failed")?;
+    db_manager.connection.busy_timeout(BUSY_TIMEOUT).context("set db busy timeout failed")?;
+    // WAL lets readers in the other processes keep going while one of them writes
+    if
+        let Err(e) = db_manager.connection.query_row("PRAGMA journal_mode = WAL", [], |row|
+            row.get::<_, String>(0)
+        )
+    {
+        warn!("Failed to enable WAL journal mode: {}", e);
+    }
 
     if
         let Ok(version) = db_manager.connection.query_row("SELECT sqlite_version()", [], |row|
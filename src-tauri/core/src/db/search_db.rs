@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use log::info;
+
+use crate::{
+    db::message_db::ConversationMessageDbManager,
+    model::{ConversationSearchHit, ConversationSearchPage, ConversationSearchParams},
+};
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 200;
+
+#[derive(Default)]
+pub struct ConversationSearchDbManager;
+
+impl ConversationSearchDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the FTS5 index over conversation messages and keep it in sync with triggers
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let index_exists = db
+            .connection
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'tb_conversation_message_fts'",
+                [],
+                |row| row.get::<_, i32>(0),
+            )
+            .unwrap_or(0)
+            > 0;
+
+        let sql = "
+            CREATE VIRTUAL TABLE IF NOT EXISTS tb_conversation_message_fts USING fts5(
+                content,
+                agent_name,
+                skill_name,
+                content = 'tb_conversation_message',
+                content_rowid = 'id',
+                tokenize = 'unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS trg_conversation_message_fts_insert AFTER INSERT ON tb_conversation_message BEGIN
+                INSERT INTO tb_conversation_message_fts (rowid, content, agent_name, skill_name)
+                VALUES (new.id, new.content, new.agent_name, new.skill_name);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS trg_conversation_message_fts_delete AFTER DELETE ON tb_conversation_message BEGIN
+                INSERT INTO tb_conversation_message_fts (tb_conversation_message_fts, rowid, content, agent_name, skill_name)
+                VALUES ('delete', old.id, old.content, old.agent_name, old.skill_name);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS trg_conversation_message_fts_update AFTER UPDATE ON tb_conversation_message BEGIN
+                INSERT INTO tb_conversation_message_fts (tb_conversation_message_fts, rowid, content, agent_name, skill_name)
+                VALUES ('delete', old.id, old.content, old.agent_name, old.skill_name);
+                INSERT INTO tb_conversation_message_fts (rowid, content, agent_name, skill_name)
+                VALUES (new.id, new.content, new.agent_name, new.skill_name);
+            END;
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create conversation search index")?;
+
+        // Messages stored before the index existed need to be indexed once
+        if !index_exists {
+            db.connection
+                .execute(
+                    "INSERT INTO tb_conversation_message_fts (tb_conversation_message_fts) VALUES ('rebuild')",
+                    [],
+                )
+                .context("failed to rebuild conversation search index")?;
+            info!("Conversation search index rebuilt");
+        }
+
+        info!("Conversation search index initialized successfully");
+        Ok(())
+    }
+
+    /// Full-text search over messages, agent names and skill names
+    pub fn search(&self, params: &ConversationSearchParams) -> Result<ConversationSearchPage> {
+        let page = params.page.unwrap_or(1).max(1);
+        let page_size = params
+            .page_size
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE);
+
+        let match_query = build_match_query(&params.query);
+        if match_query.is_empty() {
+            return Ok(ConversationSearchPage {
+                total: 0,
+                page,
+                page_size,
+                items: Vec::new(),
+            });
+        }
+
+        let mut conditions = vec!["tb_conversation_message_fts MATCH ?"];
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(match_query)];
+
+        if let Some(agent_name) = &params.agent_name {
+            conditions.push("m.agent_name = ?");
+            values.push(Box::new(agent_name.clone()));
+        }
+
+        if let Some(model_key) = &params.model_key {
+            conditions.push("m.model_key = ?");
+            values.push(Box::new(model_key.clone()));
+        }
+
+        if let Some(task_state) = &params.task_state {
+            conditions.push("m.task_state = ?");
+            values.push(Box::new(task_state.clone()));
+        }
+
+        if let Some(date_from) = &params.date_from {
+            conditions.push("m.created_at >= ?");
+            values.push(Box::new(date_from.clone()));
+        }
+
+        if let Some(date_to) = &params.date_to {
+            conditions.push("m.created_at <= ?");
+            // A bare date includes the whole day
+            let date_to = if date_to.len() == 10 {
+                format!("{} 23:59:59", date_to)
+            } else {
+                date_to.clone()
+            };
+            values.push(Box::new(date_to));
+        }
+
+        let from_clause = format!(
+            "FROM tb_conversation_message_fts JOIN tb_conversation_message m ON m.id = tb_conversation_message_fts.rowid WHERE {}",
+            conditions.join(" AND ")
+        );
+
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let total: i64 = db
+            .connection
+            .query_row(
+                &format!("SELECT COUNT(*) {}", from_clause),
+                rusqlite::params_from_iter(values.iter()),
+                |row| row.get(0),
+            )
+            .context("failed to count search results")?;
+
+        let highlight_start = params.highlight_start.as_deref().unwrap_or("<mark>");
+        let highlight_end = params.highlight_end.as_deref().unwrap_or("</mark>");
+
+        let sql = format!(
+            "SELECT m.*, snippet(tb_conversation_message_fts, -1, ?, ?, '…', 24) AS snippet, bm25(tb_conversation_message_fts) AS score
+             {}
+             ORDER BY score ASC, m.id DESC
+             LIMIT ? OFFSET ?",
+            from_clause
+        );
+
+        let mut query_values: Vec<Box<dyn rusqlite::ToSql>> = vec![
+            Box::new(highlight_start.to_string()),
+            Box::new(highlight_end.to_string()),
+        ];
+        query_values.extend(values);
+        query_values.push(Box::new(page_size));
+        query_values.push(Box::new((page - 1) * page_size));
+
+        let mut stmt = db
+            .connection
+            .prepare(&sql)
+            .context("failed to prepare search query")?;
+
+        let rows = stmt
+            .query_map(rusqlite::params_from_iter(query_values.iter()), |row| {
+                Ok(ConversationSearchHit {
+                    message: ConversationMessageDbManager::extract_message_row(row)?,
+                    snippet: row.get("snippet")?,
+                    // bm25 is lower-is-better, flip it so a higher rank means a better match
+                    rank: -row.get::<_, f64>("score")?,
+                })
+            })
+            .context("failed to map search query")?;
+
+        let items = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect search results")?;
+
+        Ok(ConversationSearchPage {
+            total,
+            page,
+            page_size,
+            items,
+        })
+    }
+}
+
+// Quote every term so user input never hits FTS5 query syntax; the last term
+// matches as a prefix to support search-as-you-type
+fn build_match_query(query: &str) -> String {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+
+    match terms.split_last() {
+        Some((last, rest)) => {
+            let mut terms = rest.to_vec();
+            terms.push(format!("{}*", last));
+            terms.join(" ")
+        }
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_query_quotes_terms_and_prefixes_the_last() {
+        assert_eq!(build_match_query("hello"), "\"hello\"*");
+        assert_eq!(build_match_query("  task  status "), "\"task\" \"status\"*");
+        assert_eq!(build_match_query("   "), "");
+    }
+
+    #[test]
+    fn match_query_escapes_fts_syntax() {
+        assert_eq!(build_match_query("say \"hi\""), "\"say\" \"\"\"hi\"\"\"*");
+        assert_eq!(
+            build_match_query("a OR b* NEAR(c)"),
+            "\"a\" \"OR\" \"b*\" \"NEAR(c)\"*"
+        );
+    }
+}
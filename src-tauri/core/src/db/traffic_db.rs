@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use log::{error, info};
+use rusqlite::Row;
+
+use crate::model::{TrafficEntry, TrafficEntrySummary, TrafficQueryParams};
+
+// Older entries are pruned on insert so a forgotten recorder cannot grow the DB forever
+const MAX_TRAFFIC_ENTRIES: i64 = 2000;
+const DEFAULT_LIST_LIMIT: u32 = 200;
+
+#[derive(Default)]
+pub struct TrafficDbManager;
+
+impl TrafficDbManager {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Initialize the traffic entry table
+    pub fn init(&self) -> Result<()> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = "
+            CREATE TABLE IF NOT EXISTS tb_traffic_entry (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                source TEXT NOT NULL,
+                method TEXT NOT NULL,
+                url TEXT NOT NULL,
+                request_headers_json TEXT NOT NULL,
+                request_body TEXT,
+                status INTEGER,
+                response_headers_json TEXT NOT NULL,
+                response_body TEXT,
+                sse_events_json TEXT NOT NULL,
+                error TEXT,
+                started_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                wait_ms INTEGER
+            );
+        ";
+
+        db.connection
+            .execute_batch(sql)
+            .context("failed to create traffic entry table")?;
+
+        info!("Traffic entry table initialized successfully");
+        Ok(())
+    }
+
+    /// Store a recorded exchange and prune the oldest entries beyond the cap
+    pub fn insert(&self, entry: &TrafficEntry) -> Result<i64> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let request_headers_json = serde_json::to_string(&entry.request_headers)
+            .context("failed to serialize request headers")?;
+        let response_headers_json = serde_json::to_string(&entry.response_headers)
+            .context("failed to serialize response headers")?;
+        let sse_events_json =
+            serde_json::to_string(&entry.sse_events).context("failed to serialize SSE events")?;
+
+        let result = db.connection.execute(
+            "INSERT INTO tb_traffic_entry (source, method, url, request_headers_json, request_body, status, response_headers_json, response_body, sse_events_json, error, started_at, duration_ms, wait_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            rusqlite::params![
+                &entry.source,
+                &entry.method,
+                &entry.url,
+                &request_headers_json,
+                &entry.request_body,
+                entry.status,
+                &response_headers_json,
+                &entry.response_body,
+                &sse_events_json,
+                &entry.error,
+                &entry.started_at,
+                entry.duration_ms as i64,
+                entry.wait_ms.map(|wait_ms| wait_ms as i64),
+            ],
+        );
+
+        match result {
+            Ok(_) => {
+                let id = db.connection.last_insert_rowid();
+                if let Err(e) = db.connection.execute(
+                    "DELETE FROM tb_traffic_entry WHERE id <= ?",
+                    [id - MAX_TRAFFIC_ENTRIES],
+                ) {
+                    error!("Failed to prune traffic entries: {}", e);
+                }
+                Ok(id)
+            }
+            Err(e) => {
+                error!("Failed to insert traffic entry: {}", e);
+                Err(e).context("failed to insert traffic entry")
+            }
+        }
+    }
+
+    /// Get entries matching the query, newest first, without headers and bodies
+    pub fn get_summaries(&self, params: &TrafficQueryParams) -> Result<Vec<TrafficEntrySummary>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let source = params.source.as_deref().filter(|source| !source.is_empty());
+        let url_pattern = params
+            .url
+            .as_deref()
+            .filter(|url| !url.is_empty())
+            .map(|url| format!("%{}%", url));
+        let limit = params.limit.unwrap_or(DEFAULT_LIST_LIMIT);
+
+        let mut stmt = db
+            .connection
+            .prepare(
+                "SELECT id, source, method, url, status, error, json_array_length(sse_events_json) AS sse_event_count, started_at, duration_ms
+                 FROM tb_traffic_entry
+                 WHERE (?1 IS NULL OR source = ?1) AND (?2 IS NULL OR url LIKE ?2)
+                 ORDER BY id DESC LIMIT ?3",
+            )
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map(rusqlite::params![source, url_pattern, limit], |row| {
+                Ok(TrafficEntrySummary {
+                    id: row.get("id")?,
+                    source: row.get("source")?,
+                    method: row.get("method")?,
+                    url: row.get("url")?,
+                    status: row.get("status")?,
+                    error: row.get("error")?,
+                    sse_event_count: row.get::<_, i64>("sse_event_count")? as usize,
+                    started_at: row.get("started_at")?,
+                    duration_ms: row.get::<_, i64>("duration_ms")? as u64,
+                })
+            })
+            .context("failed to map query")?;
+
+        let entries = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect traffic entries")?;
+
+        Ok(entries)
+    }
+
+    /// Get a full entry by id
+    pub fn get_by_id(&self, id: i64) -> Result<Option<TrafficEntry>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let mut stmt = db
+            .connection
+            .prepare("SELECT * FROM tb_traffic_entry WHERE id = ?")
+            .context("failed to prepare query")?;
+
+        let mut rows = stmt
+            .query_map([id], Self::extract_entry_row)
+            .context("failed to map query")?;
+
+        match rows.next() {
+            Some(entry) => Ok(Some(entry.context("failed to read traffic entry")?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get full entries by id, or every entry when no ids are given, oldest first
+    pub fn get_entries(&self, ids: &[i64]) -> Result<Vec<TrafficEntry>> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let sql = if ids.is_empty() {
+            "SELECT * FROM tb_traffic_entry ORDER BY id ASC".to_string()
+        } else {
+            let placeholders = vec!["?"; ids.len()].join(", ");
+            format!(
+                "SELECT * FROM tb_traffic_entry WHERE id IN ({}) ORDER BY id ASC",
+                placeholders
+            )
+        };
+        let mut stmt = db
+            .connection
+            .prepare(&sql)
+            .context("failed to prepare query")?;
+
+        let rows = stmt
+            .query_map(
+                rusqlite::params_from_iter(ids.iter()),
+                Self::extract_entry_row,
+            )
+            .context("failed to map query")?;
+
+        let entries = rows
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("failed to collect traffic entries")?;
+
+        Ok(entries)
+    }
+
+    /// Delete an entry
+    pub fn delete_by_id(&self, id: i64) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_traffic_entry WHERE id = ?", [id])
+            .context("failed to delete traffic entry")?;
+
+        info!("Deleted traffic entry with id: {}", id);
+        Ok(rows_affected)
+    }
+
+    /// Delete every entry
+    pub fn clear(&self) -> Result<usize> {
+        let db = crate::db::rusqlite::DB
+            .lock()
+            .map_err(|e| anyhow::anyhow!("failed to acquire DB lock: {e}"))?;
+
+        let rows_affected = db
+            .connection
+            .execute("DELETE FROM tb_traffic_entry", [])
+            .context("failed to clear traffic entries")?;
+
+        info!("Cleared {} traffic entries", rows_affected);
+        Ok(rows_affected)
+    }
+
+    /// Extract traffic entry from database row
+    fn extract_entry_row(row: &Row) -> rusqlite::Result<TrafficEntry> {
+        fn from_json<T: serde::de::DeserializeOwned>(json: &str) -> rusqlite::Result<T> {
+            serde_json::from_str(json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        }
+
+        Ok(TrafficEntry {
+            id: row.get("id").ok(),
+            source: row.get("source")?,
+            method: row.get("method")?,
+            url: row.get("url")?,
+            request_headers: from_json(&row.get::<_, String>("request_headers_json")?)?,
+            request_body: row.get("request_body")?,
+            status: row.get("status")?,
+            response_headers: from_json(&row.get::<_, String>("response_headers_json")?)?,
+            response_body: row.get("response_body")?,
+            sse_events: from_json(&row.get::<_, String>("sse_events_json")?)?,
+            error: row.get("error")?,
+            started_at: row.get("started_at")?,
+            duration_ms: row.get::<_, i64>("duration_ms")? as u64,
+            wait_ms: row
+                .get::<_, Option<i64>>("wait_ms")?
+                .map(|wait_ms| wait_ms as u64),
+        })
+    }
+}
@@ -0,0 +1,398 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::mcp::client::McpError;
+
+/// Broad class of a failure, what the UI switches on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Validation,
+    Database,
+    Network,
+    Auth,
+    Protocol,
+    Agent,
+    Llm,
+    Io,
+    Internal,
+}
+
+/// Every failure a command can report, each with a stable numeric code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    // Anything not classified below, keeps the code every failure used to have
+    Internal,
+    InvalidParams,
+    NotFound,
+    Conflict,
+    DbLocked,
+    Database,
+    AgentUnreachable,
+    Timeout,
+    Unauthorized,
+    RateLimited,
+    UpstreamUnavailable,
+    HttpStatus,
+    CardParse,
+    Serialization,
+    InvalidResponse,
+    JsonRpc,
+    TaskNotFound,
+    TaskNotCancelable,
+    UnsupportedOperation,
+    Llm,
+    Io,
+}
+
+impl ErrorKind {
+    pub fn code(&self) -> i32 {
+        match self {
+            Self::Internal => 1,
+            Self::InvalidParams => 1001,
+            Self::NotFound => 1002,
+            Self::Conflict => 1003,
+            Self::DbLocked => 2001,
+            Self::Database => 2002,
+            Self::AgentUnreachable => 3001,
+            Self::Timeout => 3002,
+            Self::Unauthorized => 3003,
+            Self::RateLimited => 3004,
+            Self::UpstreamUnavailable => 3005,
+            Self::HttpStatus => 3006,
+            Self::CardParse => 4001,
+            Self::Serialization => 4002,
+            Self::InvalidResponse => 4003,
+            Self::JsonRpc => 5000,
+            Self::TaskNotFound => 5001,
+            Self::TaskNotCancelable => 5002,
+            Self::UnsupportedOperation => 5003,
+            Self::Llm => 6001,
+            Self::Io => 7001,
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::InvalidParams | Self::NotFound | Self::Conflict => ErrorCategory::Validation,
+            Self::DbLocked | Self::Database => ErrorCategory::Database,
+            Self::AgentUnreachable
+            | Self::Timeout
+            | Self::RateLimited
+            | Self::UpstreamUnavailable
+            | Self::HttpStatus => ErrorCategory::Network,
+            Self::Unauthorized => ErrorCategory::Auth,
+            Self::CardParse | Self::Serialization | Self::InvalidResponse => {
+                ErrorCategory::Protocol
+            }
+            Self::JsonRpc
+            | Self::TaskNotFound
+            | Self::TaskNotCancelable
+            | Self::UnsupportedOperation => ErrorCategory::Agent,
+            Self::Llm => ErrorCategory::Llm,
+            Self::Io => ErrorCategory::Io,
+            Self::Internal => ErrorCategory::Internal,
+        }
+    }
+
+    /// Whether sending the same request again later can succeed
+    pub fn retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::DbLocked
+                | Self::AgentUnreachable
+                | Self::Timeout
+                | Self::RateLimited
+                | Self::UpstreamUnavailable
+        )
+    }
+}
+
+/// Typed error of the backend, carried inside `anyhow::Error` through the db and
+/// agent layers and turned into an `InvokeResponse` at the command boundary
+#[derive(Debug, Clone)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub details: Option<Value>,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Internal, message)
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidParams, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::NotFound, message)
+    }
+
+    /// An agent card that could not be parsed
+    pub fn card_parse(error: serde_json::Error) -> Self {
+        Self::new(
+            ErrorKind::CardParse,
+            format!("Failed to parse agent card: {}", error),
+        )
+        .with_details(json!({ "line": error.line(), "column": error.column() }))
+    }
+
+    /// A non-success HTTP status, 401 and 403 are credential problems
+    pub fn from_http_status(status: reqwest::StatusCode, body: &str) -> Self {
+        let kind = match status.as_u16() {
+            401 | 403 => ErrorKind::Unauthorized,
+            404 => ErrorKind::NotFound,
+            408 => ErrorKind::Timeout,
+            429 => ErrorKind::RateLimited,
+            500..=599 => ErrorKind::UpstreamUnavailable,
+            _ => ErrorKind::HttpStatus,
+        };
+        Self::new(
+            kind,
+            format!("Request failed with status {}: {}", status, body),
+        )
+        .with_details(json!({ "status": status.as_u16() }))
+    }
+
+    /// A JSON-RPC error object, A2A task errors get their own kinds
+    pub fn from_json_rpc(code: i64, message: &str, data: Option<&Value>) -> Self {
+        let kind = match code {
+            -32001 => ErrorKind::TaskNotFound,
+            -32002 => ErrorKind::TaskNotCancelable,
+            -32601 | -32004 => ErrorKind::UnsupportedOperation,
+            -32700 | -32600 | -32602 => ErrorKind::InvalidParams,
+            _ => ErrorKind::JsonRpc,
+        };
+        Self::new(kind, format!("JSON-RPC error {}: {}", code, message))
+            .with_details(json!({ "jsonRpcCode": code, "data": data }))
+    }
+
+    /// The error member of a JSON-RPC response, missing fields are an internal error
+    pub fn from_json_rpc_error(error: &Value) -> Self {
+        Self::from_json_rpc(
+            error.get("code").and_then(|c| c.as_i64()).unwrap_or(-32603),
+            error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("Unknown error"),
+            error.get("data"),
+        )
+    }
+
+    /// The error of a JSON-RPC response body, None when the call succeeded
+    pub fn from_json_rpc_body(body: &str) -> Option<Self> {
+        let response: Value = serde_json::from_str(body).ok()?;
+        response.get("error").map(Self::from_json_rpc_error)
+    }
+
+    /// A failure reported by the LLM client, which only exposes it as text
+    pub fn llm(error: impl fmt::Display) -> Self {
+        let message = error.to_string();
+        let lower = message.to_lowercase();
+        let kind = if lower.contains("401")
+            || lower.contains("unauthorized")
+            || lower.contains("api key")
+            || lower.contains("authentication")
+        {
+            ErrorKind::Unauthorized
+        } else if lower.contains("429") || lower.contains("rate limit") {
+            ErrorKind::RateLimited
+        } else if lower.contains("timed out") || lower.contains("timeout") {
+            ErrorKind::Timeout
+        } else if lower.contains("context length") || lower.contains("maximum context") {
+            ErrorKind::InvalidParams
+        } else {
+            ErrorKind::Llm
+        };
+        Self::new(kind, format!("LLM request failed: {}", message))
+    }
+
+    pub fn code(&self) -> i32 {
+        self.kind.code()
+    }
+
+    /// Whether the request failed before it reached the server, so it was never handled
+    pub fn is_connect_failure(&self) -> bool {
+        self.kind == ErrorKind::AgentUnreachable
+            && self
+                .details
+                .as_ref()
+                .and_then(|details| details.get("connect"))
+                .and_then(|connect| connect.as_bool())
+                .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        let message = match error.status() {
+            Some(status) => format!("Request failed with status {}", status),
+            None => format!("Request failed: {}", error),
+        };
+        Self::new(reqwest_kind(&error), message).with_details(reqwest_details(&error))
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::new(rusqlite_kind(&error), format!("Database error: {}", error))
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::new(ErrorKind::Serialization, format!("JSON error: {}", error))
+            .with_details(json!({ "line": error.line(), "column": error.column() }))
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(error: std::io::Error) -> Self {
+        Self::new(ErrorKind::Io, format!("IO error: {}", error))
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    /// Classify by the first typed error in the chain, keeping the whole chain as the message
+    fn from(error: anyhow::Error) -> Self {
+        let message = format!("{:#}", error);
+
+        for cause in error.chain() {
+            if let Some(e) = cause.downcast_ref::<AppError>() {
+                return Self {
+                    message,
+                    ..e.clone()
+                };
+            }
+            if let Some(e) = cause.downcast_ref::<McpError>() {
+                return Self {
+                    message,
+                    ..Self::from_json_rpc(e.code, &e.message, None)
+                };
+            }
+            if let Some(e) = cause.downcast_ref::<rusqlite::Error>() {
+                return Self::new(rusqlite_kind(e), message);
+            }
+            if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+                return Self::new(reqwest_kind(e), message).with_details(reqwest_details(e));
+            }
+            if let Some(e) = cause.downcast_ref::<serde_json::Error>() {
+                return Self::new(ErrorKind::Serialization, message)
+                    .with_details(json!({ "line": e.line(), "column": e.column() }));
+            }
+            if cause.downcast_ref::<std::io::Error>().is_some() {
+                return Self::new(ErrorKind::Io, message);
+            }
+        }
+
+        Self::internal(message)
+    }
+}
+
+fn rusqlite_kind(error: &rusqlite::Error) -> ErrorKind {
+    match error {
+        rusqlite::Error::SqliteFailure(failure, _) => match failure.code {
+            rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked => {
+                ErrorKind::DbLocked
+            }
+            rusqlite::ErrorCode::ConstraintViolation => ErrorKind::Conflict,
+            _ => ErrorKind::Database,
+        },
+        rusqlite::Error::QueryReturnedNoRows => ErrorKind::NotFound,
+        _ => ErrorKind::Database,
+    }
+}
+
+fn reqwest_kind(error: &reqwest::Error) -> ErrorKind {
+    match error.status() {
+        Some(status) => AppError::from_http_status(status, "").kind,
+        None if error.is_timeout() => ErrorKind::Timeout,
+        None if error.is_decode() => ErrorKind::InvalidResponse,
+        None if error.is_builder() => ErrorKind::InvalidParams,
+        None => ErrorKind::AgentUnreachable,
+    }
+}
+
+fn reqwest_details(error: &reqwest::Error) -> Value {
+    json!({
+        "url": error.url().map(|url| url.as_str()),
+        "status": error.status().map(|status| status.as_u16()),
+        "connect": error.is_connect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_rpc_body_errors_get_their_kind() {
+        let error = AppError::from_json_rpc_body(
+            r#"{"jsonrpc": "2.0", "id": "1", "error": {"code": -32001, "message": "Task not found", "data": {"id": "t1"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(error.kind, ErrorKind::TaskNotFound);
+        assert_eq!(error.message, "JSON-RPC error -32001: Task not found");
+        assert_eq!(
+            error.details,
+            Some(json!({ "jsonRpcCode": -32001, "data": { "id": "t1" } }))
+        );
+
+        let error =
+            AppError::from_json_rpc_body(r#"{"error": {"code": -32000, "message": "busy"}}"#)
+                .unwrap();
+        assert_eq!(error.kind, ErrorKind::JsonRpc);
+        assert_eq!(error.kind.category(), ErrorCategory::Agent);
+    }
+
+    #[test]
+    fn incomplete_json_rpc_errors_get_defaults() {
+        let error = AppError::from_json_rpc_body(r#"{"error": {}}"#).unwrap();
+        assert_eq!(error.kind, ErrorKind::JsonRpc);
+        assert_eq!(error.message, "JSON-RPC error -32603: Unknown error");
+    }
+
+    #[tokio::test]
+    async fn refused_connections_are_connect_failures() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = AppError::from(reqwest::get(&url).await.unwrap_err());
+        assert_eq!(error.kind, ErrorKind::AgentUnreachable);
+        assert!(error.is_connect_failure());
+        assert!(!AppError::new(ErrorKind::AgentUnreachable, "reset").is_connect_failure());
+    }
+
+    #[test]
+    fn successful_or_invalid_bodies_have_no_error() {
+        assert!(AppError::from_json_rpc_body(r#"{"jsonrpc": "2.0", "result": {}}"#).is_none());
+        assert!(AppError::from_json_rpc_body("not json").is_none());
+    }
+}
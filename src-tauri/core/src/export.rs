@@ -0,0 +1,674 @@
+//! Conversation export: the transcript, tasks and artifacts of a conversation
+//! as canonical JSON, markdown or a self-contained HTML page
+
+use std::{collections::BTreeSet, fmt::Write as _, fs};
+
+use anyhow::{Context, Result};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde::Serialize;
+
+use crate::{
+    db::{
+        a2a_db::SettingA2AServerDbManager, artifact_db::ArtifactDbManager, artifact_store,
+        conversation_db::A2AConversationContextDbManager, message_db::ConversationMessageDbManager,
+    },
+    error::AppError,
+    model::{
+        A2AConversationContext, ArtifactQueryParams, ConversationMessage, ExportConversationParams,
+        StoredArtifact,
+    },
+    redact::{header_secret_values, redact_header_json, redact_secrets},
+};
+
+// Text artifacts above this size are referenced by hash instead of inlined
+const MAX_INLINE_TEXT_BYTES: i64 = 1024 * 1024;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConversationExport {
+    format_version: u32,
+    conversation_id: String,
+    exported_at: String,
+    agents: Vec<ExportedAgent>,
+    messages: Vec<ConversationMessage>,
+    tasks: Vec<A2AConversationContext>,
+    artifacts: Vec<ExportedArtifact>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedAgent {
+    id: Option<i32>,
+    name: String,
+    agent_card_url: String,
+    custom_headers: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedArtifact {
+    #[serde(flatten)]
+    artifact: StoredArtifact,
+    text: Option<String>,
+}
+
+/// Render a conversation as markdown, JSON or HTML with its secrets redacted.
+/// Written to the target path when one is given, which is then returned
+pub fn export_conversation(params: &ExportConversationParams) -> Result<String> {
+    let (export, secrets) = build_export(&params.conversation_id)?;
+    let rendered = render(&export, &params.format, &secrets)?;
+
+    let Some(target_path) = &params.target_path else {
+        return Ok(rendered);
+    };
+    fs::write(target_path, rendered).context("Failed to write export")?;
+    log::info!(
+        "Exported conversation {} to {}",
+        params.conversation_id,
+        target_path
+    );
+    Ok(target_path.clone())
+}
+
+// The export in the given format, with the secrets scrubbed as they appear
+// there: as is, escaped inside a JSON string or escaped for HTML
+fn render(export: &ConversationExport, format: &str, secrets: &[String]) -> Result<String> {
+    let rendered = match format {
+        "markdown" | "md" => render_markdown(export),
+        "json" => render_json(export)?,
+        "html" => render_html(export),
+        other => {
+            return Err(
+                AppError::invalid_params(format!("Unsupported export format: {}", other)).into(),
+            );
+        }
+    };
+
+    let mut escaped = Vec::with_capacity(secrets.len() * 3);
+    for secret in secrets {
+        escaped.push(secret.clone());
+        escaped.push(escape_html(secret));
+        if let Ok(json) = serde_json::to_string(secret) {
+            escaped.push(json[1..json.len() - 1].to_string());
+        }
+    }
+    Ok(redact_secrets(&rendered, &escaped))
+}
+
+// Gather everything stored for a conversation, plus the secrets to scrub from the output
+fn build_export(conversation_id: &str) -> Result<(ConversationExport, Vec<String>)> {
+    let messages = ConversationMessageDbManager::new().get_by_conversation(conversation_id)?;
+    if messages.is_empty() {
+        return Err(AppError::not_found("Conversation has no messages").into());
+    }
+
+    let tasks = A2AConversationContextDbManager::new().get_by_conversation(conversation_id)?;
+    let artifacts = ArtifactDbManager::new().list(&ArtifactQueryParams {
+        conversation_id: Some(conversation_id.to_string()),
+        ..Default::default()
+    })?;
+
+    let server_ids: BTreeSet<i32> = messages
+        .iter()
+        .filter_map(|m| m.a2a_server_id)
+        .chain(tasks.iter().map(|t| t.a2a_server_id))
+        .collect();
+
+    let server_manager = SettingA2AServerDbManager::new();
+    let mut agents = Vec::new();
+    let mut secrets = Vec::new();
+    for server_id in server_ids {
+        let Some(server) = server_manager.get_by_id(server_id)? else {
+            continue;
+        };
+        let custom_headers = server.custom_header_json.as_deref().map(|headers| {
+            secrets.extend(header_secret_values(headers));
+            redact_header_json(headers)
+        });
+        agents.push(ExportedAgent {
+            id: server.id,
+            name: server.name,
+            agent_card_url: server.agent_card_url,
+            custom_headers,
+        });
+    }
+
+    let artifacts = artifacts
+        .into_iter()
+        .map(|artifact| {
+            let text = read_inline_text(&artifact);
+            ExportedArtifact { artifact, text }
+        })
+        .collect();
+
+    let export = ConversationExport {
+        format_version: 1,
+        conversation_id: conversation_id.to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        agents,
+        messages,
+        tasks,
+        artifacts,
+    };
+
+    Ok((export, secrets))
+}
+
+fn is_text_mime(mime_type: Option<&str>) -> bool {
+    let mime_type = mime_type.unwrap_or_default();
+    mime_type.starts_with("text/") || mime_type.ends_with("json") || mime_type.ends_with("xml")
+}
+
+fn read_inline_text(artifact: &StoredArtifact) -> Option<String> {
+    if !is_text_mime(artifact.mime_type.as_deref()) || artifact.size_bytes > MAX_INLINE_TEXT_BYTES {
+        return None;
+    }
+    let path = artifact_store::blob_path(&artifact.content_hash).ok()?;
+    fs::read(path)
+        .ok()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// Canonical JSON: sorted keys, pretty printed, stable across exports of the same data
+fn render_json(export: &ConversationExport) -> Result<String> {
+    let value = serde_json::to_value(export).context("failed to serialize export")?;
+    serde_json::to_string_pretty(&sort_keys(value)).context("failed to render export JSON")
+}
+
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, sort_keys(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_keys).collect())
+        }
+        other => other,
+    }
+}
+
+fn message_heading(message: &ConversationMessage) -> String {
+    let agent = message.agent_name.as_deref().unwrap_or("agent");
+    let skill = message
+        .skill_name
+        .as_deref()
+        .or(message.skill_id.as_deref())
+        .unwrap_or("-");
+
+    match message.kind.as_str() {
+        "question" => "🧑 User".to_string(),
+        "thought" => "💭 Thought".to_string(),
+        "action" => "🛠️ Action".to_string(),
+        "observation" => "👀 Observation".to_string(),
+        "final_answer" => "✅ Final Answer".to_string(),
+        "error" => "❌ Error".to_string(),
+        "agent_request" => format!("➡️ Agent call: {} / {}", agent, skill),
+        "agent_response" => format!(
+            "⬅️ Agent response: {} ({})",
+            agent,
+            message.task_state.as_deref().unwrap_or("completed")
+        ),
+        _ if message.role == "user" => "🧑 User".to_string(),
+        _ => "🤖 Assistant".to_string(),
+    }
+}
+
+fn render_markdown(export: &ConversationExport) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# Conversation `{}`\n", export.conversation_id);
+    let _ = writeln!(out, "- Exported at: {}", export.exported_at);
+    let _ = writeln!(out, "- Messages: {}", export.messages.len());
+    let _ = writeln!(out, "- Artifacts: {}\n", export.artifacts.len());
+
+    if !export.agents.is_empty() {
+        let _ = writeln!(out, "## Agents\n");
+        let _ = writeln!(out, "| Name | Agent card | Custom headers |");
+        let _ = writeln!(out, "| --- | --- | --- |");
+        for agent in &export.agents {
+            let headers = agent
+                .custom_headers
+                .as_ref()
+                .map(|h| format!("`{}`", h))
+                .unwrap_or_else(|| "-".to_string());
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                agent.name, agent.agent_card_url, headers
+            );
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(out, "## Transcript\n");
+    for message in &export.messages {
+        let _ = writeln!(out, "### {}\n", message_heading(message));
+        if let Some(created_at) = &message.created_at {
+            let _ = writeln!(out, "_{}_\n", created_at);
+        }
+        if let Some(task_id) = &message.task_id {
+            let _ = writeln!(out, "Task: `{}`\n", task_id);
+        }
+        match message.kind.as_str() {
+            "action" => {
+                let _ = writeln!(out, "```\n{}\n```\n", message.content);
+            }
+            "thought" => {
+                for line in message.content.lines() {
+                    let _ = writeln!(out, "> {}", line);
+                }
+                out.push('\n');
+            }
+            _ => {
+                let _ = writeln!(out, "{}\n", message.content);
+            }
+        }
+    }
+
+    if !export.tasks.is_empty() {
+        let _ = writeln!(out, "## Tasks\n");
+        let _ = writeln!(out, "| Agent | Context | Task | State | Updated |");
+        let _ = writeln!(out, "| --- | --- | --- | --- | --- |");
+        for task in &export.tasks {
+            let agent = agent_name(export, task.a2a_server_id);
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                agent,
+                task.context_id.as_deref().unwrap_or("-"),
+                task.task_id.as_deref().unwrap_or("-"),
+                task.task_state.as_deref().unwrap_or("-"),
+                task.updated_at.as_deref().unwrap_or("-")
+            );
+        }
+        out.push('\n');
+    }
+
+    if !export.artifacts.is_empty() {
+        let _ = writeln!(out, "## Artifacts\n");
+        for exported in &export.artifacts {
+            let artifact = &exported.artifact;
+            let _ = writeln!(out, "### {}\n", artifact_title(artifact));
+            let _ = writeln!(
+                out,
+                "- Task: `{}`\n- Type: {}\n- Size: {} bytes\n- SHA-256: `{}`\n",
+                artifact.task_id,
+                artifact.mime_type.as_deref().unwrap_or("unknown"),
+                artifact.size_bytes,
+                artifact.content_hash
+            );
+            if let Some(text) = &exported.text {
+                let _ = writeln!(out, "```\n{}\n```\n", text);
+            }
+        }
+    }
+
+    out
+}
+
+fn render_html(export: &ConversationExport) -> String {
+    let mut out = String::new();
+
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        out,
+        "<title>Conversation {}</title>",
+        escape_html(&export.conversation_id)
+    );
+    out.push_str(
+        "<style>
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; max-width: 960px; margin: 2rem auto; padding: 0 1rem; color: #1f2328; }
+h1 { font-size: 1.5rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1.5rem; font-size: 0.9rem; }
+th, td { border: 1px solid #d0d7de; padding: 6px 10px; text-align: left; vertical-align: top; }
+.message { border: 1px solid #d0d7de; border-radius: 8px; padding: 0.75rem 1rem; margin-bottom: 1rem; }
+.message h3 { margin: 0 0 0.5rem; font-size: 1rem; }
+.meta { color: #656d76; font-size: 0.8rem; margin-bottom: 0.5rem; }
+.kind-thought { background: #f6f8fa; }
+.kind-error { border-color: #cf222e; }
+.kind-final_answer { border-color: #1a7f37; }
+pre { white-space: pre-wrap; word-break: break-word; margin: 0; font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.85rem; }
+img { max-width: 100%; }
+</style>
+</head>
+<body>
+",
+    );
+
+    let _ = writeln!(
+        out,
+        "<h1>Conversation <code>{}</code></h1>",
+        escape_html(&export.conversation_id)
+    );
+    let _ = writeln!(
+        out,
+        "<p class=\"meta\">Exported at {} · {} messages · {} artifacts</p>",
+        escape_html(&export.exported_at),
+        export.messages.len(),
+        export.artifacts.len()
+    );
+
+    if !export.agents.is_empty() {
+        out.push_str("<h2>Agents</h2>\n<table>\n<tr><th>Name</th><th>Agent card</th><th>Custom headers</th></tr>\n");
+        for agent in &export.agents {
+            let headers = agent
+                .custom_headers
+                .as_ref()
+                .map(|h| h.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td><code>{}</code></td></tr>",
+                escape_html(&agent.name),
+                escape_html(&agent.agent_card_url),
+                escape_html(&headers)
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    out.push_str("<h2>Transcript</h2>\n");
+    for message in &export.messages {
+        let _ = writeln!(
+            out,
+            "<div class=\"message kind-{}\">\n<h3>{}</h3>",
+            escape_html(&message.kind),
+            escape_html(&message_heading(message))
+        );
+        let mut meta = Vec::new();
+        if let Some(created_at) = &message.created_at {
+            meta.push(escape_html(created_at));
+        }
+        if let Some(task_id) = &message.task_id {
+            meta.push(format!("task {}", escape_html(task_id)));
+        }
+        if !meta.is_empty() {
+            let _ = writeln!(out, "<div class=\"meta\">{}</div>", meta.join(" · "));
+        }
+        let _ = writeln!(out, "<pre>{}</pre>\n</div>", escape_html(&message.content));
+    }
+
+    if !export.tasks.is_empty() {
+        out.push_str("<h2>Tasks</h2>\n<table>\n<tr><th>Agent</th><th>Context</th><th>Task</th><th>State</th><th>Updated</th></tr>\n");
+        for task in &export.tasks {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&agent_name(export, task.a2a_server_id)),
+                escape_html(task.context_id.as_deref().unwrap_or("-")),
+                escape_html(task.task_id.as_deref().unwrap_or("-")),
+                escape_html(task.task_state.as_deref().unwrap_or("-")),
+                escape_html(task.updated_at.as_deref().unwrap_or("-"))
+            );
+        }
+        out.push_str("</table>\n");
+    }
+
+    if !export.artifacts.is_empty() {
+        out.push_str("<h2>Artifacts</h2>\n");
+        for exported in &export.artifacts {
+            let artifact = &exported.artifact;
+            let _ = writeln!(
+                out,
+                "<div class=\"message\">\n<h3>{}</h3>\n<div class=\"meta\">{} · {} bytes · sha256 {}</div>",
+                escape_html(&artifact_title(artifact)),
+                escape_html(artifact.mime_type.as_deref().unwrap_or("unknown")),
+                artifact.size_bytes,
+                escape_html(&artifact.content_hash)
+            );
+            out.push_str(&render_html_artifact_body(exported));
+            out.push_str("</div>\n");
+        }
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// Inline text, embed images and offer everything else as an embedded download
+fn render_html_artifact_body(exported: &ExportedArtifact) -> String {
+    if let Some(text) = &exported.text {
+        return format!("<pre>{}</pre>\n", escape_html(text));
+    }
+
+    let artifact = &exported.artifact;
+    let mime_type = embeddable_mime_type(artifact.mime_type.as_deref());
+    let bytes = match artifact_store::blob_path(&artifact.content_hash)
+        .and_then(|path| fs::read(path).context("failed to read artifact blob"))
+    {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            log::warn!("Failed to embed artifact {}: {}", artifact.content_hash, e);
+            return "<p class=\"meta\">Artifact content is not available.</p>\n".to_string();
+        }
+    };
+    let data_uri = format!("data:{};base64,{}", mime_type, STANDARD.encode(&bytes));
+
+    if mime_type.starts_with("image/") {
+        format!(
+            "<img src=\"{}\" alt=\"{}\">\n",
+            data_uri,
+            escape_html(&artifact_title(artifact))
+        )
+    } else {
+        let file_name = artifact
+            .file_name
+            .clone()
+            .unwrap_or_else(|| artifact.artifact_id.clone());
+        format!(
+            "<a href=\"{}\" download=\"{}\">Download {}</a>\n",
+            data_uri,
+            escape_html(&file_name),
+            escape_html(&file_name)
+        )
+    }
+}
+
+// The mime type comes from the agent and ends up inside an attribute, anything
+// but a bare `type/subtype` falls back to a generic one
+fn embeddable_mime_type(mime_type: Option<&str>) -> &str {
+    let is_token = |part: &str| {
+        !part.is_empty()
+            && part
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&^_.+-".contains(c))
+    };
+    mime_type
+        .filter(|mime_type| {
+            mime_type
+                .split_once('/')
+                .is_some_and(|(kind, subtype)| is_token(kind) && is_token(subtype))
+        })
+        .unwrap_or("application/octet-stream")
+}
+
+fn artifact_title(artifact: &StoredArtifact) -> String {
+    let name = artifact
+        .name
+        .as_deref()
+        .or(artifact.file_name.as_deref())
+        .unwrap_or(&artifact.artifact_id);
+    format!("{} (part {})", name, artifact.part_index)
+}
+
+fn agent_name(export: &ConversationExport, a2a_server_id: i32) -> String {
+    export
+        .agents
+        .iter()
+        .find(|agent| agent.id == Some(a2a_server_id))
+        .map(|agent| agent.name.clone())
+        .unwrap_or_else(|| format!("#{}", a2a_server_id))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    const HEADERS: &str = r#"{"Authorization": "Bearer tok3n&<pass>", "X-Api-Key": "k3y\"quoted\\value", "Accept": "application/json"}"#;
+
+    fn message(kind: &str, content: &str) -> ConversationMessage {
+        serde_json::from_value(json!({
+            "messageId": "m1", "conversationId": "c1", "role": "assistant",
+            "kind": kind, "content": content, "a2aServerId": 1, "agentName": "Echo"
+        }))
+        .unwrap()
+    }
+
+    fn artifact(mime_type: Option<&str>, content_hash: &str) -> StoredArtifact {
+        serde_json::from_value(json!({
+            "artifactId": "a1", "taskId": "t1", "partIndex": 0, "partKind": "file",
+            "mimeType": mime_type, "fileName": "report.bin",
+            "contentHash": content_hash, "sizeBytes": 4
+        }))
+        .unwrap()
+    }
+
+    fn export(agent_name: &str, messages: Vec<ConversationMessage>) -> ConversationExport {
+        ConversationExport {
+            format_version: 1,
+            conversation_id: "c1".to_string(),
+            exported_at: "2026-01-01T00:00:00Z".to_string(),
+            agents: vec![ExportedAgent {
+                id: Some(1),
+                name: agent_name.to_string(),
+                agent_card_url: "https://agent.example.com/.well-known/agent-card.json".to_string(),
+                custom_headers: Some(redact_header_json(HEADERS)),
+            }],
+            messages,
+            tasks: Vec::new(),
+            artifacts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn header_secrets_and_bearer_tokens_are_redacted_in_every_format() {
+        let export = export(
+            "Echo",
+            vec![
+                message(
+                    "action",
+                    r#"curl -H "X-Api-Key: k3y"quoted\value" -H "Authorization: Bearer tok3n&<pass>""#,
+                ),
+                message("observation", "Sent with Authorization: Bearer l00se-t0ken"),
+            ],
+        );
+        let secrets = header_secret_values(HEADERS);
+
+        for format in ["json", "markdown", "html"] {
+            let rendered = render(&export, format, &secrets).unwrap();
+            for leaked in ["tok3n", "k3y", "quoted", "l00se-t0ken"] {
+                assert!(
+                    !rendered.contains(leaked),
+                    "{} leaked in the {} export:\n{}",
+                    leaked,
+                    format,
+                    rendered
+                );
+            }
+            assert!(rendered.contains(crate::redact::REDACTED));
+            // Headers that carry no credential are kept
+            assert!(rendered.contains("application/json"));
+        }
+    }
+
+    #[test]
+    fn html_content_is_escaped() {
+        let export = export(
+            "<b>Echo</b>",
+            vec![message("answer", "<script>alert('x')</script> & \"more\"")],
+        );
+
+        let html = render(&export, "html", &[]).unwrap();
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<b>"));
+        assert!(
+            html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;more&quot;")
+        );
+        assert!(html.contains("&lt;b&gt;Echo&lt;/b&gt;"));
+        assert!(
+            render_html_artifact_body(&ExportedArtifact {
+                artifact: artifact(Some("text/html"), ""),
+                text: Some("<img src=x onerror=alert(1)>".to_string()),
+            })
+            .contains("<pre>&lt;img src=x onerror=alert(1)&gt;</pre>")
+        );
+    }
+
+    #[test]
+    fn only_well_formed_mime_types_are_embedded() {
+        for mime_type in ["image/png", "application/vnd.api+json", "text/x-c++"] {
+            assert_eq!(embeddable_mime_type(Some(mime_type)), mime_type);
+        }
+        for mime_type in [
+            None,
+            Some(""),
+            Some("image"),
+            Some("image/"),
+            Some("/png"),
+            Some("image/png\" onload=\"alert(1)"),
+            Some("text/html; charset=utf-8"),
+            Some("image/png/extra"),
+        ] {
+            assert_eq!(
+                embeddable_mime_type(mime_type),
+                "application/octet-stream",
+                "{:?}",
+                mime_type
+            );
+        }
+
+        // Every test shares one store directory, the blob is the same for all of them
+        artifact_store::init_artifact_store(
+            &std::env::temp_dir().join("a2a-export-test-artifacts"),
+        )
+        .unwrap();
+        let content_hash = artifact_store::put_blob(b"\x89PNG").unwrap();
+        let body = |mime_type: &str| {
+            render_html_artifact_body(&ExportedArtifact {
+                artifact: artifact(Some(mime_type), &content_hash),
+                text: None,
+            })
+        };
+
+        assert!(body("image/png").starts_with("<img src=\"data:image/png;base64,iVBORw==\""));
+        let smuggled = body("image/png\" onload=\"alert(1)");
+        assert!(smuggled.starts_with("<a href=\"data:application/octet-stream;base64,iVBORw==\""));
+        assert!(!smuggled.contains("onload"));
+    }
+
+    #[test]
+    fn unknown_formats_are_refused() {
+        let error = render(&export("Echo", Vec::new()), "pdf", &[]).unwrap_err();
+        assert_eq!(
+            AppError::from(error).message,
+            "Unsupported export format: pdf"
+        );
+    }
+
+    #[test]
+    fn json_exports_have_sorted_keys() {
+        let rendered = render(&export("Echo", vec![message("answer", "Hi")]), "json", &[]).unwrap();
+        let value: Value = serde_json::from_str(&rendered).unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys, sorted);
+    }
+}
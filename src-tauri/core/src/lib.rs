@@ -0,0 +1,58 @@
+//! The A2A client, LLM client and settings store behind the desktop app, with
+//! no Tauri dependency. The app's commands are thin adapters over this crate,
+//! the `a2a-cli` binary and the MCP server use it directly.
+
+pub mod a2a;
+pub mod agent;
+pub mod artifact;
+pub mod compare;
+pub mod db;
+pub mod error;
+pub mod export;
+pub mod llm;
+pub mod mcp;
+pub mod model;
+pub mod prompt_template;
+pub mod redact;
+pub mod sse;
+pub mod traffic;
+
+#[cfg(test)]
+mod test_support;
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+// Must match `identifier` in tauri.conf.json, Tauri derives app_data_dir from it
+const APP_IDENTIFIER: &str = "site.cj2a7t.opena2a";
+
+/// Where the settings store keeps index.db and the artifact blobs
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub db_path: PathBuf,
+    pub artifact_dir: PathBuf,
+}
+
+impl StoreConfig {
+    /// The layout the desktop app uses inside its data directory
+    pub fn in_dir(data_dir: &Path) -> Self {
+        Self {
+            db_path: data_dir.join("a2a-client-db").join("index.db"),
+            artifact_dir: data_dir.join("a2a-client-artifacts"),
+        }
+    }
+
+    /// The desktop app's data directory, resolved the way Tauri does
+    pub fn default_location() -> Result<Self> {
+        let data_dir = dirs::data_dir().context("failed to resolve the user data directory")?;
+        Ok(Self::in_dir(&data_dir.join(APP_IDENTIFIER)))
+    }
+}
+
+/// Open the settings store and create any missing tables
+pub fn init(config: &StoreConfig) -> Result<()> {
+    db::rusqlite::open_db_conn(&config.db_path)?;
+    db::artifact_store::init_artifact_store(&config.artifact_dir)?;
+    db::init_all_tables()
+}
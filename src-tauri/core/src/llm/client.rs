@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+
+use ai::chat_completions::{ChatCompletionChunk, ChatCompletionRequest, ChatCompletionResponse};
+use anyhow::Result;
+use reqwest::Response;
+use serde_json::Value;
+
+use crate::{
+    error::{AppError, ErrorKind},
+    llm::preset::request_body,
+    model::{GenerationOptions, SettingModel},
+    sse::{SseEvent, SseParser},
+    traffic,
+};
+
+/// Client for an OpenAI compatible chat completions API. Requests are posted as
+/// plain JSON so the generation options the ai request can't carry reach the model
+#[derive(Debug, Clone)]
+pub struct ChatClient {
+    http: reqwest::Client,
+    api_url: String,
+    api_key: String,
+}
+
+impl ChatClient {
+    pub fn new(api_url: &str, api_key: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    pub fn for_model(model: &SettingModel) -> Self {
+        Self::new(&model.api_url, &model.api_key)
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// One completion without streaming
+    pub async fn chat_completions(
+        &self,
+        request: &ChatCompletionRequest,
+        options: &GenerationOptions,
+    ) -> Result<ChatCompletionResponse> {
+        let mut body = request_body(request, options)?;
+        body["stream"] = Value::Bool(false);
+
+        let mut exchange = traffic::Exchange::llm(&self.api_url, &body, false);
+        let response = self.post(&body, &mut exchange).await?;
+        let text = match response.text().await {
+            Ok(text) => text,
+            Err(e) => {
+                exchange.error(&e);
+                return Err(AppError::from(e).into());
+            }
+        };
+        exchange.body(&text);
+
+        serde_json::from_str(&text).map_err(|e| {
+            AppError::new(
+                ErrorKind::InvalidResponse,
+                format!("Unexpected chat completion response: {}", e),
+            )
+            .into()
+        })
+    }
+
+    /// A streamed completion, the chunks are read from the returned stream
+    pub async fn stream_chat_completions(
+        &self,
+        request: &ChatCompletionRequest,
+        options: &GenerationOptions,
+    ) -> Result<ChatStream> {
+        let mut body = request_body(request, options)?;
+        body["stream"] = Value::Bool(true);
+
+        let mut exchange = traffic::Exchange::llm(&self.api_url, &body, true);
+        let response = self.post(&body, &mut exchange).await?;
+        Ok(ChatStream {
+            exchange,
+            response,
+            parser: SseParser::new(),
+            pending: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    async fn post(&self, body: &Value, exchange: &mut traffic::Exchange) -> Result<Response> {
+        let response = match self
+            .http
+            .post(format!("{}/chat/completions", self.api_url))
+            .bearer_auth(&self.api_key)
+            .json(body)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                exchange.error(&e);
+                return Err(AppError::from(e).into());
+            }
+        };
+        exchange.response(&response);
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            exchange.body(&text);
+            return Err(AppError::from_http_status(status, &text).into());
+        }
+        Ok(response)
+    }
+}
+
+/// The chunks of a streamed completion
+pub struct ChatStream {
+    exchange: traffic::Exchange,
+    response: Response,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+    done: bool,
+}
+
+impl ChatStream {
+    /// The next chunk, `None` once the model sent `[DONE]` or closed the stream
+    pub async fn next(&mut self) -> Option<Result<ChatCompletionChunk>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                self.exchange.event(&event);
+                if event.data.trim() == "[DONE]" {
+                    self.done = true;
+                    self.pending.clear();
+                    return None;
+                }
+                return Some(serde_json::from_str(&event.data).map_err(|e| {
+                    AppError::new(
+                        ErrorKind::InvalidResponse,
+                        format!("Unexpected stream chunk: {}", e),
+                    )
+                    .into()
+                }));
+            }
+            if self.done {
+                return None;
+            }
+
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.pending.extend(self.parser.push(&chunk)),
+                Ok(None) => {
+                    self.done = true;
+                    self.pending.extend(self.parser.finish());
+                }
+                Err(e) => {
+                    self.exchange.error(&e);
+                    self.done = true;
+                    return Some(Err(
+                        anyhow::Error::new(AppError::from(e)).context("Stream error")
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use ai::chat_completions::{ChatCompletionMessage, ChatCompletionRequestBuilder};
+    use axum::{
+        Json, Router,
+        extract::State,
+        http::{StatusCode, header},
+        response::IntoResponse,
+        routing::post,
+    };
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_support::serve;
+
+    type Bodies = Arc<Mutex<Vec<Value>>>;
+
+    // Records every posted body and answers as an OpenAI compatible API would
+    async fn stub_chat_api() -> (ChatClient, Bodies) {
+        async fn completions(
+            State(bodies): State<Bodies>,
+            Json(body): Json<Value>,
+        ) -> axum::response::Response {
+            let stream = body["stream"].as_bool().unwrap_or(false);
+            bodies.lock().unwrap().push(body);
+
+            if !stream {
+                return Json(json!({
+                    "id": "1", "object": "chat.completion", "created": 0, "model": "stub",
+                    "choices": [{
+                        "index": 0,
+                        "message": { "role": "assistant", "content": "Hello" },
+                        "finish_reason": "stop"
+                    }],
+                    "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+                }))
+                .into_response();
+            }
+            let chunk = |content: &str| {
+                json!({
+                    "id": "1", "object": "chat.completion.chunk", "created": 0, "model": "stub",
+                    "choices": [{ "index": 0, "delta": { "content": content } }]
+                })
+            };
+            let events = format!(
+                "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+                chunk("Hel"),
+                chunk("lo")
+            );
+            ([(header::CONTENT_TYPE, "text/event-stream")], events).into_response()
+        }
+
+        let bodies = Bodies::default();
+        let app = Router::new()
+            .route("/v1/chat/completions", post(completions))
+            .with_state(bodies.clone());
+        let url = serve(app).await;
+        (ChatClient::new(&format!("{}/v1/", url), "key"), bodies)
+    }
+
+    fn request() -> ChatCompletionRequest {
+        let mut builder = ChatCompletionRequestBuilder::default();
+        builder
+            .model("stub".to_string())
+            .messages(vec![ChatCompletionMessage::User("Hi".into())]);
+        builder.build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn merged_preset_options_reach_the_request_body() {
+        let (client, bodies) = stub_chat_api().await;
+        let preset = GenerationOptions {
+            temperature: Some(0.7),
+            top_p: Some(0.9),
+            stop: Some(vec!["END".to_string()]),
+            presence_penalty: Some(0.5),
+            seed: Some(42),
+            response_format: Some(json!({ "type": "json_object" })),
+            ..Default::default()
+        };
+        let overrides = GenerationOptions {
+            temperature: Some(0.2),
+            frequency_penalty: Some(-0.5),
+            max_tokens: Some(64),
+            ..Default::default()
+        };
+        let options = preset.merge(&overrides);
+
+        let response = client.chat_completions(&request(), &options).await.unwrap();
+        assert_eq!(
+            response.choices[0].message.content.as_deref(),
+            Some("Hello")
+        );
+
+        let body = bodies.lock().unwrap().pop().unwrap();
+        assert_eq!(body["model"], "stub");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["temperature"], json!(0.2));
+        assert_eq!(body["top_p"], json!(0.9));
+        assert_eq!(body["max_completion_tokens"], 64);
+        assert_eq!(body["stop"], json!(["END"]));
+        assert_eq!(body["presence_penalty"], json!(0.5));
+        assert_eq!(body["frequency_penalty"], json!(-0.5));
+        assert_eq!(body["seed"], 42);
+        assert_eq!(body["response_format"], json!({ "type": "json_object" }));
+    }
+
+    #[tokio::test]
+    async fn streamed_chunks_end_at_done() {
+        let (client, bodies) = stub_chat_api().await;
+        let options = GenerationOptions {
+            top_p: Some(0.5),
+            ..Default::default()
+        };
+
+        let mut stream = client
+            .stream_chat_completions(&request(), &options)
+            .await
+            .unwrap();
+        let mut content = String::new();
+        while let Some(chunk) = stream.next().await {
+            content.push_str(chunk.unwrap().choices[0].delta.content.as_deref().unwrap());
+        }
+        assert_eq!(content, "Hello");
+
+        let body = bodies.lock().unwrap().pop().unwrap();
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["top_p"], json!(0.5));
+        assert!(body.get("temperature").is_none());
+    }
+
+    #[tokio::test]
+    async fn error_statuses_keep_their_kind() {
+        let app = Router::new().route(
+            "/chat/completions",
+            post(|| async { (StatusCode::UNAUTHORIZED, "bad key") }),
+        );
+        let client = ChatClient::new(&serve(app).await, "key");
+
+        let error = client
+            .chat_completions(&request(), &GenerationOptions::default())
+            .await
+            .unwrap_err();
+        assert_eq!(AppError::from(error).kind, ErrorKind::Unauthorized);
+    }
+}
@@ -0,0 +1,650 @@
+use ai::chat_completions::{ChatCompletionMessage, ChatCompletionRequestBuilder};
+use anyhow::{Context, Result};
+
+use crate::{
+    error::AppError,
+    llm::client::ChatClient,
+    model::{ChatMessage, GenerationOptions, SettingModel},
+};
+
+// Smallest window a model can be configured with
+const MIN_CONTEXT_WINDOW: u32 = 1024;
+// Room left for the reply when the request sets no max_tokens
+const DEFAULT_RESERVED_OUTPUT_TOKENS: u32 = 1024;
+// Role markers and separators the chat format adds around every message
+const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+// The assistant header the reply is primed with
+const REPLY_PRIMING_TOKENS: usize = 3;
+// What a truncated message keeps, split between its head and tail
+const TRUNCATED_MESSAGE_TOKENS: usize = 512;
+// Length of the summary that replaces older turns
+const SUMMARY_MAX_TOKENS: u32 = 512;
+// The latest turns are kept verbatim when older ones are summarized
+const KEEP_RECENT_MESSAGES: usize = 2;
+
+const SUMMARY_PROMPT: &str = "You compress conversation history. Summarize the messages below in a few sentences. \
+Keep every fact, decision, agent name, skill name and result the conversation still needs, drop greetings and repetition. \
+Answer with the summary only.";
+
+// Non-ASCII characters, CJK mostly, cost about a token each whatever the model
+const TOKENS_PER_NON_ASCII_CHAR: f32 = 1.0;
+
+/// Estimated token counts of a model family. No tokenizer runs, ASCII text is
+/// assumed to compress by the family's average characters per token
+pub struct TokenEstimate {
+    pub family: &'static str,
+    // ASCII characters per token
+    pub chars_per_token: f32,
+    pub default_window: u32,
+}
+
+// Matched in order against the lowercased API model name, so longer prefixes come first
+static TOKEN_ESTIMATES: &[(&str, TokenEstimate)] = &[
+    (
+        "deepseek",
+        TokenEstimate {
+            family: "deepseek",
+            chars_per_token: 3.6,
+            default_window: 64_000,
+        },
+    ),
+    (
+        "gpt-4o",
+        TokenEstimate {
+            family: "gpt-4o",
+            chars_per_token: 4.0,
+            default_window: 128_000,
+        },
+    ),
+    (
+        "gpt-4.1",
+        TokenEstimate {
+            family: "gpt-4.1",
+            chars_per_token: 4.0,
+            default_window: 1_000_000,
+        },
+    ),
+    (
+        "gpt-4-turbo",
+        TokenEstimate {
+            family: "gpt-4-turbo",
+            chars_per_token: 3.8,
+            default_window: 128_000,
+        },
+    ),
+    (
+        "gpt-4",
+        TokenEstimate {
+            family: "gpt-4",
+            chars_per_token: 3.8,
+            default_window: 8_192,
+        },
+    ),
+    (
+        "gpt-3.5",
+        TokenEstimate {
+            family: "gpt-3.5",
+            chars_per_token: 3.8,
+            default_window: 16_385,
+        },
+    ),
+    (
+        "claude",
+        TokenEstimate {
+            family: "claude",
+            chars_per_token: 3.5,
+            default_window: 200_000,
+        },
+    ),
+    (
+        "qwen",
+        TokenEstimate {
+            family: "qwen",
+            chars_per_token: 3.7,
+            default_window: 32_768,
+        },
+    ),
+    (
+        "glm",
+        TokenEstimate {
+            family: "glm",
+            chars_per_token: 3.5,
+            default_window: 128_000,
+        },
+    ),
+    (
+        "moonshot",
+        TokenEstimate {
+            family: "moonshot",
+            chars_per_token: 3.5,
+            default_window: 128_000,
+        },
+    ),
+];
+
+// Unknown models get a pessimistic estimate and a small window
+static FALLBACK_ESTIMATE: TokenEstimate = TokenEstimate {
+    family: "generic",
+    chars_per_token: 3.0,
+    default_window: 8_192,
+};
+
+/// What to do when the messages do not fit the context window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextPolicy {
+    // Drop the oldest assistant steps and observations
+    DropOldest,
+    // Cut the middle out of long tool outputs, then other long messages
+    Truncate,
+    // Replace older turns with an LLM written summary
+    Summarize,
+}
+
+impl ContextPolicy {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "drop_oldest" => Some(Self::DropOldest),
+            "truncate" => Some(Self::Truncate),
+            "summarize" => Some(Self::Summarize),
+            _ => None,
+        }
+    }
+
+    // The policies tried in turn, the cheap ones back up the configured one
+    fn with_fallbacks(self) -> &'static [ContextPolicy] {
+        match self {
+            Self::DropOldest => &[Self::DropOldest, Self::Truncate],
+            Self::Truncate => &[Self::Truncate, Self::DropOldest],
+            Self::Summarize => &[Self::Summarize, Self::DropOldest, Self::Truncate],
+        }
+    }
+}
+
+/// The input budget of one request
+pub struct ContextBudget {
+    pub window: u32,
+    pub reserved_output: u32,
+    pub policy: ContextPolicy,
+    pub estimate: &'static TokenEstimate,
+}
+
+impl ContextBudget {
+    /// Budget of a model, its configured window and policy win over the estimate table
+    pub fn for_model(
+        model: Option<&SettingModel>,
+        model_name: &str,
+        max_output_tokens: Option<u32>,
+    ) -> Self {
+        let estimate = estimate_for(model_name);
+        let window = model
+            .and_then(|model| model.context_window)
+            .unwrap_or(estimate.default_window);
+        let policy = model
+            .and_then(|model| model.context_policy.as_deref())
+            .and_then(ContextPolicy::parse)
+            .unwrap_or(ContextPolicy::DropOldest);
+
+        Self {
+            window,
+            // Never let the reply starve the prompt completely
+            reserved_output: max_output_tokens
+                .unwrap_or(DEFAULT_RESERVED_OUTPUT_TOKENS)
+                .min(window / 2),
+            policy,
+            estimate,
+        }
+    }
+
+    fn input_limit(&self) -> usize {
+        self.window.saturating_sub(self.reserved_output) as usize
+    }
+
+    fn count(&self, messages: &[ChatMessage]) -> usize {
+        count_message_tokens(messages, self.estimate)
+    }
+}
+
+/// The model used to summarize older turns
+pub struct Summarizer<'a> {
+    pub client: &'a ChatClient,
+    pub model_name: &'a str,
+}
+
+/// What fitting the messages into the window did
+#[derive(Debug, Default)]
+pub struct ContextStats {
+    pub tokens_before: usize,
+    pub tokens_after: usize,
+    pub dropped: usize,
+    pub truncated: usize,
+    pub summarized: usize,
+}
+
+impl ContextStats {
+    pub fn changed(&self) -> bool {
+        self.dropped + self.truncated + self.summarized > 0
+    }
+
+    pub fn describe(&self) -> String {
+        format!(
+            "Context reduced from {} to {} tokens: {} messages dropped, {} truncated, {} summarized",
+            self.tokens_before, self.tokens_after, self.dropped, self.truncated, self.summarized
+        )
+    }
+}
+
+pub fn estimate_for(model_name: &str) -> &'static TokenEstimate {
+    let model_name = model_name.to_lowercase();
+    TOKEN_ESTIMATES
+        .iter()
+        .find(|(prefix, _)| model_name.contains(prefix))
+        .map(|(_, estimate)| estimate)
+        .unwrap_or(&FALLBACK_ESTIMATE)
+}
+
+pub fn count_tokens(text: &str, estimate: &TokenEstimate) -> usize {
+    text.chars()
+        .map(|c| char_tokens(c, estimate))
+        .sum::<f32>()
+        .ceil() as usize
+}
+
+pub fn count_message_tokens(messages: &[ChatMessage], estimate: &TokenEstimate) -> usize {
+    messages
+        .iter()
+        .map(|message| count_tokens(&message.content, estimate) + MESSAGE_OVERHEAD_TOKENS)
+        .sum::<usize>()
+        + REPLY_PRIMING_TOKENS
+}
+
+/// Apply the budget's policy until the messages fit, failing when even the
+/// system prompt, the question and the latest message do not
+pub async fn fit_messages(
+    mut messages: Vec<ChatMessage>,
+    budget: &ContextBudget,
+    summarizer: Option<Summarizer<'_>>,
+) -> Result<(Vec<ChatMessage>, ContextStats)> {
+    let limit = budget.input_limit();
+    let tokens_before = budget.count(&messages);
+    let mut stats = ContextStats {
+        tokens_before,
+        tokens_after: tokens_before,
+        ..Default::default()
+    };
+
+    if tokens_before <= limit {
+        return Ok((messages, stats));
+    }
+
+    for policy in budget.policy.with_fallbacks() {
+        if budget.count(&messages) <= limit {
+            break;
+        }
+        match policy {
+            ContextPolicy::DropOldest => stats.dropped += drop_oldest(&mut messages, budget),
+            ContextPolicy::Truncate => stats.truncated += truncate_long(&mut messages, budget),
+            ContextPolicy::Summarize => match &summarizer {
+                Some(summarizer) => {
+                    match summarize_older(&mut messages, budget, summarizer).await {
+                        Ok(summarized) => stats.summarized += summarized,
+                        Err(e) => log::warn!("Failed to summarize older messages: {}", e),
+                    }
+                }
+                None => log::warn!("No summarizer available, dropping messages instead"),
+            },
+        }
+    }
+
+    stats.tokens_after = budget.count(&messages);
+    if stats.tokens_after > limit {
+        return Err(AppError::invalid_params(format!(
+            "Messages need {} tokens but the {} token context window leaves {} for input",
+            stats.tokens_after, budget.window, limit
+        ))
+        .into());
+    }
+
+    log::info!("{}", stats.describe());
+    Ok((messages, stats))
+}
+
+/// Convert to the ai.rs message types
+pub fn to_completion_messages(messages: &[ChatMessage]) -> Vec<ChatCompletionMessage> {
+    messages
+        .iter()
+        .map(|msg| match msg.role.as_str() {
+            "system" => ChatCompletionMessage::System(msg.content.clone().into()),
+            "user" => ChatCompletionMessage::User(msg.content.clone().into()),
+            "assistant" => ChatCompletionMessage::Assistant(msg.content.clone().into()),
+            _ => ChatCompletionMessage::User(msg.content.clone().into()), // 默认为 user
+        })
+        .collect()
+}
+
+pub fn validate_context_settings(
+    context_window: Option<u32>,
+    context_policy: Option<&str>,
+) -> Result<(), String> {
+    if context_window.is_some_and(|window| window < MIN_CONTEXT_WINDOW) {
+        return Err(format!(
+            "Context window must be at least {} tokens",
+            MIN_CONTEXT_WINDOW
+        ));
+    }
+    if context_policy.is_some_and(|policy| ContextPolicy::parse(policy).is_none()) {
+        return Err("Context policy must be drop_oldest, truncate or summarize".to_string());
+    }
+    Ok(())
+}
+
+// System prompts, the question and the latest message always stay
+fn is_protected(messages: &[ChatMessage], index: usize) -> bool {
+    let first_user = messages.iter().position(|message| message.role == "user");
+    messages[index].role == "system" || Some(index) == first_user || index + 1 == messages.len()
+}
+
+fn drop_oldest(messages: &mut Vec<ChatMessage>, budget: &ContextBudget) -> usize {
+    let limit = budget.input_limit();
+    let mut dropped = 0;
+
+    while budget.count(messages) > limit {
+        let Some(index) = (0..messages.len()).find(|&i| !is_protected(messages, i)) else {
+            break;
+        };
+        let removed = messages.remove(index);
+        dropped += 1;
+
+        // An assistant step goes together with the observation answering it
+        if removed.role == "assistant"
+            && index < messages.len()
+            && messages[index].role == "user"
+            && !is_protected(messages, index)
+        {
+            messages.remove(index);
+            dropped += 1;
+        }
+    }
+
+    dropped
+}
+
+fn truncate_long(messages: &mut [ChatMessage], budget: &ContextBudget) -> usize {
+    let limit = budget.input_limit();
+
+    // Tool outputs first, oldest first within each group
+    let mut order: Vec<usize> = (0..messages.len())
+        .filter(|&i| messages[i].role != "system")
+        .collect();
+    order.sort_by_key(|&i| (!messages[i].content.contains("<observation>"), i));
+
+    let mut truncated = 0;
+    for index in order {
+        if budget.count(messages) <= limit {
+            break;
+        }
+        if let Some(content) = truncate_text(
+            &messages[index].content,
+            TRUNCATED_MESSAGE_TOKENS,
+            budget.estimate,
+        ) {
+            messages[index].content = content;
+            truncated += 1;
+        }
+    }
+
+    truncated
+}
+
+async fn summarize_older(
+    messages: &mut Vec<ChatMessage>,
+    budget: &ContextBudget,
+    summarizer: &Summarizer<'_>,
+) -> Result<usize> {
+    let removable: Vec<usize> = (0..messages.len())
+        .filter(|&i| !is_protected(messages, i))
+        .collect();
+    if removable.len() <= KEEP_RECENT_MESSAGES {
+        return Ok(0);
+    }
+    let older = &removable[..removable.len() - KEEP_RECENT_MESSAGES];
+
+    let transcript = older
+        .iter()
+        .map(|&i| format!("{}: {}", messages[i].role, messages[i].content))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    // The summary request has to fit the window as well
+    let transcript_budget = budget.input_limit().saturating_sub(
+        count_tokens(SUMMARY_PROMPT, budget.estimate) + SUMMARY_MAX_TOKENS as usize,
+    );
+    let transcript =
+        truncate_text(&transcript, transcript_budget, budget.estimate).unwrap_or(transcript);
+
+    let summary = request_summary(summarizer, transcript).await?;
+
+    let first = older[0];
+    for &index in older.iter().rev() {
+        messages.remove(index);
+    }
+    messages.insert(
+        first,
+        ChatMessage {
+            role: "user".to_string(),
+            content: format!(
+                "<summary>Summary of {} earlier messages: {}</summary>",
+                older.len(),
+                summary.trim()
+            ),
+        },
+    );
+
+    Ok(older.len())
+}
+
+async fn request_summary(summarizer: &Summarizer<'_>, transcript: String) -> Result<String> {
+    let mut builder = ChatCompletionRequestBuilder::default();
+    builder
+        .model(summarizer.model_name.to_string())
+        .messages(vec![
+            ChatCompletionMessage::System(SUMMARY_PROMPT.into()),
+            ChatCompletionMessage::User(transcript.into()),
+        ])
+        .temperature(0.2)
+        .max_completion_tokens(SUMMARY_MAX_TOKENS);
+    let request = builder
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build summary request: {}", e))?;
+
+    let response = summarizer
+        .client
+        .chat_completions(&request, &GenerationOptions::default())
+        .await
+        .map_err(|e| anyhow::anyhow!("Summary request failed: {}", e))?;
+
+    response
+        .choices
+        .first()
+        .and_then(|choice| choice.message.content.clone())
+        .filter(|summary| !summary.trim().is_empty())
+        .context("LLM returned no summary")
+}
+
+fn char_tokens(c: char, estimate: &TokenEstimate) -> f32 {
+    if c.is_ascii() {
+        1.0 / estimate.chars_per_token
+    } else {
+        TOKENS_PER_NON_ASCII_CHAR
+    }
+}
+
+// Keep the head and tail of a text, None when it is short enough already
+fn truncate_text(text: &str, keep_tokens: usize, estimate: &TokenEstimate) -> Option<String> {
+    if count_tokens(text, estimate) <= keep_tokens {
+        return None;
+    }
+
+    // How many characters from one end fit in the tokens
+    let fitting = |chars: &mut dyn Iterator<Item = char>, tokens: f32| -> usize {
+        let mut used = 0.0;
+        chars
+            .take_while(|&c| {
+                used += char_tokens(c, estimate);
+                used <= tokens
+            })
+            .count()
+    };
+    let head_tokens = (keep_tokens * 2 / 3) as f32;
+    let tail_tokens = keep_tokens as f32 - head_tokens;
+    let total_chars = text.chars().count();
+    let head_chars = fitting(&mut text.chars(), head_tokens);
+    let tail_chars = fitting(&mut text.chars().rev(), tail_tokens).min(total_chars - head_chars);
+
+    let head: String = text.chars().take(head_chars).collect();
+    let tail: String = text.chars().skip(total_chars - tail_chars).collect();
+
+    Some(format!(
+        "{}\n…[{} characters truncated]…\n{}",
+        head,
+        total_chars - head_chars - tail_chars,
+        tail
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_match_the_model_family() {
+        assert_eq!(estimate_for("gpt-4o-mini").family, "gpt-4o");
+        assert_eq!(estimate_for("gpt-4-turbo-preview").family, "gpt-4-turbo");
+        assert_eq!(estimate_for("DeepSeek-Chat").family, "deepseek");
+        assert_eq!(estimate_for("local-llama").family, "generic");
+    }
+
+    #[test]
+    fn non_ascii_characters_count_a_token_each() {
+        let estimate = estimate_for("gpt-4o");
+        assert_eq!(count_tokens("abcdefgh", estimate), 2);
+        assert_eq!(count_tokens("你好世界", estimate), 4);
+        assert_eq!(count_tokens("ab你好", estimate), 3);
+        assert_eq!(count_tokens("", estimate), 0);
+    }
+
+    #[test]
+    fn truncate_keeps_head_and_tail_within_the_tokens() {
+        let estimate = estimate_for("gpt-4o");
+        assert_eq!(truncate_text("short", 10, estimate), None);
+
+        let text = format!("{}{}", "a".repeat(400), "z".repeat(400));
+        let truncated = truncate_text(&text, 30, estimate).unwrap();
+        assert!(truncated.starts_with(&"a".repeat(80)));
+        assert!(truncated.ends_with(&"z".repeat(40)));
+        assert!(truncated.contains("[680 characters truncated]"));
+
+        let wide = "字".repeat(100);
+        let truncated = truncate_text(&wide, 30, estimate).unwrap();
+        assert!(truncated.contains("[70 characters truncated]"));
+        assert!(count_tokens(&truncated, estimate) < 50);
+    }
+
+    fn message(role: &str, content: &str) -> ChatMessage {
+        ChatMessage {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn budget(window: u32, policy: ContextPolicy) -> ContextBudget {
+        ContextBudget {
+            window,
+            reserved_output: 0,
+            policy,
+            estimate: estimate_for("gpt-4o"),
+        }
+    }
+
+    // A system prompt, the question and two steps of 104 tokens each message
+    fn conversation() -> Vec<ChatMessage> {
+        let step = "s".repeat(400);
+        vec![
+            message("system", "sys"),
+            message("user", "question"),
+            message("assistant", &step),
+            message(
+                "user",
+                &format!("<observation>{}</observation>", "o".repeat(373)),
+            ),
+            message("assistant", &step),
+            message("user", &step),
+        ]
+    }
+
+    #[tokio::test]
+    async fn messages_within_the_window_are_kept() {
+        let messages = conversation();
+        let (fitted, stats) = fit_messages(
+            messages.clone(),
+            &budget(1000, ContextPolicy::Truncate),
+            None,
+        )
+        .await
+        .unwrap();
+        let contents = |messages: &[ChatMessage]| -> Vec<String> {
+            messages.iter().map(|m| m.content.clone()).collect()
+        };
+        assert_eq!(contents(&fitted), contents(&messages));
+        assert!(!stats.changed());
+        assert_eq!(stats.tokens_before, 430);
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_removes_a_step_with_its_observation() {
+        let (fitted, stats) = fit_messages(
+            conversation(),
+            &budget(300, ContextPolicy::DropOldest),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.dropped, 2);
+        assert_eq!(stats.tokens_after, 222);
+        let roles: Vec<&str> = fitted.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "user"]);
+        assert_eq!(fitted[1].content, "question");
+    }
+
+    #[tokio::test]
+    async fn truncate_shortens_tool_outputs_first() {
+        let mut messages = conversation();
+        messages[3].content = format!("<observation>{}</observation>", "o".repeat(4000));
+        let (fitted, stats) = fit_messages(messages, &budget(1200, ContextPolicy::Truncate), None)
+            .await
+            .unwrap();
+        assert_eq!((stats.truncated, stats.dropped), (1, 0));
+        assert!(fitted[3].content.contains("characters truncated"));
+        assert_eq!(fitted[2].content, "s".repeat(400));
+    }
+
+    #[tokio::test]
+    async fn summarize_without_a_summarizer_falls_back_to_dropping() {
+        let (fitted, stats) =
+            fit_messages(conversation(), &budget(300, ContextPolicy::Summarize), None)
+                .await
+                .unwrap();
+        assert_eq!((stats.summarized, stats.dropped), (0, 2));
+        assert_eq!(fitted.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn protected_messages_that_do_not_fit_are_an_error() {
+        let messages = vec![
+            message("system", &"s".repeat(4000)),
+            message("user", "question"),
+        ];
+        let error = fit_messages(messages, &budget(500, ContextPolicy::DropOldest), None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("500 token context window"));
+    }
+}
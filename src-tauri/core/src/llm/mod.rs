@@ -0,0 +1,94 @@
+pub mod client;
+pub mod context_window;
+pub mod preset;
+
+use ai::chat_completions::ChatCompletionRequestBuilder;
+use anyhow::{Context, Result};
+
+use crate::{
+    error::AppError,
+    llm::{client::ChatClient, context_window::to_completion_messages},
+    model::{ChatMessage, GenerationOptions, SettingModel},
+};
+
+/// API model name stored in the model's settings, models saved without one
+/// are called by their key
+pub fn chat_model_name(model: &SettingModel) -> Result<String> {
+    model
+        .model_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .or_else(|| Some(model.model_key.trim()).filter(|key| !key.is_empty()))
+        .map(str::to_string)
+        .ok_or_else(|| {
+            AppError::invalid_params(format!(
+                "Model {} has no API model name configured",
+                model.model_key
+            ))
+            .into()
+        })
+}
+
+/// Client for the model's OpenAI compatible API
+pub fn client_for(model: &SettingModel) -> ChatClient {
+    ChatClient::for_model(model)
+}
+
+/// One chat completion without streaming, returns the reply content
+pub async fn complete(
+    client: &ChatClient,
+    model_name: &str,
+    messages: &[ChatMessage],
+    options: &GenerationOptions,
+) -> Result<String> {
+    let mut builder = ChatCompletionRequestBuilder::default();
+    builder
+        .model(model_name.to_string())
+        .messages(to_completion_messages(messages));
+    let request = builder
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build request: {}", e))?;
+
+    let response = client
+        .chat_completions(&request, options)
+        .await
+        .context("LLM request failed")?;
+
+    response
+        .choices
+        .first()
+        .and_then(|choice| choice.message.content.clone())
+        .context("LLM returned no content")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(model_key: &str, model_name: Option<&str>) -> SettingModel {
+        SettingModel {
+            id: Some(1),
+            model_key: model_key.to_string(),
+            model_name: model_name.map(str::to_string),
+            enabled: true,
+            api_url: "http://127.0.0.1/v1".to_string(),
+            api_key: "key".to_string(),
+            context_window: None,
+            context_policy: None,
+        }
+    }
+
+    #[test]
+    fn models_without_a_name_are_called_by_their_key() {
+        let name = |key, name| chat_model_name(&model(key, name));
+
+        assert_eq!(
+            name("DeepSeek", Some(" deepseek-chat ")).unwrap(),
+            "deepseek-chat"
+        );
+        assert_eq!(name("qwen-max", None).unwrap(), "qwen-max");
+        assert_eq!(name("qwen-max", Some("  ")).unwrap(), "qwen-max");
+        assert!(name(" ", None).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use ai::chat_completions::ChatCompletionRequest;
+use serde_json::Value;
+
+use crate::{db::model_preset_db::ModelPresetDbManager, model::GenerationOptions};
+
+// The chat APIs reject more stop sequences than this
+const MAX_STOP_SEQUENCES: usize = 4;
+
+/// Options of the given preset, or of the model's default preset when no preset is named
+pub fn preset_options(
+    model_id: Option<i32>,
+    preset_id: Option<i64>,
+) -> anyhow::Result<GenerationOptions> {
+    let db_manager = ModelPresetDbManager::new();
+
+    if let Some(preset_id) = preset_id {
+        let preset = db_manager
+            .get_by_id(preset_id)?
+            .ok_or_else(|| anyhow::anyhow!("Model preset {} not found", preset_id))?;
+        if model_id.is_some_and(|model_id| model_id != preset.model_id) {
+            return Err(anyhow::anyhow!(
+                "Model preset {} belongs to another model",
+                preset.name
+            ));
+        }
+        return Ok(preset.options);
+    }
+
+    match model_id {
+        Some(model_id) => Ok(db_manager
+            .get_default(model_id)?
+            .map(|preset| preset.options)
+            .unwrap_or_default()),
+        None => Ok(GenerationOptions::default()),
+    }
+}
+
+/// The request as the JSON body posted to the chat API, with every option that
+/// has a value set on it. The ai request has no field for most of them
+pub fn request_body(
+    request: &ChatCompletionRequest,
+    options: &GenerationOptions,
+) -> anyhow::Result<Value> {
+    let mut body = serde_json::to_value(request)?;
+
+    let fields = [
+        ("temperature", options.temperature.map(float)),
+        ("top_p", options.top_p.map(float)),
+        ("max_completion_tokens", options.max_tokens.map(Value::from)),
+        (
+            "stop",
+            options
+                .stop
+                .clone()
+                .filter(|stop| !stop.is_empty())
+                .map(Value::from),
+        ),
+        ("presence_penalty", options.presence_penalty.map(float)),
+        ("frequency_penalty", options.frequency_penalty.map(float)),
+        ("seed", options.seed.map(Value::from)),
+        ("response_format", options.response_format.clone()),
+    ];
+    for (name, value) in fields {
+        if let Some(value) = value {
+            body[name] = value;
+        }
+    }
+
+    Ok(body)
+}
+
+// Widening the f32 itself would send 0.3 as 0.30000001192092896
+fn float(value: f32) -> Value {
+    value
+        .to_string()
+        .parse::<f64>()
+        .map(Value::from)
+        .unwrap_or_else(|_| Value::from(value))
+}
+
+pub fn validate_generation_options(options: &GenerationOptions) -> Result<(), String> {
+    let check_range = |name: &str, value: Option<f32>, min: f32, max: f32| match value {
+        Some(value) if !(min..=max).contains(&value) => {
+            Err(format!("{} must be between {} and {}", name, min, max))
+        }
+        _ => Ok(()),
+    };
+
+    check_range("temperature", options.temperature, 0.0, 2.0)?;
+    check_range("top_p", options.top_p, 0.0, 1.0)?;
+    check_range("presence_penalty", options.presence_penalty, -2.0, 2.0)?;
+    check_range("frequency_penalty", options.frequency_penalty, -2.0, 2.0)?;
+
+    if options.max_tokens == Some(0) {
+        return Err("max_tokens must be greater than 0".to_string());
+    }
+
+    if let Some(stop) = &options.stop {
+        if stop.len() > MAX_STOP_SEQUENCES {
+            return Err(format!(
+                "At most {} stop sequences are allowed",
+                MAX_STOP_SEQUENCES
+            ));
+        }
+        if stop.iter().any(|sequence| sequence.is_empty()) {
+            return Err("Stop sequences cannot be empty".to_string());
+        }
+    }
+
+    if let Some(response_format) = &options.response_format {
+        match response_format.get("type").and_then(|t| t.as_str()) {
+            Some("text") | Some("json_object") => {}
+            Some("json_schema") if response_format.get("json_schema").is_some() => {}
+            Some("json_schema") => {
+                return Err("A json_schema response_format needs a json_schema".to_string());
+            }
+            _ => {
+                return Err(
+                    "response_format type must be text, json_object or json_schema".to_string(),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
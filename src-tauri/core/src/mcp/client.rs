@@ -0,0 +1,542 @@
+use std::{
+    collections::HashMap,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
+use lazy_static::lazy_static;
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    process::{Child, ChildStdin, ChildStdout, Command},
+};
+
+use crate::{
+    error::{AppError, ErrorKind},
+    model::{McpTool, SettingMcpServer},
+    sse::SseParser,
+};
+
+const PROTOCOL_VERSION: &str = "2025-06-18";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+lazy_static! {
+    // Live connections by server id, stdio servers keep running between calls
+    static ref MCP_CLIENTS: Mutex<HashMap<i32, Arc<tokio::sync::Mutex<McpClient>>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// A JSON-RPC error returned by the MCP server, the connection itself is fine
+#[derive(Debug)]
+pub struct McpError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl std::fmt::Display for McpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MCP error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for McpError {}
+
+// Boxed, the process handles are far larger than the HTTP variant
+enum Transport {
+    Stdio(Box<StdioProcess>),
+    Http {
+        client: reqwest::Client,
+        url: String,
+        headers: Vec<(String, String)>,
+        session_id: Option<String>,
+    },
+}
+
+struct StdioProcess {
+    // Held so the subprocess is killed when the client is dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+/// A connected, initialized MCP client
+pub struct McpClient {
+    transport: Transport,
+    next_id: i64,
+    initialized: bool,
+}
+
+impl McpClient {
+    /// Launch or connect to the server and run the initialize handshake
+    pub async fn connect(server: &SettingMcpServer) -> Result<Self> {
+        let transport = match server.transport.as_str() {
+            "stdio" => {
+                let command = server
+                    .command
+                    .as_deref()
+                    .filter(|c| !c.trim().is_empty())
+                    .context("stdio MCP server needs a command")?;
+                let args: Vec<String> = match &server.args_json {
+                    Some(json) if !json.trim().is_empty() => {
+                        serde_json::from_str(json).context("argsJson must be a JSON array of strings")?
+                    }
+                    _ => Vec::new(),
+                };
+                let env: HashMap<String, String> = match &server.env_json {
+                    Some(json) if !json.trim().is_empty() => {
+                        serde_json::from_str(json).context("envJson must be a JSON object of strings")?
+                    }
+                    _ => HashMap::new(),
+                };
+
+                let mut child = Command::new(command)
+                    .args(&args)
+                    .envs(&env)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .with_context(|| format!("failed to launch MCP server `{}`", command))?;
+
+                let stdin = child.stdin.take().context("MCP server stdin unavailable")?;
+                let stdout = child.stdout.take().context("MCP server stdout unavailable")?;
+
+                Transport::Stdio(Box::new(StdioProcess {
+                    _child: child,
+                    stdin,
+                    stdout: BufReader::new(stdout).lines(),
+                }))
+            }
+            "http" => {
+                let url = server
+                    .url
+                    .clone()
+                    .filter(|u| !u.trim().is_empty())
+                    .context("HTTP MCP server needs a url")?;
+
+                let mut headers = Vec::new();
+                if let Some(headers_json) = &server.custom_header_json {
+                    if let Ok(map) =
+                        serde_json::from_str::<serde_json::Map<String, Value>>(headers_json)
+                    {
+                        for (key, value) in map {
+                            if let Some(value) = value.as_str() {
+                                // Same convention as A2A servers: Authorization holds the bare token
+                                if key == "Authorization" && !value.starts_with("Bearer ") {
+                                    headers.push((key, format!("Bearer {}", value)));
+                                } else {
+                                    headers.push((key, value.to_string()));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                Transport::Http {
+                    client: reqwest::Client::new(),
+                    url,
+                    headers,
+                    session_id: None,
+                }
+            }
+            other => return Err(anyhow::anyhow!("Unknown MCP transport: {}", other)),
+        };
+
+        let mut client = Self {
+            transport,
+            next_id: 1,
+            initialized: false,
+        };
+
+        let result = client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "a2a-client",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .await
+            .with_context(|| format!("MCP initialize failed for {}", server.name))?;
+        log::info!(
+            "Connected to MCP server {} ({})",
+            server.name,
+            result
+                .pointer("/serverInfo/name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("unknown")
+        );
+
+        client.initialized = true;
+        client.notify("notifications/initialized", json!({})).await?;
+
+        Ok(client)
+    }
+
+    /// List every tool, following pagination cursors
+    pub async fn list_tools(&mut self) -> Result<Vec<McpTool>> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+
+            for tool in result
+                .get("tools")
+                .and_then(|t| t.as_array())
+                .into_iter()
+                .flatten()
+            {
+                tools.push(McpTool {
+                    name: tool
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .context("tool without a name")?
+                        .to_string(),
+                    title: tool.get("title").and_then(|t| t.as_str()).map(|t| t.to_string()),
+                    description: tool
+                        .get("description")
+                        .and_then(|d| d.as_str())
+                        .map(|d| d.to_string()),
+                    input_schema: tool
+                        .get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "type": "object" })),
+                });
+            }
+
+            cursor = result
+                .get("nextCursor")
+                .and_then(|c| c.as_str())
+                .map(|c| c.to_string());
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(tools)
+    }
+
+    /// Call a tool, returns the raw CallToolResult
+    pub async fn call_tool(&mut self, name: &str, arguments: Value) -> Result<Value> {
+        let arguments = if arguments.is_null() {
+            json!({})
+        } else {
+            arguments
+        };
+        self.request("tools/call", json!({ "name": name, "arguments": arguments }))
+            .await
+    }
+
+    async fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, self.exchange(&message, Some(id)))
+            .await
+            .map_err(|_| {
+                AppError::new(ErrorKind::Timeout, format!("MCP request {} timed out", method))
+            })??
+            .with_context(|| format!("MCP server sent no response to {}", method))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(McpError {
+                code: error.get("code").and_then(|c| c.as_i64()).unwrap_or_default(),
+                message: error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            }
+            .into());
+        }
+
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) -> Result<()> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        self.exchange(&message, None).await?;
+        Ok(())
+    }
+
+    // Send one message and, for requests, wait for the response with the same id
+    async fn exchange(&mut self, message: &Value, id: Option<i64>) -> Result<Option<Value>> {
+        let initialized = self.initialized;
+
+        match &mut self.transport {
+            Transport::Stdio(process) => {
+                let StdioProcess { stdin, stdout, .. } = process.as_mut();
+                let mut line = message.to_string();
+                line.push('\n');
+                stdin
+                    .write_all(line.as_bytes())
+                    .await
+                    .context("failed to write to MCP server")?;
+                stdin.flush().await.context("failed to write to MCP server")?;
+
+                let Some(id) = id else {
+                    return Ok(None);
+                };
+
+                while let Some(line) = stdout
+                    .next_line()
+                    .await
+                    .context("failed to read from MCP server")?
+                {
+                    let Ok(incoming) = serde_json::from_str::<Value>(&line) else {
+                        continue;
+                    };
+
+                    // Requests from the server need an answer or it may stall
+                    if let (Some(method), Some(request_id)) =
+                        (incoming.get("method").and_then(|m| m.as_str()), incoming.get("id"))
+                    {
+                        let reply = if method == "ping" {
+                            json!({ "jsonrpc": "2.0", "id": request_id, "result": {} })
+                        } else {
+                            json!({
+                                "jsonrpc": "2.0",
+                                "id": request_id,
+                                "error": { "code": -32601, "message": "Method not found" },
+                            })
+                        };
+                        let mut reply = reply.to_string();
+                        reply.push('\n');
+                        stdin.write_all(reply.as_bytes()).await?;
+                        stdin.flush().await?;
+                        continue;
+                    }
+
+                    if incoming.get("id").and_then(|i| i.as_i64()) == Some(id) {
+                        return Ok(Some(incoming));
+                    }
+                }
+
+                Err(anyhow::anyhow!("MCP server exited"))
+            }
+            Transport::Http {
+                client,
+                url,
+                headers,
+                session_id,
+            } => {
+                let mut request_builder = client
+                    .post(url.as_str())
+                    .header("Content-Type", "application/json")
+                    .header("Accept", "application/json, text/event-stream")
+                    .json(message);
+                for (key, value) in headers.iter() {
+                    request_builder = request_builder.header(key, value);
+                }
+                if let Some(session_id) = session_id.as_deref() {
+                    request_builder = request_builder.header("Mcp-Session-Id", session_id);
+                }
+                if initialized {
+                    request_builder = request_builder.header("MCP-Protocol-Version", PROTOCOL_VERSION);
+                }
+
+                let resp = request_builder
+                    .send()
+                    .await
+                    .context("MCP HTTP request failed")?;
+
+                if let Some(new_session) = resp
+                    .headers()
+                    .get("Mcp-Session-Id")
+                    .and_then(|v| v.to_str().ok())
+                {
+                    *session_id = Some(new_session.to_string());
+                }
+
+                let status = resp.status();
+                if !status.is_success() {
+                    let error_text = resp.text().await.unwrap_or_default();
+                    return Err(anyhow::Error::new(AppError::from_http_status(
+                        status,
+                        &error_text,
+                    ))
+                    .context("MCP server rejected the request"));
+                }
+
+                let Some(id) = id else {
+                    return Ok(None);
+                };
+
+                let is_event_stream = resp
+                    .headers()
+                    .get("Content-Type")
+                    .and_then(|v| v.to_str().ok())
+                    .map(|v| v.starts_with("text/event-stream"))
+                    .unwrap_or(false);
+
+                if !is_event_stream {
+                    let response = resp.json::<Value>().await.context("invalid MCP response")?;
+                    return Ok(Some(response));
+                }
+
+                let mut parser = SseParser::new();
+                let mut body = resp.bytes_stream();
+                while let Some(chunk) = body.next().await {
+                    let chunk = chunk.context("MCP event stream failed")?;
+                    for event in parser.push(&chunk) {
+                        if let Ok(incoming) = serde_json::from_str::<Value>(&event.data) {
+                            if incoming.get("method").is_none()
+                                && incoming.get("id").and_then(|i| i.as_i64()) == Some(id)
+                            {
+                                return Ok(Some(incoming));
+                            }
+                        }
+                    }
+                }
+
+                Ok(parser
+                    .finish()
+                    .and_then(|event| serde_json::from_str::<Value>(&event.data).ok()))
+            }
+        }
+    }
+}
+
+/// Get the pooled connection for a server, connecting on first use
+pub async fn get_client(server: &SettingMcpServer) -> Result<Arc<tokio::sync::Mutex<McpClient>>> {
+    let id = server.id.context("MCP server has no id")?;
+
+    if let Some(client) = MCP_CLIENTS.lock().ok().and_then(|clients| clients.get(&id).cloned()) {
+        return Ok(client);
+    }
+
+    let client = Arc::new(tokio::sync::Mutex::new(McpClient::connect(server).await?));
+    if let Ok(mut clients) = MCP_CLIENTS.lock() {
+        clients.insert(id, client.clone());
+    }
+    Ok(client)
+}
+
+/// Drop the pooled connection, stopping a stdio server
+pub fn disconnect(id: i32) {
+    if let Ok(mut clients) = MCP_CLIENTS.lock() {
+        clients.remove(&id);
+    }
+}
+
+/// List a server's tools over the pooled connection
+pub async fn list_tools(server: &SettingMcpServer) -> Result<Vec<McpTool>> {
+    let client = get_client(server).await?;
+    let result = client.lock().await.list_tools().await;
+    if let Err(e) = &result {
+        // Reconnect next time, the server may have exited
+        if e.downcast_ref::<McpError>().is_none() {
+            disconnect(server.id.unwrap_or_default());
+        }
+    }
+    result
+}
+
+/// Call a tool over the pooled connection
+pub async fn call_tool(server: &SettingMcpServer, name: &str, arguments: Value) -> Result<Value> {
+    let client = get_client(server).await?;
+    let result = client.lock().await.call_tool(name, arguments).await;
+    if let Err(e) = &result {
+        // Tool failures come back as isError results; anything but a JSON-RPC
+        // error from the server means the connection is broken
+        if e.downcast_ref::<McpError>().is_none() {
+            disconnect(server.id.unwrap_or_default());
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers initialize and a two page tools/list, pings the client first to
+    // check server requests are answered. Lines are matched as text, serde_json
+    // writes them compactly
+    const FIXTURE_SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  case "$line" in
+    *'"method":"initialize"'*)
+      echo '{"jsonrpc":"2.0","id":"server-ping","method":"ping"}'
+      echo '{"jsonrpc":"2.0","id":'"$id"',"result":{"protocolVersion":"2025-06-18","capabilities":{"tools":{}},"serverInfo":{"name":"fixture"}}}'
+      ;;
+    *'"cursor":"page-2"'*)
+      echo '{"jsonrpc":"2.0","id":'"$id"',"result":{"tools":[{"name":"echo","description":"Echo the input"}]}}'
+      ;;
+    *'"method":"tools/list"'*)
+      echo 'not json'
+      echo '{"jsonrpc":"2.0","id":'"$id"',"result":{"tools":[{"name":"add","title":"Add","inputSchema":{"type":"object","properties":{"a":{"type":"number"}}}}],"nextCursor":"page-2"}}'
+      ;;
+    *'"method":"tools/call"'*)
+      echo '{"jsonrpc":"2.0","id":'"$id"',"error":{"code":-32602,"message":"Unknown tool"}}'
+      ;;
+  esac
+done
+"#;
+
+    fn stdio_server() -> SettingMcpServer {
+        SettingMcpServer {
+            id: Some(1),
+            name: "fixture".to_string(),
+            transport: "stdio".to_string(),
+            command: Some("sh".to_string()),
+            args_json: Some(json!(["-c", FIXTURE_SERVER]).to_string()),
+            env_json: None,
+            url: None,
+            custom_header_json: None,
+            tools_json: None,
+            enabled: true,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn stdio_server_initializes_and_lists_tools() {
+        let mut client = McpClient::connect(&stdio_server()).await.unwrap();
+        assert!(client.initialized);
+
+        let tools = client.list_tools().await.unwrap();
+        let names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["add", "echo"]);
+        assert_eq!(tools[0].title.as_deref(), Some("Add"));
+        assert_eq!(tools[0].input_schema["properties"]["a"]["type"], "number");
+        assert_eq!(tools[1].description.as_deref(), Some("Echo the input"));
+        assert_eq!(tools[1].input_schema, json!({ "type": "object" }));
+    }
+
+    #[tokio::test]
+    async fn stdio_server_errors_are_mcp_errors() {
+        let mut client = McpClient::connect(&stdio_server()).await.unwrap();
+        let error = client.call_tool("missing", Value::Null).await.unwrap_err();
+        let error = error.downcast_ref::<McpError>().unwrap();
+        assert_eq!(error.code, -32602);
+        assert_eq!(error.message, "Unknown tool");
+    }
+
+    #[tokio::test]
+    async fn missing_stdio_command_fails_to_connect() {
+        let server = SettingMcpServer {
+            command: Some("a2a-client-missing-mcp-server".to_string()),
+            args_json: None,
+            ..stdio_server()
+        };
+        let error = McpClient::connect(&server).await.err().unwrap();
+        assert!(error.to_string().contains("failed to launch MCP server"));
+    }
+}
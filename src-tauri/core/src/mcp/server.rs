@@ -0,0 +1,367 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    sync::mpsc,
+};
+use uuid::Uuid;
+
+use crate::{
+    a2a::client::{
+        build_a2a_message_parts, card_service_url, extract_response_text, post_a2a_message,
+    },
+    db::a2a_db::SettingA2AServerDbManager,
+    model::{A2AMessage, SettingA2AServer},
+};
+
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+// JSON-RPC error codes used by MCP
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+// MCP clients reject longer tool names
+const MAX_TOOL_NAME_LEN: usize = 64;
+
+/// An enabled agent skill published as an MCP tool
+struct SkillTool {
+    server: SettingA2AServer,
+    skill_id: String,
+    description: String,
+}
+
+/// Serve the enabled A2A agent skills as MCP tools over stdio.
+/// Requests are newline-delimited JSON-RPC on stdin, responses go to stdout,
+/// so nothing else may print to stdout while this runs.
+pub async fn serve_stdio() -> Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<Value>();
+
+    // Single writer so concurrent tool calls never interleave their output
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(message) = rx.recv().await {
+            let mut line = message.to_string();
+            line.push('\n');
+            if stdout.write_all(line.as_bytes()).await.is_err() || stdout.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(line) = lines.next_line().await.context("failed to read stdin")? {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => {
+                let _ = tx.send(error_response(Value::Null, PARSE_ERROR, &e.to_string()));
+                continue;
+            }
+        };
+
+        // Notifications (no id) need no response
+        let Some(id) = request.get("id").cloned() else {
+            continue;
+        };
+        let method = request
+            .get("method")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let response = match handle_request(&method, params).await {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err((code, message)) => error_response(id, code, &message),
+            };
+            let _ = tx.send(response);
+        });
+    }
+
+    drop(tx);
+    let _ = writer.await;
+    Ok(())
+}
+
+async fn handle_request(method: &str, params: Value) -> Result<Value, (i64, String)> {
+    match method {
+        "initialize" => {
+            let requested = params
+                .get("protocolVersion")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let protocol_version = SUPPORTED_PROTOCOL_VERSIONS
+                .iter()
+                .find(|version| **version == requested)
+                .unwrap_or(&SUPPORTED_PROTOCOL_VERSIONS[0]);
+
+            Ok(json!({
+                "protocolVersion": protocol_version,
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": {
+                    "name": "a2a-client",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            }))
+        }
+        "ping" => Ok(json!({})),
+        "tools/list" => {
+            let tools = load_tools().map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+            let tools: Vec<Value> = tools
+                .iter()
+                .map(|(name, tool)| {
+                    json!({
+                        "name": name,
+                        "description": tool.description,
+                        "inputSchema": {
+                            "type": "object",
+                            "properties": {
+                                "text": {
+                                    "type": "string",
+                                    "description": "Message sent to the agent skill",
+                                },
+                                "contextId": {
+                                    "type": "string",
+                                    "description": "A2A contextId of an earlier call to continue that conversation",
+                                },
+                            },
+                            "required": ["text"],
+                        },
+                    })
+                })
+                .collect();
+            Ok(json!({ "tools": tools }))
+        }
+        "tools/call" => call_tool(params).await,
+        other => Err((METHOD_NOT_FOUND, format!("Method not found: {}", other))),
+    }
+}
+
+async fn call_tool(params: Value) -> Result<Value, (i64, String)> {
+    let name = params
+        .get("name")
+        .and_then(|n| n.as_str())
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+    let text = arguments
+        .get("text")
+        .and_then(|t| t.as_str())
+        .ok_or((INVALID_PARAMS, "Missing \"text\" argument".to_string()))?;
+
+    let mut tools = load_tools().map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+    let tool = tools
+        .remove(name)
+        .ok_or((INVALID_PARAMS, format!("Unknown tool: {}", name)))?;
+
+    let Some(a2a_url) = card_service_url(&tool.server) else {
+        return Ok(tool_error("A2A server has no agent card url"));
+    };
+
+    let message = A2AMessage {
+        message_id: format!("msg_id:{}", Uuid::new_v4()),
+        kind: "message".to_string(),
+        role: "user".to_string(),
+        parts: build_a2a_message_parts(&tool.server, text.to_string()),
+        context_id: arguments
+            .get("contextId")
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string()),
+        task_id: None,
+    };
+
+    let response_text = match post_a2a_message(
+        &tool.server,
+        &a2a_url,
+        format!("task_id:{}", Uuid::new_v4()),
+        message,
+        tool.skill_id.clone(),
+    )
+    .await
+    {
+        Ok(response_text) => response_text,
+        Err(e) => return Ok(tool_error(&e.to_string())),
+    };
+
+    let response: Value = serde_json::from_str(&response_text).unwrap_or(Value::Null);
+    let text = extract_response_text(&response).unwrap_or(response_text);
+
+    let mut result = json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": response.get("error").is_some()
+            || response.pointer("/result/status/state").and_then(|s| s.as_str()) == Some("failed"),
+    });
+    // structuredContent must be an object, a task or message result always is
+    if let Some(structured) = response.get("result").filter(|r| r.is_object()) {
+        result["structuredContent"] = structured.clone();
+    }
+
+    Ok(result)
+}
+
+// Tool names are "<agent>__<skill id>", reduced to the characters MCP clients accept
+fn load_tools() -> Result<BTreeMap<String, SkillTool>> {
+    let servers = SettingA2AServerDbManager::new().get_enabled()?;
+    let mut tools = BTreeMap::new();
+
+    for server in servers {
+        let Some(card) = server
+            .agent_card_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str::<Value>(json).ok())
+        else {
+            log::warn!("A2A server {} has no usable agent card, skipping", server.name);
+            continue;
+        };
+
+        for skill in card
+            .get("skills")
+            .and_then(|s| s.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let Some(skill_id) = skill.get("id").and_then(|id| id.as_str()) else {
+                continue;
+            };
+            let name = unique_tool_name(&tools, tool_name(&server.name, skill_id));
+            tools.insert(
+                name,
+                SkillTool {
+                    server: server.clone(),
+                    skill_id: skill_id.to_string(),
+                    description: skill_description(&server.name, skill),
+                },
+            );
+        }
+    }
+
+    Ok(tools)
+}
+
+fn tool_name(server_name: &str, skill_id: &str) -> String {
+    let sanitize = |value: &str| -> String {
+        value
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect()
+    };
+    let mut name = format!("{}__{}", sanitize(server_name), sanitize(skill_id));
+    name.truncate(MAX_TOOL_NAME_LEN);
+    name
+}
+
+// Sanitizing and truncating can map two skills to one name, later ones get a numeric suffix
+fn unique_tool_name(tools: &BTreeMap<String, SkillTool>, name: String) -> String {
+    if !tools.contains_key(&name) {
+        return name;
+    }
+    let unique = (2..)
+        .map(|n| {
+            let suffix = format!("_{}", n);
+            let mut candidate = name.clone();
+            candidate.truncate(MAX_TOOL_NAME_LEN - suffix.len());
+            candidate + &suffix
+        })
+        .find(|candidate| !tools.contains_key(candidate))
+        .unwrap_or_default();
+    log::warn!(
+        "MCP tool name {} is already taken, exposing the skill as {}",
+        name,
+        unique
+    );
+    unique
+}
+
+// Built from the AgentSkill fields: name, description, tags and examples
+fn skill_description(server_name: &str, skill: &Value) -> String {
+    let field = |key: &str| skill.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    let list = |key: &str| -> Vec<String> {
+        skill
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect()
+    };
+
+    let mut description = format!("[{}] {}", server_name, field("name"));
+    if !field("description").is_empty() {
+        description.push_str(": ");
+        description.push_str(field("description"));
+    }
+
+    let tags = list("tags");
+    if !tags.is_empty() {
+        description.push_str(&format!("\nTags: {}", tags.join(", ")));
+    }
+
+    let examples = list("examples");
+    if !examples.is_empty() {
+        description.push_str("\nExamples:");
+        for example in examples {
+            description.push_str(&format!("\n- {}", example));
+        }
+    }
+
+    description
+}
+
+fn tool_error(message: &str) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": message }],
+        "isError": true,
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::a2a_server;
+
+    fn skill_tool() -> SkillTool {
+        SkillTool {
+            server: a2a_server(None),
+            skill_id: "skill".to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn tool_names_are_sanitized_and_truncated() {
+        assert_eq!(tool_name("My Agent", "summarize.v2"), "My_Agent__summarize_v2");
+        assert_eq!(tool_name(&"a".repeat(80), "skill").len(), MAX_TOOL_NAME_LEN);
+    }
+
+    #[test]
+    fn colliding_tool_names_get_a_suffix() {
+        let mut tools = BTreeMap::new();
+        let long = tool_name(&"a".repeat(80), "first");
+        assert_eq!(unique_tool_name(&tools, long.clone()), long);
+
+        tools.insert(long.clone(), skill_tool());
+        let second = unique_tool_name(&tools, tool_name(&"a".repeat(80), "second"));
+        assert_eq!(second.len(), MAX_TOOL_NAME_LEN);
+        assert!(second.ends_with("_2"));
+
+        tools.insert(second, skill_tool());
+        assert!(unique_tool_name(&tools, long).ends_with("_3"));
+    }
+}
@@ -0,0 +1,1239 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, ErrorCategory, ErrorKind};
+
+#[derive(Serialize)]
+pub struct InvokeResponse<T> {
+    pub code: i32,
+    pub message: String,
+    pub data: Option<T>,
+    // Classification of a failure, absent on success
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<InvokeError>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvokeError {
+    pub kind: ErrorKind,
+    pub category: ErrorCategory,
+    pub retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl<T> InvokeResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            code: 0,
+            message: "ok".into(),
+            data: Some(data),
+            error: None,
+        }
+    }
+
+    pub fn error(err: AppError) -> Self {
+        Self {
+            code: err.code(),
+            message: err.message,
+            data: None,
+            error: Some(InvokeError {
+                kind: err.kind,
+                category: err.kind.category(),
+                retryable: err.kind.retryable(),
+                details: err.details,
+            }),
+        }
+    }
+}
+
+pub fn to_invoke_response<T>(err: anyhow::Error) -> InvokeResponse<T> {
+    InvokeResponse::error(AppError::from(err))
+}
+
+// AI chat related type definitions - Using openai crate instead
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionParams {
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub api_key: String,
+    // Defaults to the default preset of the DeepSeek model
+    pub preset_id: Option<i64>,
+}
+
+// Message structure for chat completions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String, // "system", "user", "assistant", etc.
+    pub content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionStreamParams {
+    pub messages: Vec<ChatMessage>, // 改为 messages 数组
+    pub api_key: String,
+    pub max_tokens: Option<u32>,
+    pub temperature: Option<f32>,
+    // Use this SettingModel's url and key instead of DeepSeek with api_key
+    pub model_id: Option<i32>,
+    // Defaults to the default preset of the model, the fields below override it
+    pub preset_id: Option<i64>,
+    pub top_p: Option<f32>,
+    pub stop: Option<Vec<String>>,
+    pub presence_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
+    pub seed: Option<i64>,
+    pub response_format: Option<serde_json::Value>,
+}
+
+impl ChatCompletionStreamParams {
+    /// The per-request overrides of the model preset
+    pub fn generation_overrides(&self) -> GenerationOptions {
+        GenerationOptions {
+            temperature: self.temperature,
+            top_p: self.top_p,
+            max_tokens: self.max_tokens,
+            stop: self.stop.clone(),
+            presence_penalty: self.presence_penalty,
+            frequency_penalty: self.frequency_penalty,
+            seed: self.seed,
+            response_format: self.response_format.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentSkill {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub examples: Vec<String>,
+    #[serde(rename = "inputModes")]
+    pub input_modes: Vec<String>,
+    #[serde(rename = "outputModes")]
+    pub output_modes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentProvider {
+    pub organization: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentCapabilities {
+    pub streaming: bool,
+    #[serde(rename = "pushNotifications")]
+    pub push_notifications: bool,
+    #[serde(rename = "stateTransitionHistory")]
+    pub state_transition_history: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentAuthentication {
+    pub schemes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentInterface {
+    pub url: String,
+    pub transport: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AgentCard {
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    // Transport spoken at `url`, JSONRPC when absent
+    #[serde(rename = "preferredTransport")]
+    pub preferred_transport: Option<String>,
+    #[serde(rename = "additionalInterfaces")]
+    pub additional_interfaces: Option<Vec<AgentInterface>>,
+    pub provider: AgentProvider,
+    pub version: String,
+    #[serde(rename = "documentationUrl")]
+    pub documentation_url: String,
+    pub capabilities: AgentCapabilities,
+    pub authentication: Option<AgentAuthentication>,
+    #[serde(rename = "defaultInputModes")]
+    pub default_input_modes: Vec<String>,
+    #[serde(rename = "defaultOutputModes")]
+    pub default_output_modes: Vec<String>,
+    pub skills: Vec<AgentSkill>,
+}
+
+// A2A message related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A2ATextPart {
+    pub kind: String,
+    pub text: String,
+}
+
+impl A2ATextPart {
+    pub fn new(text: String) -> Self {
+        Self {
+            kind: "text".to_string(),
+            text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A2ADataPart {
+    pub kind: String,
+    pub data: String,
+}
+
+impl A2ADataPart {
+    pub fn new(data: String) -> Self {
+        Self {
+            kind: "data".to_string(),
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum A2AMessagePart {
+    Text(A2ATextPart),
+    Data(A2ADataPart),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A2AMessage {
+    pub message_id: String,
+    pub kind: String,
+    pub role: String,
+    pub parts: Vec<A2AMessagePart>,
+    #[serde(rename = "contextId", skip_serializing_if = "Option::is_none")]
+    pub context_id: Option<String>,
+    #[serde(rename = "taskId", skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct A2ARequest {
+    pub id: String,
+    pub message: A2AMessage,
+    pub metadata: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JSONRPCRequest<T> {
+    pub jsonrpc: String,
+    pub id: String,
+    pub method: String,
+    pub params: T,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct A2AMessageParams {
+    pub a2a_server_id: i32,
+    pub a2a_url: String,
+    pub task_id: String,
+    pub message_id: String,
+    pub header_skill_id: String,
+    pub text: String,
+    // Conversation the message belongs to, used to thread contextId across turns
+    pub conversation_id: Option<String>,
+    // Explicit contextId, takes precedence over the one remembered for the conversation
+    pub context_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContinueA2ATaskParams {
+    pub conversation_id: String,
+    pub a2a_server_id: i32,
+    pub a2a_url: String,
+    pub message_id: String,
+    pub header_skill_id: String,
+    pub text: String,
+}
+
+// A2A conversation context related type definitions
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct A2AConversationContext {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub conversation_id: String,
+    pub a2a_server_id: i32,
+    pub context_id: Option<String>,
+    pub task_id: Option<String>,
+    pub task_state: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AgentCardParams {
+    pub url: String,
+    pub token: Option<String>,
+}
+
+// Model configuration related type definitions
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingModel {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub model_key: String,
+    // Model name sent to the API, e.g. deepseek-chat; model_key only names the provider
+    pub model_name: Option<String>,
+    pub enabled: bool,
+    pub api_url: String,
+    pub api_key: String,
+    // Context window in tokens, defaults to the token estimate table entry of the model
+    pub context_window: Option<u32>,
+    // drop_oldest, truncate or summarize, applied when a request exceeds the window
+    pub context_policy: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingModelParams {
+    pub model_key: String,
+    pub model_name: Option<String>,
+    pub enabled: bool,
+    pub api_url: String,
+    pub api_key: String,
+    pub context_window: Option<u32>,
+    pub context_policy: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSettingModelParams {
+    pub id: i32,
+    pub model_name: Option<String>,
+    pub enabled: Option<bool>,
+    pub api_url: Option<String>,
+    pub api_key: Option<String>,
+    pub context_window: Option<u32>,
+    pub context_policy: Option<String>,
+}
+
+// A2A Server configuration related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingA2AServer {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub name: String,
+    pub agent_card_url: String,
+    pub agent_card_json: Option<String>,
+    pub custom_header_json: Option<String>,
+    pub protocol_data_object_settings: Option<String>,
+    // Transport forced for this server ("JSONRPC", "HTTP+JSON", "GRPC"), picked from the card when empty
+    pub transport: Option<String>,
+    pub enabled: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingA2AServerParams {
+    pub name: String,
+    pub agent_card_url: String,
+    pub agent_card_json: Option<String>,
+    pub custom_header_json: Option<String>,
+    pub protocol_data_object_settings: Option<String>,
+    pub transport: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSettingA2AServerParams {
+    pub id: i32,
+    pub name: Option<String>,
+    pub agent_card_url: Option<String>,
+    pub agent_card_json: Option<String>,
+    pub custom_header_json: Option<String>,
+    pub protocol_data_object_settings: Option<String>,
+    pub transport: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+// A2A artifact related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A2AArtifact {
+    #[serde(rename = "artifactId")]
+    pub artifact_id: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parts: Vec<serde_json::Value>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct A2AArtifactUpdateEvent {
+    #[serde(rename = "taskId")]
+    pub task_id: String,
+    #[serde(rename = "contextId")]
+    pub context_id: Option<String>,
+    pub artifact: A2AArtifact,
+    pub append: Option<bool>,
+    #[serde(rename = "lastChunk")]
+    pub last_chunk: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredArtifact {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub artifact_id: String,
+    pub task_id: String,
+    pub context_id: Option<String>,
+    pub conversation_id: Option<String>,
+    pub a2a_server_id: Option<i32>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub part_index: i32,
+    pub part_kind: String,
+    pub mime_type: Option<String>,
+    pub file_name: Option<String>,
+    // Empty until a part that only carries a uri is first opened or saved
+    pub content_hash: String,
+    pub size_bytes: i64,
+    // Where the agent said the file can be fetched from
+    pub uri: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactQueryParams {
+    pub conversation_id: Option<String>,
+    pub task_id: Option<String>,
+    pub a2a_server_id: Option<i32>,
+}
+
+// Conversation history related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationMessage {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub message_id: String,
+    pub conversation_id: String,
+    pub role: String,
+    // question, thought, action, observation, final_answer, answer, error, agent_request, agent_response
+    pub kind: String,
+    pub content: String,
+    pub a2a_server_id: Option<i32>,
+    pub agent_name: Option<String>,
+    pub skill_id: Option<String>,
+    pub skill_name: Option<String>,
+    pub task_id: Option<String>,
+    pub task_state: Option<String>,
+    pub model_key: Option<String>,
+    // Agent interface that served an agent_response, and the transport spoken there
+    pub endpoint_url: Option<String>,
+    pub transport: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveConversationMessageParams {
+    pub message_id: String,
+    pub conversation_id: String,
+    pub role: String,
+    pub kind: String,
+    pub content: String,
+    pub a2a_server_id: Option<i32>,
+    pub agent_name: Option<String>,
+    pub skill_id: Option<String>,
+    pub skill_name: Option<String>,
+    pub task_id: Option<String>,
+    pub task_state: Option<String>,
+    pub model_key: Option<String>,
+    pub endpoint_url: Option<String>,
+    pub transport: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportConversationParams {
+    pub conversation_id: String,
+    // markdown, json or html
+    pub format: String,
+    // Write the export to this file instead of returning it
+    pub target_path: Option<String>,
+}
+
+// Conversation search related type definitions
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSearchParams {
+    pub query: String,
+    pub agent_name: Option<String>,
+    pub model_key: Option<String>,
+    pub task_state: Option<String>,
+    // Inclusive bounds, "YYYY-MM-DD" or "YYYY-MM-DD HH:MM:SS"
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    pub highlight_start: Option<String>,
+    pub highlight_end: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSearchHit {
+    #[serde(flatten)]
+    pub message: ConversationMessage,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConversationSearchPage {
+    pub total: i64,
+    pub page: u32,
+    pub page_size: u32,
+    pub items: Vec<ConversationSearchHit>,
+}
+
+// Batch run related type definitions
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRunParams {
+    pub a2a_server_id: i32,
+    pub skill_id: String,
+    // CSV with a "prompt" column (optional "id") or JSONL with {"prompt": ..., "id": ...} lines
+    pub input_path: String,
+    // Defaults to "<input>.results.jsonl"; a ".csv" extension writes CSV instead
+    pub output_path: Option<String>,
+    pub concurrency: Option<usize>,
+    // Defaults to the url of the stored agent card
+    pub a2a_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchPrompt {
+    pub id: Option<String>,
+    pub prompt: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRun {
+    pub id: Option<i64>,
+    pub a2a_server_id: i32,
+    pub skill_id: String,
+    pub input_path: String,
+    pub output_path: String,
+    pub concurrency: i64,
+    pub status: String,
+    pub total: i64,
+    pub succeeded: i64,
+    pub failed: i64,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRunItem {
+    pub id: Option<i64>,
+    pub run_id: i64,
+    pub item_index: i64,
+    pub prompt_id: Option<String>,
+    pub prompt: String,
+    // ok or error
+    pub status: String,
+    pub latency_ms: i64,
+    pub task_state: Option<String>,
+    pub response_text: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRunProgress {
+    pub run_id: i64,
+    pub completed: usize,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub is_complete: bool,
+    pub item: Option<BatchRunItem>,
+}
+
+// Load test related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTestParams {
+    pub name: Option<String>,
+    pub a2a_server_id: i32,
+    pub skill_id: String,
+    pub prompt: String,
+    // Defaults to the url of the stored agent card
+    pub a2a_url: Option<String>,
+    // "rate" fires at a fixed requests/sec, "concurrency" keeps N requests in flight
+    pub mode: String,
+    pub rate_per_sec: Option<f64>,
+    pub concurrency: Option<usize>,
+    pub duration_secs: u64,
+    // Use message/stream instead of message/send
+    #[serde(default)]
+    pub stream: bool,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyStats {
+    pub count: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    // Upper bound in ms (None for the overflow bucket) and sample count
+    pub histogram: Vec<(Option<f64>, usize)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTestSummary {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub elapsed_secs: f64,
+    pub throughput_rps: f64,
+    pub latency: LatencyStats,
+    pub time_to_first_event: Option<LatencyStats>,
+    pub errors_by_jsonrpc_code: std::collections::BTreeMap<String, usize>,
+    pub errors_by_http_status: std::collections::BTreeMap<String, usize>,
+    pub transport_errors: std::collections::BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTestRun {
+    pub id: Option<i64>,
+    pub name: Option<String>,
+    pub a2a_server_id: i32,
+    pub skill_id: String,
+    pub status: String,
+    pub params: LoadTestParams,
+    pub summary: Option<LoadTestSummary>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadTestProgress {
+    pub run_id: i64,
+    pub elapsed_secs: f64,
+    pub sent: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub is_complete: bool,
+    pub summary: Option<LoadTestSummary>,
+}
+
+// Comparison related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareTarget {
+    // "a2a" sends to an A2A server skill, "model" to an LLM
+    pub kind: String,
+    pub a2a_server_id: Option<i32>,
+    pub skill_id: Option<String>,
+    pub model_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareParams {
+    pub name: Option<String>,
+    pub prompt: String,
+    // Only used for model targets
+    pub system_prompt: Option<String>,
+    pub targets: Vec<CompareTarget>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comparison {
+    pub id: Option<i64>,
+    pub name: Option<String>,
+    pub prompt: String,
+    pub system_prompt: Option<String>,
+    pub target_count: i64,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonResult {
+    pub id: Option<i64>,
+    pub comparison_id: i64,
+    pub target_index: i64,
+    pub target_kind: String,
+    pub a2a_server_id: Option<i32>,
+    pub skill_id: Option<String>,
+    pub model_id: Option<i32>,
+    // Agent name or model key
+    pub label: String,
+    pub status: String,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub latency_ms: i64,
+    pub time_to_first_token_ms: Option<i64>,
+    pub prompt_tokens: Option<i64>,
+    pub completion_tokens: Option<i64>,
+    pub total_tokens: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareStreamChunk {
+    pub comparison_id: i64,
+    pub target_index: usize,
+    // "{comparison_id}:{target_index}", one channel per target
+    pub channel: String,
+    pub content: String,
+    pub is_complete: bool,
+    pub error: Option<String>,
+    pub result: Option<ComparisonResult>,
+}
+
+// MCP server configuration related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingMcpServer {
+    #[serde(skip_deserializing)]
+    pub id: Option<i32>,
+    pub name: String,
+    // "stdio" launches `command` as a subprocess, "http" connects to a streamable HTTP `url`
+    pub transport: String,
+    pub command: Option<String>,
+    // JSON array of arguments
+    pub args_json: Option<String>,
+    // JSON object of extra environment variables for the subprocess
+    pub env_json: Option<String>,
+    pub url: Option<String>,
+    // JSON object of extra HTTP headers
+    pub custom_header_json: Option<String>,
+    // Tools from the last successful tools/list
+    pub tools_json: Option<String>,
+    pub enabled: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingMcpServerParams {
+    pub name: String,
+    pub transport: String,
+    pub command: Option<String>,
+    pub args_json: Option<String>,
+    pub env_json: Option<String>,
+    pub url: Option<String>,
+    pub custom_header_json: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSettingMcpServerParams {
+    pub id: i32,
+    pub name: Option<String>,
+    pub transport: Option<String>,
+    pub command: Option<String>,
+    pub args_json: Option<String>,
+    pub env_json: Option<String>,
+    pub url: Option<String>,
+    pub custom_header_json: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpTool {
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub input_schema: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallMcpToolParams {
+    pub mcp_server_id: i32,
+    pub tool_name: String,
+    #[serde(default)]
+    pub arguments: serde_json::Value,
+}
+
+// Host agent server related type definitions
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostAgentServerParams {
+    pub port: u16,
+    pub host: Option<String>,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostAgentServerStatus {
+    pub running: bool,
+    pub url: Option<String>,
+    pub auth_required: bool,
+}
+
+// Prompt template related type definitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptVariable {
+    // Referenced as {{name}} in the template content
+    pub name: String,
+    // string, number, integer, boolean or enum
+    #[serde(rename = "type")]
+    pub var_type: String,
+    #[serde(default)]
+    pub required: bool,
+    pub default: Option<serde_json::Value>,
+    pub description: Option<String>,
+    // Allowed values of an enum variable
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplate {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: Option<String>,
+    pub content: String,
+    pub variables: Vec<PromptVariable>,
+    pub tags: Vec<String>,
+    // Optional target that pre-fills send_a2a_message
+    pub a2a_server_id: Option<i32>,
+    pub skill_id: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplateParams {
+    pub name: String,
+    pub description: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub variables: Vec<PromptVariable>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub a2a_server_id: Option<i32>,
+    pub skill_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePromptTemplateParams {
+    pub id: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub content: Option<String>,
+    pub variables: Option<Vec<PromptVariable>>,
+    pub tags: Option<Vec<String>>,
+    pub a2a_server_id: Option<i32>,
+    pub skill_id: Option<String>,
+    // Removes the server and skill binding, applied before a new binding
+    #[serde(default)]
+    pub clear_target: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderPromptTemplateParams {
+    pub id: i64,
+    #[serde(default)]
+    pub values: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedPromptTemplate {
+    pub text: String,
+    // Target fields of send_a2a_message, set when the template is bound
+    pub a2a_server_id: Option<i32>,
+    pub a2a_url: Option<String>,
+    pub header_skill_id: Option<String>,
+}
+
+// Host agent profile related type definitions
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostAgentProfile {
+    pub id: Option<i64>,
+    pub name: String,
+    pub description: Option<String>,
+    // ReAct system prompt with {{AGENT_SKILLS}} (and optionally {{MCP_TOOLS}}), None uses the built-in one
+    pub system_prompt_template: Option<String>,
+    pub max_iterations: i64,
+    // SettingModel used for planning, None keeps the default model choice
+    pub model_id: Option<i32>,
+    // Enabled A2A servers the planner may use, None means all of them
+    pub a2a_server_ids: Option<Vec<i32>>,
+    pub is_default: bool,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HostAgentProfileParams {
+    pub name: String,
+    pub description: Option<String>,
+    pub system_prompt_template: Option<String>,
+    pub max_iterations: Option<i64>,
+    pub model_id: Option<i32>,
+    pub a2a_server_ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateHostAgentProfileParams {
+    pub id: i64,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub system_prompt_template: Option<String>,
+    pub max_iterations: Option<i64>,
+    pub model_id: Option<i32>,
+    pub a2a_server_ids: Option<Vec<i32>>,
+    // Fall back to the built-in prompt, the default model and all agents respectively
+    #[serde(default)]
+    pub reset_system_prompt: bool,
+    #[serde(default)]
+    pub reset_model: bool,
+    #[serde(default)]
+    pub reset_agents: bool,
+}
+
+// Model generation preset related type definitions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationOptions {
+    pub temperature: Option<f32>,
+    pub top_p: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub stop: Option<Vec<String>>,
+    pub presence_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
+    pub seed: Option<i64>,
+    // OpenAI response_format, e.g. {"type": "json_object"}
+    pub response_format: Option<serde_json::Value>,
+}
+
+impl GenerationOptions {
+    /// Options of `self` overridden by every field set in `overrides`
+    pub fn merge(&self, overrides: &GenerationOptions) -> GenerationOptions {
+        GenerationOptions {
+            temperature: overrides.temperature.or(self.temperature),
+            top_p: overrides.top_p.or(self.top_p),
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            stop: overrides.stop.clone().or_else(|| self.stop.clone()),
+            presence_penalty: overrides.presence_penalty.or(self.presence_penalty),
+            frequency_penalty: overrides.frequency_penalty.or(self.frequency_penalty),
+            seed: overrides.seed.or(self.seed),
+            response_format: overrides
+                .response_format
+                .clone()
+                .or_else(|| self.response_format.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPreset {
+    pub id: Option<i64>,
+    pub model_id: i32,
+    pub name: String,
+    // Applied when a request names the model but no preset
+    pub is_default: bool,
+    pub options: GenerationOptions,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPresetParams {
+    pub model_id: i32,
+    pub name: String,
+    #[serde(default)]
+    pub is_default: bool,
+    #[serde(default)]
+    pub options: GenerationOptions,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateModelPresetParams {
+    pub id: i64,
+    pub name: Option<String>,
+    // Replaces all options of the preset
+    pub options: Option<GenerationOptions>,
+}
+
+// Application log related type definitions
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    // Module path of the record, e.g. app_lib::handler::chat
+    pub target: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogQueryParams {
+    // Minimum level, e.g. "warn" keeps warnings and errors
+    pub level: Option<String>,
+    // Prefix of the record target
+    pub module: Option<String>,
+    // Case-insensitive text the message must contain
+    pub search: Option<String>,
+    // Most recent entries to return, defaults to 200
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportLogsParams {
+    pub target_path: String,
+    pub level: Option<String>,
+    pub module: Option<String>,
+}
+
+// HTTP traffic recorder related type definitions
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficHeader {
+    pub name: String,
+    // Credential values are stored as [REDACTED]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+    // Since the request was sent
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficEntry {
+    pub id: Option<i64>,
+    // "a2a", "agent_card", "llm" or "replay"
+    pub source: String,
+    pub method: String,
+    pub url: String,
+    pub request_headers: Vec<TrafficHeader>,
+    pub request_body: Option<String>,
+    // None when no response arrived
+    pub status: Option<u16>,
+    pub response_headers: Vec<TrafficHeader>,
+    pub response_body: Option<String>,
+    pub sse_events: Vec<TrafficSseEvent>,
+    pub error: Option<String>,
+    pub started_at: String,
+    pub duration_ms: u64,
+    // Time to the response headers
+    pub wait_ms: Option<u64>,
+}
+
+/// A traffic entry without headers and bodies, for the list view
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficEntrySummary {
+    pub id: i64,
+    pub source: String,
+    pub method: String,
+    pub url: String,
+    pub status: Option<u16>,
+    pub error: Option<String>,
+    pub sse_event_count: usize,
+    pub started_at: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficQueryParams {
+    pub source: Option<String>,
+    // Case-insensitive text the URL must contain
+    pub url: Option<String>,
+    // Most recent entries to return, defaults to 200
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportTrafficHarParams {
+    pub target_path: String,
+    // Entries to export, all of them when empty
+    pub ids: Option<Vec<i64>>,
+}
+
+// Replay related type definitions
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayParams {
+    // Recorded A2A exchange to send again
+    pub traffic_entry_id: i64,
+    pub a2a_server_id: i32,
+    // Defaults to the url of the server's agent card
+    pub a2a_url: Option<String>,
+    // Replaces the recorded request body
+    pub body: Option<String>,
+    // Added to the recorded headers, replacing those with the same name
+    pub headers: Option<std::collections::BTreeMap<String, String>>,
+    // Recorded headers to leave out
+    pub remove_headers: Option<Vec<String>>,
+    // JSON pointers left out of the diff on top of the volatile fields
+    pub ignore_paths: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiffEntry {
+    // JSON pointer of the differing value
+    pub path: String,
+    // "added", "removed" or "changed"
+    pub kind: String,
+    pub original: Option<serde_json::Value>,
+    pub replayed: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResult {
+    pub url: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    // The replayed response, SSE responses as the array of their event payloads
+    pub response: serde_json::Value,
+    pub identical: bool,
+    pub differences: Vec<JsonDiffEntry>,
+}
+
+// Conformance suite related type definitions
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceParams {
+    pub a2a_server_id: i32,
+    // Defaults to the url of the fetched agent card
+    pub a2a_url: Option<String>,
+    // Sent as X-A2A-Skill-Id, defaults to the first skill of the card
+    pub skill_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceCheck {
+    // Stable id, e.g. "jsonrpc.unknown_method"
+    pub id: String,
+    pub name: String,
+    // "passed", "failed" or "skipped"
+    pub status: String,
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceRun {
+    pub id: Option<i64>,
+    pub a2a_server_id: i32,
+    pub a2a_url: Option<String>,
+    // "running", "passed" or "failed"
+    pub status: String,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub checks: Vec<ConformanceCheck>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConformanceProgress {
+    pub run_id: i64,
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub is_complete: bool,
+    pub check: Option<ConformanceCheck>,
+}
+
+// Agent card lint related type definitions
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardLintParams {
+    // Where the card is served, fetched unless card_json is given
+    pub url: Option<String>,
+    // Lint this card text instead of fetching one
+    pub card_json: Option<String>,
+    // Fetch the card of a configured server, with its custom headers
+    pub a2a_server_id: Option<i32>,
+    pub token: Option<String>,
+    // Probe the agent url and declared capabilities, defaults to true
+    pub check_network: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardLintFinding {
+    // JSON pointer into the card, "" for the card itself
+    pub pointer: String,
+    // "error", "warning" or "info"
+    pub severity: String,
+    // Stable rule name, e.g. "duplicate-skill-id"
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CardLintReport {
+    pub card_url: Option<String>,
+    // No error findings
+    pub valid: bool,
+    pub findings: Vec<CardLintFinding>,
+}
@@ -0,0 +1,434 @@
+//! Prompt templates: `{{name}}` placeholders filled from typed variables, and
+//! the checks a template and its bound agent target must pass before it is stored
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::{
+    a2a::client::card_service_url,
+    db::a2a_db::SettingA2AServerDbManager,
+    error::AppError,
+    model::{PromptTemplate, PromptVariable, RenderedPromptTemplate},
+};
+
+const VARIABLE_TYPES: [&str; 5] = ["string", "number", "integer", "boolean", "enum"];
+
+/// Substitute the variables of a template and resolve its bound target
+pub fn render_template(
+    template: PromptTemplate,
+    values: &serde_json::Map<String, Value>,
+) -> Result<RenderedPromptTemplate, AppError> {
+    let text = render(&template, values).map_err(AppError::invalid_params)?;
+
+    let a2a_url = match template.a2a_server_id {
+        Some(a2a_server_id) => match SettingA2AServerDbManager::new().get_by_id(a2a_server_id) {
+            Ok(Some(server)) => card_service_url(&server),
+            Ok(None) => {
+                log::warn!(
+                    "Prompt template {} is bound to a deleted A2A server {}",
+                    template.name,
+                    a2a_server_id
+                );
+                None
+            }
+            Err(e) => return Err(AppError::from(e.context("Failed to get A2A server"))),
+        },
+        None => None,
+    };
+
+    Ok(RenderedPromptTemplate {
+        text,
+        a2a_server_id: template.a2a_server_id,
+        a2a_url,
+        header_skill_id: template.skill_id,
+    })
+}
+
+/// The template content with every variable substituted, or every problem found
+pub fn render(
+    template: &PromptTemplate,
+    values: &serde_json::Map<String, Value>,
+) -> Result<String, String> {
+    let mut missing = Vec::new();
+    let mut errors = Vec::new();
+    let mut rendered = Vec::with_capacity(template.variables.len());
+
+    for variable in &template.variables {
+        let value = values
+            .get(&variable.name)
+            .filter(|value| !is_empty_value(value))
+            .or(variable
+                .default
+                .as_ref()
+                .filter(|value| !is_empty_value(value)));
+
+        match value {
+            Some(value) => match format_value(variable, value) {
+                Ok(text) => rendered.push((variable.name.as_str(), text)),
+                Err(message) => errors.push(message),
+            },
+            None if variable.required => missing.push(variable.name.as_str()),
+            None => rendered.push((variable.name.as_str(), String::new())),
+        }
+    }
+
+    if !missing.is_empty() {
+        errors.insert(
+            0,
+            format!("Missing required variables: {}", missing.join(", ")),
+        );
+    }
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+
+    Ok(substitute(&template.content, |name| {
+        rendered
+            .iter()
+            .find(|(variable, _)| *variable == name)
+            .map(|(_, text)| text.clone())
+    }))
+}
+
+// Replace every {{name}}, placeholders the lookup does not know are kept as they are
+fn substitute(content: &str, mut lookup: impl FnMut(&str) -> Option<String>) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + 2 + end + 2];
+        output.push_str(&rest[..start]);
+        match lookup(placeholder[2..placeholder.len() - 2].trim()) {
+            Some(value) => output.push_str(&value),
+            None => output.push_str(placeholder),
+        }
+        rest = &rest[start + placeholder.len()..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+fn placeholders(content: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    substitute(content, |name| {
+        names.push(name.to_string());
+        None
+    });
+    names
+}
+
+fn format_value(variable: &PromptVariable, value: &Value) -> Result<String, String> {
+    let invalid = || {
+        format!(
+            "Variable {} expects a {} value",
+            variable.name, variable.var_type
+        )
+    };
+
+    match variable.var_type.as_str() {
+        "number" => match value {
+            Value::Number(number) => Ok(number.to_string()),
+            Value::String(text) => text
+                .trim()
+                .parse::<f64>()
+                .map(|_| text.trim().to_string())
+                .map_err(|_| invalid()),
+            _ => Err(invalid()),
+        },
+        "integer" => match value {
+            Value::Number(number) if number.is_i64() || number.is_u64() => Ok(number.to_string()),
+            Value::String(text) => text
+                .trim()
+                .parse::<i64>()
+                .map(|number| number.to_string())
+                .map_err(|_| invalid()),
+            _ => Err(invalid()),
+        },
+        "boolean" => match value {
+            Value::Bool(flag) => Ok(flag.to_string()),
+            Value::String(text) if text == "true" || text == "false" => Ok(text.clone()),
+            _ => Err(invalid()),
+        },
+        "enum" => match value.as_str() {
+            Some(text) if variable.options.iter().any(|option| option == text) => {
+                Ok(text.to_string())
+            }
+            _ => Err(format!(
+                "Variable {} must be one of: {}",
+                variable.name,
+                variable.options.join(", ")
+            )),
+        },
+        _ => match value {
+            Value::String(text) => Ok(text.clone()),
+            other => Ok(other.to_string()),
+        },
+    }
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.is_empty(),
+        _ => false,
+    }
+}
+
+/// Check a template's name, content and variable declarations
+pub fn validate_template(
+    name: &str,
+    content: &str,
+    variables: &[PromptVariable],
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+    if content.trim().is_empty() {
+        return Err("Template content cannot be empty".to_string());
+    }
+
+    let mut names = HashSet::new();
+    for variable in variables {
+        let valid_name = !variable.name.is_empty()
+            && variable
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(format!(
+                "Invalid variable name \"{}\", use letters, digits and underscores",
+                variable.name
+            ));
+        }
+        if !names.insert(variable.name.as_str()) {
+            return Err(format!("Duplicate variable: {}", variable.name));
+        }
+        if !VARIABLE_TYPES.contains(&variable.var_type.as_str()) {
+            return Err(format!(
+                "Unknown type \"{}\" of variable {}, expected one of: {}",
+                variable.var_type,
+                variable.name,
+                VARIABLE_TYPES.join(", ")
+            ));
+        }
+        if variable.var_type == "enum" && variable.options.is_empty() {
+            return Err(format!("Enum variable {} needs options", variable.name));
+        }
+        if let Some(default) = variable.default.as_ref().filter(|d| !is_empty_value(d)) {
+            format_value(variable, default)
+                .map_err(|message| format!("Invalid default: {}", message))?;
+        }
+    }
+
+    let undeclared: Vec<String> = placeholders(content)
+        .into_iter()
+        .filter(|placeholder| !names.contains(placeholder.as_str()))
+        .collect();
+    if !undeclared.is_empty() {
+        return Err(format!(
+            "Template uses undeclared variables: {}",
+            undeclared.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+/// A bound skill must exist on the bound server's agent card
+pub fn validate_target(a2a_server_id: Option<i32>, skill_id: Option<&str>) -> Result<(), String> {
+    let Some(a2a_server_id) = a2a_server_id else {
+        return match skill_id {
+            Some(_) => Err("A skill binding needs an A2A server".to_string()),
+            None => Ok(()),
+        };
+    };
+
+    let server = match SettingA2AServerDbManager::new().get_by_id(a2a_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return Err("A2A server not found".to_string()),
+        Err(e) => return Err(format!("Failed to get A2A server: {}", e)),
+    };
+
+    let Some(skill_id) = skill_id else {
+        return Ok(());
+    };
+    let card: Value = server
+        .agent_card_json
+        .as_deref()
+        .and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default();
+    let known = card
+        .get("skills")
+        .and_then(|s| s.as_array())
+        .into_iter()
+        .flatten()
+        .any(|skill| skill.get("id").and_then(|id| id.as_str()) == Some(skill_id));
+
+    if known {
+        Ok(())
+    } else {
+        Err(format!(
+            "Skill {} not found on A2A server {}",
+            skill_id, server.name
+        ))
+    }
+}
+
+/// Trimmed, sorted and deduplicated tags without empty ones
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn variables(variables: Value) -> Vec<PromptVariable> {
+        serde_json::from_value(variables).unwrap()
+    }
+
+    fn template(content: &str, variables: Vec<PromptVariable>) -> PromptTemplate {
+        PromptTemplate {
+            id: Some(1),
+            name: "Greeting".to_string(),
+            description: None,
+            content: content.to_string(),
+            variables,
+            tags: Vec::new(),
+            a2a_server_id: None,
+            skill_id: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn values(values: Value) -> serde_json::Map<String, Value> {
+        values.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn required_variables_need_a_value_and_others_fall_back_to_their_default() {
+        let template = template(
+            "Hello {{ name }}, {{greeting}}{{suffix}}",
+            variables(json!([
+                { "name": "name", "type": "string", "required": true },
+                { "name": "greeting", "type": "string", "default": "welcome" },
+                { "name": "suffix", "type": "string" }
+            ])),
+        );
+
+        assert_eq!(
+            render(&template, &values(json!({ "name": "Ada" }))).unwrap(),
+            "Hello Ada, welcome"
+        );
+        assert_eq!(
+            render(
+                &template,
+                &values(json!({ "name": "Ada", "greeting": "hi" }))
+            )
+            .unwrap(),
+            "Hello Ada, hi"
+        );
+        assert_eq!(
+            render(&template, &values(json!({ "name": "" }))).unwrap_err(),
+            "Missing required variables: name"
+        );
+    }
+
+    #[test]
+    fn enum_values_must_be_one_of_the_options() {
+        let template = template(
+            "Answer in {{tone}}",
+            variables(json!([
+                { "name": "tone", "type": "enum", "options": ["formal", "casual"] }
+            ])),
+        );
+
+        assert_eq!(
+            render(&template, &values(json!({ "tone": "casual" }))).unwrap(),
+            "Answer in casual"
+        );
+        assert_eq!(
+            render(&template, &values(json!({ "tone": "angry" }))).unwrap_err(),
+            "Variable tone must be one of: formal, casual"
+        );
+    }
+
+    #[test]
+    fn numbers_integers_and_booleans_are_coerced_from_strings() {
+        let typed = variables(json!([
+            { "name": "ratio", "type": "number" },
+            { "name": "count", "type": "integer" },
+            { "name": "strict", "type": "boolean" }
+        ]));
+        let [ratio, count, strict] = [&typed[0], &typed[1], &typed[2]];
+
+        assert_eq!(format_value(ratio, &json!(0.5)).unwrap(), "0.5");
+        assert_eq!(format_value(ratio, &json!(" 1.5 ")).unwrap(), "1.5");
+        assert!(format_value(ratio, &json!("many")).is_err());
+        assert!(format_value(ratio, &json!(true)).is_err());
+
+        assert_eq!(format_value(count, &json!(3)).unwrap(), "3");
+        assert_eq!(format_value(count, &json!("42")).unwrap(), "42");
+        assert!(format_value(count, &json!(1.5)).is_err());
+        assert!(format_value(count, &json!("1.5")).is_err());
+
+        assert_eq!(format_value(strict, &json!(false)).unwrap(), "false");
+        assert_eq!(format_value(strict, &json!("true")).unwrap(), "true");
+        assert_eq!(
+            format_value(strict, &json!("yes")).unwrap_err(),
+            "Variable strict expects a boolean value"
+        );
+    }
+
+    #[test]
+    fn undeclared_placeholders_are_refused_and_unknown_ones_kept() {
+        let declared = variables(json!([{ "name": "name", "type": "string" }]));
+
+        assert!(validate_template("Greeting", "Hi {{name}}", &declared).is_ok());
+        assert_eq!(
+            validate_template("Greeting", "Hi {{name}} from {{ team }}", &declared).unwrap_err(),
+            "Template uses undeclared variables: team"
+        );
+        assert_eq!(
+            substitute("{{a}} {{b}} {{unclosed", |name| {
+                (name == "a").then(|| "1".to_string())
+            }),
+            "1 {{b}} {{unclosed"
+        );
+    }
+
+    #[test]
+    fn variable_declarations_are_checked() {
+        let check = |declared: Value| validate_template("Greeting", "Hi", &variables(declared));
+
+        assert!(check(json!([{ "name": "bad-name", "type": "string" }])).is_err());
+        assert!(
+            check(json!([
+                { "name": "a", "type": "string" },
+                { "name": "a", "type": "number" }
+            ]))
+            .is_err()
+        );
+        assert!(check(json!([{ "name": "a", "type": "date" }])).is_err());
+        assert!(check(json!([{ "name": "a", "type": "enum" }])).is_err());
+        assert_eq!(
+            check(json!([{ "name": "a", "type": "integer", "default": "x" }])).unwrap_err(),
+            "Invalid default: Variable a expects a integer value"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+pub const REDACTED: &str = "[REDACTED]";
+
+// Header names that carry credentials, matched case-insensitively as substrings
+const SENSITIVE_HEADER_MARKERS: [&str; 8] = [
+    "auth",
+    "cookie",
+    "token",
+    "secret",
+    "password",
+    "api-key",
+    "apikey",
+    "credential",
+];
+
+/// Whether a header name is likely to carry a credential
+pub fn is_sensitive_header(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_HEADER_MARKERS
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+/// Redact credential values of a `custom_header_json` object, keeping the header names
+pub fn redact_header_json(headers_json: &str) -> serde_json::Value {
+    match serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(headers_json) {
+        Ok(headers) => serde_json::Value::Object(
+            headers
+                .into_iter()
+                .map(|(key, value)| {
+                    if is_sensitive_header(&key) {
+                        (key, serde_json::Value::String(REDACTED.to_string()))
+                    } else {
+                        (key, value)
+                    }
+                })
+                .collect(),
+        ),
+        // Unparseable headers might still contain a secret, hide them entirely
+        Err(_) => serde_json::Value::String(REDACTED.to_string()),
+    }
+}
+
+/// Credential values found in a `custom_header_json` object, used to scrub
+/// them from any free text they may have leaked into
+pub fn header_secret_values(headers_json: &str) -> Vec<String> {
+    let Ok(headers) =
+        serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(headers_json)
+    else {
+        return Vec::new();
+    };
+
+    let mut secrets = Vec::new();
+    for (key, value) in headers {
+        if !is_sensitive_header(&key) {
+            continue;
+        }
+        if let Some(value) = value.as_str() {
+            let value = value.trim();
+            let token = value
+                .strip_prefix("Bearer ")
+                .or_else(|| value.strip_prefix("bearer "))
+                .unwrap_or(value)
+                .trim();
+            // Very short values would redact unrelated text
+            if token.len() >= 6 {
+                secrets.push(token.to_string());
+            }
+        }
+    }
+    secrets
+}
+
+/// Replace every occurrence of the given secrets in a text
+pub fn redact_secrets(text: &str, secrets: &[String]) -> String {
+    let mut redacted = text.to_string();
+    for secret in secrets {
+        if !secret.is_empty() {
+            redacted = redacted.replace(secret.as_str(), REDACTED);
+        }
+    }
+    redact_bearer_tokens(&redacted)
+}
+
+/// Replace the token following any `Bearer ` scheme marker
+pub fn redact_bearer_tokens(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = find_ignore_ascii_case(rest, "bearer ") {
+        let (before, after) = rest.split_at(index + "bearer ".len());
+        redacted.push_str(before);
+
+        let token_len = after
+            .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == ',')
+            .unwrap_or(after.len());
+        if token_len > 0 && !after.starts_with(REDACTED) {
+            redacted.push_str(REDACTED);
+        } else {
+            redacted.push_str(&after[..token_len]);
+        }
+        rest = &after[token_len..];
+    }
+
+    redacted.push_str(rest);
+    redacted
+}
+
+/// Scrub a log line: bearer tokens, `key=value` / `"key": "value"` pairs whose key
+/// names a credential, and provider API keys that start with `sk-`
+pub fn redact_log_text(text: &str) -> String {
+    redact_api_keys(&redact_secret_assignments(&redact_bearer_tokens(text)))
+}
+
+// Key spellings that only show up in logs and payloads, not in header names
+const EXTRA_SECRET_KEY_MARKERS: [&str; 3] = ["api_key", "access_key", "private_key"];
+
+// Key names that contain a marker but never carry a credential
+const NON_SECRET_KEYS: [&str; 3] = ["author", "auth-required", "auth_required"];
+
+// Replace the value assigned to a credential-like key
+fn redact_secret_assignments(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(value_start) = find_secret_assignment(rest) {
+        redacted.push_str(&rest[..value_start]);
+        let mut value = &rest[value_start..];
+
+        let quoted = value.starts_with('"');
+        if quoted {
+            redacted.push('"');
+            value = &value[1..];
+        }
+        // `Authorization: Bearer …` was already handled by redact_bearer_tokens
+        if let Some(scheme) = value
+            .get(.."bearer ".len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case("bearer "))
+        {
+            redacted.push_str(scheme);
+            value = &value[scheme.len()..];
+        }
+
+        let value_len = value
+            .find(|c: char| {
+                if quoted {
+                    c == '"'
+                } else {
+                    c.is_whitespace() || matches!(c, ',' | '&' | '}' | ')' | ';')
+                }
+            })
+            .unwrap_or(value.len());
+        if value_len > 0 && !value.starts_with(REDACTED) {
+            redacted.push_str(REDACTED);
+        } else {
+            redacted.push_str(&value[..value_len]);
+        }
+        rest = &value[value_len..];
+    }
+
+    redacted.push_str(rest);
+    redacted
+}
+
+// Start of the value of the first credential assignment, `key=value`, `key: value`
+// or `"key": "value"`
+fn find_secret_assignment(text: &str) -> Option<usize> {
+    let lower = text.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let is_key_char = |c: u8| c.is_ascii_alphanumeric() || c == b'_' || c == b'-';
+    let mut search_from = 0;
+
+    while search_from < lower.len() {
+        let index = SENSITIVE_HEADER_MARKERS
+            .iter()
+            .chain(EXTRA_SECRET_KEY_MARKERS.iter())
+            .filter_map(|marker| lower[search_from..].find(marker))
+            .min()
+            .map(|i| search_from + i)?;
+
+        let key_start = lower[..index]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let key_end = (index..lower.len())
+            .find(|&i| !is_key_char(bytes[i]))
+            .unwrap_or(lower.len());
+        let key = &lower[key_start..key_end];
+        search_from = key_end.max(index + 1);
+
+        // Token counts are not tokens
+        if key.ends_with("tokens") || NON_SECRET_KEYS.contains(&key) {
+            continue;
+        }
+
+        let mut value_start = key_end;
+        if bytes.get(value_start) == Some(&b'"') {
+            value_start += 1;
+        }
+        while bytes.get(value_start) == Some(&b' ') {
+            value_start += 1;
+        }
+        if !matches!(bytes.get(value_start), Some(b'=') | Some(b':')) {
+            continue;
+        }
+        value_start += 1;
+        while bytes.get(value_start) == Some(&b' ') {
+            value_start += 1;
+        }
+        // A nested object or array is not a value to redact
+        if !matches!(bytes.get(value_start), Some(b'{') | Some(b'[') | None) {
+            return Some(value_start);
+        }
+    }
+
+    None
+}
+
+// Provider keys such as `sk-...` can show up without any key name around them
+fn redact_api_keys(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(index) = rest.find("sk-") {
+        let boundary = index == 0
+            || !rest[..index]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_alphanumeric());
+        let key_len = rest[index..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(rest.len() - index);
+
+        redacted.push_str(&rest[..index]);
+        if boundary && key_len >= 20 {
+            redacted.push_str(REDACTED);
+        } else {
+            redacted.push_str(&rest[index..index + key_len]);
+        }
+        rest = &rest[index + key_len..];
+    }
+
+    redacted.push_str(rest);
+    redacted
+}
+
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_json_keeps_names_and_hides_credentials() {
+        let redacted = redact_header_json(
+            r#"{"Authorization": "Bearer abc123456", "X-Api-Key": "k", "Accept": "text/plain"}"#,
+        );
+        assert_eq!(
+            redacted,
+            serde_json::json!({
+                "Authorization": REDACTED,
+                "X-Api-Key": REDACTED,
+                "Accept": "text/plain",
+            })
+        );
+        assert_eq!(redact_header_json("not json"), serde_json::json!(REDACTED));
+    }
+
+    #[test]
+    fn header_secrets_drop_the_scheme_and_short_values() {
+        let secrets = header_secret_values(
+            r#"{"Authorization": "Bearer abc123456", "X-Token": "abc", "Accept": "text/plain-value"}"#,
+        );
+        assert_eq!(secrets, ["abc123456"]);
+
+        let text = redact_secrets("failed with abc123456 in the body", &secrets);
+        assert_eq!(text, format!("failed with {REDACTED} in the body"));
+    }
+
+    #[test]
+    fn bearer_tokens_are_redacted_once() {
+        let text = redact_bearer_tokens(r#"authorization: bearer abc.def, "Bearer xyz""#);
+        assert_eq!(
+            text,
+            format!(r#"authorization: bearer {REDACTED}, "Bearer {REDACTED}""#)
+        );
+        assert_eq!(redact_bearer_tokens(&text), text);
+    }
+
+    #[test]
+    fn log_text_redacts_assignments_and_api_keys() {
+        let text = redact_log_text(
+            r#"token=abc123 {"password": "p w", "api_key": "k1"} key sk-abcdefghijklmnopqrstu"#,
+        );
+        assert_eq!(
+            text,
+            format!(
+                r#"token={REDACTED} {{"password": "{REDACTED}", "api_key": "{REDACTED}"}} key {REDACTED}"#
+            )
+        );
+    }
+
+    #[test]
+    fn log_text_keeps_values_that_are_not_credentials() {
+        let text = r#"author=jane max_tokens=10 "auth": {"type": "none"} task-sk-1 sk-short"#;
+        assert_eq!(redact_log_text(text), text);
+    }
+}
@@ -0,0 +1,81 @@
+/// One server-sent event
+#[derive(Debug, Clone, Default)]
+pub struct SseEvent {
+    pub event: Option<String>,
+    pub id: Option<String>,
+    pub data: String,
+}
+
+/// Incremental parser for `text/event-stream` bodies.
+/// Feed it raw chunks as they arrive and it hands out complete events.
+#[derive(Default)]
+pub struct SseParser {
+    // Raw bytes, so a UTF-8 sequence split across chunks is decoded only once complete
+    buffer: Vec<u8>,
+    current: SseEvent,
+    has_data: bool,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a chunk of the body, returns every event completed by it
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|b| *b == b'\n') {
+            let raw_line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let raw_line = String::from_utf8_lossy(&raw_line);
+            let line = raw_line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                // A blank line dispatches the pending event
+                if self.has_data {
+                    events.push(std::mem::take(&mut self.current));
+                    self.has_data = false;
+                } else {
+                    self.current = SseEvent::default();
+                }
+                continue;
+            }
+
+            if line.starts_with(':') {
+                // Comment / keep-alive
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+
+            match field {
+                "data" => {
+                    if self.has_data {
+                        self.current.data.push('\n');
+                    }
+                    self.current.data.push_str(value);
+                    self.has_data = true;
+                }
+                "event" => self.current.event = Some(value.to_string()),
+                "id" => self.current.id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+
+        events
+    }
+
+    /// Flush an event left without a trailing blank line when the stream ends
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if self.has_data {
+            self.has_data = false;
+            Some(std::mem::take(&mut self.current))
+        } else {
+            None
+        }
+    }
+}
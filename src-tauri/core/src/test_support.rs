@@ -0,0 +1,32 @@
+//! Helpers shared by the unit tests: stored servers and local stub agents
+
+use axum::Router;
+use tokio::net::TcpListener;
+
+use crate::model::SettingA2AServer;
+
+/// A stored A2A server with the given agent card
+pub fn a2a_server(agent_card_json: Option<&str>) -> SettingA2AServer {
+    SettingA2AServer {
+        id: Some(1),
+        name: "stub".to_string(),
+        agent_card_url: "http://127.0.0.1/.well-known/agent-card.json".to_string(),
+        agent_card_json: agent_card_json.map(str::to_string),
+        custom_header_json: None,
+        protocol_data_object_settings: None,
+        transport: None,
+        enabled: true,
+        created_at: None,
+        updated_at: None,
+    }
+}
+
+/// Serve the router on a free local port for the rest of the test, returns its base url
+pub async fn serve(app: Router) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
@@ -0,0 +1,433 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
+
+use lazy_static::lazy_static;
+use reqwest::{RequestBuilder, Response, StatusCode, header::HeaderMap};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tonic::metadata::MetadataMap;
+
+use crate::{
+    db::traffic_db::TrafficDbManager,
+    model::{TrafficEntry, TrafficHeader, TrafficSseEvent},
+    redact::{REDACTED, is_sensitive_header},
+    sse::SseEvent,
+};
+
+// Bodies beyond this are cut, an agent returning files inline would bloat the DB
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+// Off until turned on from the inspector. Load test requests are never recorded,
+// a run would flood the table and the DB writes would skew its latencies
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    // Entries are inserted on a thread of their own, so the request path never
+    // waits for the DB lock when an exchange is dropped
+    static ref WRITER: mpsc::Sender<TrafficEntry> = spawn_writer();
+}
+
+fn spawn_writer() -> mpsc::Sender<TrafficEntry> {
+    let (sender, entries) = mpsc::channel::<TrafficEntry>();
+    let spawned = thread::Builder::new()
+        .name("traffic-writer".to_string())
+        .spawn(move || {
+            let db_manager = TrafficDbManager::new();
+            for entry in entries {
+                if let Err(e) = db_manager.insert(&entry) {
+                    log::warn!("Failed to record HTTP exchange with {}: {}", entry.url, e);
+                }
+            }
+        });
+    if let Err(e) = spawned {
+        log::error!("Failed to start the traffic writer: {}", e);
+    }
+    sender
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Relaxed)
+}
+
+pub fn set_recording(enabled: bool) {
+    RECORDING.store(enabled, Ordering::Relaxed);
+}
+
+/// The entries as a HAR 1.2 log
+pub fn har_log(entries: &[TrafficEntry]) -> Value {
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": {
+                "name": "a2a-client",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "entries": entries.iter().map(har_entry).collect::<Vec<_>>(),
+        }
+    })
+}
+
+/// One HTTP exchange being recorded. It is stored when dropped, so every early
+/// return of the caller still leaves an entry; does nothing while recording is off
+pub struct Exchange {
+    entry: Option<TrafficEntry>,
+    started: Instant,
+}
+
+impl Exchange {
+    fn start(
+        source: &str,
+        method: &str,
+        url: String,
+        request_headers: Vec<TrafficHeader>,
+        request_body: Option<String>,
+    ) -> Self {
+        let entry = RECORDING.load(Ordering::Relaxed).then(|| TrafficEntry {
+            id: None,
+            source: source.to_string(),
+            method: method.to_string(),
+            url,
+            request_headers,
+            request_body: request_body.as_deref().map(capped),
+            status: None,
+            response_headers: Vec::new(),
+            response_body: None,
+            sse_events: Vec::new(),
+            error: None,
+            started_at: chrono::Local::now().to_rfc3339(),
+            duration_ms: 0,
+            wait_ms: None,
+        });
+
+        Self {
+            entry,
+            started: Instant::now(),
+        }
+    }
+
+    fn disabled() -> Self {
+        Self {
+            entry: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// A chat completions call with the JSON body posted to the model's API,
+    /// the API key is never recorded
+    pub fn llm<T: Serialize>(api_url: &str, request: &T, stream: bool) -> Self {
+        if !RECORDING.load(Ordering::Relaxed) {
+            return Self::disabled();
+        }
+
+        let mut headers = vec![
+            header("Content-Type", "application/json"),
+            header("Authorization", REDACTED),
+        ];
+        if stream {
+            headers.push(header("Accept", "text/event-stream"));
+        }
+        Self::start(
+            "llm",
+            "POST",
+            format!("{}/chat/completions", api_url.trim_end_matches('/')),
+            headers,
+            serde_json::to_string(request).ok(),
+        )
+    }
+
+    /// A gRPC call, recorded as a POST to the method path with the request in
+    /// its JSON form since the protobuf body isn't readable. The metadata sent
+    /// with it is recorded as the request headers
+    pub fn grpc<T: Serialize>(url: &str, rpc: &str, request: &T, metadata: &MetadataMap) -> Self {
+        if !RECORDING.load(Ordering::Relaxed) {
+            return Self::disabled();
+        }
+
+        Self::start(
+            "a2a",
+            "POST",
+            format!("{}/a2a.v1.A2AService/{}", url.trim_end_matches('/'), rpc),
+            grpc_headers(metadata),
+            serde_json::to_string(request).ok(),
+        )
+    }
+
+    fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// Status and headers of the response, once they arrived
+    pub fn response(&mut self, response: &Response) {
+        let wait_ms = self.elapsed_ms();
+        if let Some(entry) = &mut self.entry {
+            entry.status = Some(response.status().as_u16());
+            entry.response_headers = recorded_headers(response.headers());
+            entry.wait_ms = Some(wait_ms);
+        }
+    }
+
+    /// A successful response of a client that only surfaces 2xx replies
+    pub fn ok(&mut self) {
+        let wait_ms = self.elapsed_ms();
+        if let Some(entry) = &mut self.entry {
+            entry.status = Some(200);
+            entry.wait_ms = Some(wait_ms);
+        }
+    }
+
+    pub fn body(&mut self, body: &str) {
+        if let Some(entry) = &mut self.entry {
+            entry.response_body = Some(capped(body));
+        }
+    }
+
+    pub fn event(&mut self, event: &SseEvent) {
+        let elapsed_ms = self.elapsed_ms();
+        if let Some(entry) = &mut self.entry {
+            entry.sse_events.push(TrafficSseEvent {
+                event: event.event.clone(),
+                id: event.id.clone(),
+                data: capped(&event.data),
+                elapsed_ms,
+            });
+        }
+    }
+
+    /// A streamed message decoded by the client, recorded as the `data:` event it arrived in
+    pub fn json_event<T: Serialize>(&mut self, chunk: &T) {
+        if self.entry.is_some() {
+            if let Ok(data) = serde_json::to_string(chunk) {
+                self.event(&SseEvent {
+                    data,
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    pub fn error(&mut self, error: impl std::fmt::Display) {
+        if let Some(entry) = &mut self.entry {
+            entry.error = Some(error.to_string());
+        }
+    }
+}
+
+impl Drop for Exchange {
+    fn drop(&mut self) {
+        let duration_ms = self.elapsed_ms();
+        if let Some(mut entry) = self.entry.take() {
+            entry.duration_ms = duration_ms;
+            if let Err(e) = WRITER.send(entry) {
+                log::warn!(
+                    "Failed to record HTTP exchange with {}: writer stopped",
+                    e.0.url
+                );
+            }
+        }
+    }
+}
+
+/// Send a request, recording the exchange while the recorder is on. The caller
+/// adds the response body or events it reads to the returned exchange
+pub async fn send(builder: RequestBuilder, source: &str) -> (Exchange, reqwest::Result<Response>) {
+    let (client, request) = builder.build_split();
+    let request = match request {
+        Ok(request) => request,
+        Err(e) => return (Exchange::disabled(), Err(e)),
+    };
+
+    let mut exchange = if RECORDING.load(Ordering::Relaxed) {
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| String::from_utf8_lossy(bytes).to_string());
+        Exchange::start(
+            source,
+            request.method().as_str(),
+            request.url().to_string(),
+            recorded_headers(request.headers()),
+            body,
+        )
+    } else {
+        Exchange::disabled()
+    };
+
+    let result = client.execute(request).await;
+    match &result {
+        Ok(response) => exchange.response(response),
+        Err(e) => exchange.error(e),
+    }
+    (exchange, result)
+}
+
+fn header(name: &str, value: &str) -> TrafficHeader {
+    TrafficHeader {
+        name: name.to_string(),
+        value: value.to_string(),
+    }
+}
+
+fn grpc_headers(metadata: &MetadataMap) -> Vec<TrafficHeader> {
+    let mut headers = vec![header("Content-Type", "application/grpc")];
+    headers.extend(recorded_headers(&metadata.clone().into_headers()));
+    headers
+}
+
+fn recorded_headers(headers: &HeaderMap) -> Vec<TrafficHeader> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_sensitive_header(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).to_string()
+            };
+            header(name.as_str(), &value)
+        })
+        .collect()
+}
+
+fn capped(body: &str) -> String {
+    if body.len() <= MAX_BODY_BYTES {
+        return body.to_string();
+    }
+
+    let mut end = MAX_BODY_BYTES;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n[truncated {} bytes]", &body[..end], body.len() - end)
+}
+
+fn header_value<'a>(headers: &'a [TrafficHeader], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.as_str())
+}
+
+fn har_headers(headers: &[TrafficHeader]) -> Vec<Value> {
+    headers
+        .iter()
+        .map(|header| json!({ "name": header.name, "value": header.value }))
+        .collect()
+}
+
+// SSE events are written back as the event stream text they were parsed from
+fn event_stream_text(events: &[TrafficSseEvent]) -> String {
+    let mut text = String::new();
+    for event in events {
+        if let Some(name) = &event.event {
+            text.push_str(&format!("event: {}\n", name));
+        }
+        if let Some(id) = &event.id {
+            text.push_str(&format!("id: {}\n", id));
+        }
+        for line in event.data.lines() {
+            text.push_str(&format!("data: {}\n", line));
+        }
+        text.push('\n');
+    }
+    text
+}
+
+fn har_entry(entry: &TrafficEntry) -> Value {
+    let query_string: Vec<Value> = reqwest::Url::parse(&entry.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default();
+    let status_text = entry
+        .status
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let content_text = if entry.sse_events.is_empty() {
+        entry.response_body.clone().unwrap_or_default()
+    } else {
+        event_stream_text(&entry.sse_events)
+    };
+    let wait_ms = entry.wait_ms.unwrap_or(entry.duration_ms);
+
+    let mut har = json!({
+        "startedDateTime": entry.started_at,
+        "time": entry.duration_ms,
+        "request": {
+            "method": entry.method,
+            "url": entry.url,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": har_headers(&entry.request_headers),
+            "queryString": query_string,
+            "headersSize": -1,
+            "bodySize": entry.request_body.as_ref().map_or(0, |body| body.len()),
+        },
+        "response": {
+            // HAR uses 0 for a request that got no response
+            "status": entry.status.unwrap_or(0),
+            "statusText": status_text,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": har_headers(&entry.response_headers),
+            "content": {
+                "size": content_text.len(),
+                "mimeType": header_value(&entry.response_headers, "content-type").unwrap_or(""),
+                "text": content_text,
+            },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+        },
+        "cache": {},
+        "timings": {
+            "send": 0,
+            "wait": wait_ms,
+            "receive": entry.duration_ms.saturating_sub(wait_ms),
+        },
+        "_source": entry.source,
+    });
+
+    if let Some(body) = &entry.request_body {
+        har["request"]["postData"] = json!({
+            "mimeType": header_value(&entry.request_headers, "content-type")
+                .unwrap_or("application/json"),
+            "text": body,
+        });
+    }
+    if let Some(error) = &entry.error {
+        har["response"]["_error"] = json!(error);
+    }
+    if !entry.sse_events.is_empty() {
+        har["response"]["_sseEvents"] = json!(entry.sse_events);
+    }
+
+    har
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grpc_metadata_is_recorded_with_secrets_redacted() {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("x-a2a-skill-id", "summarize".parse().unwrap());
+        metadata.insert("authorization", "Bearer secret".parse().unwrap());
+
+        let headers = grpc_headers(&metadata);
+        assert_eq!(
+            header_value(&headers, "content-type"),
+            Some("application/grpc")
+        );
+        assert_eq!(header_value(&headers, "x-a2a-skill-id"), Some("summarize"));
+        assert_eq!(header_value(&headers, "authorization"), Some(REDACTED));
+    }
+}
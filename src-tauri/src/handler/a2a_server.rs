@@ -1,5 +1,7 @@
 use crate::{
+    a2a::transport::Transport,
     db::a2a_db::SettingA2AServerDbManager,
+    error::AppError,
     model::{
         InvokeResponse, SettingA2AServer, SettingA2AServerParams, UpdateSettingA2AServerParams,
         to_invoke_response,
@@ -8,6 +10,9 @@ use crate::{
 
 #[tauri::command]
 pub async fn save_setting_a2a_server(params: SettingA2AServerParams) -> InvokeResponse<i64> {
+    if let Err(e) = validate_transport(params.transport.as_deref()) {
+        return InvokeResponse::error(e);
+    }
     let db_manager = SettingA2AServerDbManager::new();
 
     db_manager
@@ -20,6 +25,9 @@ pub async fn save_setting_a2a_server(params: SettingA2AServerParams) -> InvokeRe
 pub async fn update_setting_a2a_server(
     params: UpdateSettingA2AServerParams,
 ) -> InvokeResponse<usize> {
+    if let Err(e) = validate_transport(params.transport.as_deref()) {
+        return InvokeResponse::error(e);
+    }
     let db_manager = SettingA2AServerDbManager::new();
 
     db_manager
@@ -109,3 +117,13 @@ pub async fn ensure_single_setting_a2a_server_enabled(enabled_id: i32) -> Invoke
         .map(InvokeResponse::success)
         .unwrap_or_else(|e| to_invoke_response(e))
 }
+
+// An empty transport leaves the choice to the agent card
+fn validate_transport(transport: Option<&str>) -> Result<(), AppError> {
+    match transport.filter(|t| !t.is_empty()) {
+        Some(name) if Transport::from_name(name).is_none() => Err(AppError::invalid_params(
+            format!("Unknown A2A transport: {}", name),
+        )),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(not(target_os = "windows"))]
+use std::process::Command;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    artifact::{artifact_blob, delete_artifact as delete_stored_artifact, safe_file_name},
+    db::artifact_db::ArtifactDbManager,
+    model::{ArtifactQueryParams, InvokeResponse, StoredArtifact, to_invoke_response},
+};
+
+#[tauri::command]
+pub async fn list_artifacts(params: ArtifactQueryParams) -> InvokeResponse<Vec<StoredArtifact>> {
+    let db_manager = ArtifactDbManager::new();
+
+    db_manager
+        .list(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn open_artifact(id: i32) -> InvokeResponse<String> {
+    let result = artifact_blob(id).await.and_then(|(artifact, blob_path)| {
+        let path = materialize_artifact(&artifact, &blob_path)?;
+        open_with_system(&path)?;
+        Ok(path)
+    });
+
+    match result {
+        Ok(path) => InvokeResponse::success(path.display().to_string()),
+        Err(e) => to_invoke_response(e),
+    }
+}
+
+#[tauri::command]
+pub async fn save_artifact_as(id: i32, target_path: String) -> InvokeResponse<u64> {
+    let result = artifact_blob(id).await.and_then(|(_, blob_path)| {
+        fs::copy(&blob_path, &target_path).context("failed to save artifact")
+    });
+
+    match result {
+        Ok(bytes) => {
+            log::info!("Saved artifact {} to {}", id, target_path);
+            InvokeResponse::success(bytes)
+        }
+        Err(e) => to_invoke_response(e),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_artifact(id: i32) -> InvokeResponse<usize> {
+    delete_stored_artifact(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+// Copy the blob to a temp file with a proper name so the OS picks the right application
+fn materialize_artifact(artifact: &StoredArtifact, blob_path: &Path) -> Result<PathBuf> {
+    let file_name = safe_file_name(artifact)?;
+
+    let mut target = std::env::temp_dir();
+    target.push("a2a-client-artifacts");
+    target.push(artifact.content_hash.get(..12).unwrap_or(&artifact.content_hash));
+    fs::create_dir_all(&target).context("failed to create artifact temp directory")?;
+    target.push(file_name);
+
+    fs::copy(blob_path, &target).context("failed to copy artifact")?;
+    Ok(target)
+}
+
+// The path is handed to the opener as a single argument, never through a shell
+#[cfg(not(target_os = "windows"))]
+fn open_with_system(path: &Path) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(target_os = "macos"))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(path)
+        .spawn()
+        .context("failed to open artifact")?;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn open_with_system(path: &Path) -> Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+
+    use windows::{
+        Win32::{
+            Foundation::HWND,
+            UI::{Shell::ShellExecuteW, WindowsAndMessaging::SW_SHOWNORMAL},
+        },
+        core::{PCWSTR, w},
+    };
+
+    let file: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    // Opens the file with its associated application, no cmd parses the name
+    let result = unsafe {
+        ShellExecuteW(
+            HWND(0),
+            w!("open"),
+            PCWSTR(file.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values up to 32 are error codes
+    if result.0 <= 32 {
+        return Err(anyhow::anyhow!(
+            "failed to open artifact (ShellExecute error {})",
+            result.0
+        ));
+    }
+    Ok(())
+}
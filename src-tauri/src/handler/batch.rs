@@ -0,0 +1,52 @@
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    a2a::batch::{prepare_batch_run, run_batch},
+    db::batch_db::BatchRunDbManager,
+    model::{BatchRun, BatchRunItem, BatchRunParams, InvokeResponse, to_invoke_response},
+};
+
+#[tauri::command]
+pub async fn start_batch_run(params: BatchRunParams, handle: AppHandle) -> InvokeResponse<i64> {
+    let job = match prepare_batch_run(params) {
+        Ok(job) => job,
+        Err(e) => return InvokeResponse::error(e),
+    };
+    let run_id = job.run_id;
+
+    tauri::async_runtime::spawn(run_batch(job, move |progress| {
+        let _ = handle.emit("batch_run_progress", progress);
+    }));
+
+    InvokeResponse::success(run_id)
+}
+
+#[tauri::command]
+pub async fn get_batch_runs() -> InvokeResponse<Vec<BatchRun>> {
+    let db_manager = BatchRunDbManager::new();
+
+    db_manager
+        .get_all_runs()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_batch_run_items(run_id: i64) -> InvokeResponse<Vec<BatchRunItem>> {
+    let db_manager = BatchRunDbManager::new();
+
+    db_manager
+        .get_items(run_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_batch_run(id: i64) -> InvokeResponse<usize> {
+    let db_manager = BatchRunDbManager::new();
+
+    db_manager
+        .delete_run(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
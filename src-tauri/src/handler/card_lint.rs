@@ -0,0 +1,13 @@
+use crate::{
+    a2a::card_lint,
+    model::{CardLintParams, CardLintReport, InvokeResponse},
+};
+
+/// Validate an agent card against the schema and lint what the schema cannot see
+#[tauri::command]
+pub async fn lint_agent_card(params: CardLintParams) -> InvokeResponse<CardLintReport> {
+    card_lint::lint_agent_card(params)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(InvokeResponse::error)
+}
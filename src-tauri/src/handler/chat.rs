@@ -1,10 +1,16 @@
-use ai::chat_completions::{ChatCompletion, ChatCompletionMessage, ChatCompletionRequestBuilder};
-use ai::{Result, clients::openai::Client};
-use futures_util::StreamExt;
+use ai::chat_completions::ChatCompletionRequestBuilder;
 use tauri::{AppHandle, Emitter};
 
-use crate::handler::StreamChunk;
-use crate::model::{ChatCompletionStreamParams, InvokeResponse};
+use crate::db::model_db::SettingModelDbManager;
+use crate::error::{AppError, ErrorKind};
+use crate::handler::{StreamChunk, deepseek_client};
+use crate::llm::{
+    chat_model_name,
+    client::ChatClient,
+    context_window::{ContextBudget, Summarizer, fit_messages, to_completion_messages},
+    preset::{preset_options, validate_generation_options},
+};
+use crate::model::{ChatCompletionStreamParams, GenerationOptions, InvokeResponse};
 
 #[tauri::command]
 pub async fn stream_chat(
@@ -14,47 +20,89 @@ pub async fn stream_chat(
     log::info!("Starting streaming chat completion with AI API");
 
     // Validate parameters
-    if params.api_key.trim().is_empty() {
-        return error_response("API key cannot be empty");
+    if params.model_id.is_none() && params.api_key.trim().is_empty() {
+        return error_response(AppError::invalid_params("API key cannot be empty"));
     }
 
     if params.messages.is_empty() {
-        return error_response("Messages array cannot be empty");
+        return error_response(AppError::invalid_params("Messages array cannot be empty"));
     }
 
-    // Create AI client using ai.rs library
-    let client = match create_ai_client(&params.api_key).await {
-        Ok(client) => client,
-        Err(e) => return error_response(&format!("Failed to create AI client: {}", e)),
+    // A selected model brings its own url and key, otherwise DeepSeek with the given key
+    let (client, model_name, model) = match params.model_id {
+        Some(model_id) => match SettingModelDbManager::new().get_by_id(model_id) {
+            Ok(Some(model)) => {
+                let model_name = match chat_model_name(&model) {
+                    Ok(model_name) => model_name,
+                    Err(e) => return error_response(AppError::from(e)),
+                };
+                (ChatClient::for_model(&model), model_name, Some(model))
+            }
+            Ok(None) => return error_response(AppError::not_found("Model not found")),
+            Err(e) => return error_response(AppError::from(e.context("Failed to get model"))),
+        },
+        None => {
+            let model = SettingModelDbManager::new()
+                .get_by_model_key("DeepSeek")
+                .ok()
+                .flatten();
+            (
+                deepseek_client(&params.api_key),
+                "deepseek-chat".to_string(),
+                model,
+            )
+        }
     };
 
-    // Convert ChatMessage to ChatCompletionMessage
-    let chat_messages: Vec<ChatCompletionMessage> = params
-        .messages
-        .into_iter()
-        .map(|msg| match msg.role.as_str() {
-            "system" => ChatCompletionMessage::System(msg.content.into()),
-            "user" => ChatCompletionMessage::User(msg.content.into()),
-            "assistant" => ChatCompletionMessage::Assistant(msg.content.into()),
-            _ => ChatCompletionMessage::User(msg.content.into()), // 默认为 user
-        })
-        .collect();
+    // Built-in defaults, then the model preset, then the per-request overrides
+    let model_id = model.as_ref().and_then(|model| model.id);
+    let preset = match preset_options(model_id, params.preset_id) {
+        Ok(preset) => preset,
+        Err(e) => return error_response(AppError::from(e)),
+    };
+    let options = GenerationOptions {
+        max_tokens: Some(4000),
+        temperature: Some(0.3),
+        ..Default::default()
+    }
+    .merge(&preset)
+    .merge(&params.generation_overrides());
+    if let Err(message) = validate_generation_options(&options) {
+        return error_response(AppError::invalid_params(message));
+    }
+
+    // Fit the messages into the model's context window before sending them
+    let budget = ContextBudget::for_model(model.as_ref(), &model_name, options.max_tokens);
+    let summarizer = Summarizer {
+        client: &client,
+        model_name: &model_name,
+    };
+    let messages = match fit_messages(params.messages, &budget, Some(summarizer)).await {
+        Ok((messages, stats)) => {
+            if stats.changed() {
+                emit_status(&handle, "context_trimmed", &stats.describe()).await;
+            }
+            messages
+        }
+        Err(e) => return error_response(AppError::from(e)),
+    };
+    let chat_messages = to_completion_messages(&messages);
 
     // Create streaming request using ai.rs
-    let request = match ChatCompletionRequestBuilder::default()
-        .model("deepseek-chat")
-        .messages(chat_messages)
-        .max_completion_tokens(params.max_tokens.unwrap_or(4000))
-        .temperature(params.temperature.unwrap_or(0.3))
-        .stream(true)
-        .build()
-    {
+    let mut builder = ChatCompletionRequestBuilder::default();
+    builder.model(model_name).messages(chat_messages).stream(true);
+    let request = match builder.build() {
         Ok(req) => req,
-        Err(e) => return error_response(&format!("Failed to build request: {}", e)),
+        Err(e) => {
+            return error_response(AppError::internal(format!(
+                "Failed to build request: {}",
+                e
+            )));
+        }
     };
 
-    // Send streaming request
-    match client.stream_chat_completions(&request).await {
+    // Send streaming request, the generation options go in its body
+    match client.stream_chat_completions(&request, &options).await {
         Ok(mut stream) => {
             let mut full_content = String::new();
             let mut chunk_count = 0;
@@ -72,8 +120,8 @@ pub async fn stream_chat(
                 let chunk = match chunk_result {
                     Ok(chunk) => chunk,
                     Err(e) => {
-                        log::error!("Error reading stream chunk: {}", e);
-                        return InvokeResponse::fail(format!("Stream error: {}", e));
+                        log::error!("Error reading stream chunk: {:#}", e);
+                        return InvokeResponse::error(AppError::from(e));
                     }
                 };
 
@@ -98,21 +146,14 @@ pub async fn stream_chat(
             complete_streaming(&handle, chunk_count).await;
             InvokeResponse::success(full_content)
         }
-        Err(e) => error_response(&format!("Request failed: {}", e)),
+        Err(e) => error_response(AppError::from(e)),
     }
 }
 
-// Helper function to create AI client
-async fn create_ai_client(api_key: &str) -> Result<Client> {
-    // Create client with custom base URL for DeepSeek
-    let client = Client::from_url(api_key, "https://api.deepseek.com/v1")?;
-    Ok(client)
-}
-
 // Helper function to handle error response
-fn error_response(message: &str) -> InvokeResponse<String> {
-    log::error!("{}", message);
-    InvokeResponse::fail(message.to_string())
+fn error_response(err: AppError) -> InvokeResponse<String> {
+    log::error!("{}", err);
+    InvokeResponse::error(err)
 }
 
 // Emit progress or status to frontend
@@ -156,7 +197,10 @@ async fn timeout_error(handle: &AppHandle) -> InvokeResponse<String> {
             message: None,
         },
     );
-    InvokeResponse::fail("Streaming timeout after 5 minutes".to_string())
+    InvokeResponse::error(AppError::new(
+        ErrorKind::Timeout,
+        "Streaming timeout after 5 minutes",
+    ))
 }
 
 // Handle streaming completion
@@ -0,0 +1,110 @@
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    compare::{resolve_targets, run_target},
+    db::compare_db::ComparisonDbManager,
+    model::{
+        CompareParams, CompareStreamChunk, Comparison, ComparisonResult, InvokeResponse,
+        to_invoke_response,
+    },
+};
+
+#[tauri::command]
+pub async fn start_comparison(params: CompareParams, handle: AppHandle) -> InvokeResponse<i64> {
+    let resolved = match resolve_targets(&params) {
+        Ok(resolved) => resolved,
+        Err(e) => return InvokeResponse::error(e),
+    };
+
+    let comparison_id = match ComparisonDbManager::new().insert(&params) {
+        Ok(id) => id,
+        Err(e) => return to_invoke_response(e),
+    };
+
+    log::info!(
+        "Starting comparison {} across {} targets",
+        comparison_id,
+        resolved.len()
+    );
+
+    // Targets run concurrently and are independent of each other, the
+    // ensure_single_* settings only apply to the chat view
+    for (index, (target, resolved)) in params.targets.into_iter().zip(resolved).enumerate() {
+        let handle = handle.clone();
+        let prompt = params.prompt.clone();
+        let system_prompt = params.system_prompt.clone();
+        tauri::async_runtime::spawn(async move {
+            let channel = format!("{}:{}", comparison_id, index);
+            let emit = |content: &str| {
+                let _ = handle.emit(
+                    "compare_stream_chunk",
+                    CompareStreamChunk {
+                        comparison_id,
+                        target_index: index,
+                        channel: channel.clone(),
+                        content: content.to_string(),
+                        is_complete: false,
+                        error: None,
+                        result: None,
+                    },
+                );
+            };
+
+            let result = run_target(
+                comparison_id,
+                index,
+                target,
+                resolved,
+                &prompt,
+                system_prompt.as_deref(),
+                emit,
+            )
+            .await;
+
+            let _ = handle.emit(
+                "compare_stream_chunk",
+                CompareStreamChunk {
+                    comparison_id,
+                    target_index: index,
+                    channel,
+                    content: String::new(),
+                    is_complete: true,
+                    error: result.error.clone(),
+                    result: Some(result),
+                },
+            );
+        });
+    }
+
+    InvokeResponse::success(comparison_id)
+}
+
+#[tauri::command]
+pub async fn get_comparisons() -> InvokeResponse<Vec<Comparison>> {
+    let db_manager = ComparisonDbManager::new();
+
+    db_manager
+        .get_all()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_comparison_results(comparison_id: i64) -> InvokeResponse<Vec<ComparisonResult>> {
+    let db_manager = ComparisonDbManager::new();
+
+    db_manager
+        .get_results(comparison_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_comparison(id: i64) -> InvokeResponse<usize> {
+    let db_manager = ComparisonDbManager::new();
+
+    db_manager
+        .delete(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
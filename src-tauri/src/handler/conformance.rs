@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    a2a::conformance::{ConformanceSuite, REQUEST_TIMEOUT_SECS},
+    db::{a2a_db::SettingA2AServerDbManager, conformance_db::ConformanceDbManager},
+    error::AppError,
+    model::{
+        ConformanceCheck, ConformanceParams, ConformanceProgress, ConformanceRun, InvokeResponse,
+        SettingA2AServer, to_invoke_response,
+    },
+};
+
+/// Run the conformance suite against a server in the background, progress is
+/// emitted as `conformance_progress` events and the report stored with the run
+#[tauri::command]
+pub async fn start_conformance_run(
+    params: ConformanceParams,
+    handle: AppHandle,
+) -> InvokeResponse<i64> {
+    let a2a_server = match SettingA2AServerDbManager::new().get_by_id(params.a2a_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("A2A server not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get A2A server")),
+    };
+
+    let client = match Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return InvokeResponse::error(AppError::from(e)),
+    };
+
+    let run_id = match ConformanceDbManager::new()
+        .insert_run(params.a2a_server_id, params.a2a_url.as_deref())
+    {
+        Ok(run_id) => run_id,
+        Err(e) => return to_invoke_response(e),
+    };
+
+    log::info!(
+        "Starting conformance run {} against {}",
+        run_id,
+        a2a_server.name
+    );
+
+    tauri::async_runtime::spawn(run_suite(handle, run_id, client, a2a_server, params));
+
+    InvokeResponse::success(run_id)
+}
+
+#[tauri::command]
+pub async fn get_conformance_runs() -> InvokeResponse<Vec<ConformanceRun>> {
+    let db_manager = ConformanceDbManager::new();
+
+    db_manager
+        .get_all_runs()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_conformance_run(id: i64) -> InvokeResponse<ConformanceRun> {
+    match ConformanceDbManager::new().get_by_id(id) {
+        Ok(Some(run)) => InvokeResponse::success(run),
+        Ok(None) => InvokeResponse::error(AppError::not_found("Conformance run not found")),
+        Err(e) => to_invoke_response(e.context("Failed to get conformance run")),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_conformance_run(id: i64) -> InvokeResponse<usize> {
+    let db_manager = ConformanceDbManager::new();
+
+    db_manager
+        .delete_run(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+async fn run_suite(
+    handle: AppHandle,
+    run_id: i64,
+    client: Client,
+    a2a_server: SettingA2AServer,
+    params: ConformanceParams,
+) {
+    let mut report = Report {
+        handle,
+        run_id,
+        checks: Vec::new(),
+    };
+    let suite = ConformanceSuite::new(client, a2a_server, params.a2a_url, params.skill_id);
+
+    let a2a_url = suite.run(|check| report.record(check)).await;
+    report.finish(a2a_url.as_deref());
+}
+
+struct Report {
+    handle: AppHandle,
+    run_id: i64,
+    checks: Vec<ConformanceCheck>,
+}
+
+impl Report {
+    fn record(&mut self, check: ConformanceCheck) {
+        self.checks.push(check.clone());
+        self.emit(false, Some(check));
+    }
+
+    fn count(&self, status: &str) -> usize {
+        self.checks
+            .iter()
+            .filter(|check| check.status == status)
+            .count()
+    }
+
+    fn emit(&self, is_complete: bool, check: Option<ConformanceCheck>) {
+        let _ = self.handle.emit(
+            "conformance_progress",
+            ConformanceProgress {
+                run_id: self.run_id,
+                passed: self.count("passed"),
+                failed: self.count("failed"),
+                skipped: self.count("skipped"),
+                is_complete,
+                check,
+            },
+        );
+    }
+
+    fn finish(&self, a2a_url: Option<&str>) {
+        if let Err(e) = ConformanceDbManager::new().finish_run(self.run_id, a2a_url, &self.checks) {
+            log::warn!("Failed to finish conformance run: {}", e);
+        }
+        self.emit(true, None);
+    }
+}
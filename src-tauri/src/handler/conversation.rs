@@ -0,0 +1,35 @@
+use crate::{
+    a2a::conversation,
+    db::conversation_db::A2AConversationContextDbManager,
+    model::{A2AConversationContext, ContinueA2ATaskParams, InvokeResponse, to_invoke_response},
+};
+
+#[tauri::command]
+pub async fn continue_a2a_task(params: ContinueA2ATaskParams) -> InvokeResponse<String> {
+    conversation::continue_task(&params)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_a2a_conversation_contexts(
+    conversation_id: String,
+) -> InvokeResponse<Vec<A2AConversationContext>> {
+    let db_manager = A2AConversationContextDbManager::new();
+
+    db_manager
+        .get_by_conversation(&conversation_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn clear_a2a_conversation_contexts(conversation_id: String) -> InvokeResponse<usize> {
+    let db_manager = A2AConversationContextDbManager::new();
+
+    db_manager
+        .delete_by_conversation(&conversation_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
@@ -0,0 +1,11 @@
+use crate::{
+    export,
+    model::{ExportConversationParams, InvokeResponse, to_invoke_response},
+};
+
+#[tauri::command]
+pub async fn export_conversation(params: ExportConversationParams) -> InvokeResponse<String> {
+    export::export_conversation(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
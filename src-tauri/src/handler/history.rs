@@ -0,0 +1,58 @@
+use crate::{
+    db::{
+        conversation_db::A2AConversationContextDbManager, message_db::ConversationMessageDbManager,
+        search_db::ConversationSearchDbManager,
+    },
+    model::{
+        ConversationMessage, ConversationSearchPage, ConversationSearchParams, InvokeResponse,
+        SaveConversationMessageParams, to_invoke_response,
+    },
+};
+
+#[tauri::command]
+pub async fn save_conversation_message(params: SaveConversationMessageParams) -> InvokeResponse<i64> {
+    let db_manager = ConversationMessageDbManager::new();
+
+    db_manager
+        .upsert(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_conversation_messages(
+    conversation_id: String,
+) -> InvokeResponse<Vec<ConversationMessage>> {
+    let db_manager = ConversationMessageDbManager::new();
+
+    db_manager
+        .get_by_conversation(&conversation_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_conversation(conversation_id: String) -> InvokeResponse<usize> {
+    let result = ConversationMessageDbManager::new()
+        .delete_by_conversation(&conversation_id)
+        .and_then(|rows_affected| {
+            A2AConversationContextDbManager::new().delete_by_conversation(&conversation_id)?;
+            Ok(rows_affected)
+        });
+
+    result
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn search_conversations(
+    params: ConversationSearchParams,
+) -> InvokeResponse<ConversationSearchPage> {
+    let db_manager = ConversationSearchDbManager::new();
+
+    db_manager
+        .search(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
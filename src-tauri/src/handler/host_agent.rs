@@ -0,0 +1,29 @@
+use crate::{
+    agent::server,
+    model::{HostAgentServerParams, HostAgentServerStatus, InvokeResponse, to_invoke_response},
+};
+
+/// Start serving the host agent as an A2A server
+#[tauri::command]
+pub async fn start_host_agent_server(
+    params: HostAgentServerParams,
+) -> InvokeResponse<HostAgentServerStatus> {
+    server::start(params)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn stop_host_agent_server() -> InvokeResponse<bool> {
+    server::stop()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_host_agent_server_status() -> InvokeResponse<HostAgentServerStatus> {
+    server::status()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
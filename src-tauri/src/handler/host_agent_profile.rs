@@ -0,0 +1,230 @@
+use crate::{
+    agent::react::DEFAULT_MAX_ITERATIONS,
+    db::{
+        a2a_db::SettingA2AServerDbManager, host_agent_profile_db::HostAgentProfileDbManager,
+        model_db::SettingModelDbManager,
+    },
+    error::AppError,
+    model::{
+        HostAgentProfile, HostAgentProfileParams, InvokeResponse, UpdateHostAgentProfileParams,
+        to_invoke_response,
+    },
+};
+
+const MAX_ITERATIONS_LIMIT: i64 = 50;
+
+#[tauri::command]
+pub async fn save_host_agent_profile(params: HostAgentProfileParams) -> InvokeResponse<i64> {
+    let params = HostAgentProfileParams {
+        system_prompt_template: non_empty(params.system_prompt_template),
+        ..params
+    };
+    let max_iterations = params
+        .max_iterations
+        .unwrap_or(DEFAULT_MAX_ITERATIONS as i64);
+
+    if let Err(message) = validate_profile(
+        &params.name,
+        params.system_prompt_template.as_deref(),
+        max_iterations,
+        params.model_id,
+        params.a2a_server_ids.as_deref(),
+    ) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .insert(&params, max_iterations)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn update_host_agent_profile(
+    params: UpdateHostAgentProfileParams,
+) -> InvokeResponse<usize> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    let mut profile = match db_manager.get_by_id(params.id) {
+        Ok(Some(profile)) => profile,
+        Ok(None) => {
+            return InvokeResponse::error(AppError::not_found("Host agent profile not found"));
+        }
+        Err(e) => {
+            return to_invoke_response(e.context("Failed to get host agent profile"));
+        }
+    };
+
+    if let Some(name) = params.name {
+        profile.name = name;
+    }
+    if let Some(description) = params.description {
+        profile.description = Some(description);
+    }
+    if params.reset_system_prompt {
+        profile.system_prompt_template = None;
+    }
+    if let Some(template) = non_empty(params.system_prompt_template) {
+        profile.system_prompt_template = Some(template);
+    }
+    if let Some(max_iterations) = params.max_iterations {
+        profile.max_iterations = max_iterations;
+    }
+    if params.reset_model {
+        profile.model_id = None;
+    }
+    if let Some(model_id) = params.model_id {
+        profile.model_id = Some(model_id);
+    }
+    if params.reset_agents {
+        profile.a2a_server_ids = None;
+    }
+    if let Some(a2a_server_ids) = params.a2a_server_ids {
+        profile.a2a_server_ids = Some(a2a_server_ids);
+    }
+
+    if let Err(message) = validate_profile(
+        &profile.name,
+        profile.system_prompt_template.as_deref(),
+        profile.max_iterations,
+        profile.model_id,
+        profile.a2a_server_ids.as_deref(),
+    ) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    db_manager
+        .update(&profile)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_host_agent_profiles() -> InvokeResponse<Vec<HostAgentProfile>> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .get_all()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_host_agent_profile_by_id(id: i64) -> InvokeResponse<Option<HostAgentProfile>> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .get_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_host_agent_profile(id: i64) -> InvokeResponse<usize> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .delete_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Make a profile the default for conversations without a selection, None clears it
+#[tauri::command]
+pub async fn set_default_host_agent_profile(id: Option<i64>) -> InvokeResponse<usize> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .set_default(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Select the profile a conversation plans with, None goes back to the default
+#[tauri::command]
+pub async fn select_conversation_host_agent_profile(
+    conversation_id: String,
+    profile_id: Option<i64>,
+) -> InvokeResponse<usize> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    if let Some(profile_id) = profile_id {
+        match db_manager.get_by_id(profile_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+            return InvokeResponse::error(AppError::not_found("Host agent profile not found"));
+        }
+            Err(e) => return to_invoke_response(e),
+        }
+    }
+
+    db_manager
+        .select_for_conversation(&conversation_id, profile_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// The profile of a conversation, the default profile when none is selected
+#[tauri::command]
+pub async fn get_conversation_host_agent_profile(
+    conversation_id: String,
+) -> InvokeResponse<Option<HostAgentProfile>> {
+    let db_manager = HostAgentProfileDbManager::new();
+
+    db_manager
+        .get_for_conversation(&conversation_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+fn validate_profile(
+    name: &str,
+    system_prompt_template: Option<&str>,
+    max_iterations: i64,
+    model_id: Option<i32>,
+    a2a_server_ids: Option<&[i32]>,
+) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    if let Some(template) = system_prompt_template {
+        if !template.contains("{{AGENT_SKILLS}}") {
+            return Err(
+                "The system prompt template must contain {{AGENT_SKILLS}} so the planner knows the agents"
+                    .to_string(),
+            );
+        }
+    }
+
+    if !(1..=MAX_ITERATIONS_LIMIT).contains(&max_iterations) {
+        return Err(format!(
+            "Max iterations must be between 1 and {}",
+            MAX_ITERATIONS_LIMIT
+        ));
+    }
+
+    if let Some(model_id) = model_id {
+        match SettingModelDbManager::new().get_by_id(model_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err("Model not found".to_string()),
+            Err(e) => return Err(format!("Failed to get model: {}", e)),
+        }
+    }
+
+    for a2a_server_id in a2a_server_ids.unwrap_or_default() {
+        match SettingA2AServerDbManager::new().get_by_id(*a2a_server_id) {
+            Ok(Some(_)) => {}
+            Ok(None) => return Err(format!("A2A server {} not found", a2a_server_id)),
+            Err(e) => return Err(format!("Failed to get A2A server: {}", e)),
+        }
+    }
+
+    Ok(())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
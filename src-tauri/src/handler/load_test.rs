@@ -0,0 +1,205 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use reqwest::Client;
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    a2a::{
+        client::card_service_url,
+        load_test::{
+            DEFAULT_CONCURRENCY, DEFAULT_TIMEOUT_SECS, Load, LoadTestRunner, MAX_CONCURRENCY,
+            MAX_DURATION_SECS, MAX_RATE_PER_SEC,
+        },
+    },
+    db::{a2a_db::SettingA2AServerDbManager, load_test_db::LoadTestDbManager},
+    error::AppError,
+    model::{InvokeResponse, LoadTestParams, LoadTestRun, to_invoke_response},
+};
+
+lazy_static! {
+    // Stop flags of the runs currently in progress
+    static ref RUNNING_LOAD_TESTS: Mutex<HashMap<i64, Arc<AtomicBool>>> = Mutex::new(HashMap::new());
+}
+
+#[tauri::command]
+pub async fn start_load_test(params: LoadTestParams, handle: AppHandle) -> InvokeResponse<i64> {
+    let a2a_server = match SettingA2AServerDbManager::new().get_by_id(params.a2a_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("A2A server not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get A2A server")),
+    };
+
+    let Some(a2a_url) = params.a2a_url.clone().or_else(|| card_service_url(&a2a_server)) else {
+        return InvokeResponse::error(AppError::invalid_params(
+            "A2A server has no agent card url",
+        ));
+    };
+
+    let load = match params.mode.as_str() {
+        "rate" => match params.rate_per_sec {
+            Some(rate) if rate > 0.0 => Load::Rate(rate.min(MAX_RATE_PER_SEC)),
+            _ => {
+                return InvokeResponse::error(AppError::invalid_params(
+                    "Rate mode needs a positive rate_per_sec",
+                ));
+            }
+        },
+        "concurrency" => Load::Concurrency(
+            params
+                .concurrency
+                .unwrap_or(DEFAULT_CONCURRENCY)
+                .clamp(1, MAX_CONCURRENCY),
+        ),
+        other => {
+            return InvokeResponse::error(AppError::invalid_params(format!(
+                "Unknown load test mode: {}",
+                other
+            )));
+        }
+    };
+
+    if params.duration_secs == 0 || params.duration_secs > MAX_DURATION_SECS {
+        return InvokeResponse::error(AppError::invalid_params(format!(
+            "Duration must be between 1 and {} seconds",
+            MAX_DURATION_SECS
+        )));
+    }
+
+    let client = match Client::builder()
+        .timeout(Duration::from_secs(
+            params.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS).max(1),
+        ))
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => return InvokeResponse::error(AppError::from(e)),
+    };
+
+    let run_id = match LoadTestDbManager::new().insert_run(&params) {
+        Ok(run_id) => run_id,
+        Err(e) => return to_invoke_response(e),
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Ok(mut running) = RUNNING_LOAD_TESTS.lock() {
+        running.insert(run_id, stop.clone());
+    }
+
+    log::info!(
+        "Starting load test {} against {} ({:?} for {}s, stream: {})",
+        run_id,
+        a2a_server.name,
+        load,
+        params.duration_secs,
+        params.stream
+    );
+
+    let runner = Arc::new(LoadTestRunner::new(
+        client, a2a_server, a2a_url, &params, stop,
+    ));
+
+    tauri::async_runtime::spawn(run_load_test(handle, run_id, runner, load));
+
+    InvokeResponse::success(run_id)
+}
+
+#[tauri::command]
+pub async fn stop_load_test(id: i64) -> InvokeResponse<bool> {
+    let stop = RUNNING_LOAD_TESTS
+        .lock()
+        .ok()
+        .and_then(|running| running.get(&id).cloned());
+
+    match stop {
+        Some(stop) => {
+            stop.store(true, Ordering::SeqCst);
+            log::info!("Stopping load test {}", id);
+            InvokeResponse::success(true)
+        }
+        None => InvokeResponse::success(false),
+    }
+}
+
+#[tauri::command]
+pub async fn get_load_test_runs() -> InvokeResponse<Vec<LoadTestRun>> {
+    let db_manager = LoadTestDbManager::new();
+
+    db_manager
+        .get_all_runs()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn compare_load_test_runs(ids: Vec<i64>) -> InvokeResponse<Vec<LoadTestRun>> {
+    let db_manager = LoadTestDbManager::new();
+
+    db_manager
+        .get_by_ids(&ids)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_load_test_run(id: i64) -> InvokeResponse<usize> {
+    let db_manager = LoadTestDbManager::new();
+
+    db_manager
+        .delete_run(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+async fn run_load_test(handle: AppHandle, run_id: i64, runner: Arc<LoadTestRunner>, load: Load) {
+    let started = Instant::now();
+
+    // Report progress about once per second while the run is going
+    let reporter = {
+        let handle = handle.clone();
+        let runner = runner.clone();
+        tauri::async_runtime::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(1));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let _ = handle.emit("load_test_progress", runner.progress(run_id, started));
+            }
+        })
+    };
+
+    let summary = runner.clone().run(load).await;
+    reporter.abort();
+
+    let stopped = runner.is_stopped();
+    if let Ok(mut running) = RUNNING_LOAD_TESTS.lock() {
+        running.remove(&run_id);
+    }
+
+    let status = if stopped { "stopped" } else { "completed" };
+    if let Err(e) = LoadTestDbManager::new().finish_run(run_id, status, &summary) {
+        log::warn!("Failed to finish load test run: {}", e);
+    }
+
+    log::info!(
+        "Load test {} {}: {} requests, {} failed, p95 {:.1}ms",
+        run_id,
+        status,
+        summary.total,
+        summary.failed,
+        summary.latency.p95_ms
+    );
+
+    let mut progress = runner.progress(run_id, started);
+    progress.elapsed_secs = summary.elapsed_secs;
+    progress.is_complete = true;
+    progress.summary = Some(summary);
+    let _ = handle.emit("load_test_progress", progress);
+}
@@ -0,0 +1,44 @@
+use crate::{
+    error::AppError,
+    model::{ExportLogsParams, InvokeResponse, LogEntry, LogQueryParams, to_invoke_response},
+    utils::logging,
+};
+
+#[tauri::command]
+pub async fn get_log_level() -> InvokeResponse<String> {
+    InvokeResponse::success(logging::current_level())
+}
+
+/// Change the log level at runtime, one of off, error, warn, info, debug, trace
+#[tauri::command]
+pub async fn set_log_level(level: String) -> InvokeResponse<String> {
+    logging::set_level(&level)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// The most recent log entries matching the filters, oldest first
+#[tauri::command]
+pub async fn tail_logs(params: LogQueryParams) -> InvokeResponse<Vec<LogEntry>> {
+    logging::tail(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Write the matching log entries to a file, returns how many were written
+#[tauri::command]
+pub async fn export_logs(params: ExportLogsParams) -> InvokeResponse<usize> {
+    if params.target_path.trim().is_empty() {
+        return InvokeResponse::error(AppError::invalid_params("Target path cannot be empty"));
+    }
+
+    let query = LogQueryParams {
+        level: params.level,
+        module: params.module,
+        ..Default::default()
+    };
+
+    logging::export(&params.target_path, &query)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
@@ -0,0 +1,160 @@
+use crate::{
+    db::mcp_db::SettingMcpServerDbManager,
+    error::AppError,
+    mcp::client,
+    model::{
+        CallMcpToolParams, InvokeResponse, McpTool, SettingMcpServer, SettingMcpServerParams,
+        UpdateSettingMcpServerParams, to_invoke_response,
+    },
+};
+
+#[tauri::command]
+pub async fn save_setting_mcp_server(params: SettingMcpServerParams) -> InvokeResponse<i64> {
+    if let Err(message) = validate_transport(
+        &params.transport,
+        params.command.as_deref(),
+        params.url.as_deref(),
+    ) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    let db_manager = SettingMcpServerDbManager::new();
+
+    db_manager
+        .insert(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn update_setting_mcp_server(
+    params: UpdateSettingMcpServerParams,
+) -> InvokeResponse<usize> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    let result = db_manager.update(&params);
+    // Reconnect with the new settings on next use
+    client::disconnect(params.id);
+
+    result
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_all_setting_mcp_servers() -> InvokeResponse<Vec<SettingMcpServer>> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    db_manager
+        .get_all()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_enabled_setting_mcp_servers() -> InvokeResponse<Vec<SettingMcpServer>> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    db_manager
+        .get_enabled()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn toggle_setting_mcp_server_enabled(id: i32) -> InvokeResponse<usize> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    let result = db_manager.toggle_enabled(id);
+    client::disconnect(id);
+
+    result
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_setting_mcp_server(id: i32) -> InvokeResponse<usize> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    client::disconnect(id);
+
+    db_manager
+        .delete_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// List the tools of an MCP server, from the cached list unless `refresh` is set
+#[tauri::command]
+pub async fn list_mcp_tools(id: i32, refresh: Option<bool>) -> InvokeResponse<Vec<McpTool>> {
+    let db_manager = SettingMcpServerDbManager::new();
+
+    let server = match db_manager.get_by_id(id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("MCP server not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get MCP server")),
+    };
+
+    if !refresh.unwrap_or(false) {
+        if let Some(tools) = server
+            .tools_json
+            .as_deref()
+            .and_then(|json| serde_json::from_str::<Vec<McpTool>>(json).ok())
+        {
+            return InvokeResponse::success(tools);
+        }
+    }
+
+    let tools = match client::list_tools(&server).await {
+        Ok(tools) => tools,
+        Err(e) => return to_invoke_response(e.context("Failed to list MCP tools")),
+    };
+
+    match serde_json::to_string(&tools) {
+        Ok(tools_json) => {
+            if let Err(e) = db_manager.update_tools_json(id, &tools_json) {
+                log::warn!("Failed to cache MCP tools: {}", e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize MCP tools: {}", e),
+    }
+
+    InvokeResponse::success(tools)
+}
+
+/// Call an MCP tool, returns the CallToolResult (content, structuredContent, isError)
+#[tauri::command]
+pub async fn call_mcp_tool(params: CallMcpToolParams) -> InvokeResponse<serde_json::Value> {
+    let server = match SettingMcpServerDbManager::new().get_by_id(params.mcp_server_id) {
+        Ok(Some(server)) => server,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("MCP server not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get MCP server")),
+    };
+
+    if !server.enabled {
+        return InvokeResponse::error(AppError::invalid_params(format!(
+            "MCP server {} is disabled",
+            server.name
+        )));
+    }
+
+    log::info!("Calling MCP tool {} on {}", params.tool_name, server.name);
+
+    client::call_tool(&server, &params.tool_name, params.arguments)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e.context("MCP tool call failed")))
+}
+
+fn validate_transport(transport: &str, command: Option<&str>, url: Option<&str>) -> Result<(), String> {
+    let is_set = |value: Option<&str>| value.map(|v| !v.trim().is_empty()).unwrap_or(false);
+
+    match transport {
+        "stdio" if is_set(command) => Ok(()),
+        "stdio" => Err("A stdio MCP server needs a command".to_string()),
+        "http" if is_set(url) => Ok(()),
+        "http" => Err("An HTTP MCP server needs a url".to_string()),
+        other => Err(format!("Unknown MCP transport: {}", other)),
+    }
+}
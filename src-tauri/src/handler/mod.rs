@@ -1,18 +1,16 @@
-use ai::chat_completions::ChatCompletion;
-use ai::{Result, chat_completions::ChatCompletionMessage, clients::openai::Client as AiClient};
-use reqwest::Client;
+use ai::chat_completions::ChatCompletionMessage;
 use serde::{Deserialize, Serialize};
-use serde_json::from_str;
 use tauri::AppHandle;
 
-use crate::db::a2a_db::SettingA2AServerDbManager;
+use crate::a2a::{client::fetch_agent_card, conversation};
+use crate::llm::{client::ChatClient, context_window, preset};
 use crate::{
-    db::model_db::SettingModelDbManager,
+    db::{model_db::SettingModelDbManager, model_preset_db::ModelPresetDbManager},
+    error::{AppError, ErrorKind},
     model::{
-        A2ADataPart, A2AMessage, A2AMessageParams, A2AMessagePart, A2ARequest, A2ATextPart,
-        AgentCard, AgentCardParams, ChatCompletionParams, ChatCompletionStreamParams,
-        InvokeResponse, JSONRPCRequest, SettingModel, SettingModelParams, UpdateSettingModelParams,
-        to_invoke_response,
+        A2AMessageParams, AgentCard, AgentCardParams, ChatCompletionParams,
+        ChatCompletionStreamParams, GenerationOptions, InvokeResponse, SettingModel,
+        SettingModelParams, UpdateSettingModelParams, to_invoke_response,
     },
 };
 
@@ -33,40 +31,54 @@ pub async fn chat_completion(params: ChatCompletionParams) -> InvokeResponse<Str
     // Validate parameters
     if params.api_key.trim().is_empty() {
         log::error!("API key is empty");
-        return InvokeResponse::fail("API key cannot be empty".to_string());
+        return InvokeResponse::error(AppError::invalid_params("API key cannot be empty"));
     }
 
     if params.system_prompt.trim().is_empty() && params.user_prompt.trim().is_empty() {
         log::error!("Both system_prompt and user_prompt are empty");
-        return InvokeResponse::fail(
-            "At least one of system_prompt or user_prompt must be provided".to_string(),
-        );
+        return InvokeResponse::error(AppError::invalid_params(
+            "At least one of system_prompt or user_prompt must be provided",
+        ));
     }
 
-    // Create AI client using ai.rs library
-    let client = match create_ai_client(&params.api_key).await {
-        Ok(client) => client,
-        Err(e) => return InvokeResponse::fail(format!("Failed to create AI client: {}", e)),
-    };
+    let client = deepseek_client(&params.api_key);
 
     // Build messages using ai.rs types
     let messages = build_chat_messages(&params);
 
+    // Built-in defaults, then the DeepSeek model preset
+    let deepseek_id = SettingModelDbManager::new()
+        .get_by_model_key("DeepSeek")
+        .ok()
+        .flatten()
+        .and_then(|model| model.id);
+    let preset = match preset::preset_options(deepseek_id, params.preset_id) {
+        Ok(preset) => preset,
+        Err(e) => return to_invoke_response(e),
+    };
+    let options = GenerationOptions {
+        temperature: Some(0.7),
+        ..Default::default()
+    }
+    .merge(&preset);
+
     // Create request using ai.rs
-    let request = match ai::chat_completions::ChatCompletionRequestBuilder::default()
-        .model("deepseek-chat")
-        .messages(messages)
-        .temperature(0.7)
-        .build()
-    {
+    let mut builder = ai::chat_completions::ChatCompletionRequestBuilder::default();
+    builder.model("deepseek-chat").messages(messages);
+    let request = match builder.build() {
         Ok(req) => req,
-        Err(e) => return InvokeResponse::fail(format!("Failed to build request: {}", e)),
+        Err(e) => {
+            return InvokeResponse::error(AppError::internal(format!(
+                "Failed to build request: {}",
+                e
+            )));
+        }
     };
 
     log::info!("Sending request to AI API with model: deepseek-chat");
 
-    // Send request using ai.rs
-    match client.chat_completions(&request).await {
+    // Send the request with the generation options in its body
+    match client.chat_completions(&request, &options).await {
         Ok(response) => {
             log::info!("Successfully received response from AI API");
 
@@ -79,11 +91,14 @@ pub async fn chat_completion(params: ChatCompletionParams) -> InvokeResponse<Str
             }
 
             log::warn!("Unexpected response format: {:?}", response);
-            InvokeResponse::fail("Unexpected response format".to_string())
+            InvokeResponse::error(AppError::new(
+                ErrorKind::InvalidResponse,
+                "Unexpected response format",
+            ))
         }
         Err(e) => {
-            log::error!("Request failed: {}", e);
-            InvokeResponse::fail(format!("Request failed: {}", e))
+            log::error!("Request failed: {:#}", e);
+            InvokeResponse::error(AppError::from(e))
         }
     }
 }
@@ -93,187 +108,39 @@ pub async fn chat_completion_stream(
     params: ChatCompletionStreamParams,
     handle: AppHandle,
 ) -> InvokeResponse<String> {
-    return InvokeResponse::fail("Not implemented".to_string());
+    return InvokeResponse::error(AppError::new(
+        ErrorKind::UnsupportedOperation,
+        "Not implemented",
+    ));
 }
 
 #[tauri::command]
 pub async fn get_agent_card(params: AgentCardParams) -> InvokeResponse<AgentCard> {
-    let client = Client::new();
-
-    let full_url = if !params.url.starts_with("http://") && !params.url.starts_with("https://") {
-        format!("http://{}", params.url)
-    } else {
-        params.url.clone()
-    };
-
-    let response = client
-        .get(&full_url)
-        .header("Accept", "application/json")
-        .bearer_auth(params.token.unwrap_or_default())
-        .send()
-        .await;
-
-    match response {
-        Ok(resp) => {
-            let status = resp.status();
-
-            // Read body, but preserve error information
-            let body_text = match resp.text().await {
-                Ok(text) => text,
-                Err(e) => {
-                    log::error!("Failed to read body: {}", e);
-                    return InvokeResponse::fail(format!("Failed to read response body: {}", e));
-                }
-            };
-
-            if !status.is_success() {
-                log::error!("Request failed with status {}. Body: {}", status, body_text);
-                return InvokeResponse::fail(format!(
-                    "Request failed with status {}: {}",
-                    status, body_text
-                ));
-            }
-
-            match serde_json::from_str::<AgentCard>(&body_text) {
-                Ok(agent_card) => InvokeResponse::success(agent_card),
-                Err(e) => {
-                    log::error!(
-                        "Failed to parse agent card response: {}. Body: {}",
-                        e,
-                        body_text
-                    );
-                    InvokeResponse::fail(format!("Failed to parse response: {}", e))
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to send request: {}", e);
-            InvokeResponse::fail(format!("Request failed: {}", e))
-        }
-    }
+    fetch_agent_card(&params.url, params.token.as_deref())
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
 }
 
 #[tauri::command]
 pub async fn send_a2a_message(params: A2AMessageParams) -> InvokeResponse<String> {
-    let client = Client::new();
-
-    let full_url =
-        if !params.a2a_url.starts_with("http://") && !params.a2a_url.starts_with("https://") {
-            format!("http://{}", params.a2a_url)
-        } else {
-            params.a2a_url.clone()
-        };
-
-    // find a2a server by id
-    let a2a_server = match SettingA2AServerDbManager::new().get_by_id(params.a2a_server_id) {
-        Ok(Some(server)) => server,
-        Ok(None) => return InvokeResponse::fail("A2A server not found".to_string()),
-        Err(e) => return InvokeResponse::fail(format!("Failed to get A2A server: {}", e)),
-    };
-    let custom_header_json = a2a_server.custom_header_json;
-
-    let protocol_data_object_settings = a2a_server.protocol_data_object_settings;
-
-    // Parse protocol_data_object_settings and create appropriate message parts
-    let settings_obj = protocol_data_object_settings
-        .and_then(|settings_json| from_str::<serde_json::Value>(&settings_json).ok());
-
-    let parts = if let Some(obj) = settings_obj {
-        if obj.get("kind").and_then(|k| k.as_str()) == Some("data") {
-            let data = obj
-                .get("data")
-                .map(|d| {
-                    if let Some(s) = d.as_str() {
-                        s.to_string()
-                    } else {
-                        d.to_string()
-                    }
-                })
-                .unwrap_or_default()
-                .replace("{{USER_PROMPT}}", &params.text);
-            vec![A2AMessagePart::Data(A2ADataPart::new(data))]
-        } else {
-            vec![A2AMessagePart::Text(A2ATextPart::new(params.text))]
-        }
-    } else {
-        vec![A2AMessagePart::Text(A2ATextPart::new(params.text))]
-    };
-
-    let message_id = params.message_id.clone();
-    let request_body = A2ARequest {
-        id: params.task_id,
-        message: A2AMessage {
-            message_id: params.message_id,
-            kind: "message".to_string(),
-            role: "user".to_string(),
-            parts,
-        },
-        metadata: serde_json::Value::Object(serde_json::Map::new()),
-    };
-
-    let jsonrpc_request = JSONRPCRequest {
-        jsonrpc: "2.0".to_string(),
-        id: message_id,
-        method: "message/send".to_string(),
-        params: request_body,
-    };
-
-    // Build request with custom headers if they exist
-    let mut request_builder = client
-        .post(&full_url)
-        .header("Content-Type", "application/json")
-        .header("X-A2A-Skill-Id", params.header_skill_id)
-        .json(&jsonrpc_request);
-
-    // Add custom headers if they exist
-    if let Some(headers_json) = custom_header_json {
-        if let Ok(headers) =
-            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&headers_json)
-        {
-            for (key, value) in headers {
-                if let Some(header_value) = value.as_str() {
-                    if key == "Authorization" {
-                        request_builder = request_builder.bearer_auth(header_value);
-                    } else {
-                        request_builder = request_builder.header(key, header_value);
-                    }
-                }
-            }
-        }
-    }
-
-    let response = request_builder.send().await;
-
-    match response {
-        Ok(resp) => {
-            let status = resp.status();
-            if !status.is_success() {
-                let error_text = resp.text().await.unwrap_or_default();
-                return InvokeResponse::fail(format!(
-                    "A2A request failed with status {}: {}",
-                    status, error_text
-                ));
-            }
-
-            match resp.text().await {
-                Ok(response_text) => InvokeResponse::success(response_text),
-                Err(e) => {
-                    log::error!("Failed to read A2A response body: {}", e);
-                    InvokeResponse::fail(format!("Failed to read response body: {}", e))
-                }
-            }
-        }
-        Err(e) => {
-            log::error!("Failed to send A2A request: {}", e);
-            InvokeResponse::fail(format!("Request failed: {}", e))
-        }
-    }
+    conversation::send_message(&params)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
 }
 
 // Model configuration related commands
 
 #[tauri::command]
 pub async fn save_setting_model(params: SettingModelParams) -> InvokeResponse<i64> {
+    if let Err(message) = context_window::validate_context_settings(
+        params.context_window,
+        params.context_policy.as_deref(),
+    ) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
     let db_manager = SettingModelDbManager::new();
 
     db_manager
@@ -284,6 +151,13 @@ pub async fn save_setting_model(params: SettingModelParams) -> InvokeResponse<i6
 
 #[tauri::command]
 pub async fn update_setting_model(params: UpdateSettingModelParams) -> InvokeResponse<usize> {
+    if let Err(message) = context_window::validate_context_settings(
+        params.context_window,
+        params.context_policy.as_deref(),
+    ) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
     let db_manager = SettingModelDbManager::new();
 
     db_manager
@@ -326,6 +200,10 @@ pub async fn toggle_setting_model_enabled(id: i32) -> InvokeResponse<usize> {
 pub async fn delete_setting_model(id: i32) -> InvokeResponse<usize> {
     let db_manager = SettingModelDbManager::new();
 
+    if let Err(e) = ModelPresetDbManager::new().delete_by_model_id(id) {
+        log::warn!("Failed to delete presets of model {}: {}", id, e);
+    }
+
     db_manager
         .delete_by_id(id)
         .map(InvokeResponse::success)
@@ -344,13 +222,28 @@ pub async fn ensure_single_setting_model_enabled(enabled_id: i32) -> InvokeRespo
 
 // Export A2A server module
 pub mod a2a_server;
+pub mod artifact;
+pub mod batch;
+pub mod card_lint;
 pub mod chat;
-
-// Helper function to create AI client
-async fn create_ai_client(api_key: &str) -> Result<AiClient> {
-    // Create client with custom base URL for DeepSeek
-    let client = AiClient::from_url(api_key, "https://api.deepseek.com/v1")?;
-    Ok(client)
+pub mod compare;
+pub mod conformance;
+pub mod conversation;
+pub mod export;
+pub mod history;
+pub mod host_agent;
+pub mod host_agent_profile;
+pub mod load_test;
+pub mod log;
+pub mod mcp;
+pub mod model_preset;
+pub mod prompt_template;
+pub mod replay;
+pub mod traffic;
+
+// Client for DeepSeek with the given key
+pub(crate) fn deepseek_client(api_key: &str) -> ChatClient {
+    ChatClient::new("https://api.deepseek.com/v1", api_key)
 }
 
 // Helper function to build chat messages using ai.rs types
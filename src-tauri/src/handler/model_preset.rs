@@ -0,0 +1,99 @@
+use crate::{
+    db::{model_db::SettingModelDbManager, model_preset_db::ModelPresetDbManager},
+    error::AppError,
+    llm::preset::validate_generation_options,
+    model::{
+        InvokeResponse, ModelPreset, ModelPresetParams, UpdateModelPresetParams,
+        to_invoke_response,
+    },
+};
+
+#[tauri::command]
+pub async fn save_model_preset(params: ModelPresetParams) -> InvokeResponse<i64> {
+    if params.name.trim().is_empty() {
+        return InvokeResponse::error(AppError::invalid_params("Preset name cannot be empty"));
+    }
+    if let Err(message) = validate_generation_options(&params.options) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+    match SettingModelDbManager::new().get_by_id(params.model_id) {
+        Ok(Some(_)) => {}
+        Ok(None) => return InvokeResponse::error(AppError::not_found("Model not found")),
+        Err(e) => return to_invoke_response(e),
+    }
+
+    let db_manager = ModelPresetDbManager::new();
+
+    let id = match db_manager.insert(&params) {
+        Ok(id) => id,
+        Err(e) => return to_invoke_response(e),
+    };
+
+    // The first preset of a model becomes its default
+    let is_first = db_manager
+        .get_by_model_id(params.model_id)
+        .map(|presets| presets.len() == 1)
+        .unwrap_or(false);
+    if params.is_default || is_first {
+        if let Err(e) = db_manager.set_default(id) {
+            return to_invoke_response(e);
+        }
+    }
+
+    InvokeResponse::success(id)
+}
+
+#[tauri::command]
+pub async fn update_model_preset(params: UpdateModelPresetParams) -> InvokeResponse<usize> {
+    let db_manager = ModelPresetDbManager::new();
+
+    let preset = match db_manager.get_by_id(params.id) {
+        Ok(Some(preset)) => preset,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("Model preset not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get model preset")),
+    };
+
+    let name = params.name.unwrap_or(preset.name);
+    if name.trim().is_empty() {
+        return InvokeResponse::error(AppError::invalid_params("Preset name cannot be empty"));
+    }
+    let options = params.options.unwrap_or(preset.options);
+    if let Err(message) = validate_generation_options(&options) {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    db_manager
+        .update(params.id, &name, &options)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_model_presets(model_id: i32) -> InvokeResponse<Vec<ModelPreset>> {
+    let db_manager = ModelPresetDbManager::new();
+
+    db_manager
+        .get_by_model_id(model_id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn set_default_model_preset(id: i64) -> InvokeResponse<usize> {
+    let db_manager = ModelPresetDbManager::new();
+
+    db_manager
+        .set_default(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_model_preset(id: i64) -> InvokeResponse<usize> {
+    let db_manager = ModelPresetDbManager::new();
+
+    db_manager
+        .delete_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
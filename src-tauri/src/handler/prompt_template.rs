@@ -0,0 +1,136 @@
+use crate::{
+    db::prompt_template_db::PromptTemplateDbManager,
+    error::AppError,
+    model::{
+        InvokeResponse, PromptTemplate, PromptTemplateParams, RenderPromptTemplateParams,
+        RenderedPromptTemplate, UpdatePromptTemplateParams, to_invoke_response,
+    },
+    prompt_template::{normalize_tags, render_template, validate_target, validate_template},
+};
+
+#[tauri::command]
+pub async fn create_prompt_template(params: PromptTemplateParams) -> InvokeResponse<i64> {
+    let tags = normalize_tags(&params.tags);
+    if let Err(message) = validate_template(&params.name, &params.content, &params.variables)
+        .and_then(|_| validate_target(params.a2a_server_id, params.skill_id.as_deref()))
+    {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    let db_manager = PromptTemplateDbManager::new();
+
+    db_manager
+        .insert(&PromptTemplateParams { tags, ..params })
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn update_prompt_template(params: UpdatePromptTemplateParams) -> InvokeResponse<usize> {
+    let db_manager = PromptTemplateDbManager::new();
+
+    let mut template = match db_manager.get_by_id(params.id) {
+        Ok(Some(template)) => template,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("Prompt template not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get prompt template")),
+    };
+
+    if let Some(name) = params.name {
+        template.name = name;
+    }
+    if let Some(description) = params.description {
+        template.description = Some(description);
+    }
+    if let Some(content) = params.content {
+        template.content = content;
+    }
+    if let Some(variables) = params.variables {
+        template.variables = variables;
+    }
+    if let Some(tags) = params.tags {
+        template.tags = normalize_tags(&tags);
+    }
+    if params.clear_target {
+        template.a2a_server_id = None;
+        template.skill_id = None;
+    }
+    if let Some(a2a_server_id) = params.a2a_server_id {
+        // A skill belongs to one server, so a new server drops the old skill
+        if template.a2a_server_id != Some(a2a_server_id) {
+            template.skill_id = None;
+        }
+        template.a2a_server_id = Some(a2a_server_id);
+    }
+    if let Some(skill_id) = params.skill_id {
+        template.skill_id = Some(skill_id);
+    }
+
+    if let Err(message) = validate_template(&template.name, &template.content, &template.variables)
+        .and_then(|_| validate_target(template.a2a_server_id, template.skill_id.as_deref()))
+    {
+        return InvokeResponse::error(AppError::invalid_params(message));
+    }
+
+    db_manager
+        .update(&template)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// List prompt templates, only those carrying `tag` when given
+#[tauri::command]
+pub async fn get_prompt_templates(tag: Option<String>) -> InvokeResponse<Vec<PromptTemplate>> {
+    let db_manager = PromptTemplateDbManager::new();
+
+    db_manager
+        .get_all(tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty()))
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_prompt_template_by_id(id: i64) -> InvokeResponse<Option<PromptTemplate>> {
+    let db_manager = PromptTemplateDbManager::new();
+
+    db_manager
+        .get_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Replace the tags of a prompt template
+#[tauri::command]
+pub async fn tag_prompt_template(id: i64, tags: Vec<String>) -> InvokeResponse<usize> {
+    let db_manager = PromptTemplateDbManager::new();
+
+    db_manager
+        .set_tags(id, &normalize_tags(&tags))
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn delete_prompt_template(id: i64) -> InvokeResponse<usize> {
+    let db_manager = PromptTemplateDbManager::new();
+
+    db_manager
+        .delete_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Substitute the variables of a template and resolve its bound target
+#[tauri::command]
+pub async fn render_prompt_template(
+    params: RenderPromptTemplateParams,
+) -> InvokeResponse<RenderedPromptTemplate> {
+    let template = match PromptTemplateDbManager::new().get_by_id(params.id) {
+        Ok(Some(template)) => template,
+        Ok(None) => return InvokeResponse::error(AppError::not_found("Prompt template not found")),
+        Err(e) => return to_invoke_response(e.context("Failed to get prompt template")),
+    };
+
+    render_template(template, &params.values)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(InvokeResponse::error)
+}
@@ -0,0 +1,13 @@
+use crate::{
+    a2a::replay,
+    model::{InvokeResponse, ReplayParams, ReplayResult},
+};
+
+/// Send a recorded A2A request to another server and diff both responses
+#[tauri::command]
+pub async fn replay_traffic_entry(params: ReplayParams) -> InvokeResponse<ReplayResult> {
+    replay::replay_traffic_entry(params)
+        .await
+        .map(InvokeResponse::success)
+        .unwrap_or_else(InvokeResponse::error)
+}
@@ -0,0 +1,89 @@
+use std::fs;
+
+use crate::{
+    db::traffic_db::TrafficDbManager,
+    error::AppError,
+    model::{
+        ExportTrafficHarParams, InvokeResponse, TrafficEntry, TrafficEntrySummary,
+        TrafficQueryParams, to_invoke_response,
+    },
+    traffic,
+};
+
+#[tauri::command]
+pub async fn get_traffic_recording() -> InvokeResponse<bool> {
+    InvokeResponse::success(traffic::is_recording())
+}
+
+#[tauri::command]
+pub async fn set_traffic_recording(enabled: bool) -> InvokeResponse<bool> {
+    traffic::set_recording(enabled);
+    log::info!(
+        "HTTP traffic recording {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    InvokeResponse::success(enabled)
+}
+
+#[tauri::command]
+pub async fn list_traffic_entries(
+    params: TrafficQueryParams,
+) -> InvokeResponse<Vec<TrafficEntrySummary>> {
+    TrafficDbManager::new()
+        .get_summaries(&params)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn get_traffic_entry(id: i64) -> InvokeResponse<TrafficEntry> {
+    match TrafficDbManager::new().get_by_id(id) {
+        Ok(Some(entry)) => InvokeResponse::success(entry),
+        Ok(None) => InvokeResponse::error(AppError::not_found("Traffic entry not found")),
+        Err(e) => to_invoke_response(e.context("Failed to get traffic entry")),
+    }
+}
+
+#[tauri::command]
+pub async fn delete_traffic_entry(id: i64) -> InvokeResponse<usize> {
+    TrafficDbManager::new()
+        .delete_by_id(id)
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+#[tauri::command]
+pub async fn clear_traffic_entries() -> InvokeResponse<usize> {
+    TrafficDbManager::new()
+        .clear()
+        .map(InvokeResponse::success)
+        .unwrap_or_else(|e| to_invoke_response(e))
+}
+
+/// Write the entries as a HAR 1.2 file, returns how many were written
+#[tauri::command]
+pub async fn export_traffic_har(params: ExportTrafficHarParams) -> InvokeResponse<usize> {
+    if params.target_path.trim().is_empty() {
+        return InvokeResponse::error(AppError::invalid_params("Target path cannot be empty"));
+    }
+
+    let entries = match TrafficDbManager::new().get_entries(&params.ids.unwrap_or_default()) {
+        Ok(entries) => entries,
+        Err(e) => return to_invoke_response(e.context("Failed to get traffic entries")),
+    };
+
+    let result = serde_json::to_string_pretty(&traffic::har_log(&entries))
+        .map_err(anyhow::Error::from)
+        .and_then(|content| fs::write(&params.target_path, content).map_err(anyhow::Error::from));
+    match result {
+        Ok(()) => {
+            log::info!(
+                "Exported {} traffic entries to {}",
+                entries.len(),
+                params.target_path
+            );
+            InvokeResponse::success(entries.len())
+        }
+        Err(e) => to_invoke_response(e.context("Failed to write HAR file")),
+    }
+}
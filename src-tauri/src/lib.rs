@@ -1,7 +1,7 @@
 use std::sync::atomic::AtomicBool;
 
 use crate::{
-    db::{init_all_tables, rusqlite::SqlState},
+    db::rusqlite::SqlState,
     handler::{
         a2a_server::{
             delete_setting_a2a_server, delete_setting_a2a_server_by_name,
@@ -10,7 +10,56 @@ use crate::{
             get_setting_a2a_server_by_name, save_setting_a2a_server,
             toggle_setting_a2a_server_enabled, update_setting_a2a_server,
         },
+        artifact::{delete_artifact, list_artifacts, open_artifact, save_artifact_as},
+        batch::{delete_batch_run, get_batch_run_items, get_batch_runs, start_batch_run},
+        card_lint::lint_agent_card,
         chat::stream_chat,
+        compare::{delete_comparison, get_comparison_results, get_comparisons, start_comparison},
+        conformance::{
+            delete_conformance_run, get_conformance_run, get_conformance_runs,
+            start_conformance_run,
+        },
+        conversation::{
+            clear_a2a_conversation_contexts, continue_a2a_task, get_a2a_conversation_contexts,
+        },
+        export::export_conversation,
+        history::{
+            delete_conversation, get_conversation_messages, save_conversation_message,
+            search_conversations,
+        },
+        host_agent::{
+            get_host_agent_server_status, start_host_agent_server, stop_host_agent_server,
+        },
+        host_agent_profile::{
+            delete_host_agent_profile, get_conversation_host_agent_profile,
+            get_host_agent_profile_by_id, get_host_agent_profiles, save_host_agent_profile,
+            select_conversation_host_agent_profile, set_default_host_agent_profile,
+            update_host_agent_profile,
+        },
+        load_test::{
+            compare_load_test_runs, delete_load_test_run, get_load_test_runs, start_load_test,
+            stop_load_test,
+        },
+        log::{export_logs, get_log_level, set_log_level, tail_logs},
+        mcp::{
+            call_mcp_tool, delete_setting_mcp_server, get_all_setting_mcp_servers,
+            get_enabled_setting_mcp_servers, list_mcp_tools, save_setting_mcp_server,
+            toggle_setting_mcp_server_enabled, update_setting_mcp_server,
+        },
+        model_preset::{
+            delete_model_preset, get_model_presets, save_model_preset, set_default_model_preset,
+            update_model_preset,
+        },
+        prompt_template::{
+            create_prompt_template, delete_prompt_template, get_prompt_template_by_id,
+            get_prompt_templates, render_prompt_template, tag_prompt_template,
+            update_prompt_template,
+        },
+        replay::replay_traffic_entry,
+        traffic::{
+            clear_traffic_entries, delete_traffic_entry, export_traffic_har, get_traffic_entry,
+            get_traffic_recording, list_traffic_entries, set_traffic_recording,
+        },
         chat_completion, chat_completion_stream, delete_setting_model,
         ensure_single_setting_model_enabled, get_agent_card, get_all_setting_models,
         get_enabled_setting_models, save_setting_model, send_a2a_message,
@@ -18,11 +67,17 @@ use crate::{
     },
     webview::native::window_design,
 };
+use a2a_core::StoreConfig;
 use anyhow::Result;
+use tauri::Manager;
+
+pub use a2a_core::{
+    a2a, agent, artifact, compare, db, error, export, llm, mcp, model, prompt_template, redact,
+    sse, traffic,
+};
 
-pub mod db;
 pub mod handler;
-pub mod model;
+pub mod utils;
 pub mod webview;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -30,19 +85,10 @@ pub fn run() -> Result<()> {
     tauri::Builder::default()
         .manage(SqlState(AtomicBool::new(false)))
         .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
+            utils::logging::init_logging(app.handle())?;
             window_design(app)?;
-            db::rusqlite::init_db_conn(&app.handle())?;
-            
-            // Initialize all database tables
-            init_all_tables(&app.handle())?;
-            
+            a2a_core::init(&StoreConfig::in_dir(&app.path().app_data_dir()?))?;
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -50,7 +96,71 @@ pub fn run() -> Result<()> {
             chat_completion,
             chat_completion_stream,
             get_agent_card,
+            lint_agent_card,
             send_a2a_message,
+            // A2A conversation commands
+            continue_a2a_task,
+            get_a2a_conversation_contexts,
+            clear_a2a_conversation_contexts,
+            // Artifact commands
+            list_artifacts,
+            open_artifact,
+            save_artifact_as,
+            delete_artifact,
+            // Conversation history commands
+            save_conversation_message,
+            get_conversation_messages,
+            delete_conversation,
+            export_conversation,
+            search_conversations,
+            // Batch run commands
+            start_batch_run,
+            get_batch_runs,
+            get_batch_run_items,
+            delete_batch_run,
+            // Load test commands
+            start_load_test,
+            stop_load_test,
+            get_load_test_runs,
+            compare_load_test_runs,
+            delete_load_test_run,
+            // Log commands
+            get_log_level,
+            set_log_level,
+            tail_logs,
+            export_logs,
+            // HTTP traffic commands
+            get_traffic_recording,
+            set_traffic_recording,
+            list_traffic_entries,
+            get_traffic_entry,
+            delete_traffic_entry,
+            clear_traffic_entries,
+            export_traffic_har,
+            replay_traffic_entry,
+            // Comparison commands
+            start_comparison,
+            get_comparisons,
+            get_comparison_results,
+            delete_comparison,
+            // Conformance commands
+            start_conformance_run,
+            get_conformance_runs,
+            get_conformance_run,
+            delete_conformance_run,
+            // Host agent server commands
+            start_host_agent_server,
+            stop_host_agent_server,
+            get_host_agent_server_status,
+            // Host agent profile commands
+            save_host_agent_profile,
+            update_host_agent_profile,
+            get_host_agent_profiles,
+            get_host_agent_profile_by_id,
+            delete_host_agent_profile,
+            set_default_host_agent_profile,
+            select_conversation_host_agent_profile,
+            get_conversation_host_agent_profile,
             // Model commands
             save_setting_model,
             update_setting_model,
@@ -59,6 +169,12 @@ pub fn run() -> Result<()> {
             toggle_setting_model_enabled,
             delete_setting_model,
             ensure_single_setting_model_enabled,
+            // Model preset commands
+            save_model_preset,
+            update_model_preset,
+            get_model_presets,
+            set_default_model_preset,
+            delete_model_preset,
             // A2A Server commands
             save_setting_a2a_server,
             update_setting_a2a_server,
@@ -70,8 +186,32 @@ pub fn run() -> Result<()> {
             delete_setting_a2a_server,
             delete_setting_a2a_server_by_name,
             ensure_single_setting_a2a_server_enabled,
+            // MCP Server commands
+            save_setting_mcp_server,
+            update_setting_mcp_server,
+            get_all_setting_mcp_servers,
+            get_enabled_setting_mcp_servers,
+            toggle_setting_mcp_server_enabled,
+            delete_setting_mcp_server,
+            list_mcp_tools,
+            call_mcp_tool,
+            // Prompt template commands
+            create_prompt_template,
+            update_prompt_template,
+            get_prompt_templates,
+            get_prompt_template_by_id,
+            tag_prompt_template,
+            delete_prompt_template,
+            render_prompt_template,
         ])
         .run(tauri::generate_context!())?;
 
     Ok(())
 }
+
+/// Run the MCP stdio server against the desktop app's index.db
+pub fn run_mcp_server() -> Result<()> {
+    a2a_core::init(&StoreConfig::default_location()?)?;
+
+    tokio::runtime::Runtime::new()?.block_on(mcp::server::serve_stdio())
+}
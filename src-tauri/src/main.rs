@@ -4,6 +4,11 @@
 use anyhow::Result;
 
 fn main() -> Result<()> {
+    // `app mcp` serves the enabled agent skills as MCP tools over stdio instead of opening the UI
+    if std::env::args().nth(1).as_deref() == Some("mcp") {
+        return app_lib::run_mcp_server();
+    }
+
     app_lib::run()?;
     Ok(())
-}
\ No newline at end of file
+}
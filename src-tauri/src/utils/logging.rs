@@ -0,0 +1,236 @@
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use anyhow::{Context, Result};
+use log::{Level, LevelFilter};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_log::{RotationStrategy, Target, TargetKind};
+
+use crate::{
+    error::AppError,
+    model::{LogEntry, LogQueryParams},
+    redact::redact_log_text,
+};
+
+// Rotated files are named `a2a-client_<timestamp>.log` next to the current one
+const LOG_FILE_NAME: &str = "a2a-client";
+const MAX_LOG_FILE_SIZE: u128 = 5 * 1024 * 1024;
+const KEPT_LOG_FILES: usize = 5;
+// Holds the level picked in the UI so it survives restarts
+const LEVEL_FILE_NAME: &str = "log-level";
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+const DEFAULT_TAIL_LIMIT: usize = 200;
+const MAX_TAIL_LIMIT: usize = 5000;
+
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Install the log plugin for every build: JSON lines in rotating files under
+/// the app log dir, plus stdout in debug builds
+pub fn init_logging(handle: &AppHandle) -> Result<()> {
+    let log_dir = handle
+        .path()
+        .app_log_dir()
+        .context("get app_log_dir failed")?;
+    fs::create_dir_all(&log_dir).context("failed to create log directory")?;
+    let _ = LOG_DIR.set(log_dir.clone());
+
+    let mut builder = tauri_plugin_log::Builder::default()
+        .clear_targets()
+        .target(Target::new(TargetKind::LogDir {
+            file_name: Some(LOG_FILE_NAME.to_string()),
+        }))
+        .max_file_size(MAX_LOG_FILE_SIZE)
+        .rotation_strategy(RotationStrategy::KeepSome(KEPT_LOG_FILES))
+        // The plugin lets everything through, log::max_level is what set_level adjusts
+        .level(LevelFilter::Trace)
+        .format(|out, message, record| {
+            let entry = serde_json::json!({
+                "timestamp": chrono::Local::now().to_rfc3339(),
+                "level": record.level().to_string(),
+                "target": record.target(),
+                "message": redact_log_text(&message.to_string()),
+            });
+            out.finish(format_args!("{}", entry))
+        });
+    if cfg!(debug_assertions) {
+        builder = builder.target(Target::new(TargetKind::Stdout));
+    }
+    handle.plugin(builder.build())?;
+
+    let level = fs::read_to_string(log_dir.join(LEVEL_FILE_NAME))
+        .ok()
+        .and_then(|level| LevelFilter::from_str(level.trim()).ok())
+        .unwrap_or(DEFAULT_LEVEL);
+    log::set_max_level(level);
+    log::info!("Logging to {:?} at level {}", log_dir, level);
+
+    Ok(())
+}
+
+pub fn current_level() -> String {
+    log::max_level().to_string()
+}
+
+/// Change the level at runtime and remember it for the next start
+pub fn set_level(level: &str) -> Result<String> {
+    let level = LevelFilter::from_str(level.trim())
+        .map_err(|_| AppError::invalid_params(format!("Unknown log level: {}", level)))?;
+    log::set_max_level(level);
+    log::info!("Log level set to {}", level);
+
+    if let Some(log_dir) = LOG_DIR.get() {
+        if let Err(e) = fs::write(log_dir.join(LEVEL_FILE_NAME), level.to_string()) {
+            log::warn!("Failed to persist log level: {}", e);
+        }
+    }
+
+    Ok(level.to_string())
+}
+
+/// The most recent entries matching the query, oldest first
+pub fn tail(params: &LogQueryParams) -> Result<Vec<LogEntry>> {
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_TAIL_LIMIT)
+        .clamp(1, MAX_TAIL_LIMIT);
+    let filter = EntryFilter::new(params)?;
+
+    let mut entries = Vec::new();
+    for path in log_files()?.iter().rev() {
+        let lines = read_lines(path)?;
+        for line in lines.iter().rev() {
+            let entry = parse_line(line);
+            if filter.matches(&entry) {
+                entries.push(entry);
+                if entries.len() == limit {
+                    entries.reverse();
+                    return Ok(entries);
+                }
+            }
+        }
+    }
+
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Write every matching entry, oldest first, as JSON lines
+pub fn export(target_path: &str, params: &LogQueryParams) -> Result<usize> {
+    let filter = EntryFilter::new(params)?;
+    let mut file = fs::File::create(target_path)
+        .with_context(|| format!("failed to create {}", target_path))?;
+
+    let mut exported = 0;
+    for path in log_files()? {
+        for line in read_lines(&path)? {
+            let entry = parse_line(&line);
+            if filter.matches(&entry) {
+                writeln!(file, "{}", serde_json::to_string(&entry)?)
+                    .context("failed to write log export")?;
+                exported += 1;
+            }
+        }
+    }
+
+    log::info!("Exported {} log entries to {}", exported, target_path);
+    Ok(exported)
+}
+
+struct EntryFilter {
+    level: Option<LevelFilter>,
+    module: Option<String>,
+    search: Option<String>,
+}
+
+impl EntryFilter {
+    fn new(params: &LogQueryParams) -> Result<Self> {
+        let level =
+            match params.level.as_deref().filter(|level| !level.is_empty()) {
+                Some(level) => Some(LevelFilter::from_str(level).map_err(|_| {
+                    AppError::invalid_params(format!("Unknown log level: {}", level))
+                })?),
+                None => None,
+            };
+
+        Ok(Self {
+            level,
+            module: params.module.clone().filter(|module| !module.is_empty()),
+            search: params
+                .search
+                .as_deref()
+                .filter(|search| !search.is_empty())
+                .map(|search| search.to_lowercase()),
+        })
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if let Some(level) = self.level {
+            // Lines without a known level only show up unfiltered
+            match Level::from_str(&entry.level) {
+                Ok(entry_level) if entry_level <= level => {}
+                _ => return false,
+            }
+        }
+        if let Some(module) = &self.module {
+            if !entry.target.starts_with(module.as_str()) {
+                return false;
+            }
+        }
+        if let Some(search) = &self.search {
+            if !entry.message.to_lowercase().contains(search.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+// Current and rotated log files, oldest first
+fn log_files() -> Result<Vec<PathBuf>> {
+    let log_dir = LOG_DIR.get().context("logging is not initialized")?;
+
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = fs::read_dir(log_dir)
+        .context("failed to read log directory")?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(LOG_FILE_NAME) && name.ends_with(".log")
+        })
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    files.sort();
+
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>> {
+    let file = fs::File::open(path).with_context(|| format!("failed to open {:?}", path))?;
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter(|line| !line.trim().is_empty())
+        .collect())
+}
+
+// Files written before structured logging hold plain text lines
+fn parse_line(line: &str) -> LogEntry {
+    let entry = serde_json::from_str::<LogEntry>(line).unwrap_or_else(|_| LogEntry {
+        timestamp: String::new(),
+        level: String::new(),
+        target: String::new(),
+        message: line.to_string(),
+    });
+
+    LogEntry {
+        message: redact_log_text(&entry.message),
+        ..entry
+    }
+}
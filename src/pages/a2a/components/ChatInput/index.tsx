@@ -84,7 +84,7 @@ const ChatInput: React.FC = React.memo(() => {
                     debouncedUpdate(chunks.join(''));
                 }, (_) => {
                     onSetStreaming(tabKey, false);
-                });
+                }, tabKey || undefined);
             } finally {
                 debouncedUpdate.flush();
                 onResetUserMessage(tabKey);
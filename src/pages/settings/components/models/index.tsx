@@ -131,6 +131,17 @@ const Models: React.FC = () => {
                                         </div>
                                     </div>
 
+                                    {/* Model Name */}
+                                    <div className="config-section">
+                                        <div className="section-title">Model Name</div>
+                                        <Input
+                                            value={selectedModel.modelName}
+                                            placeholder="Enter the model name the API expects, e.g. deepseek-chat"
+                                            className="config-input"
+                                            onChange={(e) => handleInputChange('modelName', e.target.value)}
+                                        />
+                                    </div>
+
                                     {/* API Key */}
                                     <div className="config-section">
                                         <div className="section-title">API Key</div>
@@ -1,5 +1,6 @@
+import { CardLintParams, CardLintReport } from "@/types/a2a";
 import { ChatMessage } from "@/types/chat";
-import { InvokeResult } from "@/types/invoke";
+import { InvokeCallError, InvokeResult } from "@/types/invoke";
 import { AgentCard } from "@a2a-js/sdk";
 import { invoke } from "@tauri-apps/api/core";
 
@@ -79,7 +80,8 @@ export const invokeStreamChat = async (
     messages: ChatMessage[],
     apiKey: string,
     maxTokens?: number,
-    temperature?: number
+    temperature?: number,
+    modelId?: number
 ): Promise<void> => {
     const res: InvokeResult<string> = await invoke("stream_chat", {
         params: {
@@ -87,6 +89,7 @@ export const invokeStreamChat = async (
             api_key: apiKey,
             max_tokens: maxTokens,
             temperature: temperature,
+            model_id: modelId,
         }
     });
 
@@ -114,13 +117,24 @@ export const invokeGetAgentCard = async (url: string): Promise<AgentCard> => {
     }
 };
 
+export const invokeLintAgentCard = async (params: CardLintParams): Promise<CardLintReport> => {
+    const res: InvokeResult<CardLintReport> = await invoke("lint_agent_card", { params });
+
+    if (res.code === 0 && res.data !== undefined) {
+        return res.data;
+    } else {
+        throw new Error(res.message || "Unknown error while linting agent card");
+    }
+};
+
 export const invokeSendA2AMessage = async (
     a2aUrl: string,
     taskId: string,
     messageId: string,
     headerSkillId: string,
     text: string,
-    a2aServerId?: number
+    a2aServerId?: number,
+    conversationId?: string
 ): Promise<any> => {
     console.log("invokeSendA2AMessage a2aServerId: ", a2aServerId);
     const res: InvokeResult<string> = await invoke("send_a2a_message", {
@@ -131,6 +145,7 @@ export const invokeSendA2AMessage = async (
             message_id: messageId,
             header_skill_id: headerSkillId,
             text: text,
+            conversation_id: conversationId,
         }
     });
 
@@ -150,6 +165,40 @@ export const invokeSendA2AMessage = async (
             return res.data;
         }
     } else {
-        throw new Error(res.message || "Unknown error while sending A2A message");
+        // JSON-RPC errors of the agent fail the call too, with an agent category code
+        throw new InvokeCallError(res, "Unknown error while sending A2A message");
+    }
+};
+
+export const invokeContinueA2ATask = async (
+    conversationId: string,
+    a2aServerId: number,
+    a2aUrl: string,
+    messageId: string,
+    headerSkillId: string,
+    text: string
+): Promise<any> => {
+    const res: InvokeResult<string> = await invoke("continue_a2a_task", {
+        params: {
+            conversation_id: conversationId,
+            a2a_server_id: a2aServerId,
+            a2a_url: a2aUrl,
+            message_id: messageId,
+            header_skill_id: headerSkillId,
+            text: text,
+        }
+    });
+
+    console.log("invokeContinueA2ATask res: ", res);
+
+    if (res.code === 0 && res.data !== undefined) {
+        try {
+            return typeof res.data === "string" ? JSON.parse(res.data) : res.data;
+        } catch (parseError) {
+            console.warn("Failed to parse response as JSON, returning original string:", parseError);
+            return res.data;
+        }
+    } else {
+        throw new InvokeCallError(res, "Unknown error while continuing A2A task");
     }
 };
@@ -0,0 +1,47 @@
+import { ConformanceParams, ConformanceRun } from '@/types/conformance';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Start a conformance run, progress arrives as 'conformance_progress' events
+ * @returns Run ID
+ */
+export const startConformanceRun = async (params: ConformanceParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('start_conformance_run', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to start conformance run');
+};
+
+/**
+ * Get all conformance runs, newest first
+ */
+export const getConformanceRuns = async (): Promise<ConformanceRun[]> => {
+    const result = await invoke<{ code: number; message: string; data?: ConformanceRun[] }>('get_conformance_runs');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get conformance runs');
+};
+
+/**
+ * Get a conformance run with its report
+ */
+export const getConformanceRun = async (id: number): Promise<ConformanceRun> => {
+    const result = await invoke<{ code: number; message: string; data?: ConformanceRun }>('get_conformance_run', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get conformance run');
+};
+
+/**
+ * Delete a conformance run
+ */
+export const deleteConformanceRun = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_conformance_run', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete conformance run');
+};
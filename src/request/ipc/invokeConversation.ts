@@ -0,0 +1,85 @@
+import {
+    ConversationMessage,
+    ConversationSearchPage,
+    ConversationSearchParams,
+    SaveConversationMessageParams,
+} from '@/types/conversation';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Save a conversation message (question, thought, action, final answer...)
+ * @param params Message parameters
+ * @returns Saved message ID
+ */
+export const saveConversationMessage = async (params: SaveConversationMessageParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('save_conversation_message', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    } else {
+        throw new Error(result.message || 'Failed to save conversation message');
+    }
+};
+
+/**
+ * Get all messages of a conversation
+ * @param conversationId Conversation ID (tab key)
+ * @returns Messages in chronological order
+ */
+export const getConversationMessages = async (conversationId: string): Promise<ConversationMessage[]> => {
+    const result = await invoke<{ code: number; message: string; data?: ConversationMessage[] }>('get_conversation_messages', { conversationId });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    } else {
+        throw new Error(result.message || 'Failed to get conversation messages');
+    }
+};
+
+/**
+ * Delete a conversation with its messages and agent contexts
+ * @param conversationId Conversation ID (tab key)
+ * @returns Number of messages deleted
+ */
+export const deleteConversation = async (conversationId: string): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_conversation', { conversationId });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    } else {
+        throw new Error(result.message || 'Failed to delete conversation');
+    }
+};
+
+/**
+ * Export a conversation
+ * @param conversationId Conversation ID (tab key)
+ * @param format markdown, json or html
+ * @param targetPath Optional file to write the export to
+ * @returns Rendered export, or the target path when written to a file
+ */
+export const exportConversation = async (
+    conversationId: string,
+    format: 'markdown' | 'json' | 'html',
+    targetPath?: string
+): Promise<string> => {
+    const result = await invoke<{ code: number; message: string; data?: string }>('export_conversation', {
+        params: { conversationId, format, targetPath }
+    });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    } else {
+        throw new Error(result.message || 'Failed to export conversation');
+    }
+};
+
+/**
+ * Full-text search over conversation history
+ * @param params Query, filters and pagination
+ * @returns One page of matching messages with highlighted snippets
+ */
+export const searchConversations = async (params: ConversationSearchParams): Promise<ConversationSearchPage> => {
+    const result = await invoke<{ code: number; message: string; data?: ConversationSearchPage }>('search_conversations', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    } else {
+        throw new Error(result.message || 'Failed to search conversations');
+    }
+};
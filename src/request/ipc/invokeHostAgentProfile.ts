@@ -0,0 +1,92 @@
+import {
+    HostAgentProfile,
+    HostAgentProfileParams,
+    UpdateHostAgentProfileParams,
+} from '@/types/hostAgentProfile';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Save host agent profile
+ * @param params Profile parameters
+ * @returns Saved profile ID
+ */
+export const saveHostAgentProfile = async (params: HostAgentProfileParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('save_host_agent_profile', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to save host agent profile');
+};
+
+/**
+ * Update host agent profile
+ * @param params Update parameters
+ * @returns Number of rows updated
+ */
+export const updateHostAgentProfile = async (params: UpdateHostAgentProfileParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('update_host_agent_profile', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to update host agent profile');
+};
+
+/**
+ * Get all host agent profiles
+ */
+export const getHostAgentProfiles = async (): Promise<HostAgentProfile[]> => {
+    const result = await invoke<{ code: number; message: string; data?: HostAgentProfile[] }>('get_host_agent_profiles');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get host agent profiles');
+};
+
+/**
+ * Delete host agent profile
+ */
+export const deleteHostAgentProfile = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_host_agent_profile', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete host agent profile');
+};
+
+/**
+ * Make a profile the default, pass undefined to clear the default
+ */
+export const setDefaultHostAgentProfile = async (id?: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('set_default_host_agent_profile', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to set default host agent profile');
+};
+
+/**
+ * Select the profile of a conversation, pass undefined to use the default
+ */
+export const selectConversationHostAgentProfile = async (conversationId: string, profileId?: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('select_conversation_host_agent_profile', {
+        conversationId,
+        profileId,
+    });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to select host agent profile');
+};
+
+/**
+ * Get the profile of a conversation, the default profile when none is selected
+ */
+export const getConversationHostAgentProfile = async (conversationId: string): Promise<HostAgentProfile | null> => {
+    const result = await invoke<{ code: number; message: string; data?: HostAgentProfile | null }>('get_conversation_host_agent_profile', {
+        conversationId,
+    });
+    if (result.code === 0) {
+        return result.data ?? null;
+    }
+    throw new Error(result.message || 'Failed to get conversation host agent profile');
+};
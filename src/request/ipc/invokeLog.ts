@@ -0,0 +1,48 @@
+import { ExportLogsParams, LogEntry, LogLevel, LogQueryParams } from '@/types/log';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Get the current log level
+ */
+export const getLogLevel = async (): Promise<LogLevel> => {
+    const result = await invoke<{ code: number; message: string; data?: LogLevel }>('get_log_level');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get log level');
+};
+
+/**
+ * Change the log level at runtime, kept across restarts
+ * @returns The level now in effect
+ */
+export const setLogLevel = async (level: LogLevel): Promise<LogLevel> => {
+    const result = await invoke<{ code: number; message: string; data?: LogLevel }>('set_log_level', { level });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to set log level');
+};
+
+/**
+ * Get the most recent log entries matching the filters, oldest first
+ */
+export const tailLogs = async (params: LogQueryParams = {}): Promise<LogEntry[]> => {
+    const result = await invoke<{ code: number; message: string; data?: LogEntry[] }>('tail_logs', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get logs');
+};
+
+/**
+ * Export the matching log entries as JSON lines
+ * @returns Number of entries written
+ */
+export const exportLogs = async (params: ExportLogsParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('export_logs', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to export logs');
+};
@@ -0,0 +1,61 @@
+import { ModelPreset, ModelPresetParams, UpdateModelPresetParams } from '@/types/modelPreset';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Save model preset, the first preset of a model becomes its default
+ * @param params Preset parameters
+ * @returns Saved preset ID
+ */
+export const saveModelPreset = async (params: ModelPresetParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('save_model_preset', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to save model preset');
+};
+
+/**
+ * Update model preset
+ * @param params Update parameters, options replace all options of the preset
+ * @returns Number of rows updated
+ */
+export const updateModelPreset = async (params: UpdateModelPresetParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('update_model_preset', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to update model preset');
+};
+
+/**
+ * Get the presets of a model
+ */
+export const getModelPresets = async (modelId: number): Promise<ModelPreset[]> => {
+    const result = await invoke<{ code: number; message: string; data?: ModelPreset[] }>('get_model_presets', { modelId });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get model presets');
+};
+
+/**
+ * Make a preset the default of its model
+ */
+export const setDefaultModelPreset = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('set_default_model_preset', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to set default model preset');
+};
+
+/**
+ * Delete model preset
+ */
+export const deleteModelPreset = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_model_preset', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete model preset');
+};
@@ -0,0 +1,85 @@
+import {
+    PromptTemplate,
+    PromptTemplateParams,
+    RenderedPromptTemplate,
+    UpdatePromptTemplateParams,
+} from '@/types/promptTemplate';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Create prompt template
+ * @param params Template parameters
+ * @returns Created template ID
+ */
+export const createPromptTemplate = async (params: PromptTemplateParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('create_prompt_template', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to create prompt template');
+};
+
+/**
+ * Update prompt template
+ * @param params Update parameters
+ * @returns Number of rows updated
+ */
+export const updatePromptTemplate = async (params: UpdatePromptTemplateParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('update_prompt_template', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to update prompt template');
+};
+
+/**
+ * List prompt templates
+ * @param tag Only return templates carrying this tag
+ */
+export const getPromptTemplates = async (tag?: string): Promise<PromptTemplate[]> => {
+    const result = await invoke<{ code: number; message: string; data?: PromptTemplate[] }>('get_prompt_templates', { tag });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get prompt templates');
+};
+
+/**
+ * Replace the tags of a prompt template
+ */
+export const tagPromptTemplate = async (id: number, tags: string[]): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('tag_prompt_template', { id, tags });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to tag prompt template');
+};
+
+/**
+ * Delete prompt template
+ */
+export const deletePromptTemplate = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_prompt_template', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete prompt template');
+};
+
+/**
+ * Render a prompt template, the bound target pre-fills send_a2a_message
+ * @param id Template ID
+ * @param values Variable values, missing ones fall back to their defaults
+ */
+export const renderPromptTemplate = async (
+    id: number,
+    values: Record<string, string | number | boolean>,
+): Promise<RenderedPromptTemplate> => {
+    const result = await invoke<{ code: number; message: string; data?: RenderedPromptTemplate }>('render_prompt_template', {
+        params: { id, values },
+    });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to render prompt template');
+};
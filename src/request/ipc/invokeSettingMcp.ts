@@ -0,0 +1,129 @@
+import {
+    McpServerWithTools,
+    McpTool,
+    McpToolCallResult,
+    SettingMcpServer,
+    SettingMcpServerParams,
+    UpdateSettingMcpServerParams,
+} from '@/types/mcp';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Save MCP server
+ * @param params Server parameters
+ * @returns Saved server ID
+ */
+export const saveSettingMcpServer = async (params: SettingMcpServerParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('save_setting_mcp_server', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to save MCP server');
+};
+
+/**
+ * Update MCP server
+ * @param params Update parameters
+ * @returns Number of rows updated
+ */
+export const updateSettingMcpServer = async (params: UpdateSettingMcpServerParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('update_setting_mcp_server', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to update MCP server');
+};
+
+/**
+ * Get all MCP servers
+ */
+export const getAllSettingMcpServers = async (): Promise<SettingMcpServer[]> => {
+    const result = await invoke<{ code: number; message: string; data?: SettingMcpServer[] }>('get_all_setting_mcp_servers');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get all MCP servers');
+};
+
+/**
+ * Get enabled MCP servers
+ */
+export const getEnabledSettingMcpServers = async (): Promise<SettingMcpServer[]> => {
+    const result = await invoke<{ code: number; message: string; data?: SettingMcpServer[] }>('get_enabled_setting_mcp_servers');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get enabled MCP servers');
+};
+
+/**
+ * Toggle MCP server enabled status
+ */
+export const toggleSettingMcpServerEnabled = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('toggle_setting_mcp_server_enabled', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to toggle MCP server');
+};
+
+/**
+ * Delete MCP server
+ */
+export const deleteSettingMcpServer = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_setting_mcp_server', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete MCP server');
+};
+
+/**
+ * List the tools of an MCP server
+ * @param id Server ID
+ * @param refresh Ask the server again instead of using the cached list
+ */
+export const listMcpTools = async (id: number, refresh = false): Promise<McpTool[]> => {
+    const result = await invoke<{ code: number; message: string; data?: McpTool[] }>('list_mcp_tools', { id, refresh });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to list MCP tools');
+};
+
+/**
+ * Call an MCP tool
+ */
+export const callMcpTool = async (
+    mcpServerId: number,
+    toolName: string,
+    args: Record<string, any>,
+): Promise<McpToolCallResult> => {
+    const result = await invoke<{ code: number; message: string; data?: McpToolCallResult }>('call_mcp_tool', {
+        params: { mcpServerId, toolName, arguments: args },
+    });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to call MCP tool');
+};
+
+/**
+ * Enabled MCP servers with their tools. Servers that cannot be reached are skipped
+ * so one broken server does not block the host agent.
+ */
+export const getEnabledMcpServersWithTools = async (): Promise<McpServerWithTools[]> => {
+    const servers = await getEnabledSettingMcpServers();
+    const result: McpServerWithTools[] = [];
+    for (const server of servers) {
+        if (server.id === undefined) {
+            continue;
+        }
+        try {
+            result.push({ server, tools: await listMcpTools(server.id) });
+        } catch (error) {
+            console.warn(`Failed to list tools of MCP server ${server.name}:`, error);
+        }
+    }
+    return result;
+};
@@ -0,0 +1,98 @@
+import {
+    ExportTrafficHarParams,
+    ReplayParams,
+    ReplayResult,
+    TrafficEntry,
+    TrafficEntrySummary,
+    TrafficQueryParams,
+} from '@/types/traffic';
+import { invoke } from '@tauri-apps/api/core';
+
+/**
+ * Whether HTTP traffic is being recorded
+ */
+export const getTrafficRecording = async (): Promise<boolean> => {
+    const result = await invoke<{ code: number; message: string; data?: boolean }>('get_traffic_recording');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get traffic recording state');
+};
+
+/**
+ * Turn HTTP traffic recording on or off, it is off on every start
+ */
+export const setTrafficRecording = async (enabled: boolean): Promise<boolean> => {
+    const result = await invoke<{ code: number; message: string; data?: boolean }>('set_traffic_recording', { enabled });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to set traffic recording');
+};
+
+/**
+ * List recorded exchanges, newest first, without headers and bodies
+ */
+export const listTrafficEntries = async (params: TrafficQueryParams = {}): Promise<TrafficEntrySummary[]> => {
+    const result = await invoke<{ code: number; message: string; data?: TrafficEntrySummary[] }>('list_traffic_entries', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to list traffic entries');
+};
+
+/**
+ * Get a recorded exchange with headers, bodies and SSE events
+ */
+export const getTrafficEntry = async (id: number): Promise<TrafficEntry> => {
+    const result = await invoke<{ code: number; message: string; data?: TrafficEntry }>('get_traffic_entry', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to get traffic entry');
+};
+
+/**
+ * Delete a recorded exchange
+ */
+export const deleteTrafficEntry = async (id: number): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('delete_traffic_entry', { id });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to delete traffic entry');
+};
+
+/**
+ * Delete every recorded exchange
+ */
+export const clearTrafficEntries = async (): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('clear_traffic_entries');
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to clear traffic entries');
+};
+
+/**
+ * Export recorded exchanges as a HAR file
+ * @returns Number of entries written
+ */
+export const exportTrafficHar = async (params: ExportTrafficHarParams): Promise<number> => {
+    const result = await invoke<{ code: number; message: string; data?: number }>('export_traffic_har', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to export traffic as HAR');
+};
+
+/**
+ * Send a recorded A2A request to another server and diff both responses
+ */
+export const replayTrafficEntry = async (params: ReplayParams): Promise<ReplayResult> => {
+    const result = await invoke<{ code: number; message: string; data?: ReplayResult }>('replay_traffic_entry', { params });
+    if (result.code === 0 && result.data !== undefined) {
+        return result.data;
+    }
+    throw new Error(result.message || 'Failed to replay traffic entry');
+};
@@ -8,6 +8,7 @@ import { isEmpty, isNil } from 'lodash';
 const initState = {
     selectedModel: {
         modelKey: "DeepSeek",
+        modelName: "deepseek-chat",
         enabled: false,
         apiUrl: "https://api.deepseek.com/v1",
         apiKey: "",
@@ -25,6 +26,7 @@ const initState = {
 const settingModels = [
     {
         modelKey: "DeepSeek",
+        modelName: "deepseek-chat",
         enabled: false,
         apiUrl: "https://api.deepseek.com/v1",
         apiKey: "",
@@ -32,6 +34,7 @@ const settingModels = [
     },
     {
         modelKey: "OpenAI",
+        modelName: "gpt-4o-mini",
         enabled: false,
         apiUrl: "https://api.openai.com/v1",
         apiKey: "",
@@ -106,6 +109,7 @@ const actions = NaturFactory.actionsCreator(state)({
             if (isNil(modelId)) {
                 modelId = await saveSettingModel({
                     modelKey: modelData.modelKey,
+                    modelName: modelData.modelName,
                     enabled: modelData.enabled,
                     apiUrl: modelData.apiUrl,
                     apiKey: modelData.apiKey
@@ -119,6 +123,7 @@ const actions = NaturFactory.actionsCreator(state)({
             } else {
                 const result = await updateSettingModel({
                     id: modelId,
+                    modelName: modelData.modelName,
                     enabled: modelData.enabled,
                     apiUrl: modelData.apiUrl,
                     apiKey: modelData.apiKey
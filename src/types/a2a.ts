@@ -22,6 +22,8 @@ export interface SettingA2AServer {
     agentCardJson?: string;
     customHeaderJson?: string;
     protocolDataObjectSettings?: string;
+    // "JSONRPC", "HTTP+JSON" or "GRPC" forces the transport, otherwise it comes from the agent card
+    transport?: string;
     enabled: boolean;
     createdAt?: string;
     updatedAt?: string;
@@ -33,6 +35,7 @@ export interface SettingA2AServerParams {
     agentCardJson?: string;
     customHeaderJson?: string;
     protocolDataObjectSettings?: string;
+    transport?: string;
     enabled: boolean;
 }
 
@@ -43,5 +46,36 @@ export interface UpdateSettingA2AServerParams {
     agentCardJson?: string;
     customHeaderJson?: string;
     protocolDataObjectSettings?: string;
+    transport?: string;
     enabled?: boolean;
 }
+
+export interface CardLintParams {
+    // Where the card is served, fetched unless cardJson is given
+    url?: string;
+    // Lint this card text instead of fetching one
+    cardJson?: string;
+    // Fetch the card of a configured server, with its custom headers
+    a2aServerId?: number;
+    token?: string;
+    // Probe the agent url and declared capabilities, defaults to true
+    checkNetwork?: boolean;
+}
+
+export type CardLintSeverity = 'error' | 'warning' | 'info';
+
+export interface CardLintFinding {
+    // JSON pointer into the card, '' for the card itself
+    pointer: string;
+    severity: CardLintSeverity;
+    // Stable rule name, e.g. 'duplicate-skill-id'
+    code: string;
+    message: string;
+}
+
+export interface CardLintReport {
+    cardUrl?: string;
+    // No error findings
+    valid: boolean;
+    findings: CardLintFinding[];
+}
@@ -2,7 +2,7 @@ export interface StreamChunk {
     content: string;
     is_complete: boolean;
     error?: string;
-    // Status information (streaming_started, progress, completed)
+    // Status information (streaming_started, context_trimmed, progress, completed)
     status?: string;
     status_message?: string;
 }
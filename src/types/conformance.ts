@@ -0,0 +1,41 @@
+export interface ConformanceParams {
+    a2aServerId: number;
+    // Defaults to the url of the fetched agent card
+    a2aUrl?: string;
+    // Sent as X-A2A-Skill-Id, defaults to the first skill of the card
+    skillId?: string;
+}
+
+export type ConformanceCheckStatus = 'passed' | 'failed' | 'skipped';
+
+export interface ConformanceCheck {
+    // Stable id, e.g. 'jsonrpc.unknown_method'
+    id: string;
+    name: string;
+    status: ConformanceCheckStatus;
+    message: string;
+    durationMs: number;
+}
+
+export interface ConformanceRun {
+    id?: number;
+    a2aServerId: number;
+    a2aUrl?: string;
+    status: 'running' | 'passed' | 'failed';
+    passed: number;
+    failed: number;
+    skipped: number;
+    checks: ConformanceCheck[];
+    startedAt?: string;
+    finishedAt?: string;
+}
+
+// Payload of the 'conformance_progress' event
+export interface ConformanceProgress {
+    runId: number;
+    passed: number;
+    failed: number;
+    skipped: number;
+    isComplete: boolean;
+    check?: ConformanceCheck;
+}
@@ -0,0 +1,58 @@
+export type ConversationMessageKind =
+    | 'question'
+    | 'thought'
+    | 'action'
+    | 'observation'
+    | 'final_answer'
+    | 'answer'
+    | 'error'
+    | 'agent_request'
+    | 'agent_response';
+
+export interface SaveConversationMessageParams {
+    messageId: string;
+    conversationId: string;
+    role: string;
+    kind: ConversationMessageKind;
+    content: string;
+    a2aServerId?: number;
+    agentName?: string;
+    skillId?: string;
+    skillName?: string;
+    taskId?: string;
+    taskState?: string;
+    modelKey?: string;
+    // Agent interface that served an agent_response
+    endpointUrl?: string;
+    transport?: string;
+}
+
+export interface ConversationMessage extends SaveConversationMessageParams {
+    id?: number;
+    createdAt?: string;
+}
+
+export interface ConversationSearchParams {
+    query: string;
+    agentName?: string;
+    modelKey?: string;
+    taskState?: string;
+    dateFrom?: string;
+    dateTo?: string;
+    page?: number;
+    pageSize?: number;
+    highlightStart?: string;
+    highlightEnd?: string;
+}
+
+export interface ConversationSearchHit extends ConversationMessage {
+    snippet: string;
+    rank: number;
+}
+
+export interface ConversationSearchPage {
+    total: number;
+    page: number;
+    pageSize: number;
+    items: ConversationSearchHit[];
+}
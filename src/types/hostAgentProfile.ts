@@ -0,0 +1,37 @@
+export interface HostAgentProfile {
+    id?: number;
+    name: string;
+    description?: string;
+    // ReAct system prompt with {{AGENT_SKILLS}} and optionally {{MCP_TOOLS}}, empty uses the built-in one
+    systemPromptTemplate?: string;
+    maxIterations: number;
+    // SettingModel used for planning, empty keeps the default model choice
+    modelId?: number;
+    // Enabled A2A servers the planner may use, empty means all of them
+    a2aServerIds?: number[];
+    isDefault: boolean;
+    createdAt?: string;
+    updatedAt?: string;
+}
+
+export interface HostAgentProfileParams {
+    name: string;
+    description?: string;
+    systemPromptTemplate?: string;
+    maxIterations?: number;
+    modelId?: number;
+    a2aServerIds?: number[];
+}
+
+export interface UpdateHostAgentProfileParams {
+    id: number;
+    name?: string;
+    description?: string;
+    systemPromptTemplate?: string;
+    maxIterations?: number;
+    modelId?: number;
+    a2aServerIds?: number[];
+    resetSystemPrompt?: boolean;
+    resetModel?: boolean;
+    resetAgents?: boolean;
+}
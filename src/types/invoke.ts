@@ -4,12 +4,99 @@
  * @template T Type of returned data
  */
 export interface InvokeResult<T = any> {
-    /** Status code, 0 means success, non-zero means failure */
+    /** Status code, 0 means success, non-zero is a stable error code (see ErrorCode) */
     code: number;
     /** Error message */
     message: string;
     /** Returned data */
     data?: T;
+    /** Classification of a failure, absent on success */
+    error?: InvokeError;
+}
+
+export type ErrorCategory =
+    | 'validation'
+    | 'database'
+    | 'network'
+    | 'auth'
+    | 'protocol'
+    | 'agent'
+    | 'llm'
+    | 'io'
+    | 'internal';
+
+export type ErrorKind =
+    | 'internal'
+    | 'invalid_params'
+    | 'not_found'
+    | 'conflict'
+    | 'db_locked'
+    | 'database'
+    | 'agent_unreachable'
+    | 'timeout'
+    | 'unauthorized'
+    | 'rate_limited'
+    | 'upstream_unavailable'
+    | 'http_status'
+    | 'card_parse'
+    | 'serialization'
+    | 'invalid_response'
+    | 'json_rpc'
+    | 'task_not_found'
+    | 'task_not_cancelable'
+    | 'unsupported_operation'
+    | 'llm'
+    | 'io';
+
+/**
+ * Stable error codes, consistent with Rust backend ErrorKind::code
+ */
+export const ErrorCode = {
+    INTERNAL: 1,
+    INVALID_PARAMS: 1001,
+    NOT_FOUND: 1002,
+    CONFLICT: 1003,
+    DB_LOCKED: 2001,
+    DATABASE: 2002,
+    AGENT_UNREACHABLE: 3001,
+    TIMEOUT: 3002,
+    UNAUTHORIZED: 3003,
+    RATE_LIMITED: 3004,
+    UPSTREAM_UNAVAILABLE: 3005,
+    HTTP_STATUS: 3006,
+    CARD_PARSE: 4001,
+    SERIALIZATION: 4002,
+    INVALID_RESPONSE: 4003,
+    JSON_RPC: 5000,
+    TASK_NOT_FOUND: 5001,
+    TASK_NOT_CANCELABLE: 5002,
+    UNSUPPORTED_OPERATION: 5003,
+    LLM: 6001,
+    IO: 7001,
+} as const;
+
+export interface InvokeError {
+    kind: ErrorKind;
+    category: ErrorCategory;
+    /** Whether sending the same request again later can succeed */
+    retryable: boolean;
+    /** e.g. HTTP status, url, JSON-RPC code and data */
+    details?: Record<string, any>;
+}
+
+/**
+ * A failed call thrown by the invoke wrappers, keeps the backend's code and classification
+ */
+export class InvokeCallError extends Error {
+    code: number;
+    error?: InvokeError;
+
+    constructor(result: InvokeResult<unknown>, fallbackMessage: string) {
+        super(result.message || fallbackMessage);
+        this.name = "InvokeCallError";
+        this.code = result.code;
+        this.error = result.error;
+    }
 }
 
 /**
@@ -56,3 +143,12 @@ export function isSuccessResult<T>(result: InvokeResult<T>): result is InvokeRes
 export function isErrorResult<T>(result: InvokeResult<T>): result is InvokeResult<T> & { message: string } {
     return result.code !== 0;
 }
+
+/**
+ * Check if a failed call is worth retrying
+ * @param result InvokeResult object
+ * @returns Whether the backend marked the failure as retryable
+ */
+export function isRetryableResult<T>(result: InvokeResult<T>): boolean {
+    return result.code !== 0 && result.error?.retryable === true;
+}
@@ -0,0 +1,27 @@
+export type LogLevel = 'off' | 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+export interface LogEntry {
+    // RFC 3339, empty for lines written before structured logging
+    timestamp: string;
+    level: string;
+    // Module path of the record, e.g. app_lib::handler::chat
+    target: string;
+    message: string;
+}
+
+export interface LogQueryParams {
+    // Minimum level, e.g. 'warn' keeps warnings and errors
+    level?: LogLevel;
+    // Prefix of the record target
+    module?: string;
+    // Case-insensitive text the message must contain
+    search?: string;
+    // Most recent entries to return, defaults to 200
+    limit?: number;
+}
+
+export interface ExportLogsParams {
+    targetPath: string;
+    level?: LogLevel;
+    module?: string;
+}
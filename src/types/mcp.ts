@@ -0,0 +1,57 @@
+export type McpTransport = 'stdio' | 'http';
+
+export interface SettingMcpServer {
+    id?: number;
+    name: string;
+    transport: McpTransport;
+    command?: string;
+    argsJson?: string;
+    envJson?: string;
+    url?: string;
+    customHeaderJson?: string;
+    toolsJson?: string;
+    enabled: boolean;
+    createdAt?: string;
+    updatedAt?: string;
+}
+
+export interface SettingMcpServerParams {
+    name: string;
+    transport: McpTransport;
+    command?: string;
+    argsJson?: string;
+    envJson?: string;
+    url?: string;
+    customHeaderJson?: string;
+    enabled: boolean;
+}
+
+export interface UpdateSettingMcpServerParams {
+    id: number;
+    name?: string;
+    transport?: McpTransport;
+    command?: string;
+    argsJson?: string;
+    envJson?: string;
+    url?: string;
+    customHeaderJson?: string;
+    enabled?: boolean;
+}
+
+export interface McpTool {
+    name: string;
+    title?: string;
+    description?: string;
+    inputSchema: Record<string, any>;
+}
+
+export interface McpServerWithTools {
+    server: SettingMcpServer;
+    tools: McpTool[];
+}
+
+export interface McpToolCallResult {
+    content: Array<{ type: string; text?: string;[key: string]: any }>;
+    structuredContent?: Record<string, any>;
+    isError?: boolean;
+}
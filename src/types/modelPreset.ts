@@ -0,0 +1,34 @@
+export interface GenerationOptions {
+    temperature?: number;
+    topP?: number;
+    maxTokens?: number;
+    stop?: string[];
+    presencePenalty?: number;
+    frequencyPenalty?: number;
+    seed?: number;
+    // OpenAI response_format, e.g. { type: 'json_object' }
+    responseFormat?: Record<string, any>;
+}
+
+export interface ModelPreset {
+    id?: number;
+    modelId: number;
+    name: string;
+    isDefault: boolean;
+    options: GenerationOptions;
+    createdAt?: string;
+    updatedAt?: string;
+}
+
+export interface ModelPresetParams {
+    modelId: number;
+    name: string;
+    isDefault?: boolean;
+    options?: GenerationOptions;
+}
+
+export interface UpdateModelPresetParams {
+    id: number;
+    name?: string;
+    options?: GenerationOptions;
+}
@@ -0,0 +1,52 @@
+export type PromptVariableType = 'string' | 'number' | 'integer' | 'boolean' | 'enum';
+
+export interface PromptVariable {
+    name: string;
+    type: PromptVariableType;
+    required?: boolean;
+    default?: string | number | boolean;
+    description?: string;
+    options?: string[];
+}
+
+export interface PromptTemplate {
+    id?: number;
+    name: string;
+    description?: string;
+    content: string;
+    variables: PromptVariable[];
+    tags: string[];
+    a2aServerId?: number;
+    skillId?: string;
+    createdAt?: string;
+    updatedAt?: string;
+}
+
+export interface PromptTemplateParams {
+    name: string;
+    description?: string;
+    content: string;
+    variables?: PromptVariable[];
+    tags?: string[];
+    a2aServerId?: number;
+    skillId?: string;
+}
+
+export interface UpdatePromptTemplateParams {
+    id: number;
+    name?: string;
+    description?: string;
+    content?: string;
+    variables?: PromptVariable[];
+    tags?: string[];
+    a2aServerId?: number;
+    skillId?: string;
+    clearTarget?: boolean;
+}
+
+export interface RenderedPromptTemplate {
+    text: string;
+    a2aServerId?: number;
+    a2aUrl?: string;
+    headerSkillId?: string;
+}
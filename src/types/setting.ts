@@ -1,24 +1,37 @@
 // TypeScript type definitions
+export type ContextPolicy = 'drop_oldest' | 'truncate' | 'summarize';
+
 export interface SettingModel {
     id?: number;
     modelKey: string;
+    // Model name sent to the API, e.g. deepseek-chat
+    modelName?: string;
     enabled: boolean;
     apiUrl: string;
     apiKey: string;
+    // Tokens, defaults to the model's token estimate table entry
+    contextWindow?: number;
+    contextPolicy?: ContextPolicy;
     // ext field
     comingSoon?: boolean;
 }
 
 export interface SettingModelParams {
     modelKey: string;
+    modelName?: string;
     enabled: boolean;
     apiUrl: string;
     apiKey: string;
+    contextWindow?: number;
+    contextPolicy?: ContextPolicy;
 }
 
 export interface UpdateSettingModelParams {
     id: number;
+    modelName?: string;
     enabled?: boolean;
     apiUrl?: string;
     apiKey?: string;
+    contextWindow?: number;
+    contextPolicy?: ContextPolicy;
 }
\ No newline at end of file
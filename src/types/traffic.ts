@@ -0,0 +1,94 @@
+export type TrafficSource = 'a2a' | 'agent_card' | 'llm' | 'replay';
+
+export interface TrafficHeader {
+    name: string;
+    // Credential values are stored as [REDACTED]
+    value: string;
+}
+
+export interface TrafficSseEvent {
+    event?: string;
+    id?: string;
+    data: string;
+    // Since the request was sent
+    elapsedMs: number;
+}
+
+export interface TrafficEntry {
+    id?: number;
+    source: TrafficSource;
+    method: string;
+    url: string;
+    requestHeaders: TrafficHeader[];
+    requestBody?: string;
+    // Missing when no response arrived
+    status?: number;
+    responseHeaders: TrafficHeader[];
+    responseBody?: string;
+    sseEvents: TrafficSseEvent[];
+    error?: string;
+    startedAt: string;
+    durationMs: number;
+    // Time to the response headers
+    waitMs?: number;
+}
+
+export interface TrafficEntrySummary {
+    id: number;
+    source: TrafficSource;
+    method: string;
+    url: string;
+    status?: number;
+    error?: string;
+    sseEventCount: number;
+    startedAt: string;
+    durationMs: number;
+}
+
+export interface TrafficQueryParams {
+    source?: TrafficSource;
+    // Case-insensitive text the URL must contain
+    url?: string;
+    // Most recent entries to return, defaults to 200
+    limit?: number;
+}
+
+export interface ExportTrafficHarParams {
+    targetPath: string;
+    // Entries to export, all of them when empty
+    ids?: number[];
+}
+
+export interface ReplayParams {
+    // Recorded A2A exchange to send again
+    trafficEntryId: number;
+    a2aServerId: number;
+    // Defaults to the url of the server's agent card
+    a2aUrl?: string;
+    // Replaces the recorded request body
+    body?: string;
+    // Added to the recorded headers, replacing those with the same name
+    headers?: Record<string, string>;
+    // Recorded headers to leave out
+    removeHeaders?: string[];
+    // JSON pointers left out of the diff on top of ids and timestamps
+    ignorePaths?: string[];
+}
+
+export interface JsonDiffEntry {
+    // JSON pointer of the differing value
+    path: string;
+    kind: 'added' | 'removed' | 'changed';
+    original?: unknown;
+    replayed?: unknown;
+}
+
+export interface ReplayResult {
+    url: string;
+    status: number;
+    durationMs: number;
+    // The replayed response, SSE responses as the array of their event payloads
+    response: unknown;
+    identical: boolean;
+    differences: JsonDiffEntry[];
+}
@@ -1,4 +1,5 @@
 import { invokeSendA2AMessage } from "@/request/ipc/invoke";
+import { InvokeCallError } from "@/types/invoke";
 import { SendMessageResponse } from "@a2a-js/sdk";
 import { v4 as uuidv4 } from "uuid";
 
@@ -6,18 +7,19 @@ export const sendA2AMessage = async (
     text: string,
     a2aUrl: string,
     headerSkillId: string,
-    agentId?: number
+    agentId?: number,
+    conversationId?: string
 ): Promise<any> => {
     const messageId = "msg_id:" + uuidv4();
     const taskId = "task_id:" + uuidv4();
     try {
-        const response = await invokeSendA2AMessage(a2aUrl, taskId, messageId, headerSkillId, text, agentId);
-        if ('error' in response && response.error) {
-            throw new Error(`Failed to send task: ${response.error.message}`);
-        }
-        return response;
+        return await invokeSendA2AMessage(a2aUrl, taskId, messageId, headerSkillId, text, agentId, conversationId);
     } catch (error) {
         console.error("Failed to send message:", error);
+        // A JSON-RPC error from the agent fails the call instead of coming back in the response
+        if (error instanceof InvokeCallError && error.error?.category === 'agent') {
+            throw new Error(`Failed to send task: ${error.message}`);
+        }
         throw error;
     }
 
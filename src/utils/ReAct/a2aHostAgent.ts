@@ -7,6 +7,12 @@ import { sendA2AMessage } from "./a2a";
 import { toPrettyJsonString } from "../json";
 import { toExtractJsonString, toJsonStringWithPrefix } from "../markdown";
 import { SettingA2AServer } from "@/types/a2a";
+import { ConversationMessageKind } from "@/types/conversation";
+import { saveConversationMessage } from "@/request/ipc/invokeConversation";
+import { v4 as uuidv4 } from "uuid";
+import { McpServerWithTools, McpToolCallResult } from "@/types/mcp";
+import { callMcpTool } from "@/request/ipc/invokeSettingMcp";
+import { HostAgentProfile } from "@/types/hostAgentProfile";
 
 // Action interface definition
 export interface SendToAgentAction {
@@ -16,6 +22,15 @@ export interface SendToAgentAction {
     message: string;
 }
 
+export interface CallMcpToolAction {
+    type: 'call_mcp_tool';
+    server_name: string;
+    tool_name: string;
+    arguments: Record<string, any>;
+}
+
+export type HostAgentAction = SendToAgentAction | CallMcpToolAction;
+
 export type A2ATextKindWrapper = {
     text: string;
     text4ReAct: string;
@@ -47,15 +62,41 @@ export class A2AHostAgent {
     private onChunk: (chunk: string) => void;
     private onComplete?: (finalAnswer: string) => void;
     private settingA2AServers: SettingA2AServer[];
+    private mcpServers: McpServerWithTools[];
+    private conversationId?: string;
+    private profile?: HostAgentProfile | null;
 
     constructor(
         settingA2AServers: SettingA2AServer[],
         onChunk: (chunk: string) => void,
         onComplete?: (finalAnswer: string) => void,
+        conversationId?: string,
+        mcpServers: McpServerWithTools[] = [],
+        profile?: HostAgentProfile | null,
     ) {
         this.onChunk = onChunk;
         this.onComplete = onComplete;
         this.settingA2AServers = settingA2AServers;
+        this.mcpServers = mcpServers;
+        this.conversationId = conversationId;
+        this.profile = profile;
+    }
+
+    /**
+     * Persist a ReAct step so the conversation can be exported later.
+     * Agent calls themselves are recorded by the backend.
+     */
+    recordMessage(kind: ConversationMessageKind, content: string) {
+        if (!this.conversationId) {
+            return;
+        }
+        saveConversationMessage({
+            messageId: uuidv4(),
+            conversationId: this.conversationId,
+            role: kind === 'question' ? 'user' : 'assistant',
+            kind,
+            content,
+        }).catch((error) => console.warn('Failed to record conversation message:', error));
     }
 
     buildA2ATextKindResult(a2aResponseTask: any, reActTimes: number, agentName: string, skillName: string): A2ATextKindWrapper {
@@ -81,12 +122,34 @@ export class A2AHostAgent {
         };
     }
 
+    buildMcpTextKindResult(result: McpToolCallResult, reActTimes: number, serverName: string, toolName: string): A2ATextKindWrapper {
+        const texts = (result.content || [])
+            .map(item => item.type === 'text' ? item.text : JSON.stringify(item))
+            .filter(text => !isEmpty(text));
+        if (texts.length === 0 && result.structuredContent) {
+            texts.push(JSON.stringify(result.structuredContent));
+        }
+        const mcpText = texts.join('\n') || "ReAct: the MCP tool returned no content.";
+        const statusResult = result.isError ? "failed" : "completed";
+        const stateText = result.isError ? "🔴" : "🟢";
+        return {
+            text: mcpText,
+            text4ReAct: `<observation>${reActTimes}. ${mcpText}</observation>`,
+            text4Chunk: "#### Observation: \n" +
+                "> 🧩  **MCP Server Name:** " + serverName + "  \n" +
+                "> 🛠️  **MCP Tool Name:** " + toolName + "  \n" +
+                "##### " + stateText + " **Invocation** " + statusResult + " \n" +
+                mcpText + " \n" +
+                " \n"
+        };
+    }
+
     /**
      * Extract action from AI response
      * @param response Complete AI response text
      * @returns Parsed action object, returns null if not found
      */
-    extractAction(response: string): SendToAgentAction | null {
+    extractAction(response: string): HostAgentAction | null {
         try {
             // Use regex to match <action> tags
             const actionMatch = response.match(/<action>(.*?)<\/action>/s);
@@ -97,6 +160,12 @@ export class A2AHostAgent {
 
             const actionContent = actionMatch[1].trim();
 
+            // Parse call_mcp_tool function call
+            const callMcpToolMatch = actionContent.match(/call_mcp_tool\s*\(\s*(.*)\s*\)/s);
+            if (callMcpToolMatch) {
+                return this.extractMcpToolAction(callMcpToolMatch[1]);
+            }
+
             // Parse send_to_agent function call
             const sendToAgentMatch = actionContent.match(/send_to_agent\s*\(\s*(.*?)\s*\)/s);
             if (!sendToAgentMatch) {
@@ -126,6 +195,35 @@ export class A2AHostAgent {
         }
     }
 
+    /**
+     * Parse the parameters of call_mcp_tool(server_name="...", tool_name="...", arguments={...})
+     */
+    extractMcpToolAction(params: string): CallMcpToolAction {
+        const serverNameMatch = params.match(/server_name\s*=\s*"([^"]+)"/);
+        const toolNameMatch = params.match(/tool_name\s*=\s*"([^"]+)"/);
+        const argumentsMatch = params.match(/arguments\s*=\s*(\{.*\})/s);
+
+        if (!serverNameMatch || !toolNameMatch) {
+            throw new Error('Missing required parameters in call_mcp_tool action');
+        }
+
+        let args: Record<string, any> = {};
+        if (argumentsMatch) {
+            try {
+                args = JSON.parse(argumentsMatch[1]);
+            } catch (error) {
+                throw new Error('Invalid JSON arguments in call_mcp_tool action');
+            }
+        }
+
+        return {
+            type: 'call_mcp_tool',
+            server_name: serverNameMatch[1],
+            tool_name: toolNameMatch[1],
+            arguments: args,
+        };
+    }
+
     /**
      * Check if response contains final_answer
      * @param response AI response text
@@ -158,9 +256,12 @@ export class A2AHostAgent {
 
     async executeReAct(userPrompt: string) {
         try {
+            this.recordMessage('question', userPrompt);
 
             // build ReAct messages
-            const systemPrompt = getA2AHostAgentSystemPrompt(this.settingA2AServers);
+            const systemPrompt = this.profile?.systemPromptTemplate
+                ? getA2AHostAgentSystemPrompt(this.settingA2AServers, this.mcpServers, this.profile.systemPromptTemplate)
+                : getA2AHostAgentSystemPrompt(this.settingA2AServers, this.mcpServers);
             const reActMessages = [
                 { role: "system", content: systemPrompt },
                 { role: "user", content: "<question>" + userPrompt + "</question>" }
@@ -168,7 +269,7 @@ export class A2AHostAgent {
 
             // Simply avoid ReAct's infinite loop issue
             let reActTimes = 1;
-            await this.repeatReAct(this.MAX_RE_ACT_TIMES, async () => {
+            await this.repeatReAct(this.profile?.maxIterations ?? this.MAX_RE_ACT_TIMES, async () => {
 
                 // Call LLM to get content
                 let content = '';
@@ -177,13 +278,15 @@ export class A2AHostAgent {
                     () => { },
                     (fullContent) => {
                         content = fullContent;
-                    }
+                    },
+                    this.profile?.modelId
                 );
                 console.log('===>>>content: ', content);
 
                 // Extract [thought]
                 const thought = this.extractThought(content);
                 if (!isEmpty(thought)) {
+                    this.recordMessage('thought', thought);
                     const thoughtText = "> **Thought:** " + thought + " \n";
                     await streamText(thoughtText, this.onChunk)
                 }
@@ -194,6 +297,7 @@ export class A2AHostAgent {
                 // Extract [final answer]
                 const finalAnswer = this.extractFinalAnswer(content);
                 if (!isEmpty(finalAnswer)) {
+                    this.recordMessage('final_answer', finalAnswer);
                     const finalAnswerText = "#### Final Answer: \n" + finalAnswer + " \n";
                     await streamText(finalAnswerText, this.onChunk)
                     this.onComplete?.(finalAnswer);
@@ -204,13 +308,43 @@ export class A2AHostAgent {
                 this.onChunk("\r");
 
                 // Extract [action]
-                const action: SendToAgentAction | null = this.extractAction(content);
+                const action: HostAgentAction | null = this.extractAction(content);
                 if (action == null) {
                     throw new Error("ReAct failed, no action found");
                 }
+                if (action.type === 'call_mcp_tool') {
+                    this.recordMessage('action', toPrettyJsonString(action));
+                    await streamText(toJsonStringWithPrefix("#### Action: \n", action) + " \n", this.onChunk)
+
+                    await delay(200);
+                    this.onChunk("\r");
+
+                    // Call MCP tool
+                    const mcpServer = this.mcpServers.find(({ server }) => server.name === action.server_name);
+                    if (mcpServer == null || mcpServer.server.id === undefined) {
+                        throw new Error("ReAct failed, no mcp server found");
+                    }
+                    if (!mcpServer.tools.some(tool => tool.name === action.tool_name)) {
+                        throw new Error("ReAct failed, no mcp tool found");
+                    }
+                    const mcpResult = await callMcpTool(mcpServer.server.id, action.tool_name, action.arguments);
+
+                    // Build [observation]
+                    const mcpText = this.buildMcpTextKindResult(mcpResult, reActTimes, action.server_name, action.tool_name);
+                    this.recordMessage('observation', mcpText.text);
+                    await streamText(mcpText.text4Chunk, this.onChunk);
+                    reActMessages.push({
+                        role: "user",
+                        content: mcpText.text4ReAct
+                    })
+
+                    reActTimes++;
+                    return false;
+                }
                 const agentName = action.agent_name;
                 const skillName = action.skill_name;
                 const message = action.message;
+                this.recordMessage('action', toPrettyJsonString(action));
                 await streamText(toJsonStringWithPrefix("#### Action: \n", action) + " \n", this.onChunk)
 
                 await delay(200);
@@ -226,7 +360,7 @@ export class A2AHostAgent {
                 if (skill == undefined) {
                     throw new Error("ReAct failed, no skill found");
                 }
-                const a2aResponseTask = await sendA2AMessage(message, agentCard.url, skill.id, settingA2AServer.id);
+                const a2aResponseTask = await sendA2AMessage(message, agentCard.url, skill.id, settingA2AServer.id, this.conversationId);
 
                 // Build [observation]
                 const a2aText = this.buildA2ATextKindResult(a2aResponseTask, reActTimes, agentName, skillName);
@@ -244,6 +378,7 @@ export class A2AHostAgent {
             const errorPrefix = "##### Error: \n";
             this.onChunk(errorPrefix);
             const errorMessage = error instanceof Error ? error.message : String(error);
+            this.recordMessage('error', errorMessage);
             const errorText = toExtractJsonString(errorMessage);
             await streamText(errorText, this.onChunk);
             this.onComplete?.("finished");
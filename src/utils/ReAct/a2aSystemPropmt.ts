@@ -1,4 +1,5 @@
 import { SettingA2AServer } from "@/types/a2a";
+import { McpServerWithTools } from "@/types/mcp";
 import { XmlUtils } from "../xml";
 
 export const agentCommunicationPromptTemplate = `
@@ -9,7 +10,7 @@ export const agentCommunicationPromptTemplate = `
 所有步骤请严格使用以下 XML 标签格式输出：
 - <question> 用户问题
 - <thought> 思考
-- <action> 识别要使用的技能向对应Agent发送消息的操作，包括Agent名称、技能名称、消息内容
+- <action> 识别要使用的技能向对应Agent发送消息的操作，包括Agent名称、技能名称、消息内容；或者调用MCP工具的操作，包括MCP服务名称、工具名称、参数
 - <observation> Agent返回的结果
 - <final_answer> 最终答案
 
@@ -68,6 +69,17 @@ export const agentCommunicationPromptTemplate = `
 
 ⸻
 
+例子 4 - 调用MCP工具：
+
+<question>帮我看一下项目根目录下有哪些文件</question>
+<thought>这个任务可以直接使用MCP工具列出目录内容，不需要发送消息给Agent。</thought>
+<action>call_mcp_tool(server_name="filesystem", tool_name="list_directory", arguments={"path": "."})</action>
+<observation>README.md\nsrc\npackage.json</observation>
+<thought>已经拿到目录内容。</thought>
+<final_answer>项目根目录下有 README.md、src 和 package.json。</final_answer>
+
+⸻
+
 请严格遵守：
 - 你每次回答都必须包括两个标签，第一个是 <thought>，第二个是 <action> 或 <final_answer>
 - 输出 <action> 后立即停止生成，等待真实的 <observation>，擅自生成 <observation> 将导致错误
@@ -78,17 +90,24 @@ export const agentCommunicationPromptTemplate = `
 - 如果遇到请求action响应是成功的，如果没有接下来的action，你便直接生成<final_answer>，不需要继续执行相同的action。
 - 如果没有对应的Skill，应该直接生成<final_answer>，不需要继续执行相同的action。
 - 不要出现action中的内容带有...的情况。
+- 调用MCP工具时，arguments 必须是符合该工具 input_schema 的单行JSON对象，server_name 和 tool_name 必须来自下面的MCP工具列表。
 
 ⸻
 
 本次任务可用Agent技能列表：
 {{AGENT_SKILLS}}
 
+本次任务可用MCP工具列表：
+{{MCP_TOOLS}}
+
 `;
 
-export const getA2AHostAgentSystemPrompt = (a2aServers: SettingA2AServer[]) => {
-    return agentCommunicationPromptTemplate.replace(
-        '{{AGENT_SKILLS}}',
-        XmlUtils.buildA2AServersXml(a2aServers)
-    );
+export const getA2AHostAgentSystemPrompt = (
+    a2aServers: SettingA2AServer[],
+    mcpServers: McpServerWithTools[] = [],
+    template: string = agentCommunicationPromptTemplate,
+) => {
+    return template
+        .replace('{{AGENT_SKILLS}}', XmlUtils.buildA2AServersXml(a2aServers))
+        .replace('{{MCP_TOOLS}}', XmlUtils.buildMcpToolsXml(mcpServers));
 }
\ No newline at end of file
@@ -8,16 +8,21 @@ export const asyncChatCompletion = async (
     messages: ChatMessage[],
     onChunk?: (chunk: string) => void,
     onComplete?: (fullContent: string) => void,
+    modelId?: number,
 ): Promise<string> => {
 
     try {
 
-        const modelList = await getEnabledSettingModels();
-        const model = modelList.find(model => model.modelKey === "DeepSeek");
-        if (!model) {
-            throw new Error("Currently only deepseek models are supported, please configure your model configuration first.");
+        // A host agent profile may pick its model, otherwise DeepSeek is used
+        let apiKey = "";
+        if (modelId === undefined) {
+            const modelList = await getEnabledSettingModels();
+            const model = modelList.find(model => model.modelKey === "DeepSeek");
+            if (!model) {
+                throw new Error("Currently only deepseek models are supported, please configure your model configuration first.");
+            }
+            apiKey = model.apiKey;
         }
-        const apiKey = model.apiKey;
 
         if (!onChunk) {
             throw new Error("onChunk is not defined");
@@ -79,7 +84,7 @@ export const asyncChatCompletion = async (
                 }, 5 * 60 * 1000);
 
                 // Start streaming API
-                invokeStreamChat(messages, apiKey)
+                invokeStreamChat(messages, apiKey, undefined, undefined, modelId)
                     .then(result => {
                         console.log('Streaming API started successfully:', result);
                     })
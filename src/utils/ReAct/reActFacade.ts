@@ -6,28 +6,44 @@ import { getEnabledSettingModels } from "@/request/ipc/invokeSettingModel";
 import { toJsonStringWithPrefix } from "../markdown";
 import { isEmpty } from "lodash";
 import { SettingA2AServer } from "@/types/a2a";
+import { McpServerWithTools } from "@/types/mcp";
+import { getEnabledMcpServersWithTools } from "@/request/ipc/invokeSettingMcp";
+import { getConversationHostAgentProfile } from "@/request/ipc/invokeHostAgentProfile";
+import { HostAgentProfile } from "@/types/hostAgentProfile";
 
 
 export const executeA2AReAct = async (
     userPrompt: string,
     onChunk: (chunk: string) => void,
     onComplete?: (chunk: string) => void,
+    conversationId?: string,
 ) => {
 
-    // TODO support other LLM models
-    const modelList = await getEnabledSettingModels();
-    const model = modelList.find(model => model.modelKey === "DeepSeek");
-    if (!model) {
-        return reActError(
-            userPrompt,
-            "Currently only deepseek models are supported, please configure your model configuration first.",
-            onChunk,
-            onComplete
-        );
+    const profile = conversationId
+        ? await getConversationHostAgentProfile(conversationId).catch((error) => {
+            console.warn('Failed to load host agent profile:', error);
+            return null;
+        })
+        : null;
+
+    // A profile with its own model skips the DeepSeek lookup
+    if (profile?.modelId == null) {
+        const modelList = await getEnabledSettingModels();
+        const model = modelList.find(model => model.modelKey === "DeepSeek");
+        if (!model) {
+            return reActError(
+                userPrompt,
+                "Currently only deepseek models are supported, please configure your model configuration first.",
+                onChunk,
+                onComplete
+            );
+        }
     }
 
-    const settingA2AServers = await getEnabledSettingA2AServers();
-    if (settingA2AServers.length === 0) {
+    const settingA2AServers = (await getEnabledSettingA2AServers())
+        .filter(server => !profile?.a2aServerIds || profile.a2aServerIds.includes(server.id as number));
+    const mcpServers = await getEnabledMcpServersWithTools();
+    if (settingA2AServers.length === 0 && mcpServers.length === 0) {
         return executeSimpleChat(userPrompt, onChunk, onComplete);
     }
 
@@ -41,7 +57,7 @@ export const executeA2AReAct = async (
         );
     }
 
-    return executeReAct4A2AHostAgent(userPrompt, settingA2AServers, onChunk, onComplete);
+    return executeReAct4A2AHostAgent(userPrompt, settingA2AServers, onChunk, onComplete, conversationId, mcpServers, profile);
 }
 
 export const executeReAct4A2AHostAgent = async (
@@ -49,8 +65,11 @@ export const executeReAct4A2AHostAgent = async (
     settingA2AServers: SettingA2AServer[],
     onChunk: (chunk: string) => void,
     onComplete?: (chunk: string) => void,
+    conversationId?: string,
+    mcpServers: McpServerWithTools[] = [],
+    profile?: HostAgentProfile | null,
 ) => {
-    return new A2AHostAgent(settingA2AServers, onChunk, onComplete).executeReAct(userPrompt);
+    return new A2AHostAgent(settingA2AServers, onChunk, onComplete, conversationId, mcpServers, profile).executeReAct(userPrompt);
 }
 
 export const executeSimpleChat = async (
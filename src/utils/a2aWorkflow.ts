@@ -1,4 +1,5 @@
 import { startChatCompletionStream } from '@/request/ipc/invoke';
+import { InvokeCallError } from '@/types/invoke';
 import { AgentCard } from '@a2a-js/sdk';
 import { A2AClientUtil, createA2AClient } from './a2aClient';
 import { parseToMap } from './json';
@@ -322,14 +323,13 @@ export class A2AWorkflowUtil {
             // Create new client instance for specified Agent URL
             const agentClient = createA2AClient(agentUrl);
             // Use A2A client to send message
-            const response = await agentClient.sendMessage(userPrompt, agentUrl, "");
-            // Check response
-            if ('error' in response && response.error) {
-                throw new Error(`Failed to send task: ${response.error.message}`);
-            }
-            return response;
+            return await agentClient.sendMessage(userPrompt, agentUrl, "");
         } catch (error) {
             console.error('Failed to send task to Agent:', error);
+            // A JSON-RPC error from the agent fails the call instead of coming back in the response
+            if (error instanceof InvokeCallError && error.error?.category === 'agent') {
+                throw new Error(`Failed to send task: ${error.message}`);
+            }
             throw error;
         }
     }
@@ -10,6 +10,7 @@ import { toExtractJsonString, toJsonStringWithPrefix, toXmlStringWithPrefix } fr
 import { tauriEventListener } from "../TauriEventListener";
 import { XmlUtils } from "../xml";
 import { ChatMessage } from "@/types/chat";
+import { InvokeCallError } from "@/types/invoke";
 
 export class ChatUtil {
 
@@ -252,14 +253,13 @@ export class ChatUtil {
             // create new client instance for specified Agent URL
             const agentClient = createA2AClient(agentUrl);
             // use A2A client to send message
-            const response = await agentClient.sendMessage(userPrompt, agentUrl, headerSkillId, agentId ? parseInt(agentId) : undefined);
-            // check response
-            if ('error' in response && response.error) {
-                throw new Error(`Failed to send task: ${response.error.message}`);
-            }
-            return response;
+            return await agentClient.sendMessage(userPrompt, agentUrl, headerSkillId, agentId ? parseInt(agentId) : undefined);
         } catch (error) {
             console.error('Failed to send task to Agent:', error);
+            // A JSON-RPC error from the agent fails the call instead of coming back in the response
+            if (error instanceof InvokeCallError && error.error?.category === 'agent') {
+                throw new Error(`Failed to send task: ${error.message}`);
+            }
             throw error;
         }
     }
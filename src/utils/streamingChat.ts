@@ -4,7 +4,7 @@ export interface StreamChunk {
     content: string;
     is_complete: boolean;
     error?: string;
-    status?: string; // Status information (streaming_started, progress, completed)
+    status?: string; // Status information (streaming_started, context_trimmed, progress, completed)
     message?: string; // New: Status message
 }
 
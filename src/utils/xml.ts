@@ -1,4 +1,5 @@
 import { SettingA2AServer } from '@/types/a2a';
+import { McpServerWithTools } from '@/types/mcp';
 import { AgentCard } from '@a2a-js/sdk';
 import xmlFormatter from 'xml-formatter';
 import { formatTime } from './date';
@@ -60,6 +61,43 @@ export class XmlUtils {
         }
     }
 
+    /**
+     * Build MCP tools XML document from enabled MCP servers and their tools
+     */
+    public static buildMcpToolsXml(mcpServers: McpServerWithTools[]): string {
+        try {
+            const doc = document.implementation.createDocument(null, 'mcp_tools', null);
+            const root = doc.documentElement;
+
+            for (const { server, tools } of mcpServers) {
+                for (const tool of tools) {
+                    const toolElement = doc.createElement('tool');
+
+                    const serverNameElement = doc.createElement('server_name');
+                    serverNameElement.textContent = server.name;
+
+                    const toolNameElement = doc.createElement('tool_name');
+                    toolNameElement.textContent = tool.name;
+
+                    const descriptionElement = doc.createElement('description');
+                    descriptionElement.textContent = tool.description || tool.title || '';
+
+                    const inputSchemaElement = doc.createElement('input_schema');
+                    inputSchemaElement.textContent = JSON.stringify(tool.inputSchema);
+
+                    toolElement.appendChild(serverNameElement);
+                    toolElement.appendChild(toolNameElement);
+                    toolElement.appendChild(descriptionElement);
+                    toolElement.appendChild(inputSchemaElement);
+                    root.appendChild(toolElement);
+                }
+            }
+            return XmlUtils.formatXml(doc);
+        } catch (error) {
+            throw new Error(`Failed to build MCP tools XML: ${error instanceof Error ? error.message : 'Unknown error'}`);
+        }
+    }
+
     private static formatXml(doc: Document): string {
         try {
             const serializer = new XMLSerializer();